@@ -1,8 +1,15 @@
-use crate::credentials::{Credentials, CredentialsManager, StorageBackend};
-use crate::config::Config;
-use crate::db::{DbAccount, DbDraft, DbEmail, EmailDatabase, EmailStatus as DbEmailStatus};
-use std::collections::HashSet;
+use crate::credentials::{CredentialError, Credentials, CredentialsManager, StorageBackend};
+use crate::config::{AccountBackend, Config, ConfigOverrides};
+use crate::db::{AttachmentMeta, DbAccount, DbContact, DbDraft, DbEmail, DbFolder, EmailDatabase, EmailStatus as DbEmailStatus};
+use crate::email_sync::ValidationResult;
+use crate::vcard::VcardContact;
+use zeroize::Zeroize;
+use std::collections::{HashSet, VecDeque};
 use std::fmt;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(Debug, Clone)]
 pub struct Email {
@@ -11,7 +18,117 @@ pub struct Email {
     pub subject: String,
     pub preview: String,
     pub body: String,
+    /// The `text/html` alternative, when the message carried one. [`App::toggle_html_view`]
+    /// switches `EmailDetail` between this (stripped to text) and `body`.
+    pub body_html: Option<String>,
     pub date: String,
+    /// Attachment manifest for this message, fetched alongside it via
+    /// [`EmailDatabase::get_attachment_manifest`].
+    pub attachments: Vec<AttachmentMeta>,
+    /// PGP/MIME sign/encrypt status, set by [`crate::gpg::scan_incoming`] at parse time; `None`
+    /// for a plain message. Rendered in `EmailDetail`.
+    pub pgp_status: Option<String>,
+    /// This message's own `Message-ID`, threaded into `In-Reply-To`/`References` when the user
+    /// replies (see [`App::perform_action`]); `None` for mock/local-only messages.
+    pub message_id: Option<String>,
+    /// The `References` header this message carried, oldest first; threaded onto an outgoing
+    /// reply alongside `message_id` so it stays anchored to the same conversation.
+    pub references: Option<String>,
+    /// JWZ conversation id from [`EmailDatabase::rebuild_threads`], `None` if this message hasn't
+    /// been threaded (or threading found no relation to group it with). Drives [`View::ThreadList`].
+    pub thread_id: Option<String>,
+    /// Raw `List-Id`/`List-Post`/`List-Unsubscribe`/`List-Archive` headers (RFC 2369/2919), one
+    /// `"Name: value"` line per header present, set by [`crate::mime::parse_message`]; `None` for
+    /// non-list mail. Parsed on demand by
+    /// [`Self::list_post_address`]/[`Self::list_unsubscribe_target`]/[`Self::list_archive_url`].
+    pub list_headers: Option<String>,
+    /// Read state, mirrored from [`DbEmail::status`]. Drives the unseen styling and `*` flag in
+    /// `render_inbox_list`.
+    pub status: DbEmailStatus,
+}
+
+impl Email {
+    /// The header's value from `list_headers`, e.g. `self.list_header("List-Post")` -> the raw
+    /// `List-Post` value (still wrapped in `<...>`, possibly several comma-separated candidates).
+    fn list_header(&self, name: &str) -> Option<&str> {
+        self.list_headers.as_deref()?.lines().find_map(|line| {
+            line.strip_prefix(name)?.strip_prefix(':').map(|v| v.trim())
+        })
+    }
+
+    /// Whether this message carries any RFC 2369/2919 `List-*` header, for the inbox list's
+    /// mailing-list indicator.
+    pub fn is_list_mail(&self) -> bool {
+        self.list_headers.is_some()
+    }
+
+    /// Whether this message is still unread, for the inbox list's unseen styling and `*` flag.
+    pub fn is_unseen(&self) -> bool {
+        self.status == DbEmailStatus::Unread
+    }
+
+    /// Whether this message has been archived, for [`crate::ui::render_account_status`]'s
+    /// per-account counts.
+    pub fn is_archived(&self) -> bool {
+        self.status == DbEmailStatus::Archived
+    }
+
+    /// The `mailto:` address to reply to this message's list, from `List-Post` - `None` if the
+    /// message isn't list mail or its list has posting disabled (`List-Post: NO`).
+    pub fn list_post_address(&self) -> Option<String> {
+        let value = self.list_header("List-Post")?;
+        if value.eq_ignore_ascii_case("NO") {
+            return None;
+        }
+        extract_angle_bracket_uri(value, "mailto:")
+    }
+
+    /// The unsubscribe target from `List-Unsubscribe`: a `mailto:` address if present, otherwise
+    /// an `https:`/`http:` URL, preferring `mailto:` the way mail clients that can send mail
+    /// directly (rather than opening a browser) are expected to.
+    pub fn list_unsubscribe_target(&self) -> Option<ListUnsubscribeTarget> {
+        let value = self.list_header("List-Unsubscribe")?;
+        if let Some(addr) = extract_angle_bracket_uri(value, "mailto:") {
+            return Some(ListUnsubscribeTarget::Mailto(addr));
+        }
+        extract_angle_bracket_uri(value, "https:")
+            .or_else(|| extract_angle_bracket_uri(value, "http:"))
+            .map(ListUnsubscribeTarget::Url)
+    }
+
+    /// The list's web archive URL from `List-Archive`, shown in the detail view for list mail -
+    /// `None` if the message isn't list mail or carries no `List-Archive` header.
+    pub fn list_archive_url(&self) -> Option<String> {
+        let value = self.list_header("List-Archive")?;
+        extract_angle_bracket_uri(value, "https:").or_else(|| extract_angle_bracket_uri(value, "http:"))
+    }
+}
+
+/// Where [`Email::list_unsubscribe_target`] says an unsubscribe request should go.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListUnsubscribeTarget {
+    /// Compose and send an email to this address (the `mailto:` URI's address portion).
+    Mailto(String),
+    /// Open this URL in the user's browser.
+    Url(String),
+}
+
+/// Pull the first `<scheme:...>` entry matching `scheme` out of a comma-separated RFC 2369
+/// header value like `<mailto:leave@list.example>, <https://list.example/unsub>`, stripping the
+/// scheme prefix from `mailto:` results (the address is what `ComposeState.recipients`/a `mailto`
+/// link need) but keeping it for `https:`/`http:` results (those are opened as full URLs).
+fn extract_angle_bracket_uri(value: &str, scheme: &str) -> Option<String> {
+    value.split(',').find_map(|candidate| {
+        let candidate = candidate.trim().trim_start_matches('<').trim_end_matches('>');
+        if !candidate.to_lowercase().starts_with(scheme) {
+            return None;
+        }
+        if scheme == "mailto:" {
+            Some(candidate[scheme.len()..].to_string())
+        } else {
+            Some(candidate.to_string())
+        }
+    })
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -22,6 +139,23 @@ pub enum View {
     CredentialsSetup,
     CredentialsUnlock,
     CredentialsManagement,
+    CommandLine,
+    Contacts,
+    NotificationHistory,
+    FolderList,
+    ThreadList,
+    AccountStatus,
+}
+
+/// Density of `render_inbox_list`, cycled with `Shift-L` (see `App::cycle_listing_style`).
+/// `Conversations` hands off to the already-threaded [`View::ThreadList`] rather than duplicating
+/// its grouping logic, so the list itself only ever needs to render `Flat` or `Compact`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ListingStyle {
+    #[default]
+    Flat,
+    Compact,
+    Conversations,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -35,6 +169,14 @@ pub enum ComposeField {
     Recipients,
     Subject,
     Body,
+    /// The PGP sign toggle row, see [`App::compose_toggle_sign`]. Not a text buffer, so it's
+    /// skipped by the text-editing helpers (insert/delete char, clear) same as `Attachments`.
+    Sign,
+    /// The PGP encrypt toggle row, see [`App::compose_toggle_encrypt`].
+    Encrypt,
+    /// The attachment list, see [`App::compose_add_attachment`]/[`App::compose_remove_attachment`].
+    /// Not a text buffer, so it's skipped by the text-editing helpers (insert/delete char, clear).
+    Attachments,
 }
 
 #[derive(Debug, Clone)]
@@ -47,6 +189,45 @@ pub struct ComposeState {
     pub show_preview: bool,
     pub cursor_position: usize,
     pub initial_traversal_complete: bool,
+    /// Whether the outgoing message should be PGP-signed; see [`App::compose_toggle_sign`]
+    pub sign: bool,
+    /// Whether the outgoing message should be PGP-encrypted; see [`App::compose_toggle_encrypt`]
+    pub encrypt: bool,
+    /// Contact suggestions for the address token under the cursor in the recipients field, most
+    /// recently refreshed by [`App::compose_update_completions`]; empty means no popup is shown
+    pub completion_candidates: Vec<DbContact>,
+    /// Which `completion_candidates` entry Tab last cycled to, see [`App::compose_cycle_completion`]
+    pub completion_index: usize,
+    /// Set by [`App::perform_action`] when this draft is a reply/forward, to the original
+    /// message's `Message-ID`; sent as the outgoing `In-Reply-To` header so it threads onto the
+    /// same conversation (see [`App::request_send_email`]).
+    pub in_reply_to: Option<String>,
+    /// Set alongside `in_reply_to`: the original message's `References` with its own
+    /// `Message-ID` appended, oldest first, as the outgoing `References` header.
+    pub references: Option<String>,
+    /// Files to attach on send, added/removed via [`App::compose_add_attachment`]/
+    /// [`App::compose_remove_attachment`] and persisted by [`App::save_current_draft`].
+    pub attachments: Vec<PathBuf>,
+    /// Highlighted row in `attachments`, for the `Attachments` field's `d`/navigation keys. Not
+    /// read when `current_field != ComposeField::Attachments`.
+    pub attachment_selected: usize,
+    /// Open while the `Attachments` field's path-entry prompt (opened with `a`, see
+    /// [`App::compose_start_attachment_prompt`]) is active; `None` otherwise.
+    pub attachment_prompt: Option<AttachmentPromptState>,
+    /// Whether the Markdown preview is in follow-link mode: links are numbered and digit keys
+    /// open them instead of editing the body. See [`App::compose_enter_link_follow_mode`].
+    pub link_follow_mode: bool,
+    /// Digits typed so far while `link_follow_mode` is active, e.g. `"1"` then `"2"` to reach
+    /// link 12 before Enter confirms it.
+    pub link_follow_digits: String,
+}
+
+/// Buffer for the Compose view's attachment path-entry prompt (`a` on the `Attachments` field);
+/// same shape as [`ContactAddState`], which this mirrors.
+#[derive(Debug, Clone, Default)]
+pub struct AttachmentPromptState {
+    pub buffer: String,
+    pub cursor_position: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -56,6 +237,13 @@ pub enum Action {
     Reply,
     Compose,
     Forward,
+    /// Serialize the selected email(s) to an mboxrd file; see [`App::export_to_mbox`].
+    Export,
+    /// Reply to the mailing list a message was sent through, via its `List-Post` header; see
+    /// [`App::begin_reply_to_list`].
+    ReplyToList,
+    /// Act on a message's `List-Unsubscribe` header; see [`Email::list_unsubscribe_target`].
+    ListUnsubscribe,
 }
 
 impl fmt::Display for Action {
@@ -66,6 +254,9 @@ impl fmt::Display for Action {
             Action::Reply => write!(f, "Reply (r)"),
             Action::Compose => write!(f, "Compose (c)"),
             Action::Forward => write!(f, "Forward (f)"),
+            Action::Export => write!(f, "Export (x)"),
+            Action::ReplyToList => write!(f, "Reply to list (R)"),
+            Action::ListUnsubscribe => write!(f, "Unsubscribe (u)"),
         }
     }
 }
@@ -83,6 +274,19 @@ pub enum CredentialField {
     SmtpPassword,
     MasterPassword,
     MasterPasswordConfirm,
+    /// Local directory for a non-`Imap` backend ([`AccountBackend::Maildir`]/`Notmuch`) - the
+    /// only field those backends need instead of IMAP/SMTP server details.
+    BackendPath,
+    /// OAuth2 client ID, entered manually for a provider with no built-in preset
+    /// (`setup.custom_oauth2`). Presets fill this in from [`crate::providers::AuthType::OAuth2`]
+    /// instead.
+    OAuthClientId,
+    /// OAuth2 authorization endpoint, entered manually alongside `OAuthClientId`.
+    OAuthAuthUrl,
+    /// OAuth2 token endpoint, entered manually alongside `OAuthClientId`.
+    OAuthTokenUrl,
+    /// Space-separated OAuth2 scopes, entered manually alongside `OAuthClientId`.
+    OAuthScopes,
 }
 
 /// Editing mode for credentials setup (similar to compose)
@@ -112,6 +316,39 @@ pub struct CredentialsSetupState {
     pub provider_selection_mode: bool, // Whether we're in provider selection mode
     pub provider_list_index: usize, // Selected index in provider list
     pub mode: CredentialsMode, // Normal or Insert mode
+    /// Which [`AccountBackend`] kind this account will use; chosen in `backend_selection_mode`
+    /// before provider/field entry, since it decides whether IMAP/SMTP fields apply at all.
+    pub backend: AccountBackend,
+    /// Whether we're still picking a backend kind (the first step of setup, before provider
+    /// selection for `Imap` or path entry for `Maildir`/`Notmuch`).
+    pub backend_selection_mode: bool,
+    /// Selected index into [`CredentialsSetupState::backend_kinds`] while `backend_selection_mode`.
+    pub backend_list_index: usize,
+    /// Local directory entered for a `Maildir`/`Notmuch` backend; ignored for `Imap`.
+    pub backend_path: String,
+    /// The access/refresh token pair obtained by `App::credentials_setup_start_oauth`, once the
+    /// authorization-code-with-PKCE flow completes. `None` until then (or for non-OAuth2
+    /// providers, always).
+    pub oauth_token: Option<crate::credentials::OAuthToken>,
+    /// Progress/result message for an in-flight or just-finished OAuth2 authorization, shown in
+    /// place of the password fields this provider doesn't use.
+    pub oauth_status: Option<String>,
+    /// Whether the user has opted into OAuth2 with manually-entered client ID/auth URL/token
+    /// URL/scopes, for a provider with no built-in OAuth2 preset (toggled with `O`). Irrelevant
+    /// (and left `false`) for providers where `uses_oauth2()` is already true from the preset.
+    pub custom_oauth2: bool,
+    /// OAuth2 client ID for `custom_oauth2`.
+    pub oauth_client_id: String,
+    /// OAuth2 authorization endpoint for `custom_oauth2`.
+    pub oauth_auth_url: String,
+    /// OAuth2 token endpoint for `custom_oauth2`.
+    pub oauth_token_url: String,
+    /// Space-separated OAuth2 scopes for `custom_oauth2`.
+    pub oauth_scopes: String,
+    /// The user code/verification URL from an in-flight device-authorization-grant flow (see
+    /// `App::credentials_setup_start_oauth_device`), shown alongside `oauth_status` until the
+    /// token arrives (or the code expires).
+    pub device_authorization: Option<crate::oauth::DeviceAuthorization>,
 }
 
 impl CredentialsSetupState {
@@ -134,9 +371,68 @@ impl CredentialsSetupState {
             provider_selection_mode: true, // Start in provider selection mode
             provider_list_index: 0,
             mode: CredentialsMode::Normal, // Start in normal mode
+            oauth_token: None,
+            oauth_status: None,
+            backend: AccountBackend::Imap,
+            backend_selection_mode: true, // Start by picking a backend kind
+            backend_list_index: 0,
+            backend_path: String::new(),
+            custom_oauth2: false,
+            oauth_client_id: String::new(),
+            oauth_auth_url: String::new(),
+            oauth_token_url: String::new(),
+            oauth_scopes: String::new(),
+            device_authorization: None,
+        }
+    }
+
+    /// Wipe every plaintext password buffer held by this form. Called once the master password
+    /// has been used to derive a key (or the form is discarded on cancel), so it doesn't linger
+    /// in memory for the rest of the process's life - the same reasoning that has [`Credentials`]
+    /// and [`crate::credentials::OAuthToken`] derive `ZeroizeOnDrop`, applied to this longer-lived
+    /// UI state since it isn't dropped as soon as the password is no longer needed.
+    pub fn zeroize_passwords(&mut self) {
+        self.imap_password.zeroize();
+        self.smtp_password.zeroize();
+        self.master_password.zeroize();
+        self.master_password_confirm.zeroize();
+    }
+
+    /// The backend kinds offered in `backend_selection_mode`, in display order.
+    pub fn backend_kinds() -> Vec<AccountBackend> {
+        vec![
+            AccountBackend::Imap,
+            AccountBackend::Maildir { path: std::path::PathBuf::new() },
+            #[cfg(feature = "notmuch")]
+            AccountBackend::Notmuch { database_path: std::path::PathBuf::new() },
+        ]
+    }
+
+    /// Apply the chosen backend kind and move to the next step: provider selection for
+    /// `Imap`, or straight to path entry for a local backend (which has no provider/server
+    /// fields at all).
+    pub fn apply_backend(&mut self, backend: AccountBackend) {
+        self.backend_selection_mode = false;
+        match backend {
+            AccountBackend::Imap => {
+                self.backend = AccountBackend::Imap;
+                self.provider_selection_mode = true;
+            }
+            other => {
+                self.backend = other;
+                self.provider_selection_mode = false;
+                self.current_field = CredentialField::BackendPath;
+                self.cursor_position = self.backend_path.len();
+            }
         }
     }
 
+    /// Whether the selected backend reads from a local directory rather than a live IMAP
+    /// mailbox, i.e. it skips the provider/server/password fields entirely.
+    pub fn is_local_backend(&self) -> bool {
+        !matches!(self.backend, AccountBackend::Imap)
+    }
+
     /// Apply a provider preset to this setup state
     pub fn apply_provider(&mut self, provider: &crate::providers::EmailProvider) {
         self.selected_provider = Some(provider.id.to_string());
@@ -145,12 +441,51 @@ impl CredentialsSetupState {
         self.smtp_server = provider.smtp_server.to_string();
         self.smtp_port = provider.smtp_port.to_string();
         self.provider_selection_mode = false;
+        self.oauth_token = None;
+        self.oauth_status = None;
+        self.custom_oauth2 = false;
+        self.device_authorization = None;
+    }
+
+    /// Whether this account authenticates via OAuth2/XOAUTH2 rather than a plain IMAP/SMTP
+    /// password - either because the selected provider preset requires it, or because the user
+    /// opted into `custom_oauth2` for a provider with no preset. Either way the password fields
+    /// are skipped in favor of the `o` authorize action.
+    pub fn uses_oauth2(&self) -> bool {
+        self.custom_oauth2
+            || self.selected_provider
+                .as_deref()
+                .and_then(crate::providers::EmailProvider::by_id)
+                .map(|p| p.supports_oauth2())
+                .unwrap_or(false)
+    }
+
+    /// Whether `D` (authorize via device code) should be offered: only for a preset that
+    /// publishes a device-authorization endpoint (see [`crate::providers::AuthType::supports_device_code`]).
+    /// Manually-entered `custom_oauth2` providers don't have a device-code field to fill in, so
+    /// they're limited to the browser/loopback flow.
+    pub fn supports_device_code(&self) -> bool {
+        self.selected_provider
+            .as_deref()
+            .and_then(crate::providers::EmailProvider::by_id)
+            .map(|p| p.auth.supports_device_code())
+            .unwrap_or(false)
+    }
+
+    /// Whether `O` should be offered at all: toggling manual OAuth2 only makes sense for a
+    /// provider that doesn't already carry its own OAuth2 preset.
+    pub fn can_toggle_custom_oauth2(&self) -> bool {
+        !self.selected_provider
+            .as_deref()
+            .and_then(crate::providers::EmailProvider::by_id)
+            .map(|p| p.supports_oauth2())
+            .unwrap_or(false)
     }
 
     /// Check if user can navigate back to provider selection
     /// Only allowed in Normal mode, on the first field
     pub fn can_navigate_back_to_providers(&self) -> bool {
-        !self.provider_selection_mode 
+        !self.provider_selection_mode
             && self.mode == CredentialsMode::Normal
             && self.current_field == CredentialField::ImapServer
     }
@@ -174,6 +509,213 @@ impl CredentialsUnlockState {
     }
 }
 
+/// A typed action parsed from a `:`-command-line buffer, so every action expressible via a key
+/// binding is also expressible (and scriptable) as text. See [`App::perform_command`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Delete,
+    Archive,
+    Reply,
+    Forward,
+    Compose,
+    Quit,
+    SwitchAccount(usize),
+    Goto(String),
+    Contacts,
+    NotificationHistory,
+    Folders,
+    Threads,
+    /// Attach a file to the draft being composed; see [`App::compose_add_attachment`].
+    Attach(PathBuf),
+    /// Drop the attachment at this 0-based index from the current draft; `:unattach` takes the
+    /// 1-based number shown in the preview and stores it here minus one.
+    RemoveAttachment(usize),
+}
+
+impl Command {
+    /// Parse a command-line buffer like `"archive"` or `"account 2"` into a [`Command`].
+    /// Returns the unrecognized token as an error message suitable for display in the status line.
+    pub fn parse(line: &str) -> std::result::Result<Self, String> {
+        let mut parts = line.trim().split_whitespace();
+        let name = parts.next().ok_or_else(|| "Empty command".to_string())?;
+
+        match name.to_lowercase().as_str() {
+            "d" | "delete" => Ok(Command::Delete),
+            "a" | "archive" => Ok(Command::Archive),
+            "r" | "reply" => Ok(Command::Reply),
+            "f" | "forward" => Ok(Command::Forward),
+            "c" | "compose" => Ok(Command::Compose),
+            "q" | "quit" => Ok(Command::Quit),
+            "b" | "contacts" => Ok(Command::Contacts),
+            "n" | "history" | "notifications" => Ok(Command::NotificationHistory),
+            "g" | "folders" | "mailboxes" => Ok(Command::Folders),
+            "t" | "threads" => Ok(Command::Threads),
+            "account" | "switch" => {
+                let n: usize = parts
+                    .next()
+                    .ok_or_else(|| "Usage: :account <number>".to_string())?
+                    .parse()
+                    .map_err(|_| "Account number must be a positive integer".to_string())?;
+                Ok(Command::SwitchAccount(n.saturating_sub(1)))
+            }
+            "goto" | "cd" => {
+                let mailbox = parts
+                    .next()
+                    .ok_or_else(|| "Usage: :goto <mailbox>".to_string())?;
+                Ok(Command::Goto(mailbox.to_string()))
+            }
+            "attach" => {
+                let path = parts
+                    .next()
+                    .ok_or_else(|| "Usage: :attach <path>".to_string())?;
+                Ok(Command::Attach(PathBuf::from(path)))
+            }
+            "unattach" | "detach" => {
+                let n: usize = parts
+                    .next()
+                    .ok_or_else(|| "Usage: :unattach <number>".to_string())?
+                    .parse()
+                    .map_err(|_| "Attachment number must be a positive integer".to_string())?;
+                Ok(Command::RemoveAttachment(n.saturating_sub(1)))
+            }
+            other => Err(format!("Unknown command: {}", other)),
+        }
+    }
+}
+
+/// State for the `:`-command-line overlay (see [`View::CommandLine`])
+#[derive(Debug, Clone)]
+pub struct CommandLineState {
+    pub buffer: String,
+    pub cursor_position: usize,
+    pub error_message: Option<String>,
+    /// View to restore (and render underneath the overlay) when command mode exits
+    pub return_view: View,
+    /// Index into `App::command_history` while navigating with Up/Down; `None` means the user
+    /// is typing a fresh command rather than recalling one
+    pub history_index: Option<usize>,
+}
+
+impl CommandLineState {
+    pub fn new(return_view: View) -> Self {
+        Self {
+            buffer: String::new(),
+            cursor_position: 0,
+            error_message: None,
+            return_view,
+            history_index: None,
+        }
+    }
+}
+
+/// One row in the Contacts view: either an editable address-book entry backed by the database,
+/// or a read-only entry imported from [`crate::config::Config::contacts_vcf_folder`]
+#[derive(Debug, Clone)]
+pub enum ContactEntry {
+    Editable(DbContact),
+    ReadOnly(VcardContact),
+}
+
+impl ContactEntry {
+    pub fn display_name(&self) -> Option<&str> {
+        match self {
+            ContactEntry::Editable(c) => c.display_name.as_deref(),
+            ContactEntry::ReadOnly(c) => c.name.as_deref(),
+        }
+    }
+
+    pub fn email(&self) -> &str {
+        match self {
+            ContactEntry::Editable(c) => &c.address,
+            ContactEntry::ReadOnly(c) => &c.email,
+        }
+    }
+
+    pub fn is_editable(&self) -> bool {
+        matches!(self, ContactEntry::Editable(_))
+    }
+}
+
+/// Buffer for the Contacts view's "add" action, a single `Name <email>` (or bare `email`) line
+#[derive(Debug, Clone, Default)]
+pub struct ContactAddState {
+    pub buffer: String,
+    pub cursor_position: usize,
+}
+
+/// State for [`View::Contacts`]: a merged, alphabetical list of editable database contacts and
+/// read-only vCard imports. `return_view` is `View::Compose` when opened from the compose view
+/// so Enter inserts into whichever field was active; otherwise it's `View::InboxList`.
+#[derive(Debug, Clone)]
+pub struct ContactsState {
+    pub entries: Vec<ContactEntry>,
+    pub selected_index: usize,
+    pub add_state: Option<ContactAddState>,
+    pub return_view: View,
+}
+
+/// One past `App::status_message`, recorded by [`App::push_status_message`] before it's cleared
+/// so it can be reviewed in [`View::NotificationHistory`]. `timestamp` is Unix seconds.
+#[derive(Debug, Clone)]
+pub struct NotificationEntry {
+    pub message: String,
+    pub timestamp: u64,
+}
+
+/// State for [`View::NotificationHistory`], always reached from (and returned to) the inbox.
+#[derive(Debug, Clone, Default)]
+pub struct NotificationHistoryState {
+    pub selected_index: usize,
+}
+
+/// State for [`View::FolderList`], always reached from (and returned to) the inbox.
+#[derive(Debug, Clone, Default)]
+pub struct FolderListState {
+    pub selected_index: usize,
+}
+
+/// Background result of an IMAP `LIST` + database cache refresh, drained once per frame by
+/// [`App::poll_folder_list_result`] - the same poll-a-shared-slot pattern `pending_validation`
+/// uses, rather than a real channel, since the rest of the event loop is synchronous.
+#[derive(Debug, Clone)]
+pub struct FolderListEvent {
+    pub account_id: Option<i64>,
+    pub folders: Result<Vec<DbFolder>, String>,
+}
+
+/// One conversation grouped by `thread_id` (see [`EmailDatabase::rebuild_threads`]), or a lone
+/// message that hasn't been threaded - built by [`App::rebuild_thread_groups`] and surfaced by
+/// [`View::ThreadList`].
+#[derive(Debug, Clone)]
+pub struct ThreadGroup {
+    /// Shared `thread_id`, or `None` for a singleton thread
+    pub thread_id: Option<String>,
+    /// Indices into `App.emails`, oldest first, so the expanded reader walks messages in date
+    /// (insertion) order without re-sorting
+    pub email_indices: Vec<usize>,
+}
+
+/// State for [`View::ThreadList`], always reached from (and returned to) the inbox.
+#[derive(Debug, Clone, Default)]
+pub struct ThreadListState {
+    pub selected_index: usize,
+    /// `Some(i)` once a thread is opened for reading, where `i` indexes into the selected
+    /// group's `email_indices`; j/k then walk messages within that thread instead of across
+    /// threads, and `None` collapses back to the thread list.
+    pub expanded_message_index: Option<usize>,
+}
+
+/// One batch of new mail discovered by the background watcher [`App::start_mail_watch`] spawns
+/// (IMAP `IDLE` push, or the polling fallback for servers without it). The watcher task does the
+/// database write itself (same as [`App::request_send_email`] does for sending); this is just
+/// the queued result for [`App::poll_mail_watch_events`] to fold into the visible email list and
+/// announce with a desktop notification.
+pub struct MailWatchEvent {
+    pub account_id: Option<i64>,
+    pub folder: String,
+    pub emails: Vec<Email>,
+}
+
 pub struct App {
     pub emails: Vec<Email>,
     pub current_view: View,
@@ -184,6 +726,7 @@ pub struct App {
     pub db: Option<EmailDatabase>,
     pub draft_id: Option<i64>,
     pub show_preview_panel: bool,
+    pub listing_style: ListingStyle,
     pub visual_mode: bool,
     pub visual_selections: HashSet<usize>,
     pub visual_anchor: Option<usize>,
@@ -191,10 +734,81 @@ pub struct App {
     pub credentials: Option<Credentials>,
     pub credentials_setup_state: Option<CredentialsSetupState>,
     pub credentials_unlock_state: Option<CredentialsUnlockState>,
+    pub command_line_state: Option<CommandLineState>,
+    /// Previously entered `:`-commands, most recent last, recalled with Up/Down in command mode
+    pub command_history: Vec<String>,
+    /// Set by [`Self::request_external_editor`]; `main`'s loop owns the terminal, so it's the
+    /// one that actually suspends raw mode, spawns `$EDITOR`, and feeds the result back via
+    /// [`Self::compose_set_from_editor`].
+    pub external_editor_requested: bool,
     pub config: Config,
+    /// Resolved view-action key bindings, built once from `config.shortcuts` at startup
+    pub shortcuts: crate::keymap::Shortcuts,
     pub accounts: Vec<DbAccount>,
     pub current_account_id: Option<i64>,
     pub email_sync_manager: Option<crate::email_sync::EmailSyncManager>,
+    /// Result of an in-flight pre-flight connection validation (see `credentials_setup_validate`),
+    /// written by a background task and drained once per frame by `poll_validation_result`.
+    pub pending_validation: Arc<Mutex<Option<ValidationResult>>>,
+    /// Result of an in-flight OAuth2 authorization-code flow (see `credentials_setup_start_oauth`),
+    /// written by a background task and drained once per frame by `poll_oauth_result`.
+    pub pending_oauth: Arc<Mutex<Option<Result<crate::credentials::OAuthToken, String>>>>,
+    /// The user code/verification URL from an in-flight device-authorization-grant flow (see
+    /// `credentials_setup_start_oauth_device`), written as soon as the provider hands it back so
+    /// `poll_oauth_result` can show it before the (much later) token arrives on `pending_oauth`.
+    pub pending_device_auth: Arc<Mutex<Option<crate::oauth::DeviceAuthorization>>>,
+    pub contacts_state: Option<ContactsState>,
+    /// Digits accumulated so far for a pending vim-style count prefix (e.g. the `5` in `5j`),
+    /// consumed by the next motion/action key in `handle_inbox_keys`/`handle_detail_keys`/
+    /// `handle_visual_mode_keys`. Empty means no count is pending, so the key repeats once.
+    pub pending_count: String,
+    /// Whether the email detail view is in follow-link mode: links in the body are numbered and
+    /// digit keys select one instead of accumulating a count prefix. See
+    /// [`Self::detail_enter_link_follow_mode`].
+    pub detail_link_follow_mode: bool,
+    /// Digits typed so far while `detail_link_follow_mode` is active.
+    pub detail_link_follow_digits: String,
+    /// Past status/error messages, most recent first, see [`Self::push_status_message`]. Bounded
+    /// to [`Self::NOTIFICATION_HISTORY_CAP`] entries.
+    pub notification_history: VecDeque<NotificationEntry>,
+    pub notification_history_state: Option<NotificationHistoryState>,
+    /// Mailbox the inbox list currently shows; driven by [`Self::switch_to_folder`]. Defaults to
+    /// `"inbox"` and is threaded through `get_emails_by_folder_and_account` instead of the old
+    /// hardcoded literal.
+    pub current_folder: String,
+    /// Cached IMAP folder tree for the current account, refreshed by [`Self::request_folder_sync`]
+    pub folders: Vec<DbFolder>,
+    pub folder_list_state: Option<FolderListState>,
+    pub pending_folder_list: Arc<Mutex<Option<FolderListEvent>>>,
+    /// Thread groups for the current account/folder, rebuilt by [`Self::rebuild_thread_groups`]
+    /// whenever [`View::ThreadList`] is opened.
+    pub thread_groups: Vec<ThreadGroup>,
+    pub thread_list_state: Option<ThreadListState>,
+    /// Whether `EmailDetail` is currently showing the `body_html` view instead of `body`. Reset
+    /// to `false` by [`Self::open_email`] so each message opens on its plain text.
+    pub show_html_view: bool,
+    /// When `show_html_view` is set, whether that HTML view is showing the raw `body_html`
+    /// source rather than [`crate::ui`]'s styled rendering of it. Mirrors how
+    /// `ComposeState::show_preview` gates raw-vs-rendered Markdown in the compose body. Reset to
+    /// `false` alongside `show_html_view` by [`Self::open_email`].
+    pub show_html_source: bool,
+    /// Result of an in-flight send from [`Self::request_send_email`], written by a background
+    /// task and drained once per frame by [`Self::poll_send_result`] - the same poll-a-shared-slot
+    /// pattern `pending_folder_list` uses.
+    pub pending_send: Arc<Mutex<Option<Result<(), String>>>>,
+    /// New-mail batches from the background watcher [`Self::start_mail_watch`] spawns, drained
+    /// once per frame by [`Self::poll_mail_watch_events`]. A queue rather than a single slot,
+    /// since unlike the other `pending_*` results this one can fire more than once between frames.
+    pub pending_mail_watch: Arc<Mutex<Vec<MailWatchEvent>>>,
+    /// Set by [`Self::start_mail_watch`] and checked by the watcher loop itself so it tears its
+    /// IMAP connection down cleanly on [`Self::quit`] or when a new watcher replaces it (e.g. on
+    /// [`Self::switch_to_account`]). `None` when no watcher is running.
+    pub mail_watch_stop: Option<Arc<AtomicBool>>,
+    /// Unix-seconds timestamp of the last time [`Self::start_mail_watch`]'s background loop
+    /// actually contacted the server (an IDLE wakeup or a poll fetch), whether or not it found
+    /// new mail. Read directly by [`crate::ui::render_account_status`] - `None` means the
+    /// current account hasn't synced since this `App` started.
+    pub last_sync_at: Arc<Mutex<Option<u64>>>,
 }
 
 impl App {
@@ -209,6 +823,7 @@ impl App {
             db: None,
             draft_id: None,
             show_preview_panel: false,
+            listing_style: ListingStyle::Flat,
             visual_mode: false,
             visual_selections: HashSet::new(),
             visual_anchor: None,
@@ -216,34 +831,67 @@ impl App {
             credentials: None,
             credentials_setup_state: None,
             credentials_unlock_state: None,
+            command_line_state: None,
+            command_history: Vec::new(),
+            external_editor_requested: false,
+            shortcuts: crate::keymap::Shortcuts::from_config(&Config::default().shortcuts),
             config: Config::default(),
             accounts: Vec::new(),
             current_account_id: None,
             email_sync_manager: None,
+            pending_validation: Arc::new(Mutex::new(None)),
+            pending_oauth: Arc::new(Mutex::new(None)),
+            pending_device_auth: Arc::new(Mutex::new(None)),
+            contacts_state: None,
+            pending_count: String::new(),
+            detail_link_follow_mode: false,
+            detail_link_follow_digits: String::new(),
+            notification_history: VecDeque::new(),
+            notification_history_state: None,
+            current_folder: "inbox".to_string(),
+            folders: Vec::new(),
+            folder_list_state: None,
+            thread_groups: Vec::new(),
+            thread_list_state: None,
+            pending_folder_list: Arc::new(Mutex::new(None)),
+            show_html_view: false,
+            show_html_source: false,
+            pending_send: Arc::new(Mutex::new(None)),
+            pending_mail_watch: Arc::new(Mutex::new(Vec::new())),
+            mail_watch_stop: None,
+            last_sync_at: Arc::new(Mutex::new(None)),
         }
     }
 
-    /// Initialize the app with database support
-    pub async fn with_database(dev_mode: bool) -> anyhow::Result<Self> {
+    /// Initialize the app with database support. `config_path` overrides the usual XDG lookup
+    /// (a `--config <path>` CLI flag); `cli_overrides` is applied on top of the file and
+    /// [`ConfigOverrides::from_env`] in file < env < CLI precedence.
+    pub async fn with_database(
+        dev_mode: bool,
+        config_path: Option<std::path::PathBuf>,
+        cli_overrides: ConfigOverrides,
+    ) -> anyhow::Result<Self> {
         let db = EmailDatabase::new(None).await?;
 
         // Load configuration
-        let config = Config::load().unwrap_or_else(|e| {
-            eprintln!("Warning: Failed to load config: {}. Using defaults.", e);
+        let mut config = Config::load_from(config_path).unwrap_or_else(|e| {
+            log::warn!("Failed to load config: {}. Using defaults.", e);
             Config::default()
         });
-        
-        eprintln!("DEBUG: Config loaded. Accounts in config: {}", config.accounts.len());
+        config.apply_overrides(&ConfigOverrides::from_env());
+        config.apply_overrides(&cli_overrides);
+
+        log::debug!("Config loaded. Accounts in config: {}", config.accounts.len());
         for (key, account) in &config.accounts {
-            eprintln!("DEBUG: Config account '{}': {} ({})", key, account.name, account.email);
+            log::debug!("Config account '{}': {} ({})", key, account.name, account.email);
         }
 
         // Load accounts from database
         let accounts = db.get_accounts().await?;
-        
-        eprintln!("DEBUG: Accounts from DB: {}", accounts.len());
+
+        log::debug!("Accounts from DB: {}", accounts.len());
         for account in &accounts {
-            eprintln!("DEBUG: DB account: {} ({})", account.name, account.email);
+            log::debug!("DB account: {} ({})", account.name, account.email);
         }
 
         // Sync accounts from config to database if needed
@@ -280,6 +928,7 @@ impl App {
                     bcc_addresses: None,
                     subject: email.subject.clone(),
                     body: email.body.clone(),
+                    body_html: None,
                     preview: email.preview.clone(),
                     date: email.date.clone(),
                     status: DbEmailStatus::Unread,
@@ -287,22 +936,41 @@ impl App {
                     folder: "inbox".to_string(),
                     thread_id: None,
                     account_id: current_account_id,
+                    message_id: None,
+                    imap_uid: None,
+                    in_reply_to: None,
+                    references: None,
+                    modseq: None,
+                    pgp_status: None,
+                    list_headers: None,
+                    headers: None,
+                    has_attachment: false,
                 };
                 db.insert_email(&db_email).await?;
             }
             mock_emails
         } else {
-            db_emails
-                .into_iter()
-                .map(|e| Email {
+            let mut converted = Vec::with_capacity(db_emails.len());
+            for e in db_emails {
+                let attachments = db.get_attachment_manifest(e.id).await.unwrap_or_default();
+                converted.push(Email {
                     id: e.id,
                     from: e.from_address,
                     subject: e.subject,
                     preview: e.preview,
                     body: e.body,
+                    body_html: e.body_html,
                     date: e.date,
-                })
-                .collect()
+                    attachments,
+                    pgp_status: e.pgp_status,
+                    list_headers: e.list_headers,
+                    message_id: e.message_id,
+                    references: e.references,
+                    thread_id: e.thread_id,
+                    status: e.status,
+                });
+            }
+            converted
         };
 
         // Check if there's a draft available (but don't load it yet)
@@ -318,9 +986,11 @@ impl App {
         // Check if we have a real mailbox configured (in config or database)
         let has_configured_mailbox = !config.accounts.is_empty() || !accounts.is_empty();
         
-        eprintln!("DEBUG: has_configured_mailbox = {} (config.accounts={}, db.accounts={})", 
-            has_configured_mailbox, config.accounts.len(), accounts.len());
-        eprintln!("DEBUG: credentials_exist = {}", credentials_manager.credentials_exist());
+        log::debug!(
+            "has_configured_mailbox = {} (config.accounts={}, db.accounts={})",
+            has_configured_mailbox, config.accounts.len(), accounts.len()
+        );
+        log::debug!("credentials_exist = {}", credentials_manager.credentials_exist());
         
         // Determine initial view based on credentials and mailbox configuration
         let (initial_view, credentials, credentials_setup_state, credentials_unlock_state) = 
@@ -359,7 +1029,7 @@ impl App {
                 )
             };
 
-        Ok(Self {
+        let mut app = Self {
             emails,
             current_view: initial_view,
             selected_index: 0,
@@ -369,6 +1039,7 @@ impl App {
             db: Some(db),
             draft_id,
             show_preview_panel: false,
+            listing_style: ListingStyle::Flat,
             visual_mode: false,
             visual_selections: HashSet::new(),
             visual_anchor: None,
@@ -376,11 +1047,53 @@ impl App {
             credentials: credentials.clone(),
             credentials_setup_state,
             credentials_unlock_state,
+            command_line_state: None,
+            command_history: Vec::new(),
+            external_editor_requested: false,
+            shortcuts: crate::keymap::Shortcuts::from_config(&config.shortcuts),
             config,
             accounts,
             current_account_id,
-            email_sync_manager: Some(crate::email_sync::EmailSyncManager::new(credentials)),
-        })
+            email_sync_manager: Some({
+                let mut manager = crate::email_sync::EmailSyncManager::new();
+                if let Some(creds) = credentials.clone() {
+                    let account_name = current_account_id
+                        .and_then(|id| accounts.iter().find(|a| a.id == id))
+                        .map(|a| a.name.clone())
+                        .unwrap_or_else(|| "default".to_string());
+                    manager.add_account(account_name, creds);
+                }
+                manager
+            }),
+            pending_validation: Arc::new(Mutex::new(None)),
+            pending_oauth: Arc::new(Mutex::new(None)),
+            pending_device_auth: Arc::new(Mutex::new(None)),
+            contacts_state: None,
+            pending_count: String::new(),
+            detail_link_follow_mode: false,
+            detail_link_follow_digits: String::new(),
+            notification_history: VecDeque::new(),
+            notification_history_state: None,
+            current_folder: "inbox".to_string(),
+            folders: Vec::new(),
+            folder_list_state: None,
+            thread_groups: Vec::new(),
+            thread_list_state: None,
+            pending_folder_list: Arc::new(Mutex::new(None)),
+            show_html_view: false,
+            show_html_source: false,
+            pending_send: Arc::new(Mutex::new(None)),
+            pending_mail_watch: Arc::new(Mutex::new(Vec::new())),
+            mail_watch_stop: None,
+            last_sync_at: Arc::new(Mutex::new(None)),
+        };
+
+        // Initial sync has already happened by the time credentials are loaded (the cached
+        // emails above came straight from the database); kick off the background watcher so
+        // new mail starts showing up without the user having to manually refresh.
+        app.start_mail_watch();
+
+        Ok(app)
     }
 
     /// Sync accounts from config to database
@@ -401,6 +1114,11 @@ impl App {
                     is_default: config_account.default,
                     color: config_account.color.clone(),
                     display_order: config_account.display_order.unwrap_or(999),
+                    backend_kind: config_account.backend.db_tag().to_string(),
+                    backend_path: config_account
+                        .backend
+                        .local_path()
+                        .map(|p| p.display().to_string()),
                 };
                 let id = db.save_account(&db_account).await?;
                 db_accounts.push(DbAccount {
@@ -422,6 +1140,14 @@ impl App {
                 preview: "Hi team, I wanted to share some updates on our Q1 planning...".to_string(),
                 body: "Hi team,\n\nI wanted to share some updates on our Q1 planning. We've made significant progress on the roadmap and I'd like to schedule a meeting to discuss next steps.\n\nLooking forward to your feedback.\n\nBest regards,\nAlice".to_string(),
                 date: "2026-01-10 14:30".to_string(),
+                body_html: None,
+                attachments: Vec::new(),
+                pgp_status: None,
+                list_headers: None,
+                message_id: None,
+                references: None,
+                thread_id: None,
+                status: DbEmailStatus::Unread,
             },
             Email {
                 id: 0,
@@ -430,6 +1156,14 @@ impl App {
                 preview: "Here are the notes from our meeting yesterday...".to_string(),
                 body: "Here are the notes from our meeting yesterday:\n\n1. Discussed new feature requirements\n2. Reviewed timeline for implementation\n3. Assigned tasks to team members\n\nPlease review and let me know if I missed anything.\n\nBob".to_string(),
                 date: "2026-01-10 09:15".to_string(),
+                body_html: None,
+                attachments: Vec::new(),
+                pgp_status: None,
+                list_headers: None,
+                message_id: None,
+                references: None,
+                thread_id: None,
+                status: DbEmailStatus::Unread,
             },
             Email {
                 id: 0,
@@ -438,6 +1172,14 @@ impl App {
                 preview: "A new issue has been opened in your repository...".to_string(),
                 body: "A new issue has been opened in your repository fluxoz/tume:\n\nTitle: Create a TUI stub for this project\n\nThis project is meant to be a TUI email client...".to_string(),
                 date: "2026-01-09 22:45".to_string(),
+                body_html: None,
+                attachments: Vec::new(),
+                pgp_status: None,
+                list_headers: None,
+                message_id: None,
+                references: None,
+                thread_id: None,
+                status: DbEmailStatus::Unread,
             },
             Email {
                 id: 0,
@@ -446,6 +1188,14 @@ impl App {
                 preview: "Thanks for submitting the budget request...".to_string(),
                 body: "Thanks for submitting the budget request. I've reviewed the numbers and everything looks good. Approved!\n\nCharlie".to_string(),
                 date: "2026-01-09 16:20".to_string(),
+                body_html: None,
+                attachments: Vec::new(),
+                pgp_status: None,
+                list_headers: None,
+                message_id: None,
+                references: None,
+                thread_id: None,
+                status: DbEmailStatus::Unread,
             },
             Email {
                 id: 0,
@@ -454,6 +1204,14 @@ impl App {
                 preview: "This week in tech: Rust 1.92 brings exciting new features...".to_string(),
                 body: "This week in tech:\n\n- Rust 1.92 Released with improved compile times\n- New TUI libraries gaining popularity\n- Terminal applications making a comeback\n\nRead more at techblog.com".to_string(),
                 date: "2026-01-09 08:00".to_string(),
+                body_html: None,
+                attachments: Vec::new(),
+                pgp_status: None,
+                list_headers: None,
+                message_id: None,
+                references: None,
+                thread_id: None,
+                status: DbEmailStatus::Unread,
             },
         ]
     }
@@ -478,9 +1236,29 @@ impl App {
         }
     }
 
+    /// Accumulate one digit of a pending vim-style count prefix (e.g. the `5` then `3` of
+    /// `53j`). A leading `0` doesn't start a count (it's not a valid vim count prefix on its
+    /// own), but is accepted once a count is already pending, same as vim's `10j`.
+    pub fn push_pending_count_digit(&mut self, digit: char) {
+        if digit == '0' && self.pending_count.is_empty() {
+            return;
+        }
+        self.pending_count.push(digit);
+    }
+
+    /// Consume and clamp the pending count buffer, defaulting to 1 when empty, and resetting it
+    /// for the next motion/action. Clamped to the inbox length so a stray `999d` can't panic.
+    pub fn take_pending_count(&mut self) -> usize {
+        let count = self.pending_count.parse().unwrap_or(1).max(1);
+        self.pending_count.clear();
+        count.min(self.emails.len().max(1))
+    }
+
     pub fn open_email(&mut self) {
         if !self.emails.is_empty() && self.current_view == View::InboxList {
             self.current_view = View::EmailDetail;
+            self.show_html_view = false;
+            self.show_html_source = false;
         }
     }
 
@@ -490,6 +1268,185 @@ impl App {
         }
     }
 
+    /// Toggle `EmailDetail` between `body` and a styled rendering of `body_html` (see
+    /// `crate::ui::render_email_detail`). A no-op when the open message has no HTML alternative.
+    pub fn toggle_html_view(&mut self) {
+        let Some(email) = self.get_selected_email() else { return };
+        if email.body_html.is_some() {
+            self.show_html_view = !self.show_html_view;
+            self.show_html_source = false;
+        }
+    }
+
+    /// While the HTML view is showing, toggle between the styled rendering and the raw
+    /// `body_html` source. A no-op when `show_html_view` is off or the message has no HTML
+    /// alternative.
+    pub fn toggle_html_source(&mut self) {
+        if !self.show_html_view {
+            return;
+        }
+        let Some(email) = self.get_selected_email() else { return };
+        if email.body_html.is_some() {
+            self.show_html_source = !self.show_html_source;
+        }
+    }
+
+    /// The plain text `crate::links::find_links` should scan for the currently displayed email
+    /// body: the rendered `text/plain` body, or `body_html` run through `mime::html_to_text`
+    /// when the HTML view is showing, so tag markup doesn't get mistaken for link text.
+    pub(crate) fn detail_link_source(&self) -> Option<String> {
+        let email = self.get_selected_email()?;
+        if self.show_html_view {
+            email.body_html.as_deref().map(crate::mime::html_to_text)
+        } else {
+            Some(email.body.clone())
+        }
+    }
+
+    /// Enter follow-link mode in the message reading view: numbers every link in the displayed
+    /// body and waits for digit keys to pick one, shadowing the vim-style count-prefix digits
+    /// `handle_detail_keys` normally reads. A no-op if the body has no links to follow.
+    pub fn detail_enter_link_follow_mode(&mut self) {
+        let has_links = self.detail_link_source()
+            .map(|text| !crate::links::find_links(&text).is_empty())
+            .unwrap_or(false);
+        if has_links {
+            self.detail_link_follow_mode = true;
+            self.detail_link_follow_digits.clear();
+        }
+    }
+
+    pub fn detail_exit_link_follow_mode(&mut self) {
+        self.detail_link_follow_mode = false;
+        self.detail_link_follow_digits.clear();
+    }
+
+    pub fn detail_link_follow_digit(&mut self, digit: char) {
+        self.detail_link_follow_digits.push(digit);
+    }
+
+    /// Open the link numbered by the accumulated digits, then leave follow-link mode. A URL is
+    /// opened with the OS default handler; a `mailto:` target starts a fresh compose draft
+    /// addressed to it, since there's no existing draft to fold it into here the way there can
+    /// be in `App::compose_link_follow_confirm`.
+    pub fn detail_link_follow_confirm(&mut self) {
+        let target = self.detail_link_follow_digits.parse::<usize>().ok().and_then(|index| {
+            self.detail_link_source()
+                .map(|text| crate::links::find_links(&text))
+                .unwrap_or_default()
+                .into_iter()
+                .find(|l| l.index == index)
+                .map(|l| l.target)
+        });
+        self.detail_exit_link_follow_mode();
+
+        match target {
+            Some(crate::links::LinkTarget::Url(url)) => {
+                if let Err(e) = crate::links::open_url(&url) {
+                    self.status_message = Some(format!("Failed to open link: {}", e));
+                }
+            }
+            Some(crate::links::LinkTarget::Email(addr)) => {
+                self.enter_compose_mode();
+                if let Some(ref mut compose) = self.compose_state {
+                    compose.recipients = addr;
+                }
+            }
+            None => {
+                self.status_message = Some("No such link".to_string());
+            }
+        }
+    }
+
+    /// Save the first attachment on the selected email into the user's downloads directory
+    /// (falling back to their home directory), reporting the outcome via `status_message`. Used
+    /// by the `save_attachment` detail-view action.
+    pub fn save_selected_attachment(&mut self) {
+        let Some(email) = self.get_selected_email() else { return };
+        let Some(meta) = email.attachments.first().cloned() else {
+            self.status_message = Some("Selected email has no attachments".to_string());
+            return;
+        };
+
+        let attachment = self.block_on_db(move |db| async move { db.get_attachment(meta.id).await });
+
+        let result = (|| -> anyhow::Result<std::path::PathBuf> {
+            let attachment = attachment
+                .transpose()?
+                .ok_or_else(|| anyhow::anyhow!("attachment no longer exists"))?;
+            let dir = dirs::download_dir()
+                .or_else(dirs::home_dir)
+                .ok_or_else(|| anyhow::anyhow!("could not find a downloads directory"))?;
+            std::fs::create_dir_all(&dir)?;
+            let path = dir.join(&attachment.filename);
+            std::fs::write(&path, &attachment.data)?;
+            Ok(path)
+        })();
+
+        self.status_message = Some(match result {
+            Ok(path) => format!("Saved attachment to {}", path.display()),
+            Err(e) => format!("Failed to save attachment: {}", e),
+        });
+    }
+
+    /// Serialize `emails` as an mboxrd file (inspired by meli's `export-mbox` command) into the
+    /// user's downloads directory (falling back to their home directory), reporting the outcome
+    /// via `status_message`. Used by the `Export` action from both a single selection and a
+    /// visual-mode batch.
+    fn export_to_mbox(&mut self, emails: &[Email]) {
+        if emails.is_empty() {
+            return;
+        }
+
+        let result = (|| -> anyhow::Result<std::path::PathBuf> {
+            let dir = dirs::download_dir()
+                .or_else(dirs::home_dir)
+                .ok_or_else(|| anyhow::anyhow!("could not find a downloads directory"))?;
+            std::fs::create_dir_all(&dir)?;
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            let path = dir.join(format!("tume-export-{}.mbox", timestamp));
+            let mbox: String = emails.iter().map(Self::mbox_message).collect();
+            std::fs::write(&path, mbox)?;
+            Ok(path)
+        })();
+
+        self.status_message = Some(match result {
+            Ok(path) => format!("Exported {} email(s) to {}", emails.len(), path.display()),
+            Err(e) => format!("Failed to export mbox: {}", e),
+        });
+    }
+
+    /// Render `email` as one mboxrd message: a `From <sender> <date>` separator line, a small set
+    /// of raw headers, then the body with every line matching `^>*From ` given one more leading
+    /// `>` (mboxrd quoting, so a literal "From " at the start of a body line is never mistaken
+    /// for the next message's separator), and a trailing blank line to terminate the message.
+    ///
+    /// `email.date` is used as-is for the separator's asctime-style date field rather than
+    /// reparsed, since this repo doesn't carry a date-parsing dependency; it's already the
+    /// human-readable date `EmailDatabase` stored the message under.
+    fn mbox_message(email: &Email) -> String {
+        let mut out = format!("From {} {}\n", email.from, email.date);
+        out.push_str(&format!("From: {}\n", email.from));
+        out.push_str(&format!("Subject: {}\n", email.subject));
+        out.push_str(&format!("Date: {}\n", email.date));
+        if let Some(ref message_id) = email.message_id {
+            out.push_str(&format!("Message-ID: {}\n", message_id));
+        }
+        out.push('\n');
+        for line in email.body.lines() {
+            if line.trim_start_matches('>').starts_with("From ") {
+                out.push('>');
+            }
+            out.push_str(line);
+            out.push('\n');
+        }
+        out.push('\n');
+        out
+    }
+
     pub fn perform_action(&mut self, action: Action) {
         match action {
             Action::Delete => {
@@ -497,7 +1454,7 @@ impl App {
                     let email = &self.emails[self.selected_index];
                     let email_id = email.id;
                     let email_subject = email.subject.clone();
-                    
+
                     // Delete from database if available
                     // Note: Using fire-and-forget pattern as this is a background operation.
                     // The UI state is updated immediately for responsiveness. If the database
@@ -506,21 +1463,22 @@ impl App {
                         let db_clone = db.clone();
                         tokio::spawn(async move {
                             if let Err(e) = db_clone.delete_email(email_id).await {
-                                eprintln!("Failed to delete email from database: {}", e);
+                                log::error!("Failed to delete email from database: {}", e);
                             }
                         });
                     }
-                    
+                    self.spawn_backend_delete(email_id);
+
                     // Remove email from the vector
                     self.emails.remove(self.selected_index);
-                    
+
                     // Adjust selected_index if needed
                     if !self.emails.is_empty() {
                         self.selected_index = self.selected_index.min(self.emails.len() - 1);
                     } else {
                         self.selected_index = 0;
                     }
-                    
+
                     self.status_message = Some(format!("Deleted email: {}", email_subject));
                 }
             }
@@ -529,35 +1487,36 @@ impl App {
                     let email = &self.emails[self.selected_index];
                     let email_id = email.id;
                     let email_subject = email.subject.clone();
-                    
+
                     // Archive in database if available
                     // Note: Using fire-and-forget pattern for background database operation.
                     if let Some(ref db) = self.db {
                         let db_clone = db.clone();
                         tokio::spawn(async move {
                             if let Err(e) = db_clone.archive_email(email_id).await {
-                                eprintln!("Failed to archive email in database: {}", e);
+                                log::error!("Failed to archive email in database: {}", e);
                             }
                         });
                     }
-                    
+                    self.spawn_backend_move(email_id, "\\Archive", "Archive");
+
                     // Remove email from the vector
                     self.emails.remove(self.selected_index);
-                    
+
                     // Adjust selected_index if needed
                     if !self.emails.is_empty() {
                         self.selected_index = self.selected_index.min(self.emails.len() - 1);
                     } else {
                         self.selected_index = 0;
                     }
-                    
+
                     self.status_message = Some(format!("Archived email: {}", email_subject));
                 }
             }
             Action::Reply => {
                 if !self.emails.is_empty() {
-                    let email = &self.emails[self.selected_index];
-                    self.status_message = Some(format!("Replying to: {}", email.from));
+                    let email = self.emails[self.selected_index].clone();
+                    self.begin_reply(&email);
                 }
             }
             Action::Compose => {
@@ -565,52 +1524,333 @@ impl App {
             }
             Action::Forward => {
                 if !self.emails.is_empty() {
-                    let email = &self.emails[self.selected_index];
-                    self.status_message = Some(format!("Forwarding email: {}", email.subject));
+                    let email = self.emails[self.selected_index].clone();
+                    self.begin_forward(&email);
+                }
+            }
+            Action::Export => {
+                if let Some(email) = self.emails.get(self.selected_index).cloned() {
+                    self.export_to_mbox(&[email]);
+                }
+            }
+            Action::ReplyToList => {
+                if let Some(email) = self.emails.get(self.selected_index).cloned() {
+                    match email.list_post_address() {
+                        Some(address) => self.begin_reply_to_list(&email, &address),
+                        None => self.status_message = Some("This message has no List-Post address to reply to".to_string()),
+                    }
+                }
+            }
+            Action::ListUnsubscribe => {
+                if let Some(email) = self.emails.get(self.selected_index).cloned() {
+                    match email.list_unsubscribe_target() {
+                        Some(ListUnsubscribeTarget::Mailto(address)) => self.begin_unsubscribe_email(&address),
+                        Some(ListUnsubscribeTarget::Url(url)) => {
+                            self.status_message = Some(format!("Opening unsubscribe link: {}", url));
+                            self.spawn_open_url(url);
+                        }
+                        None => self.status_message = Some("This message has no List-Unsubscribe header".to_string()),
+                    }
                 }
             }
         }
     }
 
-    pub fn enter_compose_mode(&mut self) {
-        // If we already have a compose state (from a previous ESC exit), just switch to it
-        if self.compose_state.is_some() {
-            self.current_view = View::Compose;
-            return;
-        }
-
-        // Try to load saved draft from database (for new session)
-        if self.db.is_some() {
-            let db_clone = self.db.as_ref().unwrap().clone();
+    /// The [`AccountBackend`] and namespace string for the current account, if one is selected -
+    /// same precedence `sync_accounts_from_config` uses when populating `accounts`. `None` if no
+    /// account is selected or its backend couldn't be resolved to one the app actually has
+    /// credentials/a path for.
+    fn current_account_backend(&self) -> Option<(AccountBackend, String)> {
+        let account = self
+            .accounts
+            .iter()
+            .find(|a| Some(a.id) == self.current_account_id)?;
+        let backend = AccountBackend::from_db(&account.backend_kind, account.backend_path.as_deref());
+        Some((backend, account.email.clone()))
+    }
 
-            // Try to load draft synchronously using spawn_blocking workaround
-            // This avoids blocking the event loop while still accessing the database
-            let runtime = tokio::runtime::Handle::try_current();
-            if let Ok(handle) = runtime {
-                // Use spawn_blocking to avoid nested runtime issues
-                let draft_result = std::thread::spawn(move || {
-                    handle.block_on(async { db_clone.get_drafts().await })
-                })
-                .join();
+    /// Route a Delete through [`crate::backend::MailBackend`] so it hits the same backing store
+    /// (live IMAP mailbox or local Maildir/notmuch) the email was fetched from, not just the
+    /// local database. Fire-and-forget, like the database-side delete it runs alongside.
+    fn spawn_backend_delete(&self, email_id: i64) {
+        let Some(db) = self.db.clone() else { return };
+        let Some((backend, account_id)) = self.current_account_backend() else { return };
+        let credentials = self.credentials.clone();
+
+        tokio::spawn(async move {
+            let Ok(Some(email)) = db.get_email_by_id(email_id).await else { return };
+            let Some(msg_ref) = crate::backend::message_ref(&backend, &email) else { return };
+            let folder = email.folder.clone();
+
+            let result = tokio::task::spawn_blocking(move || {
+                let store = crate::backend::for_account(&backend, &account_id, credentials)?;
+                crate::backend::MailBackend::delete_message(&store, &folder, &msg_ref)
+            })
+            .await;
+
+            match result {
+                Ok(Err(e)) => log::error!("Failed to delete message from backend: {}", e),
+                Err(e) => log::error!("Backend delete task panicked: {}", e),
+                Ok(Ok(())) => {}
+            }
+        });
+    }
 
-                if let Ok(Ok(drafts)) = draft_result {
-                    if let Some(draft) = drafts.first() {
-                        // Load the draft into compose state
-                        self.compose_state = Some(ComposeState {
-                            recipients: draft.recipients.clone(),
-                            subject: draft.subject.clone(),
-                            body: draft.body.clone(),
-                            current_field: ComposeField::Recipients,
-                            mode: ComposeMode::Normal,
-                            show_preview: false,
-                            cursor_position: 0,
-                            initial_traversal_complete: !draft.body.is_empty(),
-                        });
-                        self.current_view = View::Compose;
-                        self.draft_id = Some(draft.id);
-                        return;
-                    }
-                }
+    /// Route an Archive (or any other same-account move) through [`crate::backend::MailBackend`].
+    /// `special_use` picks the destination folder when the backend can resolve one (currently
+    /// just IMAP, via its synced folder list); `fallback` is used otherwise, e.g. for a Maildir
+    /// account where the destination is just whatever subdirectory name the user expects.
+    fn spawn_backend_move(&self, email_id: i64, special_use: &'static str, fallback: &'static str) {
+        let Some(db) = self.db.clone() else { return };
+        let Some((backend, account_id)) = self.current_account_backend() else { return };
+        let credentials = self.credentials.clone();
+        let db_for_resolve = db.clone();
+        let current_account_id = self.current_account_id;
+
+        tokio::spawn(async move {
+            let Ok(Some(email)) = db.get_email_by_id(email_id).await else { return };
+            let Some(msg_ref) = crate::backend::message_ref(&backend, &email) else { return };
+            let folder = email.folder.clone();
+            let dest_folder = db_for_resolve
+                .resolve_special_use_folder(current_account_id, special_use)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or_else(|| fallback.to_string());
+
+            let result = tokio::task::spawn_blocking(move || {
+                let store = crate::backend::for_account(&backend, &account_id, credentials)?;
+                crate::backend::MailBackend::move_message(&store, &folder, &msg_ref, &dest_folder)
+            })
+            .await;
+
+            match result {
+                Ok(Err(e)) => log::error!("Failed to move message in backend: {}", e),
+                Err(e) => log::error!("Backend move task panicked: {}", e),
+                Ok(Ok(())) => {}
+            }
+        });
+    }
+
+    /// Build a reply draft for `email`: `Re:` subject (no double-prefixing), the original sender
+    /// as recipient, a quoted/attributed body, and threaded `In-Reply-To`/`References` headers
+    /// so the reply stays in the same conversation (see [`Self::request_send_email`]).
+    fn begin_reply(&mut self, email: &Email) {
+        self.compose_state = Some(ComposeState {
+            recipients: email.from.clone(),
+            subject: Self::prefixed_subject("Re:", &email.subject),
+            body: Self::quote_body(email),
+            current_field: ComposeField::Body,
+            mode: ComposeMode::Normal,
+            show_preview: false,
+            cursor_position: 0,
+            initial_traversal_complete: true,
+            sign: false,
+            encrypt: false,
+            completion_candidates: Vec::new(),
+            completion_index: 0,
+            in_reply_to: email.message_id.clone(),
+            references: Self::build_references(email),
+            attachments: Vec::new(),
+            attachment_selected: 0,
+            attachment_prompt: None,
+            link_follow_mode: false,
+            link_follow_digits: String::new(),
+        });
+        self.current_view = View::Compose;
+        self.draft_id = None;
+        self.status_message = Some(format!("Replying to: {}", email.from));
+    }
+
+    /// Build a forward draft for `email`: `Fwd:` subject, an empty recipient field for the user
+    /// to fill in, and the original message quoted/attributed below. Forwards start a fresh
+    /// conversation rather than threading onto the original (no `In-Reply-To`/`References`).
+    fn begin_forward(&mut self, email: &Email) {
+        self.compose_state = Some(ComposeState {
+            recipients: String::new(),
+            subject: Self::prefixed_subject("Fwd:", &email.subject),
+            body: Self::quote_body(email),
+            current_field: ComposeField::Recipients,
+            mode: ComposeMode::Normal,
+            show_preview: false,
+            cursor_position: 0,
+            initial_traversal_complete: true,
+            sign: false,
+            encrypt: false,
+            completion_candidates: Vec::new(),
+            completion_index: 0,
+            in_reply_to: None,
+            references: None,
+            attachments: Vec::new(),
+            attachment_selected: 0,
+            attachment_prompt: None,
+            link_follow_mode: false,
+            link_follow_digits: String::new(),
+        });
+        self.current_view = View::Compose;
+        self.draft_id = None;
+        self.status_message = Some(format!("Forwarding email: {}", email.subject));
+    }
+
+    /// Build a reply-to-list draft for `email`: like [`Self::begin_reply`], but addressed to the
+    /// list's `List-Post` address (already extracted by [`Email::list_post_address`]) instead of
+    /// the original sender.
+    fn begin_reply_to_list(&mut self, email: &Email, list_address: &str) {
+        self.compose_state = Some(ComposeState {
+            recipients: list_address.to_string(),
+            subject: Self::prefixed_subject("Re:", &email.subject),
+            body: Self::quote_body(email),
+            current_field: ComposeField::Body,
+            mode: ComposeMode::Normal,
+            show_preview: false,
+            cursor_position: 0,
+            initial_traversal_complete: true,
+            sign: false,
+            encrypt: false,
+            completion_candidates: Vec::new(),
+            completion_index: 0,
+            in_reply_to: email.message_id.clone(),
+            references: Self::build_references(email),
+            attachments: Vec::new(),
+            attachment_selected: 0,
+            attachment_prompt: None,
+            link_follow_mode: false,
+            link_follow_digits: String::new(),
+        });
+        self.current_view = View::Compose;
+        self.draft_id = None;
+        self.status_message = Some(format!("Replying to list: {}", list_address));
+    }
+
+    /// Build an unsubscribe draft addressed to a `List-Unsubscribe` `mailto:` address - an empty
+    /// body with the conventional `unsubscribe` subject line most list managers look for.
+    fn begin_unsubscribe_email(&mut self, address: &str) {
+        self.compose_state = Some(ComposeState {
+            recipients: address.to_string(),
+            subject: "unsubscribe".to_string(),
+            body: String::new(),
+            current_field: ComposeField::Body,
+            mode: ComposeMode::Normal,
+            show_preview: false,
+            cursor_position: 0,
+            initial_traversal_complete: true,
+            sign: false,
+            encrypt: false,
+            completion_candidates: Vec::new(),
+            completion_index: 0,
+            in_reply_to: None,
+            references: None,
+            attachments: Vec::new(),
+            attachment_selected: 0,
+            attachment_prompt: None,
+            link_follow_mode: false,
+            link_follow_digits: String::new(),
+        });
+        self.current_view = View::Compose;
+        self.draft_id = None;
+        self.status_message = Some(format!("Unsubscribing via email to: {}", address));
+    }
+
+    /// Open `url` in the user's browser via `xdg-open`, matching the repo's convention of
+    /// shelling out to external tools (gpg, `$EDITOR`) rather than vendoring the functionality.
+    /// Fire-and-forget, like [`Self::spawn_backend_delete`]/[`Self::spawn_backend_move`] - nothing
+    /// in the UI depends on it completing.
+    fn spawn_open_url(&self, url: String) {
+        tokio::spawn(async move {
+            let result = tokio::task::spawn_blocking(move || std::process::Command::new("xdg-open").arg(&url).status()).await;
+
+            match result {
+                Ok(Ok(status)) if !status.success() => {
+                    log::error!("xdg-open exited with a failure status for the unsubscribe link")
+                }
+                Ok(Err(e)) => log::error!("Failed to spawn xdg-open: {}", e),
+                Err(e) => log::error!("xdg-open task panicked: {}", e),
+                Ok(Ok(_)) => {}
+            }
+        });
+    }
+
+    /// `Re:`/`Fwd:` a subject, skipping the prefix if it's already there (case-insensitively).
+    fn prefixed_subject(prefix: &str, subject: &str) -> String {
+        if subject.to_lowercase().starts_with(&prefix.to_lowercase()) {
+            subject.to_string()
+        } else {
+            format!("{} {}", prefix, subject)
+        }
+    }
+
+    /// Quote `email`'s body with `> ` on each line under an attribution line, for a reply or
+    /// forward draft to build on.
+    fn quote_body(email: &Email) -> String {
+        let quoted = email
+            .body
+            .lines()
+            .map(|line| format!("> {}", line))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!("\n\nOn {}, {} wrote:\n{}", email.date, email.from, quoted)
+    }
+
+    /// Outgoing `References` for a reply to `email`: its own `References` (if any) with its
+    /// `Message-ID` appended, oldest first - falling back to just the `Message-ID` alone.
+    fn build_references(email: &Email) -> Option<String> {
+        let message_id = email.message_id.as_deref()?;
+        match &email.references {
+            Some(refs) if !refs.is_empty() => Some(format!("{} {}", refs, message_id)),
+            _ => Some(message_id.to_string()),
+        }
+    }
+
+    pub fn enter_compose_mode(&mut self) {
+        // If we already have a compose state (from a previous ESC exit), just switch to it
+        if self.compose_state.is_some() {
+            self.current_view = View::Compose;
+            return;
+        }
+
+        // Try to load saved draft from database (for new session)
+        if self.db.is_some() {
+            let db_clone = self.db.as_ref().unwrap().clone();
+
+            // Try to load draft synchronously using spawn_blocking workaround
+            // This avoids blocking the event loop while still accessing the database
+            let runtime = tokio::runtime::Handle::try_current();
+            if let Ok(handle) = runtime {
+                // Use spawn_blocking to avoid nested runtime issues
+                let draft_result = std::thread::spawn(move || {
+                    handle.block_on(async { db_clone.get_drafts().await })
+                })
+                .join();
+
+                if let Ok(Ok(drafts)) = draft_result {
+                    if let Some(draft) = drafts.first() {
+                        // Load the draft into compose state
+                        self.compose_state = Some(ComposeState {
+                            recipients: draft.recipients.clone(),
+                            subject: draft.subject.clone(),
+                            body: draft.body.clone(),
+                            current_field: ComposeField::Recipients,
+                            mode: ComposeMode::Normal,
+                            show_preview: false,
+                            cursor_position: 0,
+                            initial_traversal_complete: !draft.body.is_empty(),
+                            sign: false,
+                            encrypt: false,
+                            completion_candidates: Vec::new(),
+                            completion_index: 0,
+                            in_reply_to: None,
+                            references: None,
+                            attachments: draft.attachments.clone(),
+                            attachment_selected: 0,
+                            attachment_prompt: None,
+                            link_follow_mode: false,
+                            link_follow_digits: String::new(),
+                        });
+                        self.current_view = View::Compose;
+                        self.draft_id = Some(draft.id);
+                        return;
+                    }
+                }
             }
         }
 
@@ -624,6 +1864,17 @@ impl App {
             show_preview: false,
             cursor_position: 0,
             initial_traversal_complete: false,
+            sign: false,
+            encrypt: false,
+            completion_candidates: Vec::new(),
+            completion_index: 0,
+            in_reply_to: None,
+            references: None,
+            attachments: Vec::new(),
+            attachment_selected: 0,
+            attachment_prompt: None,
+            link_follow_mode: false,
+            link_follow_digits: String::new(),
         });
         self.current_view = View::Compose;
         self.draft_id = None;
@@ -639,6 +1890,7 @@ impl App {
                     compose.subject = draft.subject.clone();
                     compose.body = draft.body.clone();
                     compose.initial_traversal_complete = !draft.body.is_empty();
+                    compose.attachments = draft.attachments.clone();
                     self.draft_id = Some(draft.id);
                 }
             }
@@ -670,13 +1922,17 @@ impl App {
                 compose.current_field = match compose.current_field {
                     ComposeField::Recipients => ComposeField::Subject,
                     ComposeField::Subject => ComposeField::Body,
-                    ComposeField::Body => ComposeField::Recipients,
+                    ComposeField::Body => ComposeField::Sign,
+                    ComposeField::Sign => ComposeField::Encrypt,
+                    ComposeField::Encrypt => ComposeField::Attachments,
+                    ComposeField::Attachments => ComposeField::Recipients,
                 };
                 // Reset cursor to end of field when switching
                 compose.cursor_position = match compose.current_field {
                     ComposeField::Recipients => compose.recipients.len(),
                     ComposeField::Subject => compose.subject.len(),
                     ComposeField::Body => compose.body.len(),
+                    ComposeField::Sign | ComposeField::Encrypt | ComposeField::Attachments => 0,
                 };
             }
         }
@@ -686,15 +1942,19 @@ impl App {
         if let Some(ref mut compose) = self.compose_state {
             if compose.mode == ComposeMode::Normal {
                 compose.current_field = match compose.current_field {
-                    ComposeField::Recipients => ComposeField::Body,
+                    ComposeField::Recipients => ComposeField::Attachments,
                     ComposeField::Subject => ComposeField::Recipients,
                     ComposeField::Body => ComposeField::Subject,
+                    ComposeField::Sign => ComposeField::Body,
+                    ComposeField::Encrypt => ComposeField::Sign,
+                    ComposeField::Attachments => ComposeField::Encrypt,
                 };
                 // Reset cursor to end of field when switching
                 compose.cursor_position = match compose.current_field {
                     ComposeField::Recipients => compose.recipients.len(),
                     ComposeField::Subject => compose.subject.len(),
                     ComposeField::Body => compose.body.len(),
+                    ComposeField::Sign | ComposeField::Encrypt | ComposeField::Attachments => 0,
                 };
             }
         }
@@ -702,13 +1962,18 @@ impl App {
 
     pub fn compose_enter_insert_mode(&mut self) {
         if let Some(ref mut compose) = self.compose_state {
-            if compose.mode == ComposeMode::Normal {
+            let is_text_field = matches!(
+                compose.current_field,
+                ComposeField::Recipients | ComposeField::Subject | ComposeField::Body
+            );
+            if compose.mode == ComposeMode::Normal && is_text_field {
                 compose.mode = ComposeMode::Insert;
                 // Set cursor to end of current field
                 compose.cursor_position = match compose.current_field {
                     ComposeField::Recipients => compose.recipients.len(),
                     ComposeField::Subject => compose.subject.len(),
                     ComposeField::Body => compose.body.len(),
+                    ComposeField::Sign | ComposeField::Encrypt | ComposeField::Attachments => 0,
                 };
             }
         }
@@ -742,6 +2007,99 @@ impl App {
         }
     }
 
+    /// Enter follow-link mode in the Markdown preview: numbers every link in the body and waits
+    /// for digit keys to pick one. Only available once the preview is showing, since that's the
+    /// only place links are visually numbered; a no-op if the body has no links to follow.
+    pub fn compose_enter_link_follow_mode(&mut self) {
+        if let Some(ref mut compose) = self.compose_state {
+            if compose.show_preview && !crate::links::find_links(&compose.body).is_empty() {
+                compose.link_follow_mode = true;
+                compose.link_follow_digits.clear();
+            }
+        }
+    }
+
+    pub fn compose_exit_link_follow_mode(&mut self) {
+        if let Some(ref mut compose) = self.compose_state {
+            compose.link_follow_mode = false;
+            compose.link_follow_digits.clear();
+        }
+    }
+
+    pub fn compose_link_follow_digit(&mut self, digit: char) {
+        if let Some(ref mut compose) = self.compose_state {
+            compose.link_follow_digits.push(digit);
+        }
+    }
+
+    /// Open the link numbered by the accumulated digits, then leave follow-link mode. A `mailto:`
+    /// target is folded into the Recipients field instead of starting a new draft, since one is
+    /// already in progress here.
+    pub fn compose_link_follow_confirm(&mut self) {
+        let target = self.compose_state.as_ref().and_then(|compose| {
+            compose.link_follow_digits.parse::<usize>().ok().and_then(|index| {
+                crate::links::find_links(&compose.body)
+                    .into_iter()
+                    .find(|l| l.index == index)
+                    .map(|l| l.target)
+            })
+        });
+        self.compose_exit_link_follow_mode();
+
+        match target {
+            Some(crate::links::LinkTarget::Url(url)) => {
+                if let Err(e) = crate::links::open_url(&url) {
+                    self.status_message = Some(format!("Failed to open link: {}", e));
+                }
+            }
+            Some(crate::links::LinkTarget::Email(addr)) => {
+                if let Some(ref mut compose) = self.compose_state {
+                    if compose.recipients.trim().is_empty() {
+                        compose.recipients = addr;
+                    } else {
+                        compose.recipients.push_str(", ");
+                        compose.recipients.push_str(&addr);
+                    }
+                }
+            }
+            None => {
+                self.status_message = Some("No such link".to_string());
+            }
+        }
+    }
+
+    /// Ask the main loop to suspend the TUI and open `$EDITOR` on the draft body. The app
+    /// itself never touches the terminal; `main::run_app` owns it and handles the actual
+    /// suspend/spawn/resume dance once it sees [`Self::take_external_editor_request`] return
+    /// true.
+    pub fn request_external_editor(&mut self) {
+        if self.compose_state.is_some() {
+            self.external_editor_requested = true;
+        }
+    }
+
+    /// Drain the external-editor request flag; `true` means the main loop should launch it now
+    pub fn take_external_editor_request(&mut self) -> bool {
+        std::mem::replace(&mut self.external_editor_requested, false)
+    }
+
+    /// Replace the draft's To/Subject/body with the external editor's output once it exits;
+    /// `recipients`/`subject` are `None` when the edited file had no `To:`/`Subject:` header to
+    /// parse back out, leaving that field as the user last set it in the TUI.
+    pub fn compose_set_from_editor(&mut self, recipients: Option<String>, subject: Option<String>, body: String) {
+        if let Some(ref mut compose) = self.compose_state {
+            if let Some(recipients) = recipients {
+                compose.recipients = recipients;
+            }
+            if let Some(subject) = subject {
+                compose.subject = subject;
+            }
+            compose.body = body;
+            compose.current_field = ComposeField::Body;
+            compose.cursor_position = compose.body.len();
+        }
+    }
+
     pub fn compose_insert_char(&mut self, c: char) {
         if let Some(ref mut compose) = self.compose_state {
             if compose.mode == ComposeMode::Insert {
@@ -749,6 +2107,7 @@ impl App {
                     ComposeField::Recipients => &mut compose.recipients,
                     ComposeField::Subject => &mut compose.subject,
                     ComposeField::Body => &mut compose.body,
+                    ComposeField::Sign | ComposeField::Encrypt | ComposeField::Attachments => return,
                 };
 
                 // Insert character at cursor position
@@ -767,6 +2126,7 @@ impl App {
                     ComposeField::Recipients => &mut compose.recipients,
                     ComposeField::Subject => &mut compose.subject,
                     ComposeField::Body => &mut compose.body,
+                    ComposeField::Sign | ComposeField::Encrypt | ComposeField::Attachments => return,
                 };
 
                 // Remove character before cursor
@@ -800,6 +2160,7 @@ impl App {
                     ComposeField::Recipients => compose.recipients.len(),
                     ComposeField::Subject => compose.subject.len(),
                     ComposeField::Body => compose.body.len(),
+                    ComposeField::Sign | ComposeField::Encrypt | ComposeField::Attachments => 0,
                 };
                 if compose.cursor_position < max_pos {
                     compose.cursor_position += 1;
@@ -809,17 +2170,152 @@ impl App {
     }
 
     pub fn compose_clear_field(&mut self) {
+        let selected = self
+            .compose_state
+            .as_ref()
+            .map(|c| c.attachment_selected)
+            .unwrap_or(0);
         if let Some(ref mut compose) = self.compose_state {
             if compose.mode == ComposeMode::Normal {
                 match compose.current_field {
                     ComposeField::Recipients => compose.recipients.clear(),
                     ComposeField::Subject => compose.subject.clear(),
                     ComposeField::Body => compose.body.clear(),
+                    // Nothing to clear on a toggle row; `s`/`Shift-E` flip these regardless of
+                    // which field has focus - see `Self::compose_toggle_sign`/`compose_toggle_encrypt`.
+                    ComposeField::Sign | ComposeField::Encrypt => {}
+                    // `d` removes just the highlighted attachment rather than clearing the whole
+                    // list, unlike the text fields above - see `Self::compose_remove_attachment`.
+                    ComposeField::Attachments => {
+                        self.compose_remove_attachment(selected);
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Attach `path` to the current draft; mirrors meli's `Attachments` cursor state. A no-op
+    /// outside compose mode.
+    pub fn compose_add_attachment(&mut self, path: PathBuf) {
+        if let Some(ref mut compose) = self.compose_state {
+            compose.attachments.push(path);
+            compose.attachment_selected = compose.attachments.len() - 1;
+            self.status_message = Some("Attachment added".to_string());
+        }
+    }
+
+    /// Drop the `idx`-th attachment from the current draft; out-of-range indices are a no-op.
+    pub fn compose_remove_attachment(&mut self, idx: usize) {
+        if let Some(ref mut compose) = self.compose_state {
+            if idx < compose.attachments.len() {
+                compose.attachments.remove(idx);
+                compose.attachment_selected = compose.attachment_selected.min(compose.attachments.len().saturating_sub(1));
+                self.status_message = Some("Attachment removed".to_string());
+            }
+        }
+    }
+
+    /// Move the `Attachments` field's highlight to the next row; a no-op outside that field.
+    pub fn compose_next_attachment(&mut self) {
+        if let Some(ref mut compose) = self.compose_state {
+            if compose.current_field == ComposeField::Attachments && !compose.attachments.is_empty() {
+                compose.attachment_selected = (compose.attachment_selected + 1).min(compose.attachments.len() - 1);
+            }
+        }
+    }
+
+    /// Move the `Attachments` field's highlight to the previous row; a no-op outside that field.
+    pub fn compose_previous_attachment(&mut self) {
+        if let Some(ref mut compose) = self.compose_state {
+            if compose.current_field == ComposeField::Attachments {
+                compose.attachment_selected = compose.attachment_selected.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Open the attachment path-entry prompt; a no-op unless on the `Attachments` field in Normal
+    /// mode. Confirmed with [`Self::compose_confirm_attachment_prompt`], cancelled with
+    /// [`Self::compose_cancel_attachment_prompt`].
+    pub fn compose_start_attachment_prompt(&mut self) {
+        if let Some(ref mut compose) = self.compose_state {
+            if compose.mode == ComposeMode::Normal && compose.current_field == ComposeField::Attachments {
+                compose.attachment_prompt = Some(AttachmentPromptState::default());
+            }
+        }
+    }
+
+    pub fn compose_cancel_attachment_prompt(&mut self) {
+        if let Some(ref mut compose) = self.compose_state {
+            compose.attachment_prompt = None;
+        }
+    }
+
+    pub fn compose_attachment_prompt_insert_char(&mut self, c: char) {
+        if let Some(ref mut compose) = self.compose_state {
+            if let Some(ref mut prompt) = compose.attachment_prompt {
+                if prompt.cursor_position <= prompt.buffer.len() {
+                    prompt.buffer.insert(prompt.cursor_position, c);
+                    prompt.cursor_position += 1;
+                }
+            }
+        }
+    }
+
+    pub fn compose_attachment_prompt_delete_char(&mut self) {
+        if let Some(ref mut compose) = self.compose_state {
+            if let Some(ref mut prompt) = compose.attachment_prompt {
+                if prompt.cursor_position > 0 {
+                    prompt.cursor_position -= 1;
+                    prompt.buffer.remove(prompt.cursor_position);
+                }
+            }
+        }
+    }
+
+    pub fn compose_attachment_prompt_cursor_left(&mut self) {
+        if let Some(ref mut compose) = self.compose_state {
+            if let Some(ref mut prompt) = compose.attachment_prompt {
+                if prompt.cursor_position > 0 {
+                    prompt.cursor_position -= 1;
                 }
             }
         }
     }
 
+    pub fn compose_attachment_prompt_cursor_right(&mut self) {
+        if let Some(ref mut compose) = self.compose_state {
+            if let Some(ref mut prompt) = compose.attachment_prompt {
+                if prompt.cursor_position < prompt.buffer.len() {
+                    prompt.cursor_position += 1;
+                }
+            }
+        }
+    }
+
+    /// Parse the prompt buffer as a file path and attach it, the way [`Self::contacts_confirm_add`]
+    /// parses its own buffer. Leaves the prompt open (but does nothing) on an empty buffer.
+    pub fn compose_confirm_attachment_prompt(&mut self) {
+        let Some(path) = self
+            .compose_state
+            .as_ref()
+            .and_then(|c| c.attachment_prompt.as_ref())
+            .map(|p| p.buffer.trim().to_string())
+        else {
+            return;
+        };
+
+        if path.is_empty() {
+            return;
+        }
+
+        if let Some(ref mut compose) = self.compose_state {
+            compose.attachment_prompt = None;
+        }
+
+        self.compose_add_attachment(PathBuf::from(path));
+    }
+
     /// Save the current draft to the database
     pub fn save_current_draft(&mut self) {
         if let Some(ref compose) = self.compose_state {
@@ -834,7 +2330,7 @@ impl App {
                             // The draft ID will be picked up on next compose entry or app restart
                         }
                         Err(e) => {
-                            eprintln!("Failed to save draft to database: {}", e);
+                            log::error!("Failed to save draft to database: {}", e);
                         }
                     }
                 });
@@ -881,47 +2377,407 @@ impl App {
             created_at: String::new(),
             updated_at: String::new(),
             account_id: self.current_account_id,
+            attachments: compose.attachments.clone(),
         }
     }
 
-    // Stub methods for GPG and Yubikey hooks
-    pub fn compose_encrypt_with_gpg(&mut self) {
-        self.status_message = Some("GPG encryption hook (stub)".to_string());
+    /// Toggle whether the outgoing message will be PGP-signed; see [`Self::compose_toggle_encrypt`]
+    pub fn compose_toggle_sign(&mut self) {
+        if let Some(ref mut compose) = self.compose_state {
+            compose.sign = !compose.sign;
+            self.status_message = Some(if compose.sign {
+                "PGP signing enabled for this message".to_string()
+            } else {
+                "PGP signing disabled".to_string()
+            });
+        }
     }
 
-    pub fn compose_sign_with_yubikey(&mut self) {
-        self.status_message = Some("Yubikey signing hook (stub)".to_string());
+    /// Toggle whether the outgoing message will be PGP-encrypted to its recipients. The actual
+    /// recipient public keys are looked up by `gpg` itself from the `To`/`Cc` addresses at send
+    /// time (see [`crate::gpg::build_outgoing_body`]); here we just warn early if there isn't
+    /// yet anyone to encrypt to.
+    pub fn compose_toggle_encrypt(&mut self) {
+        if let Some(ref mut compose) = self.compose_state {
+            compose.encrypt = !compose.encrypt;
+            self.status_message = Some(if compose.encrypt {
+                if crate::gpg::recipients_from_field(&compose.recipients).is_empty() {
+                    "PGP encryption enabled, but no recipients are set yet".to_string()
+                } else {
+                    "PGP encryption enabled for this message".to_string()
+                }
+            } else {
+                "PGP encryption disabled".to_string()
+            });
+        }
     }
 
-    pub fn toggle_preview_panel(&mut self) {
-        self.show_preview_panel = !self.show_preview_panel;
+    /// Produce the final outgoing MIME body for the compose draft, running it through GPG if the
+    /// user toggled sign/encrypt via [`Self::compose_toggle_sign`]/[`Self::compose_toggle_encrypt`].
+    /// Recipients for encryption default to the addresses parsed out of the `recipients` field.
+    /// [`Self::request_send_email`] is the "send" action that calls this.
+    pub fn compose_build_outgoing_body(&self) -> anyhow::Result<crate::gpg::OutgoingBody> {
+        let compose = self
+            .compose_state
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("no draft in progress"))?;
+        let recipients = crate::gpg::recipients_from_field(&compose.recipients);
+        crate::gpg::build_outgoing_body(
+            &compose.body,
+            compose.sign,
+            compose.encrypt,
+            &recipients,
+            &compose.attachments,
+        )
     }
 
-    // Visual mode methods
-    pub fn enter_visual_mode(&mut self) {
-        if self.current_view == View::InboxList && !self.visual_mode {
-            self.visual_mode = true;
-            self.visual_anchor = Some(self.selected_index);
-            self.visual_selections.clear();
-            self.visual_selections.insert(self.selected_index);
-            self.status_message = Some("-- VISUAL LINE --".to_string());
+    /// Send the current draft: build its (possibly signed/encrypted) MIME body via
+    /// [`Self::compose_build_outgoing_body`], then hand it to the outgoing transport in the
+    /// background, mirroring how [`Self::request_folder_sync`] backgrounds its IMAP call. The
+    /// account's [`crate::config::Account::send_backend`] (looked up by email, the same way
+    /// [`Self::sync_accounts_from_config`] matches a db account back to its config entry) wins
+    /// when set - a distinct SMTP server or `sendmail` command - otherwise this falls back to
+    /// the credentials-derived [`crate::email_sync::EmailSyncManager`], the only path a
+    /// local-only (`Maildir`/`Notmuch`) account with no `send_backend` has no way to use. The
+    /// result lands in `pending_send` for [`Self::poll_send_result`] to drain next frame.
+    pub fn request_send_email(&mut self) {
+        let Some(ref compose) = self.compose_state else { return };
+
+        let outgoing = match self.compose_build_outgoing_body() {
+            Ok(outgoing) => outgoing,
+            Err(e) => {
+                self.status_message = Some(format!("Failed to prepare message: {}", e));
+                return;
+            }
+        };
+
+        let db_account = self.current_account_id.and_then(|id| self.accounts.iter().find(|a| a.id == id));
+        let account_name = db_account.map(|a| a.name.clone()).unwrap_or_else(|| "default".to_string());
+        let send_backend_account = db_account
+            .and_then(|db_account| self.config.accounts.values().find(|a| a.email == db_account.email))
+            .filter(|a| a.send_backend.is_some())
+            .cloned();
+
+        let to = compose.recipients.clone();
+        let subject = compose.subject.clone();
+        let in_reply_to = compose.in_reply_to.clone();
+        let references = compose.references.clone();
+        let slot = self.pending_send.clone();
+
+        match send_backend_account {
+            Some(account) => {
+                tokio::spawn(async move {
+                    let result = async {
+                        let transport = tokio::task::spawn_blocking(move || {
+                            crate::email_sync::send_transport_for_account(&account)
+                        })
+                        .await
+                        .map_err(|e| anyhow::anyhow!("Task join error: {}", e))??;
+                        transport
+                            .send_threaded_mime_email(
+                                &to,
+                                &subject,
+                                &outgoing.content_type,
+                                &outgoing.body,
+                                in_reply_to.as_deref(),
+                                references.as_deref(),
+                            )
+                            .await
+                    }
+                    .await
+                    .map_err(|e: anyhow::Error| e.to_string());
+                    *slot.lock().unwrap() = Some(result);
+                });
+            }
+            None => {
+                let Some(ref sync_manager) = self.email_sync_manager else {
+                    self.status_message = Some("Cannot send: no credentials configured".to_string());
+                    return;
+                };
+                let sync_manager = sync_manager.clone();
+
+                tokio::spawn(async move {
+                    let result = sync_manager
+                        .send_threaded_mime_email(
+                            &account_name,
+                            &to,
+                            &subject,
+                            &outgoing.content_type,
+                            &outgoing.body,
+                            in_reply_to.as_deref(),
+                            references.as_deref(),
+                        )
+                        .await
+                        .map_err(|e| e.to_string());
+                    *slot.lock().unwrap() = Some(result);
+                });
+            }
         }
-    }
 
-    pub fn exit_visual_mode(&mut self) {
-        self.visual_mode = false;
-        self.visual_selections.clear();
-        self.visual_anchor = None;
-        // Don't clear status message here - it may contain action results
+        self.status_message = Some("Sending...".to_string());
     }
 
-    pub fn update_visual_selection(&mut self) {
-        if let Some(anchor) = self.visual_anchor {
-            self.visual_selections.clear();
-            let start = anchor.min(self.selected_index);
-            let end = anchor.max(self.selected_index);
-            for i in start..=end {
-                self.visual_selections.insert(i);
+    /// Drain a completed send, if any: on success, clear the draft (both in-memory and from the
+    /// database) and return to the inbox; on failure, surface the error and leave the draft in
+    /// place so the user can retry.
+    pub fn poll_send_result(&mut self) {
+        let result = self.pending_send.lock().unwrap().take();
+        let Some(result) = result else { return };
+
+        match result {
+            Ok(()) => {
+                if let (Some(db), Some(draft_id)) = (self.db.clone(), self.draft_id.take()) {
+                    tokio::spawn(async move {
+                        if let Err(e) = db.delete_draft(draft_id).await {
+                            log::error!("Failed to delete sent draft from database: {}", e);
+                        }
+                    });
+                }
+                self.compose_state = None;
+                self.current_view = View::InboxList;
+                self.status_message = Some("Message sent".to_string());
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to send message: {}", e));
+            }
+        }
+    }
+
+    /// How often the polling fallback in [`Self::start_mail_watch`] re-checks the server when
+    /// it doesn't support IMAP `IDLE`.
+    const MAIL_WATCH_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+    /// Start (or restart) the background new-mail watcher for the current account/folder: IMAP
+    /// `IDLE` push sync when the server supports it, falling back to polling every
+    /// [`Self::MAIL_WATCH_POLL_INTERVAL`] otherwise. Call once the initial sync has settled, and
+    /// again whenever the active account changes (see [`Self::switch_to_account`]) - the previous
+    /// watcher is told to stop via `mail_watch_stop` first, so it doesn't keep fetching into the
+    /// same `pending_mail_watch` queue for an account that's no longer active.
+    pub fn start_mail_watch(&mut self) {
+        if let Some(stop) = self.mail_watch_stop.take() {
+            stop.store(true, Ordering::Relaxed);
+        }
+
+        let (Some(sync_manager), Some(db)) = (self.email_sync_manager.clone(), self.db.clone()) else {
+            return;
+        };
+        if !sync_manager.is_configured() {
+            return;
+        }
+
+        let account_id = self.current_account_id;
+        let folder = self.current_folder.clone();
+        let stop = Arc::new(AtomicBool::new(false));
+        self.mail_watch_stop = Some(stop.clone());
+        let slot = self.pending_mail_watch.clone();
+        let last_sync = self.last_sync_at.clone();
+        *last_sync.lock().unwrap() = None;
+
+        tokio::spawn(async move {
+            let initial_state = db.get_sync_state(account_id, &folder).await.ok().flatten();
+            let mut last_uid = initial_state
+                .as_ref()
+                .map(|s| s.last_seen_uid.max(0) as u32)
+                .unwrap_or(0);
+            let idle_capable = sync_manager.supports_idle().await.unwrap_or(false);
+
+            while !stop.load(Ordering::Relaxed) {
+                if idle_capable {
+                    match sync_manager.watch_idle(&folder, last_uid, stop.clone()).await {
+                        Ok(Some(batch)) => {
+                            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+                            *last_sync.lock().unwrap() = Some(now);
+                            last_uid = batch.last_uid;
+                            // Only `last_seen_uid` moves here; `uidvalidity`/`highest_modseq` stay
+                            // whatever the CONDSTORE sync path (see `crate::sync`) last set them
+                            // to, since IDLE doesn't track either of those itself.
+                            let mut state = initial_state.clone().unwrap_or(crate::db::FolderSyncState {
+                                account_id,
+                                folder: folder.clone(),
+                                uidvalidity: 0,
+                                highest_modseq: 0,
+                                last_seen_uid: 0,
+                            });
+                            state.last_seen_uid = last_uid as i64;
+                            let _ = db.upsert_sync_state(&state).await;
+
+                            let emails = Self::store_new_mail(&db, account_id, batch.messages).await;
+                            if !emails.is_empty() {
+                                slot.lock().unwrap().push(MailWatchEvent { account_id, folder: folder.clone(), emails });
+                            }
+                        }
+                        Ok(None) => return, // stop was requested mid-wait
+                        Err(e) => {
+                            log::error!("IDLE watch failed, falling back to polling: {}", e);
+                            tokio::time::sleep(Self::MAIL_WATCH_POLL_INTERVAL).await;
+                        }
+                    }
+                } else {
+                    match sync_manager.imap_client() {
+                        Some(client) => match client.fetch_emails(&folder, Some(20)).await {
+                            Ok(fetched) => {
+                                let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+                                *last_sync.lock().unwrap() = Some(now);
+                                let emails = Self::store_new_mail(&db, account_id, fetched).await;
+                                if !emails.is_empty() {
+                                    slot.lock().unwrap().push(MailWatchEvent { account_id, folder: folder.clone(), emails });
+                                }
+                            }
+                            Err(e) => log::error!("Mail watch poll failed: {}", e),
+                        },
+                        None => return,
+                    }
+                    tokio::time::sleep(Self::MAIL_WATCH_POLL_INTERVAL).await;
+                }
+            }
+        });
+    }
+
+    /// Upsert each fetched message (deduplicating by `(account_id, message_id)`, see
+    /// [`EmailDatabase::upsert_email`]) and return only the ones that were genuinely new, as
+    /// the [`Email`] view model `App.emails` uses, for [`Self::poll_mail_watch_events`] to fold in.
+    async fn store_new_mail(
+        db: &EmailDatabase,
+        account_id: Option<i64>,
+        messages: Vec<(DbEmail, Vec<crate::mime::ParsedAttachment>)>,
+    ) -> Vec<Email> {
+        let mut new_emails = Vec::new();
+
+        for (mut db_email, parsed_attachments) in messages {
+            db_email.account_id = account_id;
+
+            let outcome = match db.upsert_email(&db_email).await {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    log::error!("Failed to store new mail: {}", e);
+                    continue;
+                }
+            };
+
+            let crate::db::UpsertOutcome::Inserted(id) = outcome else { continue };
+
+            if let Err(e) = db.insert_attachments(id, &parsed_attachments).await {
+                log::error!("Failed to store attachments for new mail: {}", e);
+            }
+
+            // Run the user's inbox rules (see `EmailDatabase::apply_rules`) before handing the
+            // message to the view model, so a `discard`/`move_to_folder` rule is reflected here
+            // instead of briefly flashing the message in a folder it was auto-routed out of.
+            match db.apply_rules(id, account_id).await {
+                Ok(applied) if applied > 0 => match db.get_email_by_id(id).await {
+                    Ok(Some(updated)) if updated.folder == db_email.folder => db_email = updated,
+                    Ok(_) => continue, // discarded, or routed to a different folder
+                    Err(e) => log::error!("Failed to reload email after applying inbox rules: {}", e),
+                },
+                Ok(_) => {}
+                Err(e) => log::error!("Failed to apply inbox rules to new mail: {}", e),
+            }
+
+            let attachments = db.get_attachment_manifest(id).await.unwrap_or_default();
+
+            new_emails.push(Email {
+                id,
+                from: db_email.from_address,
+                subject: db_email.subject,
+                preview: db_email.preview,
+                body: db_email.body,
+                body_html: db_email.body_html,
+                date: db_email.date,
+                attachments,
+                pgp_status: db_email.pgp_status,
+                list_headers: db_email.list_headers,
+                message_id: db_email.message_id,
+                references: db_email.references,
+                thread_id: db_email.thread_id,
+                status: db_email.status,
+            });
+        }
+
+        new_emails
+    }
+
+    /// Drain new-mail batches queued by [`Self::start_mail_watch`]: prepend each message to
+    /// `self.emails` if it landed in the account/folder currently on screen, and fire a desktop
+    /// notification (title = sender, body = subject) for every one regardless, so the user is
+    /// alerted while the TUI is backgrounded.
+    ///
+    /// Inserting at the front shifts every existing row down by one, so `selected_index` and any
+    /// active visual-mode range are bumped by the number of messages inserted - otherwise a live
+    /// push mid-selection would leave the cursor pointing at the wrong email and
+    /// `visual_selections` covering the wrong range.
+    pub fn poll_mail_watch_events(&mut self) {
+        let events: Vec<MailWatchEvent> = std::mem::take(&mut *self.pending_mail_watch.lock().unwrap());
+
+        for event in events {
+            let showing = event.account_id == self.current_account_id && event.folder == self.current_folder;
+
+            let mut inserted = 0usize;
+            for email in event.emails {
+                Self::notify_new_mail(&email.from, &email.subject);
+                if showing {
+                    self.emails.insert(0, email);
+                    inserted += 1;
+                }
+            }
+
+            if inserted > 0 {
+                self.selected_index += inserted;
+                if let Some(anchor) = self.visual_anchor {
+                    self.visual_anchor = Some(anchor + inserted);
+                }
+                if !self.visual_selections.is_empty() {
+                    self.visual_selections = self
+                        .visual_selections
+                        .iter()
+                        .map(|&i| i + inserted)
+                        .collect();
+                }
+            }
+        }
+    }
+
+    /// Fire an OS desktop notification for a newly-arrived message. Best-effort: a platform
+    /// without a notification daemon (or running headless) just means this silently no-ops,
+    /// same as `notify-rust`'s own behavior on an unsupported platform.
+    fn notify_new_mail(from: &str, subject: &str) {
+        if let Err(e) = notify_rust::Notification::new()
+            .summary(from)
+            .body(subject)
+            .show()
+        {
+            log::error!("Failed to show desktop notification: {}", e);
+        }
+    }
+
+    pub fn toggle_preview_panel(&mut self) {
+        self.show_preview_panel = !self.show_preview_panel;
+    }
+
+    // Visual mode methods
+    pub fn enter_visual_mode(&mut self) {
+        if self.current_view == View::InboxList && !self.visual_mode {
+            self.visual_mode = true;
+            self.visual_anchor = Some(self.selected_index);
+            self.visual_selections.clear();
+            self.visual_selections.insert(self.selected_index);
+            self.status_message = Some("-- VISUAL LINE --".to_string());
+        }
+    }
+
+    pub fn exit_visual_mode(&mut self) {
+        self.visual_mode = false;
+        self.visual_selections.clear();
+        self.visual_anchor = None;
+        // Don't clear status message here - it may contain action results
+    }
+
+    pub fn update_visual_selection(&mut self) {
+        if let Some(anchor) = self.visual_anchor {
+            self.visual_selections.clear();
+            let start = anchor.min(self.selected_index);
+            let end = anchor.max(self.selected_index);
+            for i in start..=end {
+                self.visual_selections.insert(i);
             }
         }
     }
@@ -931,6 +2787,17 @@ impl App {
             return;
         }
 
+        if action == Action::Export {
+            let emails: Vec<Email> = self
+                .visual_selections
+                .iter()
+                .filter_map(|&index| self.emails.get(index).cloned())
+                .collect();
+            self.export_to_mbox(&emails);
+            self.exit_visual_mode();
+            return;
+        }
+
         let count = self.visual_selections.len();
         let action_name = match action {
             Action::Delete => "Deleted",
@@ -958,7 +2825,7 @@ impl App {
                     tokio::spawn(async move {
                         for email_id in email_ids {
                             if let Err(e) = db_clone.delete_email(email_id).await {
-                                eprintln!("Failed to delete email from database: {}", e);
+                                log::error!("Failed to delete email from database: {}", e);
                             }
                         }
                     });
@@ -967,7 +2834,7 @@ impl App {
                     tokio::spawn(async move {
                         for email_id in email_ids {
                             if let Err(e) = db_clone.archive_email(email_id).await {
-                                eprintln!("Failed to archive email in database: {}", e);
+                                log::error!("Failed to archive email in database: {}", e);
                             }
                         }
                     });
@@ -1000,8 +2867,191 @@ impl App {
         self.visual_selections.contains(&index)
     }
 
+    /// Enter `:`-command-line mode, remembering the view it was opened from so it can be
+    /// restored (and rendered underneath the overlay) on exit
+    pub fn enter_command_mode(&mut self) {
+        self.command_line_state = Some(CommandLineState::new(self.current_view));
+        self.current_view = View::CommandLine;
+    }
+
+    /// Cancel command-line mode without running anything
+    pub fn exit_command_mode(&mut self) {
+        if let Some(state) = self.command_line_state.take() {
+            self.current_view = state.return_view;
+        }
+    }
+
+    pub fn command_line_insert_char(&mut self, c: char) {
+        if let Some(ref mut state) = self.command_line_state {
+            if state.cursor_position <= state.buffer.len() {
+                state.buffer.insert(state.cursor_position, c);
+                state.cursor_position += 1;
+                state.history_index = None;
+            }
+        }
+    }
+
+    pub fn command_line_delete_char(&mut self) {
+        if let Some(ref mut state) = self.command_line_state {
+            if state.cursor_position > 0 {
+                state.cursor_position -= 1;
+                state.buffer.remove(state.cursor_position);
+                state.history_index = None;
+            }
+        }
+    }
+
+    pub fn command_line_cursor_left(&mut self) {
+        if let Some(ref mut state) = self.command_line_state {
+            if state.cursor_position > 0 {
+                state.cursor_position -= 1;
+            }
+        }
+    }
+
+    pub fn command_line_cursor_right(&mut self) {
+        if let Some(ref mut state) = self.command_line_state {
+            if state.cursor_position < state.buffer.len() {
+                state.cursor_position += 1;
+            }
+        }
+    }
+
+    /// Recall the previous command in history, like a shell's Up arrow
+    pub fn command_line_history_prev(&mut self) {
+        if self.command_history.is_empty() {
+            return;
+        }
+        if let Some(ref mut state) = self.command_line_state {
+            let index = match state.history_index {
+                None => self.command_history.len() - 1,
+                Some(0) => 0,
+                Some(i) => i - 1,
+            };
+            state.history_index = Some(index);
+            state.buffer = self.command_history[index].clone();
+            state.cursor_position = state.buffer.len();
+        }
+    }
+
+    /// Step forward in history, back towards an empty buffer, like a shell's Down arrow
+    pub fn command_line_history_next(&mut self) {
+        if let Some(ref mut state) = self.command_line_state {
+            match state.history_index {
+                Some(i) if i + 1 < self.command_history.len() => {
+                    state.history_index = Some(i + 1);
+                    state.buffer = self.command_history[i + 1].clone();
+                    state.cursor_position = state.buffer.len();
+                }
+                _ => {
+                    state.history_index = None;
+                    state.buffer.clear();
+                    state.cursor_position = 0;
+                }
+            }
+        }
+    }
+
+    /// Parse and run the current command-line buffer. On success, exits command mode and
+    /// dispatches through [`Self::perform_command`]; on a parse error, keeps the overlay open
+    /// and shows the error instead of the buffer.
+    pub fn command_line_submit(&mut self) {
+        let buffer = match &self.command_line_state {
+            Some(state) => state.buffer.clone(),
+            None => return,
+        };
+
+        if !buffer.trim().is_empty() {
+            self.command_history.push(buffer.clone());
+        }
+
+        match Command::parse(&buffer) {
+            Ok(command) => {
+                self.exit_command_mode();
+                self.perform_command(command);
+            }
+            Err(message) => {
+                if let Some(ref mut state) = self.command_line_state {
+                    state.buffer.clear();
+                    state.cursor_position = 0;
+                    state.history_index = None;
+                    state.error_message = Some(message);
+                }
+            }
+        }
+    }
+
+    /// Single dispatch point for every typed [`Command`], so actions reachable via key bindings
+    /// are equally reachable (and scriptable) via the `:`-command line.
+    pub fn perform_command(&mut self, command: Command) {
+        match command {
+            Command::Delete => self.perform_action(Action::Delete),
+            Command::Archive => self.perform_action(Action::Archive),
+            Command::Reply => self.perform_action(Action::Reply),
+            Command::Forward => self.perform_action(Action::Forward),
+            Command::Compose => self.perform_action(Action::Compose),
+            Command::Quit => self.quit(),
+            Command::SwitchAccount(index) => self.switch_to_account(index),
+            Command::Goto(mailbox) => self.switch_to_folder(mailbox),
+            Command::Contacts => self.enter_contacts_mode(),
+            Command::NotificationHistory => self.enter_notification_history(),
+            Command::Folders => self.enter_folder_list_mode(),
+            Command::Threads => self.enter_thread_list_mode(),
+            Command::Attach(path) => self.compose_add_attachment(path),
+            Command::RemoveAttachment(idx) => self.compose_remove_attachment(idx),
+        }
+    }
+
     pub fn quit(&mut self) {
         self.should_quit = true;
+        if let Some(stop) = self.mail_watch_stop.take() {
+            stop.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Bounded size of [`Self::notification_history`]; old entries fall off the back as new
+    /// ones are pushed to the front.
+    const NOTIFICATION_HISTORY_CAP: usize = 50;
+
+    /// Clear the transient status line, first recording it in `notification_history` if it held
+    /// a message. Called on every keypress from `events::handle_key_event` so flash messages
+    /// (sent, deleted, sync errors) remain reviewable in [`View::NotificationHistory`] after
+    /// they disappear from the footer.
+    pub fn clear_status_message(&mut self) {
+        if let Some(message) = self.status_message.take() {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            self.notification_history.push_front(NotificationEntry { message, timestamp });
+            self.notification_history.truncate(Self::NOTIFICATION_HISTORY_CAP);
+        }
+    }
+
+    pub fn enter_notification_history(&mut self) {
+        self.notification_history_state = Some(NotificationHistoryState { selected_index: 0 });
+        self.current_view = View::NotificationHistory;
+    }
+
+    pub fn exit_notification_history(&mut self) {
+        self.notification_history_state = None;
+        self.current_view = View::InboxList;
+    }
+
+    pub fn notification_history_next(&mut self) {
+        if let Some(ref mut state) = self.notification_history_state {
+            if !self.notification_history.is_empty() {
+                state.selected_index = (state.selected_index + 1).min(self.notification_history.len() - 1);
+            }
+        }
+    }
+
+    pub fn notification_history_previous(&mut self) {
+        if let Some(ref mut state) = self.notification_history_state {
+            if state.selected_index > 0 {
+                state.selected_index -= 1;
+            }
+        }
     }
 
     /// Attempt to sync emails (stub - shows not implemented message)
@@ -1030,6 +3080,42 @@ impl App {
 
     // ============ Credentials Management Methods ============
 
+    /// Navigate to next backend kind in the initial backend-selection step
+    pub fn credentials_setup_next_backend(&mut self) {
+        if let Some(ref mut setup) = self.credentials_setup_state {
+            if setup.backend_selection_mode {
+                let kinds = CredentialsSetupState::backend_kinds();
+                setup.backend_list_index = (setup.backend_list_index + 1) % kinds.len();
+            }
+        }
+    }
+
+    /// Navigate to previous backend kind in the initial backend-selection step
+    pub fn credentials_setup_prev_backend(&mut self) {
+        if let Some(ref mut setup) = self.credentials_setup_state {
+            if setup.backend_selection_mode {
+                let kinds = CredentialsSetupState::backend_kinds();
+                setup.backend_list_index = if setup.backend_list_index == 0 {
+                    kinds.len() - 1
+                } else {
+                    setup.backend_list_index - 1
+                };
+            }
+        }
+    }
+
+    /// Select the currently highlighted backend kind and move to the next step
+    pub fn credentials_setup_select_backend(&mut self) {
+        if let Some(ref mut setup) = self.credentials_setup_state {
+            if setup.backend_selection_mode {
+                let kinds = CredentialsSetupState::backend_kinds();
+                if let Some(kind) = kinds.get(setup.backend_list_index).cloned() {
+                    setup.apply_backend(kind);
+                }
+            }
+        }
+    }
+
     /// Navigate to next provider in selection list
     pub fn credentials_setup_next_provider(&mut self) {
         if let Some(ref mut setup) = self.credentials_setup_state {
@@ -1095,6 +3181,20 @@ impl App {
     /// Navigate to next field in credentials setup
     pub fn credentials_setup_next_field(&mut self) {
         if let Some(ref mut setup) = self.credentials_setup_state {
+            // A local backend only has an identifier and a path to cycle between - it skips
+            // the IMAP/SMTP fields entirely.
+            if setup.is_local_backend() {
+                setup.current_field = match setup.current_field {
+                    CredentialField::BackendPath => CredentialField::ImapUsername,
+                    _ => CredentialField::BackendPath,
+                };
+                setup.cursor_position = match setup.current_field {
+                    CredentialField::BackendPath => setup.backend_path.len(),
+                    _ => setup.imap_username.len(),
+                };
+                return;
+            }
+
             // Determine if we should go to master password field
             let use_encrypted_file = self.credentials_manager
                 .as_ref()
@@ -1118,8 +3218,40 @@ impl App {
                 }
                 CredentialField::MasterPassword => CredentialField::MasterPasswordConfirm,
                 CredentialField::MasterPasswordConfirm => CredentialField::ImapServer,
+                // Only a local backend uses this field, and that case returned above.
+                CredentialField::BackendPath => CredentialField::BackendPath,
+                // Entered only via the custom-OAuth2 override below, then walked in order.
+                CredentialField::OAuthClientId => CredentialField::OAuthAuthUrl,
+                CredentialField::OAuthAuthUrl => CredentialField::OAuthTokenUrl,
+                CredentialField::OAuthTokenUrl => CredentialField::OAuthScopes,
+                CredentialField::OAuthScopes => {
+                    if use_encrypted_file {
+                        CredentialField::MasterPassword
+                    } else {
+                        CredentialField::ImapServer
+                    }
+                }
             };
-            
+
+            // OAuth2 providers authorize via `o` instead of typing a password; skip straight
+            // past the (unused) password fields. A manually-configured (`custom_oauth2`)
+            // provider instead routes through the four OAuth2 entry fields where the password
+            // fields would have been.
+            if setup.uses_oauth2() {
+                setup.current_field = match setup.current_field {
+                    CredentialField::ImapPassword => CredentialField::SmtpServer,
+                    CredentialField::SmtpPassword if setup.custom_oauth2 => CredentialField::OAuthClientId,
+                    CredentialField::SmtpPassword => {
+                        if use_encrypted_file {
+                            CredentialField::MasterPassword
+                        } else {
+                            CredentialField::ImapServer
+                        }
+                    }
+                    other => other,
+                };
+            }
+
             // Update cursor position to end of new field
             setup.cursor_position = match setup.current_field {
                 CredentialField::ImapServer => setup.imap_server.len(),
@@ -1132,6 +3264,11 @@ impl App {
                 CredentialField::SmtpPassword => setup.smtp_password.len(),
                 CredentialField::MasterPassword => setup.master_password.len(),
                 CredentialField::MasterPasswordConfirm => setup.master_password_confirm.len(),
+                CredentialField::BackendPath => setup.backend_path.len(),
+                CredentialField::OAuthClientId => setup.oauth_client_id.len(),
+                CredentialField::OAuthAuthUrl => setup.oauth_auth_url.len(),
+                CredentialField::OAuthTokenUrl => setup.oauth_token_url.len(),
+                CredentialField::OAuthScopes => setup.oauth_scopes.len(),
             };
         }
     }
@@ -1139,6 +3276,18 @@ impl App {
     /// Navigate to previous field in credentials setup
     pub fn credentials_setup_prev_field(&mut self) {
         if let Some(ref mut setup) = self.credentials_setup_state {
+            if setup.is_local_backend() {
+                setup.current_field = match setup.current_field {
+                    CredentialField::BackendPath => CredentialField::ImapUsername,
+                    _ => CredentialField::BackendPath,
+                };
+                setup.cursor_position = match setup.current_field {
+                    CredentialField::BackendPath => setup.backend_path.len(),
+                    _ => setup.imap_username.len(),
+                };
+                return;
+            }
+
             // Determine if we should go to master password field
             let use_encrypted_file = self.credentials_manager
                 .as_ref()
@@ -1162,8 +3311,28 @@ impl App {
                 CredentialField::SmtpPassword => CredentialField::SmtpUsername,
                 CredentialField::MasterPassword => CredentialField::SmtpPassword,
                 CredentialField::MasterPasswordConfirm => CredentialField::MasterPassword,
+                CredentialField::BackendPath => CredentialField::BackendPath,
+                // Skips the (unused) SmtpPassword field directly, mirroring the override below
+                // that sends SmtpPassword here going forward.
+                CredentialField::OAuthClientId => CredentialField::SmtpUsername,
+                CredentialField::OAuthAuthUrl => CredentialField::OAuthClientId,
+                CredentialField::OAuthTokenUrl => CredentialField::OAuthAuthUrl,
+                CredentialField::OAuthScopes => CredentialField::OAuthTokenUrl,
             };
-            
+
+            // OAuth2 providers authorize via `o` instead of typing a password; skip straight
+            // past the (unused) password fields. A manually-configured (`custom_oauth2`)
+            // provider instead routes through the four OAuth2 entry fields where the password
+            // fields would have been.
+            if setup.uses_oauth2() {
+                setup.current_field = match setup.current_field {
+                    CredentialField::ImapPassword => CredentialField::ImapUsername,
+                    CredentialField::SmtpPassword if setup.custom_oauth2 => CredentialField::OAuthScopes,
+                    CredentialField::SmtpPassword => CredentialField::SmtpUsername,
+                    other => other,
+                };
+            }
+
             // Update cursor position to end of new field
             setup.cursor_position = match setup.current_field {
                 CredentialField::ImapServer => setup.imap_server.len(),
@@ -1176,6 +3345,11 @@ impl App {
                 CredentialField::SmtpPassword => setup.smtp_password.len(),
                 CredentialField::MasterPassword => setup.master_password.len(),
                 CredentialField::MasterPasswordConfirm => setup.master_password_confirm.len(),
+                CredentialField::BackendPath => setup.backend_path.len(),
+                CredentialField::OAuthClientId => setup.oauth_client_id.len(),
+                CredentialField::OAuthAuthUrl => setup.oauth_auth_url.len(),
+                CredentialField::OAuthTokenUrl => setup.oauth_token_url.len(),
+                CredentialField::OAuthScopes => setup.oauth_scopes.len(),
             };
         }
     }
@@ -1194,6 +3368,11 @@ impl App {
                 CredentialField::SmtpPassword => &mut setup.smtp_password,
                 CredentialField::MasterPassword => &mut setup.master_password,
                 CredentialField::MasterPasswordConfirm => &mut setup.master_password_confirm,
+                CredentialField::BackendPath => &mut setup.backend_path,
+                CredentialField::OAuthClientId => &mut setup.oauth_client_id,
+                CredentialField::OAuthAuthUrl => &mut setup.oauth_auth_url,
+                CredentialField::OAuthTokenUrl => &mut setup.oauth_token_url,
+                CredentialField::OAuthScopes => &mut setup.oauth_scopes,
             };
 
             if setup.cursor_position <= text.len() {
@@ -1218,6 +3397,11 @@ impl App {
                     CredentialField::SmtpPassword => &mut setup.smtp_password,
                     CredentialField::MasterPassword => &mut setup.master_password,
                     CredentialField::MasterPasswordConfirm => &mut setup.master_password_confirm,
+                    CredentialField::BackendPath => &mut setup.backend_path,
+                    CredentialField::OAuthClientId => &mut setup.oauth_client_id,
+                    CredentialField::OAuthAuthUrl => &mut setup.oauth_auth_url,
+                    CredentialField::OAuthTokenUrl => &mut setup.oauth_token_url,
+                    CredentialField::OAuthScopes => &mut setup.oauth_scopes,
                 };
 
                 setup.cursor_position -= 1;
@@ -1249,6 +3433,11 @@ impl App {
                 CredentialField::SmtpPassword => setup.smtp_password.len(),
                 CredentialField::MasterPassword => setup.master_password.len(),
                 CredentialField::MasterPasswordConfirm => setup.master_password_confirm.len(),
+                CredentialField::BackendPath => setup.backend_path.len(),
+                CredentialField::OAuthClientId => setup.oauth_client_id.len(),
+                CredentialField::OAuthAuthUrl => setup.oauth_auth_url.len(),
+                CredentialField::OAuthTokenUrl => setup.oauth_token_url.len(),
+                CredentialField::OAuthScopes => setup.oauth_scopes.len(),
             };
             if setup.cursor_position < max_pos {
                 setup.cursor_position += 1;
@@ -1263,87 +3452,428 @@ impl App {
         }
     }
 
-    /// Save credentials from setup form
-    pub fn credentials_setup_save(&mut self) {
-        let setup = match &self.credentials_setup_state {
-            Some(s) => s.clone(),
+    /// Toggle manual OAuth2 (client ID/auth URL/token URL/scopes typed in by hand) for a
+    /// provider with no built-in OAuth2 preset, e.g. a self-hosted IMAP/SMTP server that still
+    /// wants XOAUTH2. No-op for a provider that already carries its own preset.
+    pub fn credentials_setup_toggle_custom_oauth2(&mut self) {
+        if let Some(ref mut setup) = self.credentials_setup_state {
+            if !setup.can_toggle_custom_oauth2() {
+                return;
+            }
+            setup.custom_oauth2 = !setup.custom_oauth2;
+            setup.oauth_token = None;
+            setup.oauth_status = None;
+        }
+    }
+
+    /// Override the auto-detected credential [`StorageBackend`] from the provider-selection
+    /// screen, cycling between the system keyring and an encrypted file. `CredentialsManager::new`
+    /// already picks the keyring automatically when one is available, so this only matters for
+    /// someone who wants the encrypted-file/master-password flow despite having a working keyring
+    /// (or who wants to retry keyring detection after fixing it).
+    pub fn credentials_setup_toggle_backend(&mut self) {
+        let current = match self.credentials_manager.as_ref().map(|m| m.backend()) {
+            Some(b) => b,
             None => return,
         };
+        let next = match current {
+            StorageBackend::SystemKeyring => StorageBackend::EncryptedFile,
+            StorageBackend::EncryptedFile => {
+                if CredentialsManager::is_keyring_available() {
+                    StorageBackend::SystemKeyring
+                } else {
+                    self.status_message = Some("System keyring is not available on this machine".to_string());
+                    return;
+                }
+            }
+            other => other,
+        };
+        self.credentials_manager = Some(CredentialsManager::with_backend(next));
+    }
 
-        let manager = match &self.credentials_manager {
-            Some(m) => m,
+    /// Kick off a pre-flight connectivity/credential check for the in-progress setup
+    /// form, without blocking the UI. The result is picked up by `poll_validation_result`
+    /// on a later frame.
+    pub fn credentials_setup_validate(&mut self) {
+        let setup = match &self.credentials_setup_state {
+            Some(s) => s.clone(),
             None => return,
         };
 
-        // Validate fields
-        if setup.imap_server.is_empty() || setup.imap_username.is_empty() 
-            || setup.smtp_server.is_empty() || setup.smtp_username.is_empty() {
-            self.status_message = Some("Please fill in all required fields".to_string());
+        if setup.is_local_backend() {
+            self.status_message = Some(
+                "No server to validate for a local backend - just save to finish setup".to_string(),
+            );
             return;
         }
 
-        // Parse ports
         let imap_port = match setup.imap_port.parse::<u16>() {
             Ok(p) => p,
             Err(_) => {
-                self.status_message = Some("Invalid IMAP port number".to_string());
+                self.status_message = Some("Invalid IMAP port".to_string());
                 return;
             }
         };
-
         let smtp_port = match setup.smtp_port.parse::<u16>() {
             Ok(p) => p,
             Err(_) => {
-                self.status_message = Some("Invalid SMTP port number".to_string());
+                self.status_message = Some("Invalid SMTP port".to_string());
                 return;
             }
         };
 
-        // For encrypted file backend, validate master password
-        let master_password = if manager.backend() == StorageBackend::EncryptedFile {
-            if setup.master_password.is_empty() {
-                self.status_message = Some("Master password is required".to_string());
-                return;
-            }
-            if setup.master_password != setup.master_password_confirm {
-                self.status_message = Some("Master passwords do not match".to_string());
-                return;
-            }
-            if setup.master_password.len() < 8 {
-                self.status_message = Some("Master password must be at least 8 characters".to_string());
-                return;
-            }
-            Some(setup.master_password.as_str())
-        } else {
-            None
-        };
+        let selected_provider = setup.selected_provider.as_ref()
+            .and_then(|id| crate::providers::EmailProvider::by_id(id));
+        let imap_security = selected_provider.as_ref()
+            .map(|p| p.imap_security)
+            .unwrap_or(crate::providers::SecurityType::Tls);
+        let smtp_security = selected_provider.as_ref()
+            .map(|p| p.smtp_security)
+            .unwrap_or(crate::providers::SecurityType::StartTls);
 
-        // Create credentials object
         let credentials = Credentials {
             imap_server: setup.imap_server.clone(),
             imap_port,
+            imap_security,
             imap_username: setup.imap_username.clone(),
             imap_password: setup.imap_password.clone(),
             smtp_server: setup.smtp_server.clone(),
             smtp_port,
+            smtp_security,
             smtp_username: setup.smtp_username.clone(),
             smtp_password: setup.smtp_password.clone(),
+            oauth_token: setup.oauth_token.clone(),
         };
 
-        // Save credentials
-        match manager.save_credentials(&credentials, master_password) {
-            Ok(_) => {
-                self.credentials = Some(credentials.clone());
-                
-                // Save account configuration to config file
-                // Use selected provider or fallback to "custom"
-                let provider_id = setup.selected_provider.as_ref()
-                    .map(|s| s.clone())
-                    .unwrap_or_else(|| "custom".to_string());
-                    
-                let provider_name = crate::providers::EmailProvider::by_id(&provider_id)
-                    .map(|p| p.name)
-                    .unwrap_or("Custom");
+        self.status_message = Some("Validating connection...".to_string());
+        let slot = self.pending_validation.clone();
+        tokio::spawn(async move {
+            let result = crate::email_sync::validate_credentials(&credentials).await;
+            *slot.lock().unwrap() = Some(result);
+        });
+    }
+
+    /// Drain a completed validation result, if any, and surface it as a status message
+    /// with actionable guidance for whichever leg failed.
+    pub fn poll_validation_result(&mut self) {
+        let result = self.pending_validation.lock().unwrap().take();
+        let Some(result) = result else { return };
+
+        if result.is_ok() {
+            self.status_message = Some("Connection validated successfully".to_string());
+            return;
+        }
+
+        let mut problems = Vec::new();
+        if let crate::email_sync::LegResult::Failed(ref e) = result.imap {
+            problems.push(format!("IMAP: {}", e.guidance()));
+        }
+        if let crate::email_sync::LegResult::Failed(ref e) = result.smtp {
+            problems.push(format!("SMTP: {}", e.guidance()));
+        }
+        self.status_message = Some(problems.join(" | "));
+    }
+
+    /// Kick off the authorization-code-with-PKCE flow for an OAuth2 provider preset - or, for
+    /// `custom_oauth2`, the manually-entered client ID/auth URL/token URL/scopes - without
+    /// blocking the UI. The result is picked up by `poll_oauth_result` on a later frame.
+    pub fn credentials_setup_start_oauth(&mut self) {
+        let setup = match &self.credentials_setup_state {
+            Some(s) => s.clone(),
+            None => return,
+        };
+
+        let (auth_url, token_url, client_id, scopes) = if setup.custom_oauth2 {
+            if setup.oauth_client_id.trim().is_empty()
+                || setup.oauth_auth_url.trim().is_empty()
+                || setup.oauth_token_url.trim().is_empty() {
+                self.status_message = Some(
+                    "Please fill in client ID, auth URL and token URL before authorizing".to_string(),
+                );
+                return;
+            }
+            let scopes: Vec<String> = setup.oauth_scopes
+                .split_whitespace()
+                .map(|s| s.to_string())
+                .collect();
+            (setup.oauth_auth_url.clone(), setup.oauth_token_url.clone(), setup.oauth_client_id.clone(), scopes)
+        } else {
+            let Some(provider) = setup.selected_provider.as_ref()
+                .and_then(|id| crate::providers::EmailProvider::by_id(id)) else {
+                self.status_message = Some("No provider selected".to_string());
+                return;
+            };
+
+            let crate::providers::AuthType::OAuth2 { auth_url, token_url, scopes, client_id, .. } = provider.auth.clone() else {
+                self.status_message = Some("Selected provider does not use OAuth2".to_string());
+                return;
+            };
+            (auth_url, token_url, client_id, scopes)
+        };
+
+        if let Some(ref mut setup) = self.credentials_setup_state {
+            setup.oauth_status = Some("Opening browser for authorization...".to_string());
+        }
+
+        let login_hint = setup.imap_username.clone();
+        let slot = self.pending_oauth.clone();
+        tokio::spawn(async move {
+            let result = crate::oauth::run_authorization_flow(&auth_url, &token_url, &client_id, &scopes, &login_hint)
+                .await
+                .map_err(|e| e.to_string());
+            *slot.lock().unwrap() = Some(result);
+        });
+    }
+
+    /// Kick off the device-authorization-grant flow (RFC 8628) for a provider preset that
+    /// publishes a device-code endpoint - the alternative to [`Self::credentials_setup_start_oauth`]
+    /// for a machine with no local browser to receive the redirect. The user code and
+    /// verification URL are picked up by `poll_oauth_result` as soon as the provider hands them
+    /// back, well before the (much later) token itself arrives.
+    pub fn credentials_setup_start_oauth_device(&mut self) {
+        let setup = match &self.credentials_setup_state {
+            Some(s) => s.clone(),
+            None => return,
+        };
+
+        let Some(provider) = setup.selected_provider.as_ref()
+            .and_then(|id| crate::providers::EmailProvider::by_id(id)) else {
+            self.status_message = Some("No provider selected".to_string());
+            return;
+        };
+        let crate::providers::AuthType::OAuth2 { token_url, scopes, client_id, device_auth_url: Some(device_auth_url), .. } = provider.auth.clone() else {
+            self.status_message = Some("Selected provider has no device-code endpoint".to_string());
+            return;
+        };
+
+        if let Some(ref mut setup) = self.credentials_setup_state {
+            setup.oauth_status = Some("Requesting a device code...".to_string());
+            setup.device_authorization = None;
+        }
+
+        let oauth_slot = self.pending_oauth.clone();
+        let device_slot = self.pending_device_auth.clone();
+        tokio::spawn(async move {
+            let result = crate::oauth::run_device_code_flow(&device_auth_url, &token_url, &client_id, &scopes, move |device| {
+                *device_slot.lock().unwrap() = Some(device);
+            })
+            .await
+            .map_err(|e| e.to_string());
+            *oauth_slot.lock().unwrap() = Some(result);
+        });
+    }
+
+    /// Drain a completed OAuth2 authorization result, if any, and store the token (or surface
+    /// the failure) in the in-progress setup form. Also picks up the device code/verification
+    /// URL from an in-flight [`Self::credentials_setup_start_oauth_device`] flow, which arrives
+    /// well before the token itself.
+    pub fn poll_oauth_result(&mut self) {
+        let device = self.pending_device_auth.lock().unwrap().take();
+        if let Some(device) = device {
+            if let Some(ref mut setup) = self.credentials_setup_state {
+                setup.oauth_status = Some(format!("Enter code {} at {}", device.user_code, device.verification_uri));
+                setup.device_authorization = Some(device);
+            }
+        }
+
+        let result = self.pending_oauth.lock().unwrap().take();
+        let Some(result) = result else { return };
+
+        let Some(ref mut setup) = self.credentials_setup_state else { return };
+        match result {
+            Ok(token) => {
+                setup.oauth_status = Some("Authorization successful".to_string());
+                setup.oauth_token = Some(token);
+            }
+            Err(e) => {
+                setup.oauth_status = Some(format!("Authorization failed: {}", e));
+                setup.oauth_token = None;
+            }
+        }
+    }
+
+    /// Save setup for a `Maildir`/`Notmuch` account: no IMAP/SMTP credentials to store, just a
+    /// config entry pointing `backend` at the chosen local directory.
+    fn credentials_setup_save_local(&mut self, setup: CredentialsSetupState) {
+        if setup.imap_username.trim().is_empty() {
+            self.status_message = Some("Please enter an account name/email".to_string());
+            return;
+        }
+        if setup.backend_path.trim().is_empty() {
+            self.status_message = Some("Please enter a local directory".to_string());
+            return;
+        }
+
+        let path = std::path::PathBuf::from(setup.backend_path.trim());
+        let backend = match &setup.backend {
+            AccountBackend::Maildir { .. } => AccountBackend::Maildir { path },
+            #[cfg(feature = "notmuch")]
+            AccountBackend::Notmuch { .. } => AccountBackend::Notmuch { database_path: path },
+            AccountBackend::Imap => return, // unreachable: is_local_backend() guards this path
+        };
+
+        let account = crate::config::Account {
+            name: format!("{} Account", backend.label()),
+            email: setup.imap_username.clone(),
+            provider: "local".to_string(),
+            default: true,
+            color: Some("blue".to_string()),
+            display_order: Some(1),
+            folder_sync: crate::config::FolderSyncFilter::All,
+            folder_aliases: crate::config::FolderAliases::default(),
+            backend: backend.clone(),
+            send_backend: None,
+            settings: crate::config::Settings::default(),
+        };
+
+        let account_key = setup.imap_username.replace(['@', ' ', '.'], "_").to_lowercase();
+        self.config.accounts.insert(account_key, account.clone());
+
+        let config_saved = match self.config.save() {
+            Ok(_) => true,
+            Err(e) => {
+                self.status_message = Some(format!(
+                    "ERROR: Failed to save config file: {}. Account will be lost on restart!",
+                    e
+                ));
+                false
+            }
+        };
+
+        let db_account = DbAccount {
+            id: 0,
+            name: account.name.clone(),
+            email: account.email.clone(),
+            provider: account.provider.clone(),
+            is_default: account.default,
+            color: account.color.clone(),
+            display_order: account.display_order.unwrap_or(999),
+            backend_kind: backend.db_tag().to_string(),
+            backend_path: backend.local_path().map(|p| p.display().to_string()),
+        };
+        self.accounts.push(db_account.clone());
+        self.current_account_id = Some(db_account.id);
+
+        if config_saved {
+            self.status_message = Some(format!(
+                "Account configured to read from {} at {}",
+                backend.label(),
+                setup.backend_path
+            ));
+        }
+
+        if let Some(ref mut s) = self.credentials_setup_state {
+            s.zeroize_passwords();
+        }
+        self.credentials_setup_state = None;
+        self.current_view = View::InboxList;
+    }
+
+    /// Save credentials from setup form
+    pub fn credentials_setup_save(&mut self) {
+        let setup = match &self.credentials_setup_state {
+            Some(s) => s.clone(),
+            None => return,
+        };
+
+        if setup.is_local_backend() {
+            self.credentials_setup_save_local(setup);
+            return;
+        }
+
+        let manager = match &self.credentials_manager {
+            Some(m) => m,
+            None => return,
+        };
+
+        // Validate fields
+        if setup.imap_server.is_empty() || setup.imap_username.is_empty()
+            || setup.smtp_server.is_empty() || setup.smtp_username.is_empty() {
+            self.status_message = Some("Please fill in all required fields".to_string());
+            return;
+        }
+
+        if setup.uses_oauth2() && setup.oauth_token.is_none() {
+            self.status_message = Some("Press 'o' to authorize with this provider before saving".to_string());
+            return;
+        }
+
+        // Parse ports
+        let imap_port = match setup.imap_port.parse::<u16>() {
+            Ok(p) => p,
+            Err(_) => {
+                self.status_message = Some("Invalid IMAP port number".to_string());
+                return;
+            }
+        };
+
+        let smtp_port = match setup.smtp_port.parse::<u16>() {
+            Ok(p) => p,
+            Err(_) => {
+                self.status_message = Some("Invalid SMTP port number".to_string());
+                return;
+            }
+        };
+
+        // For encrypted file backend, validate master password
+        let master_password = if manager.backend() == StorageBackend::EncryptedFile {
+            if setup.master_password.is_empty() {
+                self.status_message = Some("Master password is required".to_string());
+                return;
+            }
+            if setup.master_password != setup.master_password_confirm {
+                self.status_message = Some("Master passwords do not match".to_string());
+                return;
+            }
+            if setup.master_password.len() < 8 {
+                self.status_message = Some("Master password must be at least 8 characters".to_string());
+                return;
+            }
+            Some(setup.master_password.as_str())
+        } else {
+            None
+        };
+
+        // Use the selected preset's security type, if any, else fall back to the Tls/StartTls
+        // defaults a "custom" provider would use
+        let selected_provider = setup.selected_provider.as_ref()
+            .and_then(|id| crate::providers::EmailProvider::by_id(id));
+        let imap_security = selected_provider.as_ref()
+            .map(|p| p.imap_security)
+            .unwrap_or(crate::providers::SecurityType::Tls);
+        let smtp_security = selected_provider.as_ref()
+            .map(|p| p.smtp_security)
+            .unwrap_or(crate::providers::SecurityType::StartTls);
+
+        // Create credentials object
+        let credentials = Credentials {
+            imap_server: setup.imap_server.clone(),
+            imap_port,
+            imap_security,
+            imap_username: setup.imap_username.clone(),
+            imap_password: setup.imap_password.clone(),
+            smtp_server: setup.smtp_server.clone(),
+            smtp_port,
+            smtp_security,
+            smtp_username: setup.smtp_username.clone(),
+            smtp_password: setup.smtp_password.clone(),
+            oauth_token: setup.oauth_token.clone(),
+        };
+
+        // Save credentials
+        match manager.save_credentials(&credentials, master_password) {
+            Ok(_) => {
+                self.credentials = Some(credentials.clone());
+                
+                // Save account configuration to config file
+                // Use selected provider or fallback to "custom"
+                let provider_id = setup.selected_provider.as_ref()
+                    .map(|s| s.clone())
+                    .unwrap_or_else(|| "custom".to_string());
+                    
+                let provider_name = crate::providers::EmailProvider::by_id(&provider_id)
+                    .map(|p| p.name)
+                    .unwrap_or_else(|| "Custom".to_string());
                 
                 // Create account entry
                 let account = crate::config::Account {
@@ -1353,8 +3883,13 @@ impl App {
                     default: true, // First account is default
                     color: Some("blue".to_string()),
                     display_order: Some(1),
+                    folder_sync: crate::config::FolderSyncFilter::All,
+                    folder_aliases: crate::config::FolderAliases::default(),
+                    backend: setup.backend.clone(),
+                    send_backend: None,
+                    settings: crate::config::Settings::default(),
                 };
-                
+
                 // Add to config and save
                 let account_key = provider_id.replace(" ", "_").to_lowercase();
                 self.config.accounts.insert(account_key, account.clone());
@@ -1362,11 +3897,11 @@ impl App {
                 // Try to save config - if it fails, still continue but show error
                 let config_saved = match self.config.save() {
                     Ok(_) => {
-                        eprintln!("DEBUG: Config saved successfully to {:?}", crate::config::Config::config_path());
+                        log::debug!("Config saved successfully to {:?}", crate::config::Config::config_path());
                         true
                     },
                     Err(e) => {
-                        eprintln!("ERROR: Failed to save config file: {}", e);
+                        log::error!("Failed to save config file: {}", e);
                         self.status_message = Some(format!("ERROR: Failed to save config file: {}. Account will be lost on restart!", e));
                         false
                     }
@@ -1381,6 +3916,8 @@ impl App {
                     is_default: account.default,
                     color: account.color.clone(),
                     display_order: account.display_order.unwrap_or(999),
+                    backend_kind: account.backend.db_tag().to_string(),
+                    backend_path: account.backend.local_path().map(|p| p.display().to_string()),
                 };
                 self.accounts.push(db_account.clone());
                 self.current_account_id = Some(db_account.id);
@@ -1393,12 +3930,31 @@ impl App {
                 } else {
                     // Error message already set above
                 }
-                
+
+                if let Some(ref mut s) = self.credentials_setup_state {
+                    s.zeroize_passwords();
+                }
                 self.credentials_setup_state = None;
                 self.current_view = View::InboxList;
-                
+
                 // Initialize email sync manager with credentials
-                self.email_sync_manager = Some(crate::email_sync::EmailSyncManager::new(Some(credentials)));
+                self.email_sync_manager = Some(crate::email_sync::EmailSyncManager::with_account(
+                    db_account.name.clone(),
+                    credentials,
+                ));
+                self.start_mail_watch();
+            }
+            Err(CredentialError::KeyringUnavailable(e)) if manager.backend() == StorageBackend::SystemKeyring => {
+                // No Secret Service/kwallet/Keychain daemon to talk to - fall back to the
+                // encrypted-file backend instead of leaving the user stuck. They'll need to fill
+                // in a master password (now that the backend has changed, the field-cycling logic
+                // in `credentials_setup_next_field` offers it) and press Enter again.
+                self.credentials_manager = Some(CredentialsManager::with_backend(StorageBackend::EncryptedFile));
+                self.credentials_setup_state = Some(setup);
+                self.status_message = Some(format!(
+                    "System keyring unavailable ({}); falling back to an encrypted file. Please set a master password and save again.",
+                    e
+                ));
             }
             Err(e) => {
                 self.status_message = Some(format!("Failed to save credentials: {}", e));
@@ -1417,6 +3973,9 @@ impl App {
         }
 
         // Otherwise, clear setup state and return to inbox
+        if let Some(ref mut s) = self.credentials_setup_state {
+            s.zeroize_passwords();
+        }
         self.credentials_setup_state = None;
         self.current_view = View::InboxList;
     }
@@ -1504,6 +4063,16 @@ impl App {
         self.current_view = View::InboxList;
     }
 
+    /// Enter the per-account status/statistics dashboard (see
+    /// [`crate::ui::render_account_status`]).
+    pub fn enter_account_status(&mut self) {
+        self.current_view = View::AccountStatus;
+    }
+
+    pub fn exit_account_status(&mut self) {
+        self.current_view = View::InboxList;
+    }
+
     /// Reset credentials (delete and return to setup)
     pub fn credentials_reset(&mut self) {
         if let Some(ref manager) = self.credentials_manager {
@@ -1546,6 +4115,7 @@ impl App {
             let account_name = self.accounts[index].name.clone();
             self.current_account_id = Some(account_id);
             self.reload_emails_for_current_account();
+            self.start_mail_watch();
             self.status_message = Some(format!("Switched to account: {}", account_name));
         }
     }
@@ -1592,131 +4162,921 @@ impl App {
         if let Some(ref db) = self.db {
             let db_clone = db.clone();
             let account_id = self.current_account_id;
-            
+            let folder = self.current_folder.clone();
+
             // Use spawn_blocking to avoid nested runtime issues
             let runtime = tokio::runtime::Handle::try_current();
             if let Ok(handle) = runtime {
                 let emails_result = std::thread::spawn(move || {
                     handle.block_on(async {
-                        if let Some(acc_id) = account_id {
-                            db_clone.get_emails_by_folder_and_account("inbox", Some(acc_id)).await
+                        let db_emails = if let Some(acc_id) = account_id {
+                            db_clone.get_emails_by_folder_and_account(&folder, Some(acc_id)).await?
                         } else {
-                            db_clone.get_emails_by_folder("inbox").await
+                            db_clone.get_emails_by_folder(&folder).await?
+                        };
+
+                        let mut emails = Vec::with_capacity(db_emails.len());
+                        for e in db_emails {
+                            let attachments = db_clone.get_attachment_manifest(e.id).await.unwrap_or_default();
+                            emails.push(Email {
+                                id: e.id,
+                                from: e.from_address,
+                                subject: e.subject,
+                                preview: e.preview,
+                                body: e.body,
+                                body_html: e.body_html,
+                                date: e.date,
+                                attachments,
+                                pgp_status: e.pgp_status,
+                                list_headers: e.list_headers,
+                                message_id: e.message_id,
+                                references: e.references,
+                                thread_id: e.thread_id,
+                                status: e.status,
+                            });
                         }
+                        anyhow::Result::<Vec<Email>>::Ok(emails)
                     })
                 })
                 .join();
 
-                if let Ok(Ok(db_emails)) = emails_result {
-                    self.emails = db_emails
-                        .into_iter()
-                        .map(|e| Email {
-                            id: e.id,
-                            from: e.from_address,
-                            subject: e.subject,
-                            preview: e.preview,
-                            body: e.body,
-                            date: e.date,
-                        })
-                        .collect();
+                if let Ok(Ok(emails)) = emails_result {
+                    self.emails = emails;
                     self.selected_index = 0;
                 }
             }
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    // ============ Folder List Methods ============
+
+    /// Switch the inbox list to `folder` and reload its emails for the current account.
+    pub fn switch_to_folder(&mut self, folder: String) {
+        self.current_folder = folder;
+        self.reload_emails_for_current_account();
+        self.start_mail_watch();
+        self.status_message = Some(format!("Switched to folder: {}", self.current_folder));
+    }
+
+    /// Open the folder browser from the inbox, kicking off a background IMAP `LIST` refresh
+    /// so the list is current even if it was last synced a while ago.
+    pub fn enter_folder_list_mode(&mut self) {
+        self.folder_list_state = Some(FolderListState { selected_index: 0 });
+        self.current_view = View::FolderList;
+        self.request_folder_sync();
+    }
+
+    pub fn exit_folder_list_mode(&mut self) {
+        self.folder_list_state = None;
+        self.current_view = View::InboxList;
+    }
+
+    pub fn folder_list_next(&mut self) {
+        if let Some(ref mut state) = self.folder_list_state {
+            if !self.folders.is_empty() {
+                state.selected_index = (state.selected_index + 1).min(self.folders.len() - 1);
+            }
+        }
+    }
+
+    pub fn folder_list_previous(&mut self) {
+        if let Some(ref mut state) = self.folder_list_state {
+            if state.selected_index > 0 {
+                state.selected_index -= 1;
+            }
+        }
+    }
+
+    /// Switch to the highlighted folder and return to the inbox.
+    pub fn folder_list_select(&mut self) {
+        let Some(state) = self.folder_list_state.as_ref() else { return };
+        let Some(folder) = self.folders.get(state.selected_index) else { return };
+        let name = folder.name.clone();
+        self.exit_folder_list_mode();
+        self.switch_to_folder(name);
+    }
+
+    /// Kick off a background IMAP `LIST`, caching the result in the database and dropping it
+    /// into `pending_folder_list` for [`Self::poll_folder_list_result`] to pick up next frame -
+    /// mirrors how [`Self::credentials_setup_validate`] backgrounds its connection check.
+    pub fn request_folder_sync(&mut self) {
+        let (Some(sync_manager), Some(db), Some(account_id)) =
+            (self.email_sync_manager.clone(), self.db.clone(), self.current_account_id)
+        else {
+            return;
+        };
+
+        let slot = self.pending_folder_list.clone();
+        tokio::spawn(async move {
+            let result = async {
+                let imap_folders = sync_manager.list_folders().await?;
+                let tuples: Vec<(String, String, Option<String>)> = imap_folders
+                    .into_iter()
+                    .map(|f| (f.name, f.delimiter, f.special_use))
+                    .collect();
+                db.sync_folders_from_imap(account_id, &tuples).await
+            }
+            .await;
+
+            *slot.lock().unwrap() = Some(FolderListEvent {
+                account_id: Some(account_id),
+                folders: result.map_err(|e| e.to_string()),
+            });
+        });
+    }
+
+    /// Drain a completed folder sync, if any, updating the cached folder list (and the
+    /// browser's selection, if it's open) and surfacing a status message on failure.
+    pub fn poll_folder_list_result(&mut self) {
+        let event = self.pending_folder_list.lock().unwrap().take();
+        let Some(event) = event else { return };
+
+        if event.account_id != self.current_account_id {
+            return;
+        }
+
+        match event.folders {
+            Ok(folders) => {
+                self.folders = folders;
+                if let Some(ref mut state) = self.folder_list_state {
+                    state.selected_index = state.selected_index.min(self.folders.len().saturating_sub(1));
+                }
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to list folders: {}", e));
+            }
+        }
+    }
+
+    // ============ Thread List Methods ============
+
+    /// Group `self.emails` by `thread_id` into `self.thread_groups`, a message with no
+    /// `thread_id` becoming a singleton group of its own. Groups are ordered by their newest
+    /// message (matching the inbox's own newest-first order), and each group's `email_indices`
+    /// are oldest-first for the expanded reader to walk in conversation order.
+    fn rebuild_thread_groups(&mut self) {
+        let mut by_thread: std::collections::HashMap<String, Vec<usize>> = std::collections::HashMap::new();
+        let mut order: Vec<Option<String>> = Vec::new();
+
+        for (i, email) in self.emails.iter().enumerate() {
+            match &email.thread_id {
+                Some(thread_id) => {
+                    if !by_thread.contains_key(thread_id) {
+                        order.push(Some(thread_id.clone()));
+                    }
+                    by_thread.entry(thread_id.clone()).or_default().push(i);
+                }
+                None => order.push(None),
+            }
+        }
+
+        self.thread_groups = order
+            .into_iter()
+            .map(|thread_id| match thread_id {
+                Some(id) => {
+                    let mut email_indices = by_thread.remove(&id).unwrap_or_default();
+                    email_indices.reverse(); // collected newest-first; conversation order is oldest-first
+                    ThreadGroup { thread_id: Some(id), email_indices }
+                }
+                None => ThreadGroup { thread_id: None, email_indices: vec![] },
+            })
+            .collect();
+
+        // Singleton groups recorded their index in `order` as `None`, not via `by_thread`; fill
+        // them back in now that every threaded group has claimed its slot.
+        let mut singleton_indices = self
+            .emails
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.thread_id.is_none())
+            .map(|(i, _)| i);
+        for group in self.thread_groups.iter_mut() {
+            if group.thread_id.is_none() {
+                if let Some(i) = singleton_indices.next() {
+                    group.email_indices = vec![i];
+                }
+            }
+        }
+    }
+
+    /// Open the thread list from the inbox, grouping the currently-loaded emails by `thread_id`.
+    pub fn enter_thread_list_mode(&mut self) {
+        self.rebuild_thread_groups();
+        self.thread_list_state = Some(ThreadListState::default());
+        self.current_view = View::ThreadList;
+    }
+
+    pub fn exit_thread_list_mode(&mut self) {
+        self.thread_list_state = None;
+        self.current_view = View::InboxList;
+        self.listing_style = ListingStyle::Flat;
+    }
+
+    /// Cycle `Flat -> Compact -> Conversations -> Flat`. `Conversations` hands off to
+    /// [`Self::enter_thread_list_mode`] immediately rather than rendering grouped rows inline;
+    /// leaving that view (`Esc`/`q`, see [`Self::exit_thread_list_mode`]) resets back to `Flat`.
+    pub fn cycle_listing_style(&mut self) {
+        self.listing_style = match self.listing_style {
+            ListingStyle::Flat => ListingStyle::Compact,
+            ListingStyle::Compact => {
+                self.enter_thread_list_mode();
+                ListingStyle::Conversations
+            }
+            ListingStyle::Conversations => ListingStyle::Flat,
+        };
+    }
+
+    pub fn thread_list_next(&mut self) {
+        let Some(ref mut state) = self.thread_list_state else { return };
+        match state.expanded_message_index {
+            Some(ref mut i) => {
+                if let Some(group) = self.thread_groups.get(state.selected_index) {
+                    *i = (*i + 1).min(group.email_indices.len().saturating_sub(1));
+                }
+            }
+            None => {
+                if !self.thread_groups.is_empty() {
+                    state.selected_index = (state.selected_index + 1).min(self.thread_groups.len() - 1);
+                }
+            }
+        }
+    }
+
+    pub fn thread_list_previous(&mut self) {
+        let Some(ref mut state) = self.thread_list_state else { return };
+        match state.expanded_message_index {
+            Some(ref mut i) => *i = i.saturating_sub(1),
+            None => state.selected_index = state.selected_index.saturating_sub(1),
+        }
+    }
+
+    /// `Enter` on the thread list: expand the highlighted thread into its message list, or
+    /// (already expanded) jump straight to reading the highlighted message in that thread.
+    pub fn thread_list_select(&mut self) {
+        let Some(ref mut state) = self.thread_list_state else { return };
+
+        match state.expanded_message_index {
+            Some(message_index) => {
+                let Some(group) = self.thread_groups.get(state.selected_index) else { return };
+                let Some(&email_index) = group.email_indices.get(message_index) else { return };
+                self.selected_index = email_index;
+                // `open_email` only opens from the inbox list.
+                self.current_view = View::InboxList;
+                self.open_email();
+            }
+            None => state.expanded_message_index = Some(0),
+        }
+    }
+
+    /// `Esc`/`h` on the thread list: collapse an expanded thread back to the thread list, or
+    /// leave the thread list entirely if nothing was expanded.
+    pub fn thread_list_back(&mut self) {
+        let Some(ref mut state) = self.thread_list_state else { return };
+        if state.expanded_message_index.take().is_none() {
+            self.exit_thread_list_mode();
+        }
+    }
+
+    // ============ Contacts Methods ============
+
+    /// Run an async `EmailDatabase` call to completion from sync code, via the same
+    /// spawn-a-thread-and-block-on-it workaround used elsewhere (e.g. `reload_emails_for_current_account`)
+    /// to avoid nesting a runtime inside the already-running one.
+    fn block_on_db<F, Fut, T>(&self, f: F) -> Option<T>
+    where
+        F: FnOnce(EmailDatabase) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = T> + Send,
+        T: Send + 'static,
+    {
+        let db = self.db.as_ref()?.clone();
+        let handle = tokio::runtime::Handle::try_current().ok()?;
+        std::thread::spawn(move || handle.block_on(f(db))).join().ok()
+    }
+
+    /// Load the merged Contacts list: editable database contacts plus any read-only `.vcf`
+    /// imports, both alphabetical by display name (falling back to address).
+    fn load_contact_entries(&self) -> Vec<ContactEntry> {
+        let db_contacts = self
+            .block_on_db(|db| async move { db.list_contacts().await })
+            .and_then(|r| r.ok())
+            .unwrap_or_default();
+
+        let mut entries: Vec<ContactEntry> =
+            db_contacts.into_iter().map(ContactEntry::Editable).collect();
+
+        if let Some(ref folder) = self.config.contacts_vcf_folder {
+            entries.extend(crate::vcard::load_vcf_folder(folder).into_iter().map(ContactEntry::ReadOnly));
+        }
+
+        entries.sort_by(|a, b| {
+            a.display_name()
+                .unwrap_or_else(|| a.email())
+                .to_lowercase()
+                .cmp(&b.display_name().unwrap_or_else(|| b.email()).to_lowercase())
+        });
+        entries
+    }
+
+    /// Open the Contacts view from the inbox, for browsing/managing the address book
+    pub fn enter_contacts_mode(&mut self) {
+        self.enter_contacts_mode_from(View::InboxList);
+    }
+
+    /// Open the Contacts view, remembering `return_view` so Enter knows whether to insert the
+    /// selected address into an in-progress compose (see [`Self::contacts_insert_selected`])
+    fn enter_contacts_mode_from(&mut self, return_view: View) {
+        self.contacts_state = Some(ContactsState {
+            entries: self.load_contact_entries(),
+            selected_index: 0,
+            add_state: None,
+            return_view,
+        });
+        self.current_view = View::Contacts;
+    }
+
+    /// Open the Contacts view from compose, so Enter inserts into the field being edited
+    pub fn enter_contacts_mode_from_compose(&mut self) {
+        self.enter_contacts_mode_from(View::Compose);
+    }
+
+    pub fn exit_contacts_mode(&mut self) {
+        if let Some(state) = self.contacts_state.take() {
+            self.current_view = state.return_view;
+        }
+    }
+
+    pub fn contacts_next(&mut self) {
+        if let Some(ref mut state) = self.contacts_state {
+            if !state.entries.is_empty() {
+                state.selected_index = (state.selected_index + 1) % state.entries.len();
+            }
+        }
+    }
+
+    pub fn contacts_previous(&mut self) {
+        if let Some(ref mut state) = self.contacts_state {
+            if !state.entries.is_empty() {
+                state.selected_index = if state.selected_index == 0 {
+                    state.entries.len() - 1
+                } else {
+                    state.selected_index - 1
+                };
+            }
+        }
+    }
+
+    /// Insert the selected contact's address into the compose view's active field, then return
+    /// to it. A no-op if Contacts wasn't opened from compose.
+    pub fn contacts_insert_selected(&mut self) {
+        let Some(ref state) = self.contacts_state else { return };
+        if state.return_view != View::Compose {
+            return;
+        }
+        let Some(entry) = state.entries.get(state.selected_index) else { return };
+        let address = entry.email().to_string();
+
+        if let Some(ref mut compose) = self.compose_state {
+            let field = match compose.current_field {
+                ComposeField::Recipients => &mut compose.recipients,
+                ComposeField::Subject => &mut compose.subject,
+                ComposeField::Body => &mut compose.body,
+                ComposeField::Sign | ComposeField::Encrypt | ComposeField::Attachments => {
+                    self.exit_contacts_mode();
+                    return;
+                }
+            };
+            if field.is_empty() {
+                *field = address;
+            } else {
+                field.push_str(", ");
+                field.push_str(&address);
+            }
+            compose.cursor_position = field.len();
+        }
+
+        self.exit_contacts_mode();
+    }
+
+    pub fn contacts_start_add(&mut self) {
+        if let Some(ref mut state) = self.contacts_state {
+            state.add_state = Some(ContactAddState::default());
+        }
+    }
+
+    pub fn contacts_cancel_add(&mut self) {
+        if let Some(ref mut state) = self.contacts_state {
+            state.add_state = None;
+        }
+    }
+
+    pub fn contacts_add_insert_char(&mut self, c: char) {
+        if let Some(ref mut state) = self.contacts_state {
+            if let Some(ref mut add) = state.add_state {
+                if add.cursor_position <= add.buffer.len() {
+                    add.buffer.insert(add.cursor_position, c);
+                    add.cursor_position += 1;
+                }
+            }
+        }
+    }
+
+    pub fn contacts_add_delete_char(&mut self) {
+        if let Some(ref mut state) = self.contacts_state {
+            if let Some(ref mut add) = state.add_state {
+                if add.cursor_position > 0 {
+                    add.cursor_position -= 1;
+                    add.buffer.remove(add.cursor_position);
+                }
+            }
+        }
+    }
+
+    pub fn contacts_add_cursor_left(&mut self) {
+        if let Some(ref mut state) = self.contacts_state {
+            if let Some(ref mut add) = state.add_state {
+                if add.cursor_position > 0 {
+                    add.cursor_position -= 1;
+                }
+            }
+        }
+    }
+
+    pub fn contacts_add_cursor_right(&mut self) {
+        if let Some(ref mut state) = self.contacts_state {
+            if let Some(ref mut add) = state.add_state {
+                if add.cursor_position < add.buffer.len() {
+                    add.cursor_position += 1;
+                }
+            }
+        }
+    }
+
+    /// Parse the add buffer as a `Name <email>` (or bare `email`) entry, save it to the database,
+    /// and refresh the list. Leaves the buffer in place with an error message on a bad address.
+    pub fn contacts_confirm_add(&mut self) {
+        let Some(buffer) = self
+            .contacts_state
+            .as_ref()
+            .and_then(|s| s.add_state.as_ref())
+            .map(|a| a.buffer.clone())
+        else {
+            return;
+        };
+
+        let Some((name, address)) = crate::db::parse_address_list(&buffer).into_iter().next() else {
+            self.status_message = Some("Enter an address like \"Name <addr@example.com>\" or addr@example.com".to_string());
+            return;
+        };
+
+        let saved = self.block_on_db(move |db| async move { db.add_contact(&address, name.as_deref()).await });
+
+        match saved {
+            Some(Ok(_id)) => {
+                let entries = self.load_contact_entries();
+                if let Some(ref mut state) = self.contacts_state {
+                    state.add_state = None;
+                    state.entries = entries;
+                }
+                self.status_message = Some("Contact added".to_string());
+            }
+            _ => {
+                self.status_message = Some("Failed to add contact".to_string());
+            }
+        }
+    }
+
+    /// Delete the selected contact if it's an editable (database-backed) entry; read-only vCard
+    /// imports can't be deleted from here.
+    pub fn contacts_delete_selected(&mut self) {
+        let Some(ref state) = self.contacts_state else { return };
+        let Some(ContactEntry::Editable(contact)) = state.entries.get(state.selected_index).cloned() else {
+            self.status_message = Some("Can't delete a read-only vCard contact".to_string());
+            return;
+        };
+
+        let deleted = self.block_on_db(move |db| async move { db.delete_contact(contact.id).await });
+
+        if let Some(Ok(())) = deleted {
+            let entries = self.load_contact_entries();
+            if let Some(ref mut state) = self.contacts_state {
+                state.entries = entries;
+                if state.selected_index >= state.entries.len() {
+                    state.selected_index = state.entries.len().saturating_sub(1);
+                }
+            }
+            self.status_message = Some("Contact deleted".to_string());
+        } else {
+            self.status_message = Some("Failed to delete contact".to_string());
+        }
+    }
+
+    // ============ Compose Autocomplete Methods ============
+
+    /// Byte range of the address token under the cursor in the recipients field: everything
+    /// since the last comma, trimmed of leading whitespace so `"a@x.com, b"` resolves to `"b"`
+    /// rather than `" b"`. Shared by [`Self::compose_update_completions`] (reads the token) and
+    /// [`Self::compose_cycle_completion`] (replaces it), so the tokenization rule only lives once.
+    fn recipients_token_bounds(recipients: &str, cursor_position: usize) -> (usize, usize) {
+        let end = cursor_position.min(recipients.len());
+        let before_cursor = &recipients[..end];
+        let token_start = match before_cursor.rfind(',') {
+            Some(comma) => comma + 1,
+            None => 0,
+        };
+        let trimmed_start = token_start + (before_cursor[token_start..].len() - before_cursor[token_start..].trim_start().len());
+        (trimmed_start, end)
+    }
+
+    /// Refresh `completion_candidates` for the recipients field from the current cursor's
+    /// address token. Called after every insert/delete while editing recipients; a no-op for the
+    /// other fields, and clears candidates on an empty prefix.
+    pub fn compose_update_completions(&mut self) {
+        let Some(ref compose) = self.compose_state else { return };
+        if compose.current_field != ComposeField::Recipients {
+            return;
+        }
+
+        let (token_start, token_end) = Self::recipients_token_bounds(&compose.recipients, compose.cursor_position);
+        let prefix = compose.recipients[token_start..token_end].to_string();
+        if prefix.is_empty() {
+            if let Some(ref mut compose) = self.compose_state {
+                compose.completion_candidates.clear();
+                compose.completion_index = 0;
+            }
+            return;
+        }
+
+        let vcf_prefix = prefix.clone();
+        let mut candidates = self
+            .block_on_db(move |db| async move { db.search_contacts(&prefix, 5).await })
+            .and_then(|r| r.ok())
+            .unwrap_or_default();
+
+        if candidates.len() < 5 {
+            if let Some(ref folder) = self.config.contacts_vcf_folder {
+                let vcf_contacts = crate::vcard::load_vcf_folder(folder);
+                for vcard in crate::vcard::search(&vcf_contacts, &vcf_prefix) {
+                    if candidates.len() >= 5 {
+                        break;
+                    }
+                    if candidates.iter().any(|c| c.address == vcard.email) {
+                        continue;
+                    }
+                    candidates.push(DbContact {
+                        id: -1,
+                        account_id: None,
+                        address: vcard.email.clone(),
+                        display_name: vcard.name.clone(),
+                        times_seen: 0,
+                        last_seen_date: String::new(),
+                    });
+                }
+            }
+        }
+
+        if let Some(ref mut compose) = self.compose_state {
+            compose.completion_candidates = candidates;
+            compose.completion_index = 0;
+        }
+    }
+
+    /// Cycle to the next completion candidate and splice its address into the recipients field
+    /// in place of the token under the cursor, like a shell's Tab completion.
+    pub fn compose_cycle_completion(&mut self) {
+        let Some(ref mut compose) = self.compose_state else { return };
+        if compose.current_field != ComposeField::Recipients || compose.completion_candidates.is_empty() {
+            return;
+        }
+
+        let candidate = &compose.completion_candidates[compose.completion_index];
+        let token = match candidate.display_name.as_deref() {
+            Some(name) if !name.is_empty() => format!("{} <{}>", name, candidate.address),
+            _ => candidate.address.clone(),
+        };
+        let (token_start, token_end) = Self::recipients_token_bounds(&compose.recipients, compose.cursor_position);
+
+        compose.recipients.replace_range(token_start..token_end, &token);
+        compose.cursor_position = token_start + token.len();
+        compose.completion_index = (compose.completion_index + 1) % compose.completion_candidates.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_initialization() {
+        let app = App::new();
+        assert_eq!(app.current_view, View::InboxList);
+        assert_eq!(app.selected_index, 0);
+        assert_eq!(app.should_quit, false);
+        assert_eq!(app.emails.len(), 5);
+    }
+
+    #[test]
+    fn test_navigation() {
+        let mut app = App::new();
+        assert_eq!(app.selected_index, 0);
+
+        app.next_email();
+        assert_eq!(app.selected_index, 1);
+
+        app.next_email();
+        assert_eq!(app.selected_index, 2);
+
+        app.previous_email();
+        assert_eq!(app.selected_index, 1);
+
+        app.previous_email();
+        assert_eq!(app.selected_index, 0);
+
+        // Should not go below 0
+        app.previous_email();
+        assert_eq!(app.selected_index, 0);
+    }
+
+    #[test]
+    fn test_navigation_bounds() {
+        let mut app = App::new();
+
+        // Move to the last email
+        for _ in 0..10 {
+            app.next_email();
+        }
+
+        // Should not exceed last index
+        assert_eq!(app.selected_index, 4);
+    }
+
+    #[test]
+    fn test_pending_count_digits_accumulate_and_reset() {
+        let mut app = App::new();
+        app.push_pending_count_digit('1');
+        app.push_pending_count_digit('2');
+        assert_eq!(app.take_pending_count(), 12usize.min(app.emails.len()));
+        // Consuming clears the buffer for the next key
+        assert_eq!(app.take_pending_count(), 1);
+    }
+
+    #[test]
+    fn test_pending_count_leading_zero_is_ignored() {
+        let mut app = App::new();
+        app.push_pending_count_digit('0');
+        // A lone leading zero never starts a count
+        assert_eq!(app.take_pending_count(), 1);
+
+        app.push_pending_count_digit('2');
+        app.push_pending_count_digit('0');
+        // But a zero after a non-zero digit is a normal digit, e.g. `20j`
+        assert_eq!(app.take_pending_count(), 5); // clamped to emails.len()
+    }
+
+    #[test]
+    fn test_count_prefix_repeats_motion() {
+        let mut app = App::new();
+        let count = app.take_pending_count();
+        assert_eq!(count, 1);
+
+        app.push_pending_count_digit('2');
+        let count = app.take_pending_count();
+        for _ in 0..count {
+            app.next_email();
+        }
+        assert_eq!(app.selected_index, 2);
+    }
+
+    #[test]
+    fn test_view_switching() {
+        let mut app = App::new();
+        assert_eq!(app.current_view, View::InboxList);
+
+        app.open_email();
+        assert_eq!(app.current_view, View::EmailDetail);
+
+        app.close_email();
+        assert_eq!(app.current_view, View::InboxList);
+
+        // Opening from detail view should not change
+        app.open_email();
+        app.open_email();
+        assert_eq!(app.current_view, View::EmailDetail);
+    }
+
+    #[test]
+    fn test_actions() {
+        let mut app = App::new();
+        let initial_count = app.emails.len();
+
+        app.perform_action(Action::Delete);
+        assert!(app.status_message.is_some());
+        assert!(app.status_message.as_ref().unwrap().contains("Deleted"));
+        // Delete should remove the email from the list
+        assert_eq!(app.emails.len(), initial_count - 1);
+
+        app.perform_action(Action::Archive);
+        assert!(app.status_message.as_ref().unwrap().contains("Archived"));
+        // Archive should also remove the email from the list
+        assert_eq!(app.emails.len(), initial_count - 2);
+
+        app.perform_action(Action::Reply);
+        assert!(app.status_message.as_ref().unwrap().contains("Replying"));
+
+        app.perform_action(Action::Compose);
+        assert_eq!(app.current_view, View::Compose);
+        assert!(app.compose_state.is_some());
+
+        app.exit_compose_mode();
+        app.perform_action(Action::Forward);
+        assert!(app.status_message.as_ref().unwrap().contains("Forwarding"));
+    }
+
+    #[test]
+    fn test_reply_populates_threaded_compose_state() {
+        let mut app = App::new();
+        app.emails[0].message_id = Some("<orig@example.com>".to_string());
+        app.emails[0].references = Some("<earlier@example.com>".to_string());
+        let original = app.emails[0].clone();
+
+        app.perform_action(Action::Reply);
+
+        let compose = app.compose_state.as_ref().unwrap();
+        assert_eq!(compose.recipients, original.from);
+        assert_eq!(compose.subject, format!("Re: {}", original.subject));
+        assert!(compose.body.contains(&format!("{} wrote:", original.from)));
+        assert!(compose.body.contains(&format!("> {}", original.body.lines().next().unwrap())));
+        assert_eq!(compose.in_reply_to.as_deref(), Some("<orig@example.com>"));
+        assert_eq!(compose.references.as_deref(), Some("<earlier@example.com> <orig@example.com>"));
+        assert_eq!(app.current_view, View::Compose);
+
+        // Replying to an already-`Re:`d subject doesn't double-prefix it
+        app.exit_compose_mode();
+        app.compose_state = None;
+        app.emails[0].subject = format!("Re: {}", original.subject);
+        app.perform_action(Action::Reply);
+        assert_eq!(app.compose_state.as_ref().unwrap().subject, format!("Re: {}", original.subject));
+    }
+
+    #[test]
+    fn test_forward_populates_quoted_compose_state_without_threading() {
+        let mut app = App::new();
+        app.emails[0].message_id = Some("<orig@example.com>".to_string());
+        let original = app.emails[0].clone();
+
+        app.perform_action(Action::Forward);
+
+        let compose = app.compose_state.as_ref().unwrap();
+        assert_eq!(compose.recipients, "");
+        assert_eq!(compose.subject, format!("Fwd: {}", original.subject));
+        assert!(compose.body.contains(&format!("{} wrote:", original.from)));
+        assert!(compose.body.contains(&format!("> {}", original.body.lines().next().unwrap())));
+        assert!(compose.in_reply_to.is_none());
+        assert!(compose.references.is_none());
+    }
+
+    #[test]
+    fn test_cycle_listing_style_goes_flat_compact_conversations_flat() {
+        let mut app = App::new();
+        assert_eq!(app.listing_style, ListingStyle::Flat);
+
+        app.cycle_listing_style();
+        assert_eq!(app.listing_style, ListingStyle::Compact);
+        assert_eq!(app.current_view, View::InboxList);
+
+        app.cycle_listing_style();
+        assert_eq!(app.listing_style, ListingStyle::Conversations);
+        assert_eq!(app.current_view, View::ThreadList);
+
+        app.cycle_listing_style();
+        assert_eq!(app.listing_style, ListingStyle::Flat);
+    }
+
+    #[test]
+    fn test_exiting_thread_list_resets_listing_style_to_flat() {
+        let mut app = App::new();
+        app.cycle_listing_style();
+        app.cycle_listing_style();
+        assert_eq!(app.listing_style, ListingStyle::Conversations);
+
+        app.exit_thread_list_mode();
+        assert_eq!(app.listing_style, ListingStyle::Flat);
+        assert_eq!(app.current_view, View::InboxList);
+    }
+
+    #[test]
+    fn test_mock_emails_are_unseen_by_default() {
+        let app = App::new();
+        assert!(app.emails.iter().all(|e| e.is_unseen()));
+    }
+
+    #[test]
+    fn test_is_unseen_tracks_status() {
+        let mut app = App::new();
+        app.emails[0].status = DbEmailStatus::Read;
+        assert!(!app.emails[0].is_unseen());
+        app.emails[0].status = DbEmailStatus::Unread;
+        assert!(app.emails[0].is_unseen());
+    }
 
     #[test]
-    fn test_app_initialization() {
-        let app = App::new();
-        assert_eq!(app.current_view, View::InboxList);
-        assert_eq!(app.selected_index, 0);
-        assert_eq!(app.should_quit, false);
-        assert_eq!(app.emails.len(), 5);
+    fn test_is_archived_tracks_status() {
+        let mut app = App::new();
+        assert!(!app.emails[0].is_archived());
+        app.emails[0].status = DbEmailStatus::Archived;
+        assert!(app.emails[0].is_archived());
     }
 
     #[test]
-    fn test_navigation() {
+    fn test_enter_and_exit_account_status() {
         let mut app = App::new();
-        assert_eq!(app.selected_index, 0);
+        app.enter_account_status();
+        assert_eq!(app.current_view, View::AccountStatus);
+        app.exit_account_status();
+        assert_eq!(app.current_view, View::InboxList);
+    }
 
-        app.next_email();
-        assert_eq!(app.selected_index, 1);
+    #[test]
+    fn test_toggle_html_view_requires_html_alternative() {
+        let mut app = App::new();
+        app.emails[0].body_html = None;
+        app.toggle_html_view();
+        assert!(!app.show_html_view);
+
+        app.emails[0].body_html = Some("<p>hi</p>".to_string());
+        app.toggle_html_view();
+        assert!(app.show_html_view);
+        app.toggle_html_view();
+        assert!(!app.show_html_view);
+    }
 
-        app.next_email();
-        assert_eq!(app.selected_index, 2);
+    #[test]
+    fn test_toggle_html_source_only_applies_while_html_view_is_open() {
+        let mut app = App::new();
+        app.emails[0].body_html = Some("<p>hi</p>".to_string());
 
-        app.previous_email();
-        assert_eq!(app.selected_index, 1);
+        // No-op while the HTML view isn't showing.
+        app.toggle_html_source();
+        assert!(!app.show_html_source);
 
-        app.previous_email();
-        assert_eq!(app.selected_index, 0);
+        app.toggle_html_view();
+        app.toggle_html_source();
+        assert!(app.show_html_source);
 
-        // Should not go below 0
-        app.previous_email();
-        assert_eq!(app.selected_index, 0);
+        // Leaving the HTML view resets it, same as show_html_view.
+        app.toggle_html_view();
+        assert!(!app.show_html_source);
     }
 
     #[test]
-    fn test_navigation_bounds() {
+    fn test_thread_list_groups_emails_by_thread_id() {
         let mut app = App::new();
+        // The first two mock emails share a conversation; the rest are singletons.
+        app.emails[0].thread_id = Some("t1".to_string());
+        app.emails[1].thread_id = Some("t1".to_string());
 
-        // Move to the last email
-        for _ in 0..10 {
-            app.next_email();
-        }
+        app.enter_thread_list_mode();
 
-        // Should not exceed last index
-        assert_eq!(app.selected_index, 4);
+        assert_eq!(app.current_view, View::ThreadList);
+        // 2 mock emails collapse into 1 thread, plus 3 singletons = 4 groups
+        assert_eq!(app.thread_groups.len(), 4);
+        let conversation = app.thread_groups.iter().find(|g| g.thread_id.as_deref() == Some("t1")).unwrap();
+        assert_eq!(conversation.email_indices, vec![1, 0]); // oldest first; mock emails are newest-first
     }
 
     #[test]
-    fn test_view_switching() {
+    fn test_thread_list_expand_and_open_message() {
         let mut app = App::new();
-        assert_eq!(app.current_view, View::InboxList);
+        app.emails[0].thread_id = Some("t1".to_string());
+        app.emails[1].thread_id = Some("t1".to_string());
+        app.enter_thread_list_mode();
 
-        app.open_email();
-        assert_eq!(app.current_view, View::EmailDetail);
+        let thread_index = app.thread_groups.iter().position(|g| g.thread_id.as_deref() == Some("t1")).unwrap();
+        app.thread_list_state.as_mut().unwrap().selected_index = thread_index;
 
-        app.close_email();
-        assert_eq!(app.current_view, View::InboxList);
+        // First Enter expands the thread instead of opening a message
+        app.thread_list_select();
+        assert_eq!(app.thread_list_state.as_ref().unwrap().expanded_message_index, Some(0));
+        assert_eq!(app.current_view, View::ThreadList);
 
-        // Opening from detail view should not change
-        app.open_email();
-        app.open_email();
+        // Second Enter opens the highlighted message
+        app.thread_list_select();
         assert_eq!(app.current_view, View::EmailDetail);
+        assert_eq!(app.selected_index, 1);
     }
 
     #[test]
-    fn test_actions() {
+    fn test_thread_list_back_collapses_then_exits() {
         let mut app = App::new();
-        let initial_count = app.emails.len();
-
-        app.perform_action(Action::Delete);
-        assert!(app.status_message.is_some());
-        assert!(app.status_message.as_ref().unwrap().contains("Deleted"));
-        // Delete should remove the email from the list
-        assert_eq!(app.emails.len(), initial_count - 1);
-
-        app.perform_action(Action::Archive);
-        assert!(app.status_message.as_ref().unwrap().contains("Archived"));
-        // Archive should also remove the email from the list
-        assert_eq!(app.emails.len(), initial_count - 2);
+        app.enter_thread_list_mode();
+        app.thread_list_state.as_mut().unwrap().expanded_message_index = Some(0);
 
-        app.perform_action(Action::Reply);
-        assert!(app.status_message.as_ref().unwrap().contains("Replying"));
-
-        app.perform_action(Action::Compose);
-        assert_eq!(app.current_view, View::Compose);
-        assert!(app.compose_state.is_some());
+        app.thread_list_back();
+        assert_eq!(app.current_view, View::ThreadList);
+        assert!(app.thread_list_state.as_ref().unwrap().expanded_message_index.is_none());
 
-        app.exit_compose_mode();
-        app.perform_action(Action::Forward);
-        assert!(app.status_message.as_ref().unwrap().contains("Forwarding"));
+        app.thread_list_back();
+        assert_eq!(app.current_view, View::InboxList);
+        assert!(app.thread_list_state.is_none());
     }
 
     #[test]
@@ -1783,6 +5143,24 @@ mod tests {
             ComposeField::Body
         );
 
+        app.compose_next_field();
+        assert_eq!(
+            app.compose_state.as_ref().unwrap().current_field,
+            ComposeField::Sign
+        );
+
+        app.compose_next_field();
+        assert_eq!(
+            app.compose_state.as_ref().unwrap().current_field,
+            ComposeField::Encrypt
+        );
+
+        app.compose_next_field();
+        assert_eq!(
+            app.compose_state.as_ref().unwrap().current_field,
+            ComposeField::Attachments
+        );
+
         app.compose_next_field();
         assert_eq!(
             app.compose_state.as_ref().unwrap().current_field,
@@ -1792,10 +5170,28 @@ mod tests {
         app.compose_previous_field();
         assert_eq!(
             app.compose_state.as_ref().unwrap().current_field,
-            ComposeField::Body
+            ComposeField::Attachments
         );
     }
 
+    #[test]
+    fn test_compose_sign_encrypt_fields_are_not_text_editable() {
+        let mut app = App::new();
+        app.enter_compose_mode();
+        let compose = app.compose_state.as_mut().unwrap();
+        compose.current_field = ComposeField::Sign;
+
+        app.compose_enter_insert_mode();
+        assert_eq!(app.compose_state.as_ref().unwrap().mode, ComposeMode::Normal);
+
+        app.compose_toggle_sign();
+        assert!(app.compose_state.as_ref().unwrap().sign);
+
+        app.compose_state.as_mut().unwrap().current_field = ComposeField::Encrypt;
+        app.compose_toggle_encrypt();
+        assert!(app.compose_state.as_ref().unwrap().encrypt);
+    }
+
     #[test]
     fn test_compose_insert_mode() {
         let mut app = App::new();
@@ -1924,6 +5320,313 @@ mod tests {
         assert_eq!(app.compose_state.as_ref().unwrap().subject, "");
     }
 
+    #[test]
+    fn test_compose_clear_field_on_attachments_removes_only_highlighted() {
+        let mut app = App::new();
+        app.enter_compose_mode();
+        app.compose_add_attachment(PathBuf::from("/tmp/a.txt"));
+        app.compose_add_attachment(PathBuf::from("/tmp/b.txt"));
+        app.compose_add_attachment(PathBuf::from("/tmp/c.txt"));
+        app.compose_state.as_mut().unwrap().current_field = ComposeField::Attachments;
+        app.compose_state.as_mut().unwrap().attachment_selected = 1;
+
+        app.compose_clear_field();
+
+        let attachments = &app.compose_state.as_ref().unwrap().attachments;
+        assert_eq!(attachments.len(), 2);
+        assert_eq!(attachments[0], PathBuf::from("/tmp/a.txt"));
+        assert_eq!(attachments[1], PathBuf::from("/tmp/c.txt"));
+    }
+
+    #[test]
+    fn test_compose_attachment_navigation_stays_in_bounds() {
+        let mut app = App::new();
+        app.enter_compose_mode();
+        app.compose_add_attachment(PathBuf::from("/tmp/a.txt"));
+        app.compose_add_attachment(PathBuf::from("/tmp/b.txt"));
+        app.compose_state.as_mut().unwrap().current_field = ComposeField::Attachments;
+        app.compose_state.as_mut().unwrap().attachment_selected = 0;
+
+        app.compose_previous_attachment();
+        assert_eq!(app.compose_state.as_ref().unwrap().attachment_selected, 0);
+
+        app.compose_next_attachment();
+        assert_eq!(app.compose_state.as_ref().unwrap().attachment_selected, 1);
+
+        app.compose_next_attachment();
+        assert_eq!(app.compose_state.as_ref().unwrap().attachment_selected, 1);
+    }
+
+    #[test]
+    fn test_compose_attachment_prompt_add_and_cancel() {
+        let mut app = App::new();
+        app.enter_compose_mode();
+        app.compose_state.as_mut().unwrap().current_field = ComposeField::Attachments;
+
+        app.compose_start_attachment_prompt();
+        assert!(app.compose_state.as_ref().unwrap().attachment_prompt.is_some());
+
+        for c in "/tmp/new.txt".chars() {
+            app.compose_attachment_prompt_insert_char(c);
+        }
+        app.compose_confirm_attachment_prompt();
+
+        let compose = app.compose_state.as_ref().unwrap();
+        assert!(compose.attachment_prompt.is_none());
+        assert_eq!(compose.attachments.last(), Some(&PathBuf::from("/tmp/new.txt")));
+
+        app.compose_start_attachment_prompt();
+        app.compose_attachment_prompt_insert_char('x');
+        app.compose_cancel_attachment_prompt();
+        assert!(app.compose_state.as_ref().unwrap().attachment_prompt.is_none());
+        assert_eq!(app.compose_state.as_ref().unwrap().attachments.len(), 1);
+    }
+
+    #[test]
+    fn test_compose_external_editor_request_and_apply() {
+        let mut app = App::new();
+        app.enter_compose_mode();
+
+        // Not flagged until explicitly requested
+        assert!(!app.take_external_editor_request());
+
+        app.request_external_editor();
+        assert!(app.take_external_editor_request());
+        // Draining the flag clears it
+        assert!(!app.take_external_editor_request());
+
+        // `main::run_external_editor` feeds the edited body (and optionally headers) back in
+        app.compose_set_from_editor(
+            Some("alice@example.com".to_string()),
+            Some("Updated subject".to_string()),
+            "Edited in $EDITOR".to_string(),
+        );
+        let compose = app.compose_state.as_ref().unwrap();
+        assert_eq!(compose.recipients, "alice@example.com");
+        assert_eq!(compose.subject, "Updated subject");
+        assert_eq!(compose.body, "Edited in $EDITOR");
+        assert_eq!(compose.current_field, ComposeField::Body);
+
+        // A non-zero editor exit means `main` never calls `compose_set_from_editor`, so the
+        // draft is left exactly as it was - nothing to assert here beyond that being the only
+        // way the body changes.
+    }
+
+    #[test]
+    fn test_contacts_navigation_wraps() {
+        let mut app = App::new();
+        app.contacts_state = Some(ContactsState {
+            entries: vec![
+                ContactEntry::Editable(DbContact {
+                    id: 1,
+                    account_id: None,
+                    address: "a@example.com".to_string(),
+                    display_name: None,
+                    times_seen: 0,
+                    last_seen_date: String::new(),
+                }),
+                ContactEntry::Editable(DbContact {
+                    id: 2,
+                    account_id: None,
+                    address: "b@example.com".to_string(),
+                    display_name: None,
+                    times_seen: 0,
+                    last_seen_date: String::new(),
+                }),
+            ],
+            selected_index: 0,
+            add_state: None,
+            return_view: View::InboxList,
+        });
+
+        app.contacts_previous();
+        assert_eq!(app.contacts_state.as_ref().unwrap().selected_index, 1);
+        app.contacts_next();
+        assert_eq!(app.contacts_state.as_ref().unwrap().selected_index, 0);
+    }
+
+    #[test]
+    fn test_contacts_insert_selected_appends_to_recipients() {
+        let mut app = App::new();
+        app.enter_compose_mode();
+        app.compose_state.as_mut().unwrap().recipients = "existing@example.com".to_string();
+
+        app.contacts_state = Some(ContactsState {
+            entries: vec![ContactEntry::Editable(DbContact {
+                id: 1,
+                account_id: None,
+                address: "new@example.com".to_string(),
+                display_name: None,
+                times_seen: 0,
+                last_seen_date: String::new(),
+            })],
+            selected_index: 0,
+            add_state: None,
+            return_view: View::Compose,
+        });
+
+        app.contacts_insert_selected();
+        assert_eq!(
+            app.compose_state.as_ref().unwrap().recipients,
+            "existing@example.com, new@example.com"
+        );
+        assert_eq!(app.current_view, View::Compose);
+        assert!(app.contacts_state.is_none());
+    }
+
+    #[test]
+    fn test_recipients_token_bounds_uses_token_after_last_comma() {
+        let recipients = "alice@x.com, bo";
+        let (start, end) = App::recipients_token_bounds(recipients, 15);
+        assert_eq!(&recipients[start..end], "bo");
+
+        let recipients = "ali";
+        let (start, end) = App::recipients_token_bounds(recipients, 3);
+        assert_eq!(&recipients[start..end], "ali");
+
+        let recipients = "a@x.com, ";
+        let (start, end) = App::recipients_token_bounds(recipients, 9);
+        assert_eq!(&recipients[start..end], "");
+    }
+
+    #[test]
+    fn test_clear_status_message_pushes_to_history() {
+        let mut app = App::new();
+        app.status_message = Some("Deleted email: Hello".to_string());
+
+        app.clear_status_message();
+
+        assert!(app.status_message.is_none());
+        assert_eq!(app.notification_history.len(), 1);
+        assert_eq!(app.notification_history[0].message, "Deleted email: Hello");
+    }
+
+    #[test]
+    fn test_clear_status_message_no_op_when_none() {
+        let mut app = App::new();
+        app.clear_status_message();
+        assert!(app.notification_history.is_empty());
+    }
+
+    #[test]
+    fn test_notification_history_caps_at_max_entries() {
+        let mut app = App::new();
+        for i in 0..(App::NOTIFICATION_HISTORY_CAP + 10) {
+            app.status_message = Some(format!("message {}", i));
+            app.clear_status_message();
+        }
+        assert_eq!(app.notification_history.len(), App::NOTIFICATION_HISTORY_CAP);
+        // Most recent message stays at the front
+        assert_eq!(app.notification_history[0].message, format!("message {}", App::NOTIFICATION_HISTORY_CAP + 9));
+    }
+
+    #[test]
+    fn test_notification_history_navigation() {
+        let mut app = App::new();
+        app.status_message = Some("first".to_string());
+        app.clear_status_message();
+        app.status_message = Some("second".to_string());
+        app.clear_status_message();
+
+        app.enter_notification_history();
+        assert_eq!(app.current_view, View::NotificationHistory);
+        assert_eq!(app.notification_history_state.as_ref().unwrap().selected_index, 0);
+
+        app.notification_history_next();
+        assert_eq!(app.notification_history_state.as_ref().unwrap().selected_index, 1);
+
+        // Doesn't scroll past the last entry
+        app.notification_history_next();
+        assert_eq!(app.notification_history_state.as_ref().unwrap().selected_index, 1);
+
+        app.notification_history_previous();
+        assert_eq!(app.notification_history_state.as_ref().unwrap().selected_index, 0);
+
+        app.exit_notification_history();
+        assert_eq!(app.current_view, View::InboxList);
+        assert!(app.notification_history_state.is_none());
+    }
+
+    fn test_folder(id: i64, name: &str) -> DbFolder {
+        DbFolder {
+            id,
+            account_id: None,
+            name: name.to_string(),
+            parent_id: None,
+            delimiter: "/".to_string(),
+            special_use: None,
+            display_order: id,
+        }
+    }
+
+    #[test]
+    fn test_folder_list_navigation_stays_in_bounds() {
+        let mut app = App::new();
+        app.folders = vec![test_folder(1, "INBOX"), test_folder(2, "Sent"), test_folder(3, "Archive")];
+        app.enter_folder_list_mode();
+        assert_eq!(app.current_view, View::FolderList);
+        assert_eq!(app.folder_list_state.as_ref().unwrap().selected_index, 0);
+
+        app.folder_list_next();
+        app.folder_list_next();
+        assert_eq!(app.folder_list_state.as_ref().unwrap().selected_index, 2);
+
+        // Doesn't scroll past the last entry
+        app.folder_list_next();
+        assert_eq!(app.folder_list_state.as_ref().unwrap().selected_index, 2);
+
+        app.folder_list_previous();
+        assert_eq!(app.folder_list_state.as_ref().unwrap().selected_index, 1);
+    }
+
+    #[test]
+    fn test_folder_list_select_switches_folder_and_returns_to_inbox() {
+        let mut app = App::new();
+        app.folders = vec![test_folder(1, "INBOX"), test_folder(2, "Sent")];
+        app.enter_folder_list_mode();
+        app.folder_list_next();
+
+        app.folder_list_select();
+
+        assert_eq!(app.current_view, View::InboxList);
+        assert!(app.folder_list_state.is_none());
+        assert_eq!(app.current_folder, "Sent");
+    }
+
+    #[test]
+    fn test_request_folder_sync_without_credentials_is_a_no_op() {
+        let mut app = App::new();
+        app.request_folder_sync();
+        assert!(app.pending_folder_list.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_poll_folder_list_result_updates_cache_on_success() {
+        let mut app = App::new();
+        *app.pending_folder_list.lock().unwrap() = Some(FolderListEvent {
+            account_id: app.current_account_id,
+            folders: Ok(vec![test_folder(1, "INBOX")]),
+        });
+
+        app.poll_folder_list_result();
+
+        assert_eq!(app.folders.len(), 1);
+        assert_eq!(app.folders[0].name, "INBOX");
+    }
+
+    #[test]
+    fn test_poll_folder_list_result_surfaces_error() {
+        let mut app = App::new();
+        *app.pending_folder_list.lock().unwrap() = Some(FolderListEvent {
+            account_id: app.current_account_id,
+            folders: Err("connection refused".to_string()),
+        });
+
+        app.poll_folder_list_result();
+
+        assert_eq!(app.status_message, Some("Failed to list folders: connection refused".to_string()));
+    }
+
     #[tokio::test]
     async fn test_draft_save_and_load() {
         // Use a unique database for this test to avoid locking issues
@@ -1945,6 +5648,7 @@ mod tests {
             db: Some(db),
             draft_id: None,
             show_preview_panel: false,
+            listing_style: ListingStyle::Flat,
             visual_mode: false,
             visual_selections: HashSet::new(),
             visual_anchor: None,
@@ -1952,10 +5656,35 @@ mod tests {
             credentials: None,
             credentials_setup_state: None,
             credentials_unlock_state: None,
+            command_line_state: None,
+            command_history: Vec::new(),
+            external_editor_requested: false,
+            shortcuts: crate::keymap::Shortcuts::default(),
             config: Config::default(),
             accounts: Vec::new(),
             current_account_id: None,
             email_sync_manager: None,
+            pending_validation: Arc::new(Mutex::new(None)),
+            pending_oauth: Arc::new(Mutex::new(None)),
+            pending_device_auth: Arc::new(Mutex::new(None)),
+            contacts_state: None,
+            pending_count: String::new(),
+            detail_link_follow_mode: false,
+            detail_link_follow_digits: String::new(),
+            notification_history: VecDeque::new(),
+            notification_history_state: None,
+            current_folder: "inbox".to_string(),
+            folders: Vec::new(),
+            folder_list_state: None,
+            thread_groups: Vec::new(),
+            thread_list_state: None,
+            pending_folder_list: Arc::new(Mutex::new(None)),
+            show_html_view: false,
+            show_html_source: false,
+            pending_send: Arc::new(Mutex::new(None)),
+            pending_mail_watch: Arc::new(Mutex::new(Vec::new())),
+            mail_watch_stop: None,
+            last_sync_at: Arc::new(Mutex::new(None)),
         };
 
         // Enter compose mode and add some content
@@ -1978,6 +5707,9 @@ mod tests {
         app.compose_insert_char('t');
         app.compose_exit_insert_mode(); // Move to body
 
+        app.compose_add_attachment(PathBuf::from("/tmp/report.pdf"));
+        app.compose_add_attachment(PathBuf::from("/tmp/notes.txt"));
+
         // Manually save the draft
         app.save_current_draft();
 
@@ -1994,6 +5726,10 @@ mod tests {
         let compose = app.compose_state.as_ref().unwrap();
         assert_eq!(compose.recipients, "test");
         assert_eq!(compose.subject, "My Draft");
+        assert_eq!(
+            compose.attachments,
+            vec![PathBuf::from("/tmp/report.pdf"), PathBuf::from("/tmp/notes.txt")]
+        );
 
         // Cleanup
         let _ = std::fs::remove_file(&path);
@@ -2020,6 +5756,7 @@ mod tests {
             db: Some(db),
             draft_id: None,
             show_preview_panel: false,
+            listing_style: ListingStyle::Flat,
             visual_mode: false,
             visual_selections: HashSet::new(),
             visual_anchor: None,
@@ -2027,10 +5764,35 @@ mod tests {
             credentials: None,
             credentials_setup_state: None,
             credentials_unlock_state: None,
+            command_line_state: None,
+            command_history: Vec::new(),
+            external_editor_requested: false,
+            shortcuts: crate::keymap::Shortcuts::default(),
             config: Config::default(),
             accounts: Vec::new(),
             current_account_id: None,
             email_sync_manager: None,
+            pending_validation: Arc::new(Mutex::new(None)),
+            pending_oauth: Arc::new(Mutex::new(None)),
+            pending_device_auth: Arc::new(Mutex::new(None)),
+            contacts_state: None,
+            pending_count: String::new(),
+            detail_link_follow_mode: false,
+            detail_link_follow_digits: String::new(),
+            notification_history: VecDeque::new(),
+            notification_history_state: None,
+            current_folder: "inbox".to_string(),
+            folders: Vec::new(),
+            folder_list_state: None,
+            thread_groups: Vec::new(),
+            thread_list_state: None,
+            pending_folder_list: Arc::new(Mutex::new(None)),
+            show_html_view: false,
+            show_html_source: false,
+            pending_send: Arc::new(Mutex::new(None)),
+            pending_mail_watch: Arc::new(Mutex::new(Vec::new())),
+            mail_watch_stop: None,
+            last_sync_at: Arc::new(Mutex::new(None)),
         };
 
         // Enter compose mode and add some content
@@ -2235,6 +5997,82 @@ mod tests {
         assert!(app.status_message.as_ref().unwrap().contains(&email_subject));
     }
 
+    #[test]
+    fn test_single_export_action() {
+        let mut app = App::new();
+        let initial_count = app.emails.len();
+
+        app.perform_action(Action::Export);
+
+        // Export is non-destructive, unlike delete/archive.
+        assert_eq!(app.emails.len(), initial_count);
+
+        assert!(app.status_message.is_some());
+        assert!(app.status_message.as_ref().unwrap().contains("Exported"));
+        assert!(app.status_message.as_ref().unwrap().contains('1'));
+    }
+
+    #[test]
+    fn test_visual_mode_batch_export() {
+        let mut app = App::new();
+        let initial_count = app.emails.len();
+
+        app.enter_visual_mode();
+        app.next_email();
+        assert_eq!(app.visual_selections.len(), 2);
+
+        app.perform_batch_action(Action::Export);
+
+        // Visual mode is exited but, unlike batch delete/archive, no emails are removed.
+        assert_eq!(app.visual_mode, false);
+        assert_eq!(app.visual_selections.len(), 0);
+        assert_eq!(app.emails.len(), initial_count);
+
+        assert!(app.status_message.is_some());
+        assert!(app.status_message.as_ref().unwrap().contains("Exported"));
+        assert!(app.status_message.as_ref().unwrap().contains('2'));
+    }
+
+    #[test]
+    fn test_mbox_message_quotes_from_lines() {
+        let mut app = App::new();
+        app.emails[0].body = "Hi there\nFrom the team,\n>From nested quote\nBye".to_string();
+        let email = app.emails[0].clone();
+
+        let rendered = App::mbox_message(&email);
+        let mut lines = rendered.lines();
+
+        assert!(lines.next().unwrap().starts_with(&format!("From {} ", email.from)));
+        assert!(rendered.contains("\n>From the team,\n"));
+        assert!(rendered.contains("\n>>From nested quote\n"));
+        // The message ends with a blank line separating it from the next one.
+        assert!(rendered.ends_with("\n\n"));
+    }
+
+    #[test]
+    fn test_poll_mail_watch_events_shifts_selection_and_visual_range() {
+        let mut app = App::new();
+        app.selected_index = 2;
+        app.enter_visual_mode(); // anchors at 2
+        app.next_email(); // extends selection to {2, 3}
+        assert_eq!(app.visual_selections, std::collections::HashSet::from([2, 3]));
+
+        let mut pushed = App::mock_emails();
+        let new_email = pushed.remove(0);
+        app.pending_mail_watch.lock().unwrap().push(MailWatchEvent {
+            account_id: app.current_account_id,
+            folder: app.current_folder.clone(),
+            emails: vec![new_email],
+        });
+
+        app.poll_mail_watch_events();
+
+        // The new message was prepended, so everything after it shifts down by one.
+        assert_eq!(app.selected_index, 3);
+        assert_eq!(app.visual_anchor, Some(3));
+        assert_eq!(app.visual_selections, std::collections::HashSet::from([3, 4]));
+    }
+
     #[test]
     fn test_is_email_selected() {
         let mut app = App::new();
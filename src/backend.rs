@@ -0,0 +1,500 @@
+/// Pluggable message-store backends.
+///
+/// `App` used to assume mail always came from the live IMAP sync into the SQLite
+/// [`crate::db::EmailDatabase`]. [`MailBackend`] abstracts the operations [`crate::app::App`]
+/// actually needs from a mailbox - list folders, fetch headers/bodies, flag/move/delete a
+/// message - so an account can instead be backed by a local [`crate::maildir`] directory
+/// ([`LocalMaildirBackend`]) or, behind the `notmuch` feature, a notmuch-indexed store
+/// ([`NotmuchBackend`]), with no IMAP server involved at all.
+///
+/// This is the mail-store analogue of [`crate::credentials::CredentialStore`]: one trait, one
+/// implementation constructed per account from [`crate::config::AccountBackend`]. Unlike
+/// `CredentialStore` it isn't boxed as `dyn` here - `App` only ever holds the backend for the
+/// current account, the same way it holds one [`crate::email_sync::EmailSyncManager`], so a
+/// generic-free enum is enough and avoids the extra indirection. Trait methods are synchronous,
+/// mirroring `CredentialStore` and `ImapClient`'s own `_blocking` helpers: the IMAP
+/// implementation is blocking network I/O under the hood either way, so callers run these via
+/// `tokio::task::spawn_blocking` rather than the trait pretending otherwise.
+use crate::config::AccountBackend;
+use crate::credentials::Credentials;
+use crate::db::DbEmail;
+use crate::email_sync::ImapClient;
+use crate::maildir::{MaildirFlag, MaildirMirror};
+use crate::mime::ParsedAttachment;
+use anyhow::{Context, Result, anyhow, bail};
+use std::path::PathBuf;
+
+/// One mailbox/folder as reported by a backend - the backend-agnostic form of
+/// [`crate::email_sync::ImapFolder`].
+#[derive(Debug, Clone)]
+pub struct BackendFolder {
+    pub name: String,
+    pub delimiter: String,
+    pub special_use: Option<String>,
+}
+
+/// A flag settable on a message, independent of any one backend's own encoding (IMAP's
+/// `\Flags`, Maildir's `:2,` suffix, notmuch's tags).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageFlag {
+    Seen,
+    Answered,
+    Flagged,
+    Deleted,
+    Draft,
+}
+
+impl MessageFlag {
+    /// The bare IMAP flag name, without its leading `\`, as used in a `STORE` command.
+    fn imap_name(self) -> &'static str {
+        match self {
+            MessageFlag::Seen => "Seen",
+            MessageFlag::Answered => "Answered",
+            MessageFlag::Flagged => "Flagged",
+            MessageFlag::Deleted => "Deleted",
+            MessageFlag::Draft => "Draft",
+        }
+    }
+
+    fn to_maildir(self) -> MaildirFlag {
+        match self {
+            MessageFlag::Seen => MaildirFlag::Seen,
+            MessageFlag::Answered => MaildirFlag::Answered,
+            MessageFlag::Flagged => MaildirFlag::Flagged,
+            MessageFlag::Deleted => MaildirFlag::Deleted,
+            MessageFlag::Draft => MaildirFlag::Draft,
+        }
+    }
+}
+
+/// Where a backend fetches/mutates messages: list folders, pull headers/bodies, and
+/// flag/move/delete a message by the ref it was listed under (an IMAP UID for [`ImapBackend`],
+/// a mirrored UID for [`LocalMaildirBackend`], a `Message-Id` for [`NotmuchBackend`]).
+pub trait MailBackend {
+    fn list_folders(&self) -> Result<Vec<BackendFolder>>;
+    /// Every message currently in `folder` (capped to `limit`, most recent first), each paired
+    /// with the ref to pass back to `fetch_body`/`set_flag`/`move_message`/`delete_message`.
+    fn fetch_headers(&self, folder: &str, limit: Option<usize>) -> Result<Vec<(String, DbEmail)>>;
+    fn fetch_body(&self, folder: &str, msg_ref: &str) -> Result<(DbEmail, Vec<ParsedAttachment>)>;
+    fn set_flag(&self, folder: &str, msg_ref: &str, flag: MessageFlag, value: bool) -> Result<()>;
+    fn move_message(&self, folder: &str, msg_ref: &str, dest_folder: &str) -> Result<()>;
+    fn delete_message(&self, folder: &str, msg_ref: &str) -> Result<()>;
+}
+
+/// Build the [`MailBackend`] for an account, from the backend kind its [`AccountBackend`]
+/// config selected and (for IMAP) the credentials resolved for it. For a [`AccountBackend::Maildir`]
+/// account, `account_id` just namespaces the on-disk layout under the configured `path` the same
+/// way [`crate::maildir::MaildirMirror`] namespaces an IMAP account's offline mirror - it isn't
+/// required to match any other id this account is known by elsewhere.
+pub fn for_account(backend: &AccountBackend, account_id: &str, credentials: Option<Credentials>) -> Result<Backend> {
+    match backend {
+        AccountBackend::Imap => {
+            let credentials = credentials
+                .ok_or_else(|| anyhow!("IMAP backend requires credentials but none were provided"))?;
+            Ok(Backend::Imap(ImapBackend::new(credentials)))
+        }
+        AccountBackend::Maildir { path } => {
+            Ok(Backend::Maildir(LocalMaildirBackend::new(path.clone(), account_id.to_string())))
+        }
+        #[cfg(feature = "notmuch")]
+        AccountBackend::Notmuch { database_path } => {
+            Ok(Backend::Notmuch(NotmuchBackend::new(database_path.clone())))
+        }
+    }
+}
+
+/// The backend-specific ref to pass back into `fetch_body`/`set_flag`/`move_message`/
+/// `delete_message` for an already-fetched `email`, given which [`AccountBackend`] it came
+/// from: an IMAP UID or mirrored Maildir UID (both carried in `imap_uid`), or a notmuch
+/// `Message-Id`. `None` if `email` is missing the field its backend addresses messages by
+/// (e.g. a mock/local-only email with no UID).
+pub fn message_ref(backend: &AccountBackend, email: &DbEmail) -> Option<String> {
+    match backend {
+        #[cfg(feature = "notmuch")]
+        AccountBackend::Notmuch { .. } => email.message_id.clone(),
+        _ => email.imap_uid.map(|uid| uid.to_string()),
+    }
+}
+
+/// Concrete [`MailBackend`] for one account, selected by [`for_account`]. An enum rather than
+/// `Box<dyn MailBackend>` since `App` only ever holds one at a time for the current account.
+pub enum Backend {
+    Imap(ImapBackend),
+    Maildir(LocalMaildirBackend),
+    #[cfg(feature = "notmuch")]
+    Notmuch(NotmuchBackend),
+}
+
+impl MailBackend for Backend {
+    fn list_folders(&self) -> Result<Vec<BackendFolder>> {
+        match self {
+            Backend::Imap(b) => b.list_folders(),
+            Backend::Maildir(b) => b.list_folders(),
+            #[cfg(feature = "notmuch")]
+            Backend::Notmuch(b) => b.list_folders(),
+        }
+    }
+
+    fn fetch_headers(&self, folder: &str, limit: Option<usize>) -> Result<Vec<(String, DbEmail)>> {
+        match self {
+            Backend::Imap(b) => b.fetch_headers(folder, limit),
+            Backend::Maildir(b) => b.fetch_headers(folder, limit),
+            #[cfg(feature = "notmuch")]
+            Backend::Notmuch(b) => b.fetch_headers(folder, limit),
+        }
+    }
+
+    fn fetch_body(&self, folder: &str, msg_ref: &str) -> Result<(DbEmail, Vec<ParsedAttachment>)> {
+        match self {
+            Backend::Imap(b) => b.fetch_body(folder, msg_ref),
+            Backend::Maildir(b) => b.fetch_body(folder, msg_ref),
+            #[cfg(feature = "notmuch")]
+            Backend::Notmuch(b) => b.fetch_body(folder, msg_ref),
+        }
+    }
+
+    fn set_flag(&self, folder: &str, msg_ref: &str, flag: MessageFlag, value: bool) -> Result<()> {
+        match self {
+            Backend::Imap(b) => b.set_flag(folder, msg_ref, flag, value),
+            Backend::Maildir(b) => b.set_flag(folder, msg_ref, flag, value),
+            #[cfg(feature = "notmuch")]
+            Backend::Notmuch(b) => b.set_flag(folder, msg_ref, flag, value),
+        }
+    }
+
+    fn move_message(&self, folder: &str, msg_ref: &str, dest_folder: &str) -> Result<()> {
+        match self {
+            Backend::Imap(b) => b.move_message(folder, msg_ref, dest_folder),
+            Backend::Maildir(b) => b.move_message(folder, msg_ref, dest_folder),
+            #[cfg(feature = "notmuch")]
+            Backend::Notmuch(b) => b.move_message(folder, msg_ref, dest_folder),
+        }
+    }
+
+    fn delete_message(&self, folder: &str, msg_ref: &str) -> Result<()> {
+        match self {
+            Backend::Imap(b) => b.delete_message(folder, msg_ref),
+            Backend::Maildir(b) => b.delete_message(folder, msg_ref),
+            #[cfg(feature = "notmuch")]
+            Backend::Notmuch(b) => b.delete_message(folder, msg_ref),
+        }
+    }
+}
+
+/// [`MailBackend`] over the existing live IMAP sync, addressed by IMAP UID.
+pub struct ImapBackend {
+    credentials: Credentials,
+}
+
+impl ImapBackend {
+    pub fn new(credentials: Credentials) -> Self {
+        Self { credentials }
+    }
+
+    fn parse_uid(msg_ref: &str) -> Result<u32> {
+        msg_ref.parse().with_context(|| format!("Not a valid IMAP UID: {}", msg_ref))
+    }
+}
+
+impl MailBackend for ImapBackend {
+    fn list_folders(&self) -> Result<Vec<BackendFolder>> {
+        Ok(ImapClient::list_folders_blocking(&self.credentials)?
+            .into_iter()
+            .map(|f| BackendFolder { name: f.name, delimiter: f.delimiter, special_use: f.special_use })
+            .collect())
+    }
+
+    fn fetch_headers(&self, folder: &str, limit: Option<usize>) -> Result<Vec<(String, DbEmail)>> {
+        Ok(ImapClient::fetch_by_uid_blocking(&self.credentials, folder, limit)?
+            .into_iter()
+            .map(|(uid, email, _attachments)| (uid.to_string(), email))
+            .collect())
+    }
+
+    fn fetch_body(&self, folder: &str, msg_ref: &str) -> Result<(DbEmail, Vec<ParsedAttachment>)> {
+        let uid = Self::parse_uid(msg_ref)?;
+        ImapClient::fetch_one_by_uid_blocking(&self.credentials, folder, uid)
+    }
+
+    fn set_flag(&self, folder: &str, msg_ref: &str, flag: MessageFlag, value: bool) -> Result<()> {
+        let uid = Self::parse_uid(msg_ref)?;
+        ImapClient::set_flag_blocking(&self.credentials, folder, uid, flag.imap_name(), value)
+    }
+
+    fn move_message(&self, folder: &str, msg_ref: &str, dest_folder: &str) -> Result<()> {
+        let uid = Self::parse_uid(msg_ref)?;
+        ImapClient::move_message_blocking(&self.credentials, folder, uid, dest_folder)
+    }
+
+    fn delete_message(&self, folder: &str, msg_ref: &str) -> Result<()> {
+        let uid = Self::parse_uid(msg_ref)?;
+        ImapClient::delete_message_blocking(&self.credentials, folder, uid)
+    }
+}
+
+/// [`MailBackend`] over a local Maildir directory tree, with no server behind it at all - one
+/// [`MaildirMirror`] per folder (subdirectory under the account's mirror root). `msg_ref` is the
+/// mirrored message's UID rendered as a string, the same way a synced IMAP account's UID would
+/// be; a purely-local account just assigns its own instead of inheriting one from the server.
+pub struct LocalMaildirBackend {
+    data_dir: PathBuf,
+    account_id: String,
+}
+
+impl LocalMaildirBackend {
+    pub fn new(data_dir: PathBuf, account_id: String) -> Self {
+        Self { data_dir, account_id }
+    }
+
+    fn mirror(&self, folder: &str) -> Result<MaildirMirror> {
+        MaildirMirror::open(&self.data_dir, &self.account_id, folder)
+    }
+
+    fn parse_ref(msg_ref: &str) -> Result<u32> {
+        msg_ref.parse().with_context(|| format!("Not a valid mirrored message ref: {}", msg_ref))
+    }
+}
+
+impl MailBackend for LocalMaildirBackend {
+    fn list_folders(&self) -> Result<Vec<BackendFolder>> {
+        let root = self.data_dir.join("maildir").join(crate::maildir::sanitize(&self.account_id));
+        let mut folders = Vec::new();
+        if root.is_dir() {
+            for entry in std::fs::read_dir(&root).context("Failed to read Maildir account directory")? {
+                let entry = entry?;
+                if entry.file_type()?.is_dir() {
+                    if let Some(name) = entry.file_name().to_str() {
+                        folders.push(BackendFolder {
+                            name: name.to_string(),
+                            delimiter: "/".to_string(),
+                            special_use: None,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(folders)
+    }
+
+    fn fetch_headers(&self, folder: &str, limit: Option<usize>) -> Result<Vec<(String, DbEmail)>> {
+        let mirror = self.mirror(folder)?;
+        let mut messages = mirror.list_messages()?;
+        messages.sort_by_key(|m| std::cmp::Reverse(m.uid));
+        if let Some(limit) = limit {
+            messages.truncate(limit);
+        }
+
+        let mut out = Vec::new();
+        for message in messages {
+            let raw = std::fs::read(&message.path)
+                .with_context(|| format!("Failed to read cached message {:?}", message.path))?;
+            if let Some((mut email, _attachments)) = MaildirMirror::parse_cached(&raw, &message.flags, folder) {
+                email.imap_uid = Some(message.uid);
+                out.push((message.uid.to_string(), email));
+            }
+        }
+        Ok(out)
+    }
+
+    fn fetch_body(&self, folder: &str, msg_ref: &str) -> Result<(DbEmail, Vec<ParsedAttachment>)> {
+        let uid = Self::parse_ref(msg_ref)?;
+        let mirror = self.mirror(folder)?;
+        let message = mirror
+            .list_messages()?
+            .into_iter()
+            .find(|m| m.uid == uid)
+            .ok_or_else(|| anyhow!("No cached message with ref {} in {}", msg_ref, folder))?;
+
+        let raw = std::fs::read(&message.path)
+            .with_context(|| format!("Failed to read cached message {:?}", message.path))?;
+        let (mut email, attachments) = MaildirMirror::parse_cached(&raw, &message.flags, folder)
+            .ok_or_else(|| anyhow!("Failed to parse cached message at {:?}", message.path))?;
+        email.imap_uid = Some(uid);
+        Ok((email, attachments))
+    }
+
+    fn set_flag(&self, folder: &str, msg_ref: &str, flag: MessageFlag, value: bool) -> Result<()> {
+        let uid = Self::parse_ref(msg_ref)?;
+        let mirror = self.mirror(folder)?;
+        let message = mirror
+            .list_messages()?
+            .into_iter()
+            .find(|m| m.uid == uid)
+            .ok_or_else(|| anyhow!("No cached message with ref {} in {}", msg_ref, folder))?;
+
+        let mut flags = message.flags;
+        let target = flag.to_maildir();
+        if value {
+            if !flags.contains(&target) {
+                flags.push(target);
+            }
+        } else {
+            flags.retain(|f| *f != target);
+        }
+        mirror.apply_flags(uid, &flags)
+    }
+
+    fn move_message(&self, folder: &str, msg_ref: &str, dest_folder: &str) -> Result<()> {
+        let uid = Self::parse_ref(msg_ref)?;
+        let source = self.mirror(folder)?;
+        let message = source
+            .list_messages()?
+            .into_iter()
+            .find(|m| m.uid == uid)
+            .ok_or_else(|| anyhow!("No cached message with ref {} in {}", msg_ref, folder))?;
+
+        let raw = std::fs::read(&message.path)
+            .with_context(|| format!("Failed to read cached message {:?}", message.path))?;
+        let dest = self.mirror(dest_folder)?;
+        dest.store(uid, &raw, &message.flags)?;
+        source.remove(uid)
+    }
+
+    fn delete_message(&self, folder: &str, msg_ref: &str) -> Result<()> {
+        let uid = Self::parse_ref(msg_ref)?;
+        self.mirror(folder)?.remove(uid)
+    }
+}
+
+/// [`MailBackend`] over a notmuch-indexed mail store, queried via the `notmuch` CLI rather than
+/// a notmuch client library - this avoids a build-time dependency on libnotmuch for everyone who
+/// doesn't enable the feature. notmuch has no folder concept of its own, so `folder` here maps
+/// onto a notmuch tag (as most notmuch setups already tag each Maildir folder they index), and
+/// `msg_ref` is a message's `Message-Id` (without the surrounding `id:`/`<>`).
+#[cfg(feature = "notmuch")]
+pub struct NotmuchBackend {
+    database_path: PathBuf,
+}
+
+#[cfg(feature = "notmuch")]
+impl NotmuchBackend {
+    pub fn new(database_path: PathBuf) -> Self {
+        Self { database_path }
+    }
+
+    /// Where the minimal `notmuch` config pointing at `database_path` lives; written lazily by
+    /// [`Self::run`] the first time this backend is used, rather than requiring the user to have
+    /// already run `notmuch setup` (or share tume's own config with an unrelated notmuch setup).
+    fn config_path(&self) -> PathBuf {
+        self.database_path.join(".notmuch-config")
+    }
+
+    fn ensure_config(&self) -> Result<()> {
+        let path = self.config_path();
+        if !path.exists() {
+            std::fs::write(&path, format!("[database]\npath={}\n", self.database_path.display()))
+                .with_context(|| format!("Failed to write notmuch config at {:?}", path))?;
+        }
+        Ok(())
+    }
+
+    /// Run `notmuch` with `args`, pointed at `database_path` via `NOTMUCH_CONFIG`.
+    fn run(&self, args: &[&str]) -> Result<String> {
+        self.ensure_config()?;
+
+        let output = std::process::Command::new("notmuch")
+            .env("NOTMUCH_CONFIG", self.config_path())
+            .args(args)
+            .output()
+            .context("Failed to run notmuch (is it installed and on PATH?)")?;
+
+        if !output.status.success() {
+            bail!("notmuch {:?} failed: {}", args, String::from_utf8_lossy(&output.stderr));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    fn tag_query(msg_ref: &str) -> String {
+        format!("id:{}", msg_ref)
+    }
+}
+
+#[cfg(feature = "notmuch")]
+impl MailBackend for NotmuchBackend {
+    fn list_folders(&self) -> Result<Vec<BackendFolder>> {
+        let out = self.run(&["search", "--output=tags", "*"])?;
+        Ok(out
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|tag| BackendFolder { name: tag.to_string(), delimiter: "/".to_string(), special_use: None })
+            .collect())
+    }
+
+    fn fetch_headers(&self, folder: &str, limit: Option<usize>) -> Result<Vec<(String, DbEmail)>> {
+        let query = format!("tag:{}", folder);
+        let mut args = vec!["search", "--output=messages", "--sort=newest-first", query.as_str()];
+        let limit_arg;
+        if let Some(limit) = limit {
+            limit_arg = format!("--limit={}", limit);
+            args.insert(1, limit_arg.as_str());
+        }
+        let out = self.run(&args)?;
+
+        let mut emails = Vec::new();
+        for line in out.lines().filter(|l| !l.is_empty()) {
+            let msg_ref = line.trim_start_matches("id:").to_string();
+            match self.fetch_body(folder, &msg_ref) {
+                Ok((email, _attachments)) => emails.push((msg_ref, email)),
+                Err(e) => log::warn!("Failed to read notmuch message {}: {}", msg_ref, e),
+            }
+        }
+        Ok(emails)
+    }
+
+    fn fetch_body(&self, folder: &str, msg_ref: &str) -> Result<(DbEmail, Vec<ParsedAttachment>)> {
+        let out = self.run(&["show", "--format=raw", Self::tag_query(msg_ref).as_str()])?;
+        let raw = out.into_bytes();
+
+        let tags_out = self.run(&["search", "--output=tags", Self::tag_query(msg_ref).as_str()])?;
+        let tags: Vec<&str> = tags_out.lines().filter(|l| !l.is_empty()).collect();
+        let flags: Vec<MaildirFlag> = tags
+            .iter()
+            .filter_map(|tag| match *tag {
+                "flagged" => Some(MaildirFlag::Flagged),
+                "replied" => Some(MaildirFlag::Answered),
+                "draft" => Some(MaildirFlag::Draft),
+                _ => None,
+            })
+            .collect();
+        let mut flags = flags;
+        if !tags.contains(&"unread") {
+            flags.push(MaildirFlag::Seen);
+        }
+
+        let (mut email, attachments) = MaildirMirror::parse_cached(&raw, &flags, folder)
+            .ok_or_else(|| anyhow!("Failed to parse notmuch message {}", msg_ref))?;
+        email.message_id = Some(msg_ref.to_string());
+        Ok((email, attachments))
+    }
+
+    fn set_flag(&self, _folder: &str, msg_ref: &str, flag: MessageFlag, value: bool) -> Result<()> {
+        let tag = match flag {
+            MessageFlag::Seen => "unread", // inverted: tagging +unread means NOT seen
+            MessageFlag::Flagged => "flagged",
+            MessageFlag::Answered => "replied",
+            MessageFlag::Draft => "draft",
+            MessageFlag::Deleted => "deleted",
+        };
+        let set_value = if flag == MessageFlag::Seen { !value } else { value };
+        let op = if set_value { format!("+{}", tag) } else { format!("-{}", tag) };
+        self.run(&["tag", op.as_str(), "--", Self::tag_query(msg_ref).as_str()])?;
+        Ok(())
+    }
+
+    /// notmuch has no concept of moving a file between mailboxes; since `folder` is modeled as
+    /// a tag here, a "move" is just retagging away from the source tag and onto the destination.
+    fn move_message(&self, folder: &str, msg_ref: &str, dest_folder: &str) -> Result<()> {
+        let remove = format!("-{}", folder);
+        let add = format!("+{}", dest_folder);
+        self.run(&["tag", remove.as_str(), add.as_str(), "--", Self::tag_query(msg_ref).as_str()])?;
+        Ok(())
+    }
+
+    /// Soft-delete: tags the message `+deleted` rather than unlinking its file, since notmuch
+    /// indexes files in place and removing one out from under the database needs a `notmuch
+    /// new` reindex tume has no reason to trigger itself.
+    fn delete_message(&self, _folder: &str, msg_ref: &str) -> Result<()> {
+        self.run(&["tag", "+deleted", "--", Self::tag_query(msg_ref).as_str()])?;
+        Ok(())
+    }
+}
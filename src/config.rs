@@ -16,6 +16,503 @@ pub struct Account {
     pub color: Option<String>,
     #[serde(default)]
     pub display_order: Option<i64>,
+    /// Which server folders get mirrored locally for offline reading
+    #[serde(default)]
+    pub folder_sync: FolderSyncFilter,
+    /// Maps canonical folder names (inbox/sent/drafts/trash) to this provider's actual
+    /// folder names, so the UI can stay provider-agnostic
+    #[serde(default)]
+    pub folder_aliases: FolderAliases,
+    /// Where this account's mail actually lives - the default live IMAP/SMTP mailbox, or a
+    /// local store that needs no server at all. See [`AccountBackend`].
+    #[serde(default)]
+    pub backend: AccountBackend,
+    /// Where this account's outgoing mail goes, when that differs from the receiving backend's
+    /// own server. `None` means "send the same way mail came in" - SMTP via [`crate::credentials`]
+    /// for [`AccountBackend::Imap`], nothing for a local-only backend. See [`SendBackend`].
+    #[serde(default)]
+    pub send_backend: Option<SendBackend>,
+    /// Presentation preferences that override [`Config::settings`] for this account alone - see
+    /// [`Config::effective_settings`].
+    #[serde(default)]
+    pub settings: Settings,
+}
+
+impl Account {
+    /// Check that this account's backend configuration is internally consistent - a non-empty
+    /// host/command where one is required - beyond what deserialization already enforces by
+    /// requiring the variant's fields to be present at all. Called from [`Config::load`] so a
+    /// malformed block fails fast with a clear message instead of surfacing as a confusing
+    /// connection error later.
+    pub fn validate(&self) -> Result<()> {
+        match &self.backend {
+            AccountBackend::Maildir { path } if path.as_os_str().is_empty() => {
+                return Err(anyhow::anyhow!("Account {:?}: maildir backend needs a non-empty path", self.name));
+            }
+            #[cfg(feature = "notmuch")]
+            AccountBackend::Notmuch { database_path } if database_path.as_os_str().is_empty() => {
+                return Err(anyhow::anyhow!("Account {:?}: notmuch backend needs a non-empty database_path", self.name));
+            }
+            _ => {}
+        }
+
+        match &self.send_backend {
+            Some(SendBackend::Smtp { host, login, .. }) if host.is_empty() || login.is_empty() => {
+                return Err(anyhow::anyhow!("Account {:?}: smtp send backend needs a non-empty host and login", self.name));
+            }
+            Some(SendBackend::Smtp { secret, .. }) if secret.is_empty() => {
+                return Err(anyhow::anyhow!("Account {:?}: smtp send backend's secret is empty", self.name));
+            }
+            Some(SendBackend::Sendmail { command }) if command.is_empty() => {
+                return Err(anyhow::anyhow!("Account {:?}: sendmail send backend needs a non-empty command", self.name));
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Resolve this account's declared SMTP secret (see [`SendBackend::Smtp::secret`]) to its
+    /// actual value. Called lazily, right before a connection is opened - never from
+    /// [`Config::load`] - so a stale keyring entry or unreachable secret command doesn't prevent
+    /// startup.
+    pub fn resolve_secret(&self) -> Result<String> {
+        match &self.send_backend {
+            Some(SendBackend::Smtp { secret, .. }) => secret.resolve(),
+            _ => Err(anyhow::anyhow!(
+                "Account {:?} has no send_backend secret to resolve",
+                self.name
+            )),
+        }
+    }
+}
+
+/// Where an account's mail actually lives: the default remote IMAP/SMTP mailbox, a local
+/// Maildir directory, or (behind the `notmuch` feature) a notmuch-indexed store. Only `Imap`
+/// needs `provider`/credentials at all; the other two just need a path on disk, so
+/// [`crate::credentials`] setup skips the server fields entirely for them (see
+/// `CredentialsSetupState::backend` in `app.rs`) and [`crate::backend::for_account`] builds the
+/// matching [`crate::backend::MailBackend`] straight from this.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AccountBackend {
+    Imap,
+    Maildir { path: PathBuf },
+    #[cfg(feature = "notmuch")]
+    Notmuch { database_path: PathBuf },
+}
+
+impl Default for AccountBackend {
+    fn default() -> Self {
+        AccountBackend::Imap
+    }
+}
+
+impl AccountBackend {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AccountBackend::Imap => "IMAP",
+            AccountBackend::Maildir { .. } => "Local Maildir",
+            #[cfg(feature = "notmuch")]
+            AccountBackend::Notmuch { .. } => "notmuch",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            AccountBackend::Imap => "Syncs with a remote mailbox over IMAP/SMTP",
+            AccountBackend::Maildir { .. } => "Reads and writes a local Maildir directory; no server required",
+            #[cfg(feature = "notmuch")]
+            AccountBackend::Notmuch { .. } => "Queries a notmuch-indexed mail store on disk; no server required",
+        }
+    }
+
+    /// Short machine-readable tag for the `accounts.backend_kind` database column (see
+    /// [`crate::db::DbAccount::backend_kind`]).
+    pub fn db_tag(&self) -> &'static str {
+        match self {
+            AccountBackend::Imap => "imap",
+            AccountBackend::Maildir { .. } => "maildir",
+            #[cfg(feature = "notmuch")]
+            AccountBackend::Notmuch { .. } => "notmuch",
+        }
+    }
+
+    /// The on-disk path this backend reads from, if it isn't `Imap`.
+    pub fn local_path(&self) -> Option<&std::path::Path> {
+        match self {
+            AccountBackend::Imap => None,
+            AccountBackend::Maildir { path } => Some(path),
+            #[cfg(feature = "notmuch")]
+            AccountBackend::Notmuch { database_path } => Some(database_path),
+        }
+    }
+
+    /// Reconstruct an [`AccountBackend`] from a [`Self::db_tag`] and the path saved alongside
+    /// it, for accounts loaded back out of [`crate::db::DbAccount`]. Falls back to `Imap` for an
+    /// unrecognized tag (e.g. a `notmuch` account loaded by a build without the feature) so a
+    /// stale row can't crash startup.
+    pub fn from_db(tag: &str, path: Option<&str>) -> Self {
+        match (tag, path) {
+            ("maildir", Some(path)) => AccountBackend::Maildir { path: PathBuf::from(path) },
+            #[cfg(feature = "notmuch")]
+            ("notmuch", Some(path)) => AccountBackend::Notmuch { database_path: PathBuf::from(path) },
+            _ => AccountBackend::Imap,
+        }
+    }
+}
+
+/// How outgoing mail is encrypted in transit for a [`SendBackend::Smtp`] block. Distinct from
+/// [`crate::providers::SecurityType`] (used by the already-resolved [`crate::credentials::Credentials`])
+/// because a `send_backend` is declared directly in `config.toml`, where an explicit opt-out
+/// needs its own variant rather than defaulting silently to encrypted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SendEncryption {
+    SslTls,
+    StartTls,
+    None,
+}
+
+impl Default for SendEncryption {
+    /// Implicit TLS is the safer default when a caller hasn't specified one
+    fn default() -> Self {
+        SendEncryption::SslTls
+    }
+}
+
+/// Where an account's outgoing mail goes, when it isn't just "the default SMTP server from
+/// credentials setup" - mirrors [`AccountBackend`] on the receiving side, letting an account
+/// split its inbound and outbound paths (e.g. a local [`AccountBackend::Maildir`] account that
+/// still relays outgoing mail through a real SMTP server, or an IMAP account routed through
+/// `sendmail`/msmtp instead of tume dialing SMTP itself).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SendBackend {
+    Smtp {
+        host: String,
+        port: u16,
+        login: String,
+        #[serde(default)]
+        encryption: SendEncryption,
+        /// Where the SMTP password comes from - never stored inline in plaintext by choice of
+        /// the wizard, though [`SecretRef::Inline`] remains available for anyone who opts out.
+        secret: SecretRef,
+    },
+    Sendmail {
+        command: String,
+    },
+}
+
+/// Controls which server folders are mirrored into the local Maildir cache.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum FolderSyncFilter {
+    /// Mirror every folder the server reports
+    All,
+    /// Mirror only these folders
+    Include { folders: Vec<String> },
+    /// Mirror every folder except these
+    Exclude { folders: Vec<String> },
+}
+
+impl FolderSyncFilter {
+    /// Whether `folder` should be mirrored locally under this filter
+    pub fn should_sync(&self, folder: &str) -> bool {
+        match self {
+            FolderSyncFilter::All => true,
+            FolderSyncFilter::Include { folders } => folders.iter().any(|f| f == folder),
+            FolderSyncFilter::Exclude { folders } => !folders.iter().any(|f| f == folder),
+        }
+    }
+}
+
+impl Default for FolderSyncFilter {
+    fn default() -> Self {
+        FolderSyncFilter::All
+    }
+}
+
+/// Canonical folder names the UI works with, mapped to this account's actual provider
+/// folder names (which vary, e.g. Gmail's "[Gmail]/Sent Mail" vs plain "Sent").
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FolderAliases {
+    #[serde(default = "default_inbox_folder")]
+    pub inbox: String,
+    #[serde(default = "default_sent_folder")]
+    pub sent: String,
+    #[serde(default = "default_drafts_folder")]
+    pub drafts: String,
+    #[serde(default = "default_trash_folder")]
+    pub trash: String,
+}
+
+fn default_inbox_folder() -> String {
+    "INBOX".to_string()
+}
+
+fn default_sent_folder() -> String {
+    "Sent".to_string()
+}
+
+fn default_drafts_folder() -> String {
+    "Drafts".to_string()
+}
+
+fn default_trash_folder() -> String {
+    "Trash".to_string()
+}
+
+/// Display and listing preferences, settable globally on [`Config`] and overridden per-account
+/// on [`Account::settings`] - [`Config::effective_settings`] merges the two, field by field, with
+/// the account's own value winning wherever it set one. Modeled on Himalaya's
+/// `DeserializedConfig`, which carries the same kind of presentation knobs alongside its
+/// connection settings.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Settings {
+    #[serde(default)]
+    pub email_listing_page_size: Option<usize>,
+    #[serde(default)]
+    pub email_listing_datetime_fmt: Option<String>,
+    #[serde(default)]
+    pub email_listing_datetime_local_tz: Option<bool>,
+    /// Raw server folder renames, keyed by the server's own folder name - e.g. mapping Gmail's
+    /// `"[Gmail]/All Mail"` onto a friendlier label for display. Distinct from
+    /// [`FolderAliases`], which maps the four roles (inbox/sent/drafts/trash) the UI already
+    /// understands onto a provider's folder names.
+    #[serde(default)]
+    pub folder_aliases: HashMap<String, String>,
+    #[serde(default)]
+    pub signature: Option<String>,
+    #[serde(default)]
+    pub signature_delim: Option<String>,
+    #[serde(default)]
+    pub downloads_dir: Option<PathBuf>,
+}
+
+impl Settings {
+    /// Merge `self` (the global block) with `account` (a per-account override), the account's
+    /// own value winning wherever it set one - `Some`/non-empty beats `None`/empty, same as
+    /// [`Config::apply_overrides`]'s file-then-override layering.
+    fn merge_override(&self, account: &Settings) -> Settings {
+        Settings {
+            email_listing_page_size: account.email_listing_page_size.or(self.email_listing_page_size),
+            email_listing_datetime_fmt: account
+                .email_listing_datetime_fmt
+                .clone()
+                .or_else(|| self.email_listing_datetime_fmt.clone()),
+            email_listing_datetime_local_tz: account
+                .email_listing_datetime_local_tz
+                .or(self.email_listing_datetime_local_tz),
+            folder_aliases: if account.folder_aliases.is_empty() {
+                self.folder_aliases.clone()
+            } else {
+                account.folder_aliases.clone()
+            },
+            signature: account.signature.clone().or_else(|| self.signature.clone()),
+            signature_delim: account.signature_delim.clone().or_else(|| self.signature_delim.clone()),
+            downloads_dir: account.downloads_dir.clone().or_else(|| self.downloads_dir.clone()),
+        }
+    }
+}
+
+/// A user-defined palette for the handful of UI elements meli-style themes let you override by
+/// name, configured under `[themes.<name>]` in `config.toml`. Each field holds a raw color
+/// string - a named terminal color, a `#rrggbb` hex triplet, or a 0-255 index - parsed with
+/// [`crate::theme::ColorSpec::parse`] and validated by [`Self::validate`] at [`Config::load`]
+/// time. Complements [`crate::theme::Theme`]'s built-in presets (Rust literals, not
+/// user-editable) rather than replacing them.
+///
+/// Field names are kebab-case in `config.toml` (`selected-row`, `account-indicator`) to match
+/// the element names this was modeled on, rather than this crate's usual snake_case.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct UiTheme {
+    pub header: Option<String>,
+    pub selected_row: Option<String>,
+    pub unread: Option<String>,
+    pub flagged: Option<String>,
+    pub account_indicator: Option<String>,
+}
+
+impl UiTheme {
+    /// Look up one of this theme's elements by its `config.toml` (kebab-case) name, for
+    /// [`Config::resolve_account_color`] to check whether an account's `color` names a theme
+    /// element rather than a literal color.
+    fn element(&self, name: &str) -> Option<&str> {
+        match name {
+            "header" => self.header.as_deref(),
+            "selected-row" => self.selected_row.as_deref(),
+            "unread" => self.unread.as_deref(),
+            "flagged" => self.flagged.as_deref(),
+            "account-indicator" => self.account_indicator.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Check that every color string this theme sets actually parses, so a typo fails at load
+    /// instead of silently rendering the wrong color later.
+    fn validate(&self, theme_name: &str) -> Result<()> {
+        for (element, value) in [
+            ("header", &self.header),
+            ("selected-row", &self.selected_row),
+            ("unread", &self.unread),
+            ("flagged", &self.flagged),
+            ("account-indicator", &self.account_indicator),
+        ] {
+            if let Some(value) = value {
+                crate::theme::ColorSpec::parse(value)
+                    .map_err(|e| anyhow::anyhow!("Theme {:?} element {:?}: {}", theme_name, element, e))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How verbose [`crate::logging`] should be, under `level` in the `[log]` section.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Default for LogLevel {
+    fn default() -> Self {
+        LogLevel::Info
+    }
+}
+
+impl LogLevel {
+    pub fn to_level_filter(self) -> log::LevelFilter {
+        match self {
+            LogLevel::Error => log::LevelFilter::Error,
+            LogLevel::Warn => log::LevelFilter::Warn,
+            LogLevel::Info => log::LevelFilter::Info,
+            LogLevel::Debug => log::LevelFilter::Debug,
+            LogLevel::Trace => log::LevelFilter::Trace,
+        }
+    }
+}
+
+/// Diagnostics configuration, under `[log]` in `config.toml`. [`Config::load_from`] installs
+/// this via [`crate::logging::init`] right after loading - before that, every `log::*!` macro
+/// call in the crate is a silent no-op, never an `eprintln!` to a terminal that might be mid-draw.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LogSettings {
+    #[serde(default)]
+    pub level: LogLevel,
+    /// Write log lines to this file instead of stderr - stderr is shared with the TUI's
+    /// alternate screen, so anything above `error` there during a normal run would corrupt it.
+    #[serde(default)]
+    pub file: Option<PathBuf>,
+    /// Replace anything that looks like an email address in a log line with `[redacted]`, so a
+    /// log pasted into a bug report doesn't leak who someone corresponds with.
+    #[serde(default)]
+    pub redact: bool,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self { level: LogLevel::default(), file: None, redact: false }
+    }
+}
+
+impl Default for FolderAliases {
+    fn default() -> Self {
+        Self {
+            inbox: default_inbox_folder(),
+            sent: default_sent_folder(),
+            drafts: default_drafts_folder(),
+            trash: default_trash_folder(),
+        }
+    }
+}
+
+/// A reference to a secret (e.g. an account password), resolved lazily at connection time
+/// instead of being stored inline in the config file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SecretRef {
+    /// Look the secret up in the OS keyring, keyed by `(provider_id, username)`
+    Keyring { provider_id: String, username: String },
+    /// The secret value, stored directly (used when the user opts out of secure storage)
+    Inline { value: String },
+    /// Run a shell command and use its trimmed stdout as the secret (e.g. `pass show mail/me`)
+    Command { command: String },
+    /// A cached OAuth2 access token, read from the keyring entry `tume:{account_key}` that
+    /// [`crate::oauth::run_authorization_flow`] stashed it under as a serialized
+    /// [`crate::credentials::OAuthToken`]. `resolve` only ever returns the cached access token -
+    /// refreshing an expired one needs an async HTTP round-trip, so that happens at the same
+    /// async call sites that already retry once on an expired/invalid token (see `email_sync`),
+    /// not here.
+    OAuth2 { account_key: String },
+}
+
+impl SecretRef {
+    /// Whether this reference is missing the identifying detail it needs to ever resolve -
+    /// an empty inline value, command, keyring username, or account key. Used by
+    /// [`Account::validate`] to reject an obviously-broken `config.toml` entry up front, the
+    /// same way the other backend fields are checked.
+    fn is_empty(&self) -> bool {
+        match self {
+            SecretRef::Inline { value } => value.is_empty(),
+            SecretRef::Keyring { provider_id, username } => provider_id.is_empty() || username.is_empty(),
+            SecretRef::Command { command } => command.is_empty(),
+            SecretRef::OAuth2 { account_key } => account_key.is_empty(),
+        }
+    }
+
+    /// Resolve this reference to its actual secret value
+    pub fn resolve(&self) -> Result<String> {
+        match self {
+            SecretRef::Inline { value } => Ok(value.clone()),
+            SecretRef::Keyring { provider_id, username } => {
+                let entry_name = format!("{}:{}", provider_id, username);
+                keyring::Entry::new("tume-email-client", &entry_name)
+                    .context("Failed to create keyring entry")?
+                    .get_password()
+                    .context("Failed to read secret from keyring")
+            }
+            SecretRef::Command { command } => {
+                let output = std::process::Command::new("sh")
+                    .arg("-c")
+                    .arg(command)
+                    .output()
+                    .context("Failed to execute secret command")?;
+                if !output.status.success() {
+                    return Err(anyhow::anyhow!(
+                        "Secret command exited with status {}",
+                        output.status
+                    ));
+                }
+                Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+            }
+            SecretRef::OAuth2 { account_key } => {
+                let entry_name = format!("tume:{}", account_key);
+                let json = keyring::Entry::new("tume-email-client", &entry_name)
+                    .context("Failed to create keyring entry")?
+                    .get_password()
+                    .context("Failed to read cached OAuth2 token from keyring")?;
+                let token: crate::credentials::OAuthToken = serde_json::from_str(&json)
+                    .context("Failed to parse cached OAuth2 token")?;
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs() as i64;
+                if token.is_expired(now) {
+                    return Err(anyhow::anyhow!(
+                        "OAuth2 access token for account {:?} has expired and needs to be refreshed",
+                        account_key
+                    ));
+                }
+                Ok(token.access_token)
+            }
+        }
+    }
 }
 
 /// Keybindings configuration
@@ -74,6 +571,52 @@ pub struct Config {
     pub accounts: HashMap<String, Account>,
     #[serde(default)]
     pub keybindings: Keybindings,
+    /// Per-view action rebindings, merged over the built-in defaults by
+    /// [`crate::keymap::Shortcuts::from_config`]
+    #[serde(default)]
+    pub shortcuts: crate::keymap::ShortcutsConfig,
+    /// Folder of `.vcf` files loaded as read-only contacts in the Contacts view, alongside the
+    /// editable contacts stored in the local database. `None` means no vCard folder is configured.
+    #[serde(default)]
+    pub contacts_vcf_folder: Option<PathBuf>,
+    /// Global display/listing defaults, overridden per-account via [`Account::settings`] - see
+    /// [`Config::effective_settings`].
+    #[serde(default)]
+    pub settings: Settings,
+    /// User-defined palettes, keyed by theme name, configured under `[themes.<name>]`. See
+    /// [`UiTheme`] and [`Self::resolve_account_color`].
+    #[serde(default)]
+    pub themes: HashMap<String, UiTheme>,
+    /// Which of [`Self::themes`] is active. `None` means no custom theme is in effect, so
+    /// [`Self::resolve_account_color`] treats every account's `color` as a literal.
+    #[serde(default)]
+    pub active_theme: Option<String>,
+    /// Diagnostics/logging configuration - see [`LogSettings`] and [`crate::logging`].
+    #[serde(default)]
+    pub log: LogSettings,
+}
+
+/// Individual settings that can override whatever was loaded from the config file without
+/// editing it, applied via [`Config::apply_overrides`] in file < env < CLI precedence order -
+/// env overrides the file, and CLI overrides both. All fields are optional; only the ones
+/// actually set replace the corresponding value.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigOverrides {
+    pub default_account: Option<String>,
+    pub next_account_key: Option<String>,
+    pub prev_account_key: Option<String>,
+}
+
+impl ConfigOverrides {
+    /// Pull overrides from `TUME_DEFAULT_ACCOUNT`/`TUME_NEXT_ACCOUNT_KEY`/`TUME_PREV_ACCOUNT_KEY`
+    /// environment variables, for the env layer in file < env < CLI precedence.
+    pub fn from_env() -> Self {
+        Self {
+            default_account: std::env::var("TUME_DEFAULT_ACCOUNT").ok(),
+            next_account_key: std::env::var("TUME_NEXT_ACCOUNT_KEY").ok(),
+            prev_account_key: std::env::var("TUME_PREV_ACCOUNT_KEY").ok(),
+        }
+    }
 }
 
 impl Default for Config {
@@ -81,39 +624,119 @@ impl Default for Config {
         Self {
             accounts: HashMap::new(),
             keybindings: Keybindings::default(),
+            shortcuts: crate::keymap::ShortcutsConfig::default(),
+            contacts_vcf_folder: None,
+            settings: Settings::default(),
+            themes: HashMap::new(),
+            active_theme: None,
+            log: LogSettings::default(),
         }
     }
 }
 
 impl Config {
-    /// Load configuration from file or return default config
+    /// Load configuration from the default search path (see [`Self::config_path`]), or return a
+    /// default config if no file exists yet.
     pub fn load() -> Result<Self> {
-        let config_path = Self::config_path()?;
-        
-        eprintln!("DEBUG: Loading config from {:?}", config_path);
-        eprintln!("DEBUG: Config file exists: {}", config_path.exists());
-        
+        Self::load_from(None)
+    }
+
+    /// Load configuration from `path`, or resolve the default search path via
+    /// [`Self::config_path`] when `None` - for a `--config <path>` CLI override that bypasses
+    /// the usual XDG lookup entirely.
+    pub fn load_from(path: Option<PathBuf>) -> Result<Self> {
+        let config_path = match path {
+            Some(path) => path,
+            None => Self::config_path()?,
+        };
+
+        log::debug!("Loading config from {:?}", config_path);
+
         if !config_path.exists() {
-            eprintln!("DEBUG: Config file doesn't exist, generating skeleton");
+            log::debug!("Config file doesn't exist, generating skeleton");
             // Generate skeleton config file for user reference
             Self::generate_skeleton_config(&config_path)?;
             // Return default config
-            return Ok(Self::default());
+            let config = Self::default();
+            let _ = crate::logging::init(&config.log);
+            return Ok(config);
         }
 
         let contents = fs::read_to_string(&config_path)
             .context("Failed to read config file")?;
-        
-        eprintln!("DEBUG: Config file contents ({} bytes):\n{}", contents.len(), contents);
-        
+
+        log::debug!("Config file is {} bytes", contents.len());
+
         let config: Config = toml::from_str(&contents)
             .context("Failed to parse config file")?;
-        
-        eprintln!("DEBUG: Parsed config with {} accounts", config.accounts.len());
-        
+
+        for (name, theme) in &config.themes {
+            theme.validate(name)?;
+        }
+
+        if let Some(active) = &config.active_theme {
+            if !config.themes.contains_key(active) {
+                return Err(anyhow::anyhow!("active_theme {:?} is not defined in [themes]", active));
+            }
+        }
+
+        for (key, account) in &config.accounts {
+            account.validate()?;
+            if let Some(Err(e)) = config.resolve_account_color(account) {
+                return Err(anyhow::anyhow!("Account {:?}: invalid color {:?}: {}", key, account.color, e));
+            }
+        }
+
+        let _ = crate::logging::init(&config.log);
+        log::info!("Loaded config with {} accounts", config.accounts.len());
+
         Ok(config)
     }
 
+    /// Resolve `account.color` against [`Self::active_theme`]: if it names one of that theme's
+    /// elements (`header`/`selected-row`/`unread`/`flagged`/`account-indicator`), use that
+    /// element's color; otherwise parse it as a literal color directly. `None` if the account set
+    /// no color at all; `Some(Err(_))` if it set one but it's neither a known theme element nor a
+    /// color [`crate::theme::ColorSpec::parse`] recognizes.
+    pub fn resolve_account_color(&self, account: &Account) -> Option<Result<crate::theme::ColorSpec, String>> {
+        let raw = account.color.as_ref()?;
+
+        if let Some(theme) = self.active_theme.as_ref().and_then(|name| self.themes.get(name)) {
+            if let Some(value) = theme.element(raw) {
+                return Some(crate::theme::ColorSpec::parse(value));
+            }
+        }
+
+        Some(crate::theme::ColorSpec::parse(raw))
+    }
+
+    /// Apply `overrides` on top of whatever was already loaded, replacing only the fields that
+    /// are `Some`. Call once per layer in file < env < CLI precedence order (see
+    /// [`ConfigOverrides::from_env`]) - later calls win.
+    pub fn apply_overrides(&mut self, overrides: &ConfigOverrides) {
+        if let Some(account_key) = &overrides.default_account {
+            for (key, account) in self.accounts.iter_mut() {
+                account.default = key == account_key;
+            }
+        }
+        if let Some(key) = &overrides.next_account_key {
+            self.keybindings.next_account = key.clone();
+        }
+        if let Some(key) = &overrides.prev_account_key {
+            self.keybindings.prev_account = key.clone();
+        }
+    }
+
+    /// The display/listing [`Settings`] that actually apply to `account_key` - the global
+    /// [`Self::settings`] block with that account's own [`Account::settings`] overriding it
+    /// field by field. Falls back to the bare global settings if `account_key` isn't known.
+    pub fn effective_settings(&self, account_key: &str) -> Settings {
+        match self.accounts.get(account_key) {
+            Some(account) => self.settings.merge_override(&account.settings),
+            None => self.settings.clone(),
+        }
+    }
+
     /// Save configuration to file
     pub fn save(&self) -> Result<()> {
         let config_path = Self::config_path()?;
@@ -133,14 +756,41 @@ impl Config {
         Ok(())
     }
 
-    /// Get config file path (~/.config/tume/config.toml)
+    /// Candidate config file paths in lookup order: `$XDG_CONFIG_HOME/tume/config.toml`, then
+    /// `~/.config/tume/config.toml`, then `/etc/tume/config.toml` as a last-resort system
+    /// default.
+    fn candidate_config_paths() -> Vec<PathBuf> {
+        let mut candidates = Vec::new();
+
+        if let Some(xdg_config_home) = std::env::var_os("XDG_CONFIG_HOME") {
+            let xdg_config_home = PathBuf::from(xdg_config_home);
+            if !xdg_config_home.as_os_str().is_empty() {
+                candidates.push(xdg_config_home.join("tume").join("config.toml"));
+            }
+        }
+
+        if let Some(home) = dirs::home_dir() {
+            candidates.push(home.join(".config").join("tume").join("config.toml"));
+        }
+
+        candidates.push(PathBuf::from("/etc/tume/config.toml"));
+
+        candidates
+    }
+
+    /// Get the config file path: the first of [`Self::candidate_config_paths`] that already
+    /// exists, or - if none do - the preferred path for a fresh write (`$XDG_CONFIG_HOME` if
+    /// set, else `~/.config/tume/config.toml`).
     pub fn config_path() -> Result<PathBuf> {
-        let mut path = dirs::home_dir()
-            .context("Could not find home directory")?;
-        path.push(".config");
-        path.push("tume");
-        path.push("config.toml");
-        Ok(path)
+        let candidates = Self::candidate_config_paths();
+
+        if let Some(existing) = candidates.iter().find(|path| path.exists()) {
+            return Ok(existing.clone());
+        }
+
+        candidates.into_iter().next().context(
+            "Could not determine a config directory (no XDG_CONFIG_HOME and no home directory)",
+        )
     }
 
     /// Generate a skeleton config file with all possible values commented out
@@ -154,6 +804,10 @@ impl Config {
         let skeleton = r#"# TUME Email Client Configuration
 # This is a skeleton configuration file with all available options.
 # Uncomment and modify the values you want to use.
+#
+# Launching tume from an interactive terminal instead runs a setup wizard that
+# writes one of these [accounts.*] blocks for you - this file is only left as-is
+# when stdin isn't a TTY to prompt against (e.g. a headless/CI run).
 
 # ==============================================================================
 # ACCOUNTS CONFIGURATION
@@ -186,6 +840,99 @@ impl Config {
 # color = "yellow"
 # display_order = 3
 
+# ------------------------------------------------------------------------------
+# BACKEND CONFIGURATION (optional)
+# ------------------------------------------------------------------------------
+# By default an account receives mail over IMAP (credentials set up separately,
+# see the onboarding wizard) and sends back out over the same server's SMTP.
+# `backend` picks a different receiving store instead:
+#
+# [accounts.side.backend]
+# kind = "maildir"               # "imap" (default), "maildir", or "notmuch"
+# path = "/home/me/Maildir/side" # maildir only: root of the Maildir directory
+# # database_path = "/home/me/Maildir/side/.notmuch"  # notmuch only
+#
+# `send_backend` splits outgoing mail onto its own path - useful for a local
+# maildir/notmuch account that still relays through a real SMTP server, or an
+# IMAP account routed through a system MTA instead of tume dialing SMTP itself:
+#
+# [accounts.side.send_backend]
+# kind = "smtp"                  # "smtp" or "sendmail"
+# host = "smtp.project.io"
+# port = 587
+# login = "side@project.io"
+# encryption = "start_tls"       # "ssl_tls" (default), "start_tls", or "none"
+#
+# `secret` says where the SMTP password comes from - never put it here as plain
+# text if you can avoid it:
+#
+# [accounts.side.send_backend.secret]
+# kind = "keyring"                # "keyring", "command", "oauth2", or "inline"
+# provider_id = "smtp"
+# username = "side@project.io"
+# # kind = "command"
+# # command = "pass show smtp/side"
+# # kind = "oauth2"
+# # account_key = "side"          # reads the token cached under this account's key
+# # kind = "inline"
+# # value = "hunter2"              # discouraged - stored in plain text
+#
+# [accounts.side.send_backend]
+# kind = "sendmail"
+# command = "/usr/sbin/sendmail -t"
+
+# ==============================================================================
+# DISPLAY/LISTING SETTINGS (optional)
+# ==============================================================================
+# A top-level [settings] block sets the defaults for every account; a
+# [accounts.<key>.settings] block overrides just that account, field by field.
+#
+# [settings]
+# email_listing_page_size = 50
+# email_listing_datetime_fmt = "%Y-%m-%d %H:%M"
+# email_listing_datetime_local_tz = true
+# signature = "Sent from tume"
+# signature_delim = "-- "
+# downloads_dir = "/home/me/Downloads"
+#
+# [settings.folder_aliases]
+# "[Gmail]/All Mail" = "Archive"
+#
+# [accounts.side.settings]
+# email_listing_page_size = 100   # overrides the global page size for this account only
+
+# ==============================================================================
+# THEMES (optional)
+# ==============================================================================
+# Define one or more named palettes, then pick the active one with active_theme.
+# Each color can be a named terminal color ("red"), a "#rrggbb" hex triplet, or a
+# 0-255 256-color index - an unrecognized value fails to load instead of being
+# silently ignored. An account's `color` (above) can reference one of these
+# element names instead of a literal color, and it'll resolve against whichever
+# theme is active.
+#
+# active_theme = "work-theme"
+#
+# [themes.work-theme]
+# header = "#3c3836"
+# selected-row = "blue"
+# unread = "yellow"
+# flagged = "red"
+# account-indicator = "green"
+
+# ==============================================================================
+# LOGGING (optional)
+# ==============================================================================
+# Diagnostics go to stderr at "info" level by default. Raise `level` for more
+# detail, point `file` at a path to keep stderr clean for the TUI, and turn on
+# `redact` to scrub anything that looks like an email address from log lines
+# before they're written.
+#
+# [log]
+# level = "debug"
+# file = "/home/me/.local/share/tume/tume.log"
+# redact = true
+
 # ==============================================================================
 # KEYBINDINGS CONFIGURATION
 # ==============================================================================
@@ -284,6 +1031,11 @@ mod tests {
                 default: true,
                 color: Some("blue".to_string()),
                 display_order: Some(1),
+                folder_sync: crate::config::FolderSyncFilter::All,
+                folder_aliases: crate::config::FolderAliases::default(),
+                backend: crate::config::AccountBackend::Imap,
+                send_backend: None,
+                settings: crate::config::Settings::default(),
             },
         );
 
@@ -308,6 +1060,11 @@ mod tests {
                 default: false,
                 color: None,
                 display_order: Some(2),
+                folder_sync: crate::config::FolderSyncFilter::All,
+                folder_aliases: crate::config::FolderAliases::default(),
+                backend: crate::config::AccountBackend::Imap,
+                send_backend: None,
+                settings: crate::config::Settings::default(),
             },
         );
         config.set_account(
@@ -319,6 +1076,11 @@ mod tests {
                 default: true,
                 color: None,
                 display_order: Some(1),
+                folder_sync: crate::config::FolderSyncFilter::All,
+                folder_aliases: crate::config::FolderAliases::default(),
+                backend: crate::config::AccountBackend::Imap,
+                send_backend: None,
+                settings: crate::config::Settings::default(),
             },
         );
 
@@ -340,6 +1102,11 @@ mod tests {
                 default: true,
                 color: None,
                 display_order: Some(1),
+                folder_sync: crate::config::FolderSyncFilter::All,
+                folder_aliases: crate::config::FolderAliases::default(),
+                backend: crate::config::AccountBackend::Imap,
+                send_backend: None,
+                settings: crate::config::Settings::default(),
             },
         );
 
@@ -377,4 +1144,244 @@ mod tests {
         // Cleanup
         let _ = fs::remove_file(&test_path);
     }
+
+    #[test]
+    fn test_secret_ref_inline_resolves_directly() {
+        let secret = SecretRef::Inline { value: "hunter2".to_string() };
+        assert_eq!(secret.resolve().unwrap(), "hunter2");
+    }
+
+    #[test]
+    fn test_secret_ref_command_resolves_stdout() {
+        let secret = SecretRef::Command { command: "echo hunter2".to_string() };
+        assert_eq!(secret.resolve().unwrap(), "hunter2");
+    }
+
+    #[test]
+    fn test_secret_ref_command_failure_is_err() {
+        let secret = SecretRef::Command { command: "exit 1".to_string() };
+        assert!(secret.resolve().is_err());
+    }
+
+    #[test]
+    fn test_secret_ref_serialization_round_trip() {
+        let secret = SecretRef::Keyring {
+            provider_id: "gmail".to_string(),
+            username: "jane@gmail.com".to_string(),
+        };
+        let toml_str = toml::to_string(&secret).unwrap();
+        let parsed: SecretRef = toml::from_str(&toml_str).unwrap();
+        assert_eq!(parsed, secret);
+    }
+
+    fn test_account(send_backend: Option<SendBackend>) -> Account {
+        Account {
+            name: "Side".to_string(),
+            email: "side@project.io".to_string(),
+            provider: "imap".to_string(),
+            default: false,
+            color: None,
+            display_order: None,
+            folder_sync: FolderSyncFilter::All,
+            folder_aliases: FolderAliases::default(),
+            backend: AccountBackend::Imap,
+            send_backend,
+            settings: Settings::default(),
+        }
+    }
+
+    #[test]
+    fn test_send_backend_serialization_round_trip() {
+        let account = test_account(Some(SendBackend::Smtp {
+            host: "smtp.project.io".to_string(),
+            port: 587,
+            login: "side@project.io".to_string(),
+            encryption: SendEncryption::StartTls,
+            secret: SecretRef::Command { command: "pass show smtp/side".to_string() },
+        }));
+
+        let toml_str = toml::to_string(&account).unwrap();
+        assert!(toml_str.contains("kind = \"smtp\""));
+        assert!(toml_str.contains("start_tls"));
+
+        let parsed: Account = toml::from_str(&toml_str).unwrap();
+        assert_eq!(parsed.send_backend, account.send_backend);
+    }
+
+    #[test]
+    fn test_account_validate_rejects_empty_sendmail_command() {
+        let account = test_account(Some(SendBackend::Sendmail { command: "".to_string() }));
+        assert!(account.validate().is_err());
+    }
+
+    #[test]
+    fn test_account_validate_rejects_empty_smtp_secret() {
+        let account = test_account(Some(SendBackend::Smtp {
+            host: "smtp.project.io".to_string(),
+            port: 587,
+            login: "side@project.io".to_string(),
+            encryption: SendEncryption::StartTls,
+            secret: SecretRef::Inline { value: "".to_string() },
+        }));
+        assert!(account.validate().is_err());
+    }
+
+    #[test]
+    fn test_account_validate_accepts_no_send_backend() {
+        let account = test_account(None);
+        assert!(account.validate().is_ok());
+    }
+
+    #[test]
+    fn test_resolve_secret_resolves_smtp_secret() {
+        let account = test_account(Some(SendBackend::Smtp {
+            host: "smtp.project.io".to_string(),
+            port: 587,
+            login: "side@project.io".to_string(),
+            encryption: SendEncryption::StartTls,
+            secret: SecretRef::Command { command: "echo hunter2".to_string() },
+        }));
+        assert_eq!(account.resolve_secret().unwrap(), "hunter2");
+    }
+
+    #[test]
+    fn test_resolve_secret_errs_without_send_backend() {
+        let account = test_account(None);
+        assert!(account.resolve_secret().is_err());
+    }
+
+    #[test]
+    fn test_secret_ref_oauth2_errs_when_no_cached_token_exists() {
+        let secret = SecretRef::OAuth2 { account_key: "no-such-account-in-keyring".to_string() };
+        assert!(secret.resolve().is_err());
+    }
+
+    #[test]
+    fn test_effective_settings_falls_back_to_global_for_unset_account_fields() {
+        let mut config = Config::default();
+        config.settings.email_listing_page_size = Some(50);
+        config.settings.signature = Some("Sent from tume".to_string());
+
+        let mut account = test_account(None);
+        account.settings.signature = Some("Sent from my phone".to_string());
+        config.set_account("work".to_string(), account);
+
+        let settings = config.effective_settings("work");
+        assert_eq!(settings.email_listing_page_size, Some(50));
+        assert_eq!(settings.signature, Some("Sent from my phone".to_string()));
+    }
+
+    #[test]
+    fn test_effective_settings_unknown_account_returns_global() {
+        let mut config = Config::default();
+        config.settings.email_listing_page_size = Some(50);
+        assert_eq!(config.effective_settings("no-such-account").email_listing_page_size, Some(50));
+    }
+
+    #[test]
+    fn test_resolve_account_color_follows_active_theme_element() {
+        let mut config = Config::default();
+        config.themes.insert(
+            "work-theme".to_string(),
+            UiTheme { flagged: Some("#ff0000".to_string()), ..UiTheme::default() },
+        );
+        config.active_theme = Some("work-theme".to_string());
+
+        let mut account = test_account(None);
+        account.color = Some("flagged".to_string());
+
+        assert_eq!(
+            config.resolve_account_color(&account).unwrap().unwrap(),
+            crate::theme::ColorSpec::Rgb(255, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_resolve_account_color_falls_back_to_literal() {
+        let config = Config::default();
+        let mut account = test_account(None);
+        account.color = Some("blue".to_string());
+        assert_eq!(
+            config.resolve_account_color(&account).unwrap().unwrap(),
+            crate::theme::ColorSpec::Named("blue".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_account_color_rejects_unknown_color() {
+        let config = Config::default();
+        let mut account = test_account(None);
+        account.color = Some("not-a-real-color".to_string());
+        assert!(config.resolve_account_color(&account).unwrap().is_err());
+    }
+
+    #[test]
+    fn test_load_from_rejects_active_theme_not_defined() {
+        let dir = std::env::temp_dir().join(format!("tume_test_config_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        std::fs::write(&path, "active_theme = \"missing\"\n").unwrap();
+
+        assert!(Config::load_from(Some(path)).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_ui_theme_validate_rejects_unparsable_color() {
+        let theme = UiTheme { header: Some("not-a-real-color".to_string()), ..UiTheme::default() };
+        assert!(theme.validate("bad-theme").is_err());
+    }
+
+    #[test]
+    fn test_apply_overrides_sets_default_account_and_keybinding() {
+        let mut config = Config::default();
+        config.set_account("work".to_string(), test_account(None));
+        config.set_account("personal".to_string(), {
+            let mut account = test_account(None);
+            account.default = true;
+            account
+        });
+
+        config.apply_overrides(&ConfigOverrides {
+            default_account: Some("work".to_string()),
+            next_account_key: Some(">".to_string()),
+            prev_account_key: None,
+        });
+
+        assert!(config.accounts["work"].default);
+        assert!(!config.accounts["personal"].default);
+        assert_eq!(config.keybindings.next_account, ">");
+        assert_eq!(config.keybindings.prev_account, "[");
+    }
+
+    #[test]
+    fn test_apply_overrides_none_fields_leave_config_untouched() {
+        let mut config = Config::default();
+        let original = config.keybindings.clone();
+        config.apply_overrides(&ConfigOverrides::default());
+        assert_eq!(config.keybindings.next_account, original.next_account);
+        assert_eq!(config.keybindings.prev_account, original.prev_account);
+    }
+
+    #[test]
+    fn test_log_level_defaults_to_info() {
+        assert_eq!(LogSettings::default().level, LogLevel::Info);
+        assert_eq!(LogLevel::default().to_level_filter(), log::LevelFilter::Info);
+    }
+
+    #[test]
+    fn test_log_settings_round_trips_through_toml() {
+        let settings = LogSettings { level: LogLevel::Debug, file: Some("/tmp/tume.log".into()), redact: true };
+        let toml_str = toml::to_string(&settings).unwrap();
+        let parsed: LogSettings = toml::from_str(&toml_str).unwrap();
+        assert_eq!(parsed, settings);
+    }
+
+    #[test]
+    fn test_config_default_has_no_log_file_and_stderr_sink() {
+        let config = Config::default();
+        assert_eq!(config.log.level, LogLevel::Info);
+        assert!(config.log.file.is_none());
+        assert!(!config.log.redact);
+    }
 }
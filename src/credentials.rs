@@ -1,14 +1,20 @@
 use aes_gcm::{
-    aead::{Aead, KeyInit, OsRng},
+    aead::{Aead as _, KeyInit as _},
     Aes256Gcm, Nonce,
 };
 use anyhow::{Context, Result, anyhow};
-use argon2::{Argon2, PasswordHasher};
+use argon2::{Algorithm, Argon2, Params, PasswordHasher, Version};
 use argon2::password_hash::{PasswordHash, PasswordVerifier, SaltString};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, OsRng},
+    XChaCha20Poly1305, XNonce,
+};
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
+use thiserror::Error;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// Service name for keyring storage
@@ -17,17 +23,148 @@ const SERVICE_NAME: &str = "tume-email-client";
 /// User identifier for keyring storage
 const USERNAME: &str = "default";
 
+/// Keyring username under which [`HybridStore`] keeps the data-encryption key
+const DEK_KEYRING_USERNAME: &str = "dek";
+
+/// Default Argon2id cost parameters for [`EncryptedFileStore`], used for every newly-created
+/// credentials file. Chosen per the OWASP password storage cheat sheet's Argon2id minimums;
+/// persisted per-file (see [`EncryptedData`]) rather than hardcoded so an existing file keeps
+/// working if these constants are tuned upward later.
+const ARGON2_MEMORY_KIB: u32 = 19_456;
+const ARGON2_ITERATIONS: u32 = 2;
+const ARGON2_PARALLELISM: u32 = 1;
+
+/// Structured failure for the credential-manager entry points, so the TUI can react to a
+/// specific condition (re-prompt on [`CredentialError::WrongPassword`], offer setup on
+/// [`CredentialError::NotConfigured`]) instead of pattern-matching an error string. Lower-level
+/// code still mostly returns `anyhow::Result` internally; [`CredentialError::classify`] maps
+/// those opaque messages onto a variant the same way `email_sync::ValidationError::classify`
+/// does, since the underlying `keyring`/AEAD error types don't expose a stable category.
+#[derive(Debug, Error)]
+pub enum CredentialError {
+    #[error("incorrect master password")]
+    WrongPassword,
+    #[error("no credentials are configured for this backend")]
+    NotConfigured,
+    #[error("the system keyring is unavailable: {0}")]
+    KeyringUnavailable(String),
+    #[error("credentials were stored with a different backend than the one currently active")]
+    BackendMismatch,
+    #[error("stored credentials are corrupted or unreadable: {0}")]
+    Corrupted(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl CredentialError {
+    /// Classify an opaque error surfaced by a [`CredentialStore`] into a specific variant by
+    /// inspecting its message. Falls back to [`CredentialError::Other`], which still preserves
+    /// the full source chain for logging.
+    fn classify(err: anyhow::Error) -> Self {
+        let message = format!("{err:#}");
+        let lower = message.to_lowercase();
+
+        if lower.contains("incorrect master password") || lower.contains("failed to decrypt") {
+            CredentialError::WrongPassword
+        } else if lower.contains("not found") || lower.contains("please configure")
+            || lower.contains("no credentials stored for account")
+        {
+            CredentialError::NotConfigured
+        } else if lower.contains("keyring") {
+            CredentialError::KeyringUnavailable(message)
+        } else if lower.contains("only available for encrypted file backend") {
+            CredentialError::BackendMismatch
+        } else if lower.contains("corrupt") || lower.contains("parse") || lower.contains("deserialize") {
+            CredentialError::Corrupted(message)
+        } else {
+            CredentialError::Other(err)
+        }
+    }
+}
+
 /// Represents email server credentials
 #[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
 pub struct Credentials {
     pub imap_server: String,
     pub imap_port: u16,
+    #[serde(default)]
+    pub imap_security: crate::providers::SecurityType,
     pub imap_username: String,
     pub imap_password: String,
     pub smtp_server: String,
     pub smtp_port: u16,
+    #[serde(default)]
+    pub smtp_security: crate::providers::SecurityType,
     pub smtp_username: String,
     pub smtp_password: String,
+    /// OAuth2 token set, present when the account authenticates via XOAUTH2 instead of a password
+    pub oauth_token: Option<OAuthToken>,
+}
+
+/// An OAuth2 access/refresh token pair for a single account
+///
+/// `imap_password`/`smtp_password` are left empty for OAuth2 accounts; the XOAUTH2 SASL
+/// string is built from `access_token` at connection time instead.
+#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+pub struct OAuthToken {
+    pub access_token: String,
+    pub refresh_token: String,
+    /// Unix timestamp (seconds) after which `access_token` must be refreshed
+    pub expires_at: i64,
+    /// The provider's token endpoint, carried alongside the token so
+    /// [`crate::oauth::refresh_access_token`] can renew it without needing the provider preset
+    /// back in scope.
+    pub token_url: String,
+    pub client_id: String,
+}
+
+impl OAuthToken {
+    /// Whether the access token has expired and needs to be refreshed before use
+    pub fn is_expired(&self, now_unix: i64) -> bool {
+        now_unix >= self.expires_at
+    }
+}
+
+/// Build the XOAUTH2 SASL initial-response string for IMAP/SMTP authentication
+///
+/// Format per RFC: `base64("user=" + email + "\x01auth=Bearer " + access_token + "\x01\x01")`
+pub fn xoauth2_sasl_string(email: &str, access_token: &str) -> String {
+    let raw = format!("user={}\x01auth=Bearer {}\x01\x01", email, access_token);
+    base64::encode(raw.as_bytes())
+}
+
+impl Credentials {
+    /// Build credentials by resolving the IMAP/SMTP secrets from [`crate::config::SecretRef`]s
+    /// at this call site, rather than holding the plaintext password from config load onward.
+    /// `email_sync` calls this right before opening a connection.
+    pub fn from_secret_refs(
+        imap_server: String,
+        imap_port: u16,
+        imap_security: crate::providers::SecurityType,
+        imap_username: String,
+        imap_password: &crate::config::SecretRef,
+        smtp_server: String,
+        smtp_port: u16,
+        smtp_security: crate::providers::SecurityType,
+        smtp_username: String,
+        smtp_password: &crate::config::SecretRef,
+    ) -> Result<Self> {
+        Ok(Credentials {
+            imap_server,
+            imap_port,
+            imap_security,
+            imap_username,
+            imap_password: imap_password.resolve()?,
+            smtp_server,
+            smtp_port,
+            smtp_security,
+            smtp_username,
+            smtp_password: smtp_password.resolve()?,
+            oauth_token: None,
+        })
+    }
 }
 
 /// Backend type for credentials storage
@@ -37,6 +174,11 @@ pub enum StorageBackend {
     SystemKeyring,
     /// Encrypted file with master password
     EncryptedFile,
+    /// Ciphertext in a file, data-encryption key in the system keyring; no master password
+    HybridKeyringFile,
+    /// Delegated to an external command (`pass`, the 1Password `op` CLI, a custom script); see
+    /// [`CommandHelperConfig`]
+    CommandHelper,
 }
 
 impl StorageBackend {
@@ -44,6 +186,8 @@ impl StorageBackend {
         match self {
             StorageBackend::SystemKeyring => "System Keyring",
             StorageBackend::EncryptedFile => "Encrypted File",
+            StorageBackend::HybridKeyringFile => "Hybrid (Keyring + File)",
+            StorageBackend::CommandHelper => "External Command",
         }
     }
 
@@ -55,55 +199,849 @@ impl StorageBackend {
             StorageBackend::EncryptedFile => {
                 "Credentials stored in an encrypted file at ~/.local/share/tume/credentials.enc, protected by your master password"
             }
+            StorageBackend::HybridKeyringFile => {
+                "Credentials encrypted in ~/.local/share/tume/credentials.enc, with the encryption key held in your system's secure keyring instead of a master password"
+            }
+            StorageBackend::CommandHelper => {
+                "Credentials read and written by a user-configured external command, such as `pass` or the 1Password CLI"
+            }
         }
     }
 }
 
-/// Credentials manager with hybrid storage support
-pub struct CredentialsManager {
-    backend: StorageBackend,
+/// A place credentials can be persisted: the system keyring, an encrypted file, a hybrid of the
+/// two, or an external helper command. [`CredentialsManager`] holds one of these behind
+/// a `Box<dyn CredentialStore>` rather than matching on [`StorageBackend`] in every method, so a
+/// new store only has to be written and wired into [`CredentialsManager::with_backend`] once.
+trait CredentialStore {
+    /// Save `credentials` under `account`, a named slot (e.g. `"default"` for the single-account
+    /// API, or a user-chosen profile name for multi-account use).
+    fn save_for(&self, account: &str, credentials: &Credentials, secret: Option<&str>) -> Result<()>;
+    fn load_for(&self, account: &str, secret: Option<&str>) -> Result<Credentials>;
+    /// `secret` is required by stores whose accounts share one encrypted blob (they must decrypt
+    /// it to remove just one account); `None` falls back to wiping the whole store, matching the
+    /// pre-multi-account behaviour of the single-account API.
+    fn delete_for(&self, account: &str, secret: Option<&str>) -> Result<()>;
+    /// Every account name currently stored, if the backend is able to enumerate them.
+    fn list_accounts(&self, secret: Option<&str>) -> Result<Vec<String>>;
+    fn exists_for(&self, account: &str) -> bool;
+    /// Lets [`CredentialsManager::verify_master_password`] reach the one store that supports it
+    /// without adding a password-verification method every other store would have to stub out.
+    fn as_any(&self) -> &dyn std::any::Any;
+}
+
+/// [`CredentialStore`] backed by the system keyring (macOS Keychain, Windows Credential
+/// Manager, Linux Secret Service). Ignores the `secret` parameter; the keyring itself is the
+/// secret store, so there's no master password to check. Each account is its own keyring entry
+/// keyed by account name, so there's no shared blob to decrypt or re-encrypt.
+struct KeyringStore;
+
+impl CredentialStore for KeyringStore {
+    fn save_for(&self, account: &str, credentials: &Credentials, _secret: Option<&str>) -> Result<()> {
+        let entry = keyring::Entry::new(SERVICE_NAME, account)
+            .context("Failed to create keyring entry")?;
+
+        let json = serde_json::to_string(credentials)
+            .context("Failed to serialize credentials")?;
+
+        entry.set_password(&json)
+            .context("Failed to save credentials to keyring")?;
+
+        Ok(())
+    }
+
+    fn load_for(&self, account: &str, _secret: Option<&str>) -> Result<Credentials> {
+        let entry = keyring::Entry::new(SERVICE_NAME, account)
+            .context("Failed to create keyring entry")?;
+
+        let json = entry.get_password()
+            .context("Failed to retrieve credentials from keyring. Please configure credentials first.")?;
+
+        let credentials: Credentials = serde_json::from_str(&json)
+            .context("Failed to parse credentials from keyring")?;
+
+        Ok(credentials)
+    }
+
+    fn delete_for(&self, account: &str, _secret: Option<&str>) -> Result<()> {
+        let entry = keyring::Entry::new(SERVICE_NAME, account)
+            .context("Failed to create keyring entry")?;
+
+        entry.delete_credential()
+            .context("Failed to delete credentials from keyring")?;
+
+        Ok(())
+    }
+
+    fn list_accounts(&self, _secret: Option<&str>) -> Result<Vec<String>> {
+        Err(anyhow!("The system keyring backend can't enumerate accounts; pass account names explicitly"))
+    }
+
+    fn exists_for(&self, account: &str) -> bool {
+        match keyring::Entry::new(SERVICE_NAME, account) {
+            Ok(entry) => entry.get_password().is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// [`CredentialStore`] backed by an XChaCha20-Poly1305 encrypted file, using envelope encryption so the
+/// master password can be changed without re-encrypting the credentials (à la Aerogramme's
+/// `PasswordProtected { root_blob }`). A random 32-byte data-encryption key (DEK) encrypts a
+/// `HashMap<String, Credentials>` of every account, keyed by account name, as one JSON blob; the
+/// DEK itself is wrapped under a key-encryption key (KEK) derived from the master password via
+/// Argon2. `secret` is that master password; required on every operation except
+/// [`Self::exists_for`](CredentialStore::exists_for) and a `delete_for` without it, which wipes
+/// the whole file.
+struct EncryptedFileStore {
     file_path: PathBuf,
 }
 
 /// Encrypted credentials file structure
+///
+/// `ciphertext` is the account map JSON encrypted under the DEK; `wrapped_dek` is the DEK
+/// encrypted under the KEK derived from the master password. Changing the master password only
+/// needs to re-derive the KEK and re-wrap `wrapped_dek` — `ciphertext` never changes.
 #[derive(Serialize, Deserialize)]
 struct EncryptedData {
-    /// Salt for key derivation (base64)
+    /// Salt for KEK derivation (base64)
     salt: String,
-    /// Nonce for AES-GCM (base64)
+    /// Nonce used to wrap the DEK under the KEK (base64, 24 bytes for XChaCha20-Poly1305)
+    wrap_nonce: String,
+    /// DEK encrypted under the KEK (base64)
+    wrapped_dek: String,
+    /// Nonce used to encrypt the credentials under the DEK (base64, 24 bytes for
+    /// XChaCha20-Poly1305)
     nonce: String,
     /// Encrypted credentials (base64)
     ciphertext: String,
     /// Password verification hash (PHC string format)
     password_hash: String,
+    /// Argon2id memory cost in KiB this file's `salt`/`password_hash` were derived with
+    kdf_memory_kib: u32,
+    /// Argon2id time cost (iterations) this file's `salt`/`password_hash` were derived with
+    kdf_iterations: u32,
+    /// Argon2id parallelism (lanes) this file's `salt`/`password_hash` were derived with
+    kdf_parallelism: u32,
+}
+
+impl EncryptedFileStore {
+    /// Build an `Argon2id` instance with the given cost parameters, used for both the password
+    /// verification hash and KEK derivation so a file's persisted `kdf_*` fields always describe
+    /// both.
+    fn argon2_with(memory_kib: u32, iterations: u32, parallelism: u32) -> Result<Argon2<'static>> {
+        let params = Params::new(memory_kib, iterations, parallelism, None)
+            .map_err(|e| anyhow!("Invalid Argon2id cost parameters: {}", e))?;
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+
+    /// Verify `password` against the stored Argon2id hash without decrypting the credentials,
+    /// e.g. to check a password before prompting the user to re-enter it. `false` if there's no
+    /// file to check against yet.
+    fn verify_password(&self, password: &str) -> Result<bool> {
+        if !self.file_path.exists() {
+            return Ok(false);
+        }
+
+        let encrypted_data: EncryptedData = {
+            let json = fs::read_to_string(&self.file_path)
+                .context("Failed to read encrypted credentials file")?;
+            serde_json::from_str(&json).context("Failed to parse encrypted credentials file")?
+        };
+
+        let parsed_hash = PasswordHash::new(&encrypted_data.password_hash)
+            .map_err(|e| anyhow!("Failed to parse password hash: {}", e))?;
+        let argon2 = Self::argon2_with(
+            encrypted_data.kdf_memory_kib,
+            encrypted_data.kdf_iterations,
+            encrypted_data.kdf_parallelism,
+        )?;
+
+        Ok(argon2.verify_password(password.as_bytes(), &parsed_hash).is_ok())
+    }
+
+    /// Derive the key-encryption key (KEK) used to wrap/unwrap the data-encryption key (DEK)
+    /// from the master password and a salt, via Argon2id with the file's persisted cost
+    /// parameters.
+    fn derive_kek(password: &str, salt_bytes: &[u8], memory_kib: u32, iterations: u32, parallelism: u32) -> Result<[u8; 32]> {
+        let mut kek = [0u8; 32];
+        Self::argon2_with(memory_kib, iterations, parallelism)?
+            .hash_password_into(password.as_bytes(), salt_bytes, &mut kek)
+            .map_err(|e| anyhow!("Failed to derive key-encryption key: {}", e))?;
+        Ok(kek)
+    }
+
+    /// Encrypt `dek` under `kek` with a fresh random 24-byte nonce (XChaCha20-Poly1305),
+    /// returning the wrapped DEK ("root blob") and the nonce it was wrapped with.
+    fn wrap_dek(kek: &[u8; 32], dek: &[u8; 32]) -> Result<(Vec<u8>, [u8; 24])> {
+        let mut nonce_bytes = [0u8; 24];
+        OsRng.fill_bytes(&mut nonce_bytes);
+        let nonce = XNonce::from_slice(&nonce_bytes);
+
+        let cipher = XChaCha20Poly1305::new_from_slice(kek)
+            .map_err(|e| anyhow!("Failed to create cipher: {}", e))?;
+        let wrapped_dek = cipher
+            .encrypt(nonce, dek.as_ref())
+            .map_err(|_| anyhow!("Failed to wrap data-encryption key"))?;
+
+        Ok((wrapped_dek, nonce_bytes))
+    }
+
+    /// Decrypt a wrapped DEK under `kek`, the inverse of [`Self::wrap_dek`].
+    fn unwrap_dek(kek: &[u8; 32], wrapped_dek: &[u8], nonce_bytes: &[u8]) -> Result<[u8; 32]> {
+        let cipher = XChaCha20Poly1305::new_from_slice(kek)
+            .map_err(|e| anyhow!("Failed to create cipher: {}", e))?;
+        let nonce = XNonce::from_slice(nonce_bytes);
+        let dek = cipher
+            .decrypt(nonce, wrapped_dek)
+            .map_err(|_| anyhow!("Incorrect master password"))?;
+
+        dek.try_into()
+            .map_err(|_| anyhow!("Unwrapped data-encryption key had an unexpected length"))
+    }
+
+    /// Re-derive the KEK from `new_password` and re-wrap the existing DEK under it, leaving the
+    /// credential ciphertext untouched. This is what lets the master password change without
+    /// re-encrypting every credential. Re-derives with the current [`ARGON2_MEMORY_KIB`]/
+    /// `ARGON2_ITERATIONS`/`ARGON2_PARALLELISM` constants, so changing the master password also
+    /// upgrades an older file's cost parameters.
+    fn change_password(&self, old_password: &str, new_password: &str) -> Result<()> {
+        if !self.file_path.exists() {
+            return Err(anyhow!("Credentials file not found. Please configure credentials first."));
+        }
+
+        let mut encrypted_data: EncryptedData = {
+            let json = fs::read_to_string(&self.file_path)
+                .context("Failed to read encrypted credentials file")?;
+            serde_json::from_str(&json).context("Failed to parse encrypted credentials file")?
+        };
+
+        let parsed_hash = PasswordHash::new(&encrypted_data.password_hash)
+            .map_err(|e| anyhow!("Failed to parse password hash: {}", e))?;
+        Self::argon2_with(encrypted_data.kdf_memory_kib, encrypted_data.kdf_iterations, encrypted_data.kdf_parallelism)?
+            .verify_password(old_password.as_bytes(), &parsed_hash)
+            .map_err(|_| anyhow!("Incorrect master password"))?;
+
+        let old_salt_bytes = base64::decode(&encrypted_data.salt).context("Failed to decode salt")?;
+        let old_wrap_nonce = base64::decode(&encrypted_data.wrap_nonce).context("Failed to decode wrap nonce")?;
+        let wrapped_dek = base64::decode(&encrypted_data.wrapped_dek).context("Failed to decode wrapped key")?;
+
+        let mut old_kek = Self::derive_kek(
+            old_password,
+            &old_salt_bytes,
+            encrypted_data.kdf_memory_kib,
+            encrypted_data.kdf_iterations,
+            encrypted_data.kdf_parallelism,
+        )?;
+        let mut dek = Self::unwrap_dek(&old_kek, &wrapped_dek, &old_wrap_nonce)?;
+        old_kek.zeroize();
+
+        let new_salt = SaltString::generate(&mut OsRng);
+        let new_argon2 = Self::argon2_with(ARGON2_MEMORY_KIB, ARGON2_ITERATIONS, ARGON2_PARALLELISM)?;
+        let new_password_hash = new_argon2
+            .hash_password(new_password.as_bytes(), &new_salt)
+            .map_err(|e| anyhow!("Failed to hash master password: {}", e))?
+            .to_string();
+
+        let mut new_kek = Self::derive_kek(
+            new_password,
+            new_salt.as_str().as_bytes(),
+            ARGON2_MEMORY_KIB,
+            ARGON2_ITERATIONS,
+            ARGON2_PARALLELISM,
+        )?;
+        let (new_wrapped_dek, new_wrap_nonce) = Self::wrap_dek(&new_kek, &dek)?;
+        new_kek.zeroize();
+        dek.zeroize();
+
+        encrypted_data.salt = base64::encode(new_salt.as_str().as_bytes());
+        encrypted_data.wrap_nonce = base64::encode(&new_wrap_nonce);
+        encrypted_data.wrapped_dek = base64::encode(&new_wrapped_dek);
+        encrypted_data.password_hash = new_password_hash;
+        encrypted_data.kdf_memory_kib = ARGON2_MEMORY_KIB;
+        encrypted_data.kdf_iterations = ARGON2_ITERATIONS;
+        encrypted_data.kdf_parallelism = ARGON2_PARALLELISM;
+
+        let json = serde_json::to_string(&encrypted_data)
+            .context("Failed to serialize encrypted data")?;
+        fs::write(&self.file_path, json)
+            .context("Failed to write encrypted credentials file")?;
+
+        Ok(())
+    }
+
+    /// Decrypt the account map, or an empty map if the file doesn't exist yet.
+    fn read_map(&self, master_password: &str) -> Result<HashMap<String, Credentials>> {
+        if !self.file_path.exists() {
+            return Ok(HashMap::new());
+        }
+
+        let encrypted_data: EncryptedData = {
+            let json = fs::read_to_string(&self.file_path)
+                .context("Failed to read encrypted credentials file")?;
+            serde_json::from_str(&json)
+                .context("Failed to parse encrypted credentials file")?
+        };
+
+        let parsed_hash = PasswordHash::new(&encrypted_data.password_hash)
+            .map_err(|e| anyhow!("Failed to parse password hash: {}", e))?;
+        Self::argon2_with(encrypted_data.kdf_memory_kib, encrypted_data.kdf_iterations, encrypted_data.kdf_parallelism)?
+            .verify_password(master_password.as_bytes(), &parsed_hash)
+            .map_err(|_| anyhow!("Incorrect master password"))?;
+
+        let salt_bytes = base64::decode(&encrypted_data.salt)
+            .context("Failed to decode salt")?;
+        let wrap_nonce_bytes = base64::decode(&encrypted_data.wrap_nonce)
+            .context("Failed to decode wrap nonce")?;
+        let wrapped_dek = base64::decode(&encrypted_data.wrapped_dek)
+            .context("Failed to decode wrapped key")?;
+        let nonce_bytes = base64::decode(&encrypted_data.nonce)
+            .context("Failed to decode nonce")?;
+        let ciphertext = base64::decode(&encrypted_data.ciphertext)
+            .context("Failed to decode ciphertext")?;
+
+        let mut kek = Self::derive_kek(
+            master_password,
+            &salt_bytes,
+            encrypted_data.kdf_memory_kib,
+            encrypted_data.kdf_iterations,
+            encrypted_data.kdf_parallelism,
+        )?;
+        let mut dek = Self::unwrap_dek(&kek, &wrapped_dek, &wrap_nonce_bytes)?;
+        kek.zeroize();
+
+        let cipher = XChaCha20Poly1305::new_from_slice(&dek)
+            .map_err(|e| anyhow!("Failed to create cipher: {}", e))?;
+        let nonce = XNonce::from_slice(&nonce_bytes);
+        let plaintext = cipher
+            .decrypt(nonce, ciphertext.as_ref())
+            .map_err(|_| anyhow!("Failed to decrypt credentials"))?;
+        dek.zeroize();
+
+        let json = String::from_utf8(plaintext)
+            .context("Failed to parse decrypted data as UTF-8")?;
+        serde_json::from_str(&json).context("Failed to parse credentials JSON")
+    }
+
+    /// Encrypt and write the account map, reusing the existing DEK/salt/cost parameters (so
+    /// unrelated accounts' ciphertext isn't rotated on every save) when the file already exists,
+    /// or generating a fresh DEK under the current [`ARGON2_MEMORY_KIB`]/`ARGON2_ITERATIONS`/
+    /// `ARGON2_PARALLELISM` constants for a brand new file.
+    fn write_map(&self, map: &HashMap<String, Credentials>, master_password: &str) -> Result<()> {
+        if let Some(parent) = self.file_path.parent() {
+            fs::create_dir_all(parent)
+                .context("Failed to create credentials directory")?;
+        }
+
+        let (salt_b64, wrap_nonce_b64, wrapped_dek_b64, password_hash, kdf_memory_kib, kdf_iterations, kdf_parallelism, mut dek) = if self.file_path.exists() {
+            let encrypted_data: EncryptedData = {
+                let json = fs::read_to_string(&self.file_path)
+                    .context("Failed to read encrypted credentials file")?;
+                serde_json::from_str(&json)
+                    .context("Failed to parse encrypted credentials file")?
+            };
+
+            let parsed_hash = PasswordHash::new(&encrypted_data.password_hash)
+                .map_err(|e| anyhow!("Failed to parse password hash: {}", e))?;
+            Self::argon2_with(encrypted_data.kdf_memory_kib, encrypted_data.kdf_iterations, encrypted_data.kdf_parallelism)?
+                .verify_password(master_password.as_bytes(), &parsed_hash)
+                .map_err(|_| anyhow!("Incorrect master password"))?;
+
+            let salt_bytes = base64::decode(&encrypted_data.salt).context("Failed to decode salt")?;
+            let wrap_nonce_bytes = base64::decode(&encrypted_data.wrap_nonce).context("Failed to decode wrap nonce")?;
+            let wrapped_dek = base64::decode(&encrypted_data.wrapped_dek).context("Failed to decode wrapped key")?;
+
+            let mut kek = Self::derive_kek(
+                master_password,
+                &salt_bytes,
+                encrypted_data.kdf_memory_kib,
+                encrypted_data.kdf_iterations,
+                encrypted_data.kdf_parallelism,
+            )?;
+            let dek = Self::unwrap_dek(&kek, &wrapped_dek, &wrap_nonce_bytes)?;
+            kek.zeroize();
+
+            (
+                encrypted_data.salt,
+                encrypted_data.wrap_nonce,
+                encrypted_data.wrapped_dek,
+                encrypted_data.password_hash,
+                encrypted_data.kdf_memory_kib,
+                encrypted_data.kdf_iterations,
+                encrypted_data.kdf_parallelism,
+                dek,
+            )
+        } else {
+            let salt = SaltString::generate(&mut OsRng);
+            let password_hash = Self::argon2_with(ARGON2_MEMORY_KIB, ARGON2_ITERATIONS, ARGON2_PARALLELISM)?
+                .hash_password(master_password.as_bytes(), &salt)
+                .map_err(|e| anyhow!("Failed to hash master password: {}", e))?
+                .to_string();
+
+            let mut dek = [0u8; 32];
+            OsRng.fill_bytes(&mut dek);
+
+            let mut kek = Self::derive_kek(master_password, salt.as_str().as_bytes(), ARGON2_MEMORY_KIB, ARGON2_ITERATIONS, ARGON2_PARALLELISM)?;
+            let (wrapped_dek, wrap_nonce_bytes) = Self::wrap_dek(&kek, &dek)?;
+            kek.zeroize();
+
+            (
+                base64::encode(salt.as_str().as_bytes()),
+                base64::encode(&wrap_nonce_bytes),
+                base64::encode(&wrapped_dek),
+                password_hash,
+                ARGON2_MEMORY_KIB,
+                ARGON2_ITERATIONS,
+                ARGON2_PARALLELISM,
+                dek,
+            )
+        };
+
+        let mut nonce_bytes = [0u8; 24];
+        OsRng.fill_bytes(&mut nonce_bytes);
+        let nonce = XNonce::from_slice(&nonce_bytes);
+
+        let json = serde_json::to_string(map).context("Failed to serialize credentials")?;
+
+        let cipher = XChaCha20Poly1305::new_from_slice(&dek)
+            .map_err(|e| anyhow!("Failed to create cipher: {}", e))?;
+        let ciphertext = cipher
+            .encrypt(nonce, json.as_bytes())
+            .map_err(|_| anyhow!("Failed to encrypt credentials"))?;
+        dek.zeroize();
+
+        let encrypted_data = EncryptedData {
+            salt: salt_b64,
+            wrap_nonce: wrap_nonce_b64,
+            wrapped_dek: wrapped_dek_b64,
+            nonce: base64::encode(&nonce_bytes),
+            ciphertext: base64::encode(&ciphertext),
+            password_hash,
+            kdf_memory_kib,
+            kdf_iterations,
+            kdf_parallelism,
+        };
+
+        let json = serde_json::to_string(&encrypted_data)
+            .context("Failed to serialize encrypted data")?;
+        fs::write(&self.file_path, json)
+            .context("Failed to write encrypted credentials file")?;
+
+        Ok(())
+    }
+}
+
+impl CredentialStore for EncryptedFileStore {
+    fn save_for(&self, account: &str, credentials: &Credentials, secret: Option<&str>) -> Result<()> {
+        let master_password = secret
+            .ok_or_else(|| anyhow!("Master password required for encrypted file storage"))?;
+
+        let mut map = self.read_map(master_password)?;
+        map.insert(account.to_string(), credentials.clone());
+        self.write_map(&map, master_password)
+    }
+
+    fn load_for(&self, account: &str, secret: Option<&str>) -> Result<Credentials> {
+        let master_password = secret
+            .ok_or_else(|| anyhow!("Master password required for encrypted file storage"))?;
+
+        if !self.file_path.exists() {
+            return Err(anyhow!("Credentials file not found. Please configure credentials first."));
+        }
+
+        let map = self.read_map(master_password)?;
+        map.get(account)
+            .cloned()
+            .ok_or_else(|| anyhow!("No credentials stored for account '{}'", account))
+    }
+
+    fn delete_for(&self, account: &str, secret: Option<&str>) -> Result<()> {
+        match secret {
+            Some(master_password) => {
+                let mut map = self.read_map(master_password)?;
+                if map.remove(account).is_none() {
+                    return Err(anyhow!("No credentials stored for account '{}'", account));
+                }
+                if map.is_empty() {
+                    if self.file_path.exists() {
+                        fs::remove_file(&self.file_path)
+                            .context("Failed to delete encrypted credentials file")?;
+                    }
+                    Ok(())
+                } else {
+                    self.write_map(&map, master_password)
+                }
+            }
+            // No password to decrypt the account map with: fall back to wiping the whole file,
+            // matching the pre-multi-account behaviour of the single-account API.
+            None => {
+                if self.file_path.exists() {
+                    fs::remove_file(&self.file_path)
+                        .context("Failed to delete encrypted credentials file")?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn list_accounts(&self, secret: Option<&str>) -> Result<Vec<String>> {
+        let master_password = secret
+            .ok_or_else(|| anyhow!("Master password required for encrypted file storage"))?;
+        Ok(self.read_map(master_password)?.into_keys().collect())
+    }
+
+    fn exists_for(&self, _account: &str) -> bool {
+        // Without the master password we can't decrypt the map to check a specific account, so
+        // this is a best-effort "is there anything stored at all" check, matching the old
+        // single-account behaviour.
+        self.file_path.exists()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// [`CredentialStore`] that keeps the DEK in the system keyring and the AES-256-GCM ciphertext
+/// in a file, combining the two existing backends instead of picking a KEK derived from a
+/// password: there is no master password, so `secret` is ignored on every operation.
+struct HybridStore {
+    file_path: PathBuf,
+}
+
+/// On-disk structure for [`HybridStore`]: just the nonce and ciphertext, since the DEK lives in
+/// the keyring rather than being wrapped under a password-derived KEK. The ciphertext holds a
+/// `HashMap<String, Credentials>` of every account, keyed by account name, as one JSON blob.
+#[derive(Serialize, Deserialize)]
+struct HybridFileData {
+    /// Nonce for AES-GCM (base64)
+    nonce: String,
+    /// Encrypted credentials (base64)
+    ciphertext: String,
+}
+
+impl HybridStore {
+    fn dek_entry(&self) -> Result<keyring::Entry> {
+        keyring::Entry::new(SERVICE_NAME, DEK_KEYRING_USERNAME)
+            .context("Failed to create keyring entry")
+    }
+
+    /// Decrypt the account map, or an empty map if the file doesn't exist yet.
+    fn read_map(&self) -> Result<HashMap<String, Credentials>> {
+        if !self.file_path.exists() {
+            return Ok(HashMap::new());
+        }
+
+        let file_data: HybridFileData = {
+            let json = fs::read_to_string(&self.file_path)
+                .context("Failed to read encrypted credentials file")?;
+            serde_json::from_str(&json).context("Failed to parse encrypted credentials file")?
+        };
+
+        let dek_b64 = self
+            .dek_entry()?
+            .get_password()
+            .context("Failed to retrieve data-encryption key from keyring. Please configure credentials first.")?;
+        let mut dek: [u8; 32] = base64::decode(&dek_b64)
+            .context("Failed to decode data-encryption key")?
+            .try_into()
+            .map_err(|_| anyhow!("Data-encryption key from keyring had an unexpected length"))?;
+
+        let nonce_bytes = base64::decode(&file_data.nonce).context("Failed to decode nonce")?;
+        let ciphertext = base64::decode(&file_data.ciphertext).context("Failed to decode ciphertext")?;
+
+        let cipher = Aes256Gcm::new_from_slice(&dek)
+            .map_err(|e| anyhow!("Failed to create cipher: {}", e))?;
+        let nonce = Nonce::from_slice(&nonce_bytes);
+        let plaintext = cipher
+            .decrypt(nonce, ciphertext.as_ref())
+            .map_err(|_| anyhow!("Failed to decrypt credentials"))?;
+        dek.zeroize();
+
+        let json = String::from_utf8(plaintext)
+            .context("Failed to parse decrypted data as UTF-8")?;
+        serde_json::from_str(&json).context("Failed to parse credentials JSON")
+    }
+
+    /// Encrypt and write the account map, reusing the existing DEK from the keyring when present
+    /// or generating and storing a fresh one otherwise.
+    fn write_map(&self, map: &HashMap<String, Credentials>) -> Result<()> {
+        if let Some(parent) = self.file_path.parent() {
+            fs::create_dir_all(parent)
+                .context("Failed to create credentials directory")?;
+        }
+
+        let mut dek: [u8; 32] = match self.dek_entry()?.get_password() {
+            Ok(dek_b64) => base64::decode(&dek_b64)
+                .context("Failed to decode data-encryption key")?
+                .try_into()
+                .map_err(|_| anyhow!("Data-encryption key from keyring had an unexpected length"))?,
+            Err(_) => {
+                let mut dek = [0u8; 32];
+                OsRng.fill_bytes(&mut dek);
+                self.dek_entry()?
+                    .set_password(&base64::encode(&dek))
+                    .context("Failed to save data-encryption key to keyring")?;
+                dek
+            }
+        };
+
+        let mut nonce_bytes = [0u8; 12];
+        OsRng.fill_bytes(&mut nonce_bytes);
+        let nonce = Nonce::from_slice(&nonce_bytes);
+
+        let json = serde_json::to_string(map).context("Failed to serialize credentials")?;
+
+        let cipher = Aes256Gcm::new_from_slice(&dek)
+            .map_err(|e| anyhow!("Failed to create cipher: {}", e))?;
+        let ciphertext = cipher
+            .encrypt(nonce, json.as_bytes())
+            .map_err(|_| anyhow!("Failed to encrypt credentials"))?;
+        dek.zeroize();
+
+        let file_data = HybridFileData {
+            nonce: base64::encode(&nonce_bytes),
+            ciphertext: base64::encode(&ciphertext),
+        };
+        let json = serde_json::to_string(&file_data)
+            .context("Failed to serialize encrypted data")?;
+        fs::write(&self.file_path, json)
+            .context("Failed to write encrypted credentials file")?;
+
+        Ok(())
+    }
+}
+
+impl CredentialStore for HybridStore {
+    fn save_for(&self, account: &str, credentials: &Credentials, _secret: Option<&str>) -> Result<()> {
+        let mut map = self.read_map()?;
+        map.insert(account.to_string(), credentials.clone());
+        self.write_map(&map)
+    }
+
+    fn load_for(&self, account: &str, _secret: Option<&str>) -> Result<Credentials> {
+        if !self.file_path.exists() {
+            return Err(anyhow!("Credentials file not found. Please configure credentials first."));
+        }
+        self.read_map()?
+            .remove(account)
+            .ok_or_else(|| anyhow!("No credentials stored for account '{}'", account))
+    }
+
+    fn delete_for(&self, account: &str, _secret: Option<&str>) -> Result<()> {
+        // There's no password gating this store's ciphertext, so unlike the encrypted-file
+        // backend we can always do a precise per-account removal.
+        let mut map = self.read_map()?;
+        if map.remove(account).is_none() {
+            return Err(anyhow!("No credentials stored for account '{}'", account));
+        }
+        if map.is_empty() {
+            if self.file_path.exists() {
+                fs::remove_file(&self.file_path)
+                    .context("Failed to delete encrypted credentials file")?;
+            }
+            let _ = self.dek_entry()?.delete_credential();
+            Ok(())
+        } else {
+            self.write_map(&map)
+        }
+    }
+
+    fn list_accounts(&self, _secret: Option<&str>) -> Result<Vec<String>> {
+        Ok(self.read_map()?.into_keys().collect())
+    }
+
+    fn exists_for(&self, _account: &str) -> bool {
+        self.file_path.exists()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Configuration for [`CommandStore`]: which commands to run and how to run them. Modeled on
+/// cargo's external credential providers and the 1Password `op` CLI integration.
+#[derive(Debug, Clone, Default)]
+pub struct CommandHelperConfig {
+    /// Shell command run to persist credentials; receives the credentials JSON on stdin. May
+    /// contain a `{account}` placeholder, substituted with the account name being saved.
+    pub save_command: String,
+    /// Shell command run to retrieve credentials; emits the credentials JSON on stdout. May
+    /// contain a `{account}` placeholder, substituted with the account name being loaded.
+    pub load_command: String,
+    /// Shell command run to remove credentials, if the helper supports it. May contain a
+    /// `{account}` placeholder, substituted with the account name being deleted.
+    pub delete_command: Option<String>,
+    /// Reconnect the child process to our controlling terminal instead of piping its stdin/stdout,
+    /// for helpers (like `op`) that need to prompt interactively to unlock. The credentials JSON
+    /// is then exchanged through a temporary file instead, since a single fd can't be both a live
+    /// terminal and a data pipe.
+    pub attach_tty: bool,
+}
+
+/// [`CredentialStore`] that shells out to an external command instead of storing secrets itself,
+/// for users who already keep secrets in a password manager like `pass` or 1Password. Ignores
+/// the `secret` parameter; the command is responsible for its own unlocking.
+struct CommandStore {
+    config: CommandHelperConfig,
+}
+
+impl CommandStore {
+    /// Run `command` through the shell, handling the stdin/stdout exchange described by
+    /// [`CommandHelperConfig::attach_tty`]. `input` is the credentials JSON to send for a save,
+    /// or `None` for a load/delete. Returns the credentials JSON emitted by a load; for a save or
+    /// delete, whatever the command printed on stdout (nothing when `attach_tty` is set).
+    fn run(command: &str, input: Option<&[u8]>, attach_tty: bool) -> Result<Vec<u8>> {
+        use std::io::Write;
+        use std::process::Stdio;
+
+        if attach_tty {
+            let temp_path = std::env::temp_dir()
+                .join(format!("tume-credential-helper-{}.json", std::process::id()));
+
+            if let Some(data) = input {
+                fs::write(&temp_path, data).context("Failed to write temporary credentials file")?;
+            }
+
+            let status = std::process::Command::new("sh")
+                .arg("-c")
+                .arg(command)
+                .env("TUME_CREDENTIALS_FILE", &temp_path)
+                .status()
+                .context("Failed to execute credential helper command")?;
+
+            let output = if status.success() && input.is_none() {
+                fs::read(&temp_path).unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+            let _ = fs::remove_file(&temp_path);
+
+            if !status.success() {
+                return Err(anyhow!("Credential helper command exited with status {}", status));
+            }
+            Ok(output)
+        } else {
+            let mut child = std::process::Command::new("sh")
+                .arg("-c")
+                .arg(command)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .spawn()
+                .context("Failed to execute credential helper command")?;
+
+            if let Some(data) = input {
+                child.stdin.take().expect("stdin was piped")
+                    .write_all(data)
+                    .context("Failed to write to credential helper command")?;
+            } else {
+                drop(child.stdin.take());
+            }
+
+            let output = child.wait_with_output()
+                .context("Failed to wait for credential helper command")?;
+            if !output.status.success() {
+                return Err(anyhow!("Credential helper command exited with status {}", output.status));
+            }
+            Ok(output.stdout)
+        }
+    }
+}
+
+impl CredentialStore for CommandStore {
+    fn save_for(&self, account: &str, credentials: &Credentials, _secret: Option<&str>) -> Result<()> {
+        if self.config.save_command.is_empty() {
+            return Err(anyhow!("No save command configured for the external command backend"));
+        }
+        let json = serde_json::to_string(credentials).context("Failed to serialize credentials")?;
+        let command = self.config.save_command.replace("{account}", account);
+        Self::run(&command, Some(json.as_bytes()), self.config.attach_tty)?;
+        Ok(())
+    }
+
+    fn load_for(&self, account: &str, _secret: Option<&str>) -> Result<Credentials> {
+        if self.config.load_command.is_empty() {
+            return Err(anyhow!("No load command configured for the external command backend"));
+        }
+        let command = self.config.load_command.replace("{account}", account);
+        let output = Self::run(&command, None, self.config.attach_tty)?;
+        let json = String::from_utf8(output)
+            .context("Credential helper command output was not valid UTF-8")?;
+        serde_json::from_str(json.trim()).context("Failed to parse credentials from helper command output")
+    }
+
+    fn delete_for(&self, account: &str, _secret: Option<&str>) -> Result<()> {
+        let command = self.config.delete_command.as_ref()
+            .ok_or_else(|| anyhow!("No delete command configured for the external command backend"))?
+            .replace("{account}", account);
+        Self::run(&command, None, self.config.attach_tty)?;
+        Ok(())
+    }
+
+    fn list_accounts(&self, _secret: Option<&str>) -> Result<Vec<String>> {
+        Err(anyhow!("The external command backend can't enumerate accounts; pass account names explicitly"))
+    }
+
+    fn exists_for(&self, _account: &str) -> bool {
+        !self.config.load_command.is_empty()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Credentials manager with hybrid storage support
+pub struct CredentialsManager {
+    backend: StorageBackend,
+    store: Box<dyn CredentialStore>,
 }
 
 impl CredentialsManager {
     /// Create a new credentials manager with automatic backend detection
     pub fn new() -> Self {
         let backend = Self::detect_available_backend();
-        let file_path = Self::default_file_path();
-        
+
         let mut debug_log = std::fs::OpenOptions::new()
             .create(true)
             .append(true)
             .open("/tmp/tume_debug.log")
             .ok();
-        
+
         if let Some(ref mut log) = debug_log {
             use std::io::Write;
             let _ = writeln!(log, "\n=== CredentialsManager::new() ===");
             let _ = writeln!(log, "Detected backend: {:?}", backend);
-            let _ = writeln!(log, "File path: {:?}", file_path);
         }
-        
-        Self { backend, file_path }
+
+        Self::with_backend(backend)
     }
 
     /// Create a credentials manager with a specific backend
     pub fn with_backend(backend: StorageBackend) -> Self {
-        let file_path = Self::default_file_path();
-        Self { backend, file_path }
+        let store: Box<dyn CredentialStore> = match backend {
+            StorageBackend::SystemKeyring => Box::new(KeyringStore),
+            StorageBackend::EncryptedFile => Box::new(EncryptedFileStore { file_path: Self::default_file_path() }),
+            StorageBackend::HybridKeyringFile => Box::new(HybridStore { file_path: Self::default_file_path() }),
+            // Needs user-supplied commands; construct via `with_command_helper` instead.
+            StorageBackend::CommandHelper => Box::new(CommandStore { config: CommandHelperConfig::default() }),
+        };
+        Self { backend, store }
+    }
+
+    /// Create a credentials manager backed by an external command, e.g. `pass` or the 1Password
+    /// CLI, instead of `with_backend(StorageBackend::CommandHelper)`, which can't know the
+    /// commands to run.
+    pub fn with_command_helper(config: CommandHelperConfig) -> Self {
+        Self {
+            backend: StorageBackend::CommandHelper,
+            store: Box::new(CommandStore { config }),
+        }
     }
 
     /// Get the currently active backend
@@ -111,6 +1049,16 @@ impl CredentialsManager {
         self.backend
     }
 
+    /// Build an encrypted-file-backed manager pointed at a specific path instead of
+    /// [`Self::default_file_path`], so tests don't clobber a real user's credentials file.
+    #[cfg(test)]
+    fn with_encrypted_file_path(file_path: PathBuf) -> Self {
+        Self {
+            backend: StorageBackend::EncryptedFile,
+            store: Box::new(EncryptedFileStore { file_path }),
+        }
+    }
+
     /// Detect which storage backend is available
     fn detect_available_backend() -> StorageBackend {
         // Try to check if system keyring is available
@@ -122,7 +1070,7 @@ impl CredentialsManager {
     }
 
     /// Check if system keyring is available
-    fn is_keyring_available() -> bool {
+    pub(crate) fn is_keyring_available() -> bool {
         // Try a test operation to see if keyring is available
         match keyring::Entry::new(SERVICE_NAME, "test-availability") {
             Ok(entry) => {
@@ -148,306 +1096,395 @@ impl CredentialsManager {
         path
     }
 
-    /// Check if credentials exist in the current backend
+    /// Check if credentials exist in the current backend, under the default account
     pub fn credentials_exist(&self) -> bool {
         let mut debug_log = std::fs::OpenOptions::new()
             .create(true)
             .append(true)
             .open("/tmp/tume_debug.log")
             .ok();
-        
-        let result = match self.backend {
-            StorageBackend::SystemKeyring => self.keyring_credentials_exist(),
-            StorageBackend::EncryptedFile => {
-                let exists = self.file_path.exists();
-                if let Some(ref mut log) = debug_log {
-                    use std::io::Write;
-                    let _ = writeln!(log, "Checking encrypted file credentials: {:?} exists = {}", self.file_path, exists);
-                }
-                exists
-            },
-        };
-        
+
+        let result = self.store.exists_for(USERNAME);
+
         if let Some(ref mut log) = debug_log {
             use std::io::Write;
             let _ = writeln!(log, "credentials_exist({:?}) = {}", self.backend, result);
         }
-        
-        result
-    }
 
-    /// Check if credentials exist in keyring
-    fn keyring_credentials_exist(&self) -> bool {
-        match keyring::Entry::new(SERVICE_NAME, USERNAME) {
-            Ok(entry) => entry.get_password().is_ok(),
-            Err(_) => false,
-        }
+        result
     }
 
-    /// Save credentials using the current backend
-    pub fn save_credentials(&self, credentials: &Credentials, master_password: Option<&str>) -> Result<()> {
+    /// Save credentials using the current backend, under the default account. Thin wrapper over
+    /// [`Self::save_credentials_for`] for single-account use.
+    pub fn save_credentials(&self, credentials: &Credentials, master_password: Option<&str>) -> std::result::Result<(), CredentialError> {
         let mut debug_log = std::fs::OpenOptions::new()
             .create(true)
             .append(true)
             .open("/tmp/tume_debug.log")
             .ok();
-        
+
         if let Some(ref mut log) = debug_log {
             use std::io::Write;
             let _ = writeln!(log, "\n=== save_credentials() called ===");
             let _ = writeln!(log, "Backend: {:?}", self.backend);
-            let _ = writeln!(log, "File path: {:?}", self.file_path);
             let _ = writeln!(log, "Master password provided: {}", master_password.is_some());
         }
-        
-        let result = match self.backend {
-            StorageBackend::SystemKeyring => self.save_to_keyring(credentials),
-            StorageBackend::EncryptedFile => {
-                let password = master_password
-                    .ok_or_else(|| anyhow!("Master password required for encrypted file storage"))?;
-                self.save_to_encrypted_file(credentials, password)
-            }
-        };
-        
+
+        let result = self.save_credentials_for(USERNAME, credentials, master_password)
+            .map_err(CredentialError::classify);
+
         if let Some(ref mut log) = debug_log {
             use std::io::Write;
             match &result {
                 Ok(_) => {
                     let _ = writeln!(log, "Credentials saved successfully");
-                    let _ = writeln!(log, "File exists after save: {}", self.file_path.exists());
+                    let _ = writeln!(log, "Exists after save: {}", self.store.exists_for(USERNAME));
                 },
                 Err(e) => {
                     let _ = writeln!(log, "Failed to save credentials: {}", e);
                 }
             }
         }
-        
+
         result
     }
 
-    /// Load credentials using the current backend
-    pub fn load_credentials(&self, master_password: Option<&str>) -> Result<Credentials> {
-        match self.backend {
-            StorageBackend::SystemKeyring => self.load_from_keyring(),
-            StorageBackend::EncryptedFile => {
-                let password = master_password
-                    .ok_or_else(|| anyhow!("Master password required for encrypted file storage"))?;
-                self.load_from_encrypted_file(password)
-            }
-        }
+    /// Load credentials using the current backend, under the default account. Thin wrapper over
+    /// [`Self::load_credentials_for`] for single-account use.
+    pub fn load_credentials(&self, master_password: Option<&str>) -> std::result::Result<Credentials, CredentialError> {
+        self.load_credentials_for(USERNAME, master_password).map_err(CredentialError::classify)
     }
 
-    /// Delete credentials from the current backend
+    /// Delete credentials from the current backend, under the default account. Thin wrapper over
+    /// [`Self::delete_account`] for single-account use.
     pub fn delete_credentials(&self) -> Result<()> {
-        match self.backend {
-            StorageBackend::SystemKeyring => self.delete_from_keyring(),
-            StorageBackend::EncryptedFile => self.delete_encrypted_file(),
-        }
+        self.delete_account(USERNAME, None)
     }
 
-    /// Verify master password (for encrypted file backend)
-    pub fn verify_master_password(&self, password: &str) -> Result<bool> {
-        if self.backend != StorageBackend::EncryptedFile {
-            return Err(anyhow!("Password verification only available for encrypted file backend"));
-        }
+    /// Every account name currently stored, if the backend is able to enumerate them (the system
+    /// keyring and external command backends can't, since they have no listing API).
+    pub fn list_accounts(&self, master_password: Option<&str>) -> Result<Vec<String>> {
+        self.store.list_accounts(master_password)
+    }
 
-        if !self.file_path.exists() {
-            return Ok(false);
-        }
+    /// Save credentials for a named account, so a single backend can hold more than one mailbox.
+    pub fn save_credentials_for(&self, account: &str, credentials: &Credentials, master_password: Option<&str>) -> Result<()> {
+        self.store.save_for(account, credentials, master_password)
+    }
 
-        let encrypted_data: EncryptedData = {
-            let json = fs::read_to_string(&self.file_path)
-                .context("Failed to read encrypted credentials file")?;
-            serde_json::from_str(&json).context("Failed to parse encrypted credentials file")?
-        };
+    /// Load credentials for a named account
+    pub fn load_credentials_for(&self, account: &str, master_password: Option<&str>) -> Result<Credentials> {
+        self.store.load_for(account, master_password)
+    }
 
-        // Verify password against stored hash
-        let parsed_hash = PasswordHash::new(&encrypted_data.password_hash)
-            .map_err(|e| anyhow!("Failed to parse password hash: {}", e))?;
-        
-        Ok(Argon2::default()
-            .verify_password(password.as_bytes(), &parsed_hash)
-            .is_ok())
+    /// Delete a named account's credentials. `master_password` is required for backends whose
+    /// accounts share one encrypted blob, unless `account` is the only one stored.
+    pub fn delete_account(&self, account: &str, master_password: Option<&str>) -> Result<()> {
+        self.store.delete_for(account, master_password)
+    }
+
+    /// Verify master password (for encrypted file backend)
+    pub fn verify_master_password(&self, password: &str) -> std::result::Result<bool, CredentialError> {
+        self.store
+            .as_any()
+            .downcast_ref::<EncryptedFileStore>()
+            .ok_or(CredentialError::BackendMismatch)?
+            .verify_password(password)
+            .map_err(CredentialError::classify)
+    }
+
+    /// Change the master password for the encrypted file backend without re-encrypting any
+    /// credentials: only the key-encryption key is re-derived and the data-encryption key is
+    /// re-wrapped under it.
+    pub fn change_master_password(&self, old_password: &str, new_password: &str) -> Result<()> {
+        self.store
+            .as_any()
+            .downcast_ref::<EncryptedFileStore>()
+            .ok_or_else(|| anyhow!("Changing the master password is only available for encrypted file backend"))?
+            .change_password(old_password, new_password)
     }
 
     /// Migrate credentials from one backend to another
-    pub fn migrate_to(&self, target_backend: StorageBackend, 
+    pub fn migrate_to(&self, target_backend: StorageBackend,
                       current_master_password: Option<&str>,
-                      new_master_password: Option<&str>) -> Result<()> {
+                      new_master_password: Option<&str>) -> std::result::Result<(), CredentialError> {
         // Load credentials from current backend
         let credentials = self.load_credentials(current_master_password)?;
-        
+
         // Create a new manager with target backend
         let target_manager = Self::with_backend(target_backend);
-        
+
         // Save to target backend
         target_manager.save_credentials(&credentials, new_master_password)?;
-        
+
         // Delete from current backend
-        self.delete_credentials()?;
-        
+        self.delete_credentials().map_err(CredentialError::classify)?;
+
         Ok(())
     }
 
-    // ============ System Keyring Operations ============
+    /// Entry name used to key a single account's secret in the system keyring, stable
+    /// across runs so `(provider_id, username)` always resolves to the same entry.
+    fn keyed_entry_name(provider_id: &str, username: &str) -> String {
+        format!("{}:{}", provider_id, username)
+    }
 
-    fn save_to_keyring(&self, credentials: &Credentials) -> Result<()> {
-        let entry = keyring::Entry::new(SERVICE_NAME, USERNAME)
+    /// Save one account's credentials to the system keyring under a `(provider_id, username)`
+    /// entry, independent of the default single-account entry used by [`Self::save_credentials`].
+    /// This is how multiple accounts' secrets coexist in the keyring.
+    pub fn save_account_secret(
+        provider_id: &str,
+        username: &str,
+        credentials: &Credentials,
+    ) -> Result<()> {
+        let entry_name = Self::keyed_entry_name(provider_id, username);
+        let entry = keyring::Entry::new(SERVICE_NAME, &entry_name)
             .context("Failed to create keyring entry")?;
-        
-        // Serialize credentials to JSON
-        let json = serde_json::to_string(credentials)
-            .context("Failed to serialize credentials")?;
-        
-        entry.set_password(&json)
-            .context("Failed to save credentials to keyring")?;
-        
+        let json = serde_json::to_string(credentials).context("Failed to serialize credentials")?;
+        entry.set_password(&json).context("Failed to save credentials to keyring")?;
         Ok(())
     }
 
-    fn load_from_keyring(&self) -> Result<Credentials> {
-        let entry = keyring::Entry::new(SERVICE_NAME, USERNAME)
+    /// Load one account's credentials from the system keyring by `(provider_id, username)`
+    pub fn load_account_secret(provider_id: &str, username: &str) -> Result<Credentials> {
+        let entry_name = Self::keyed_entry_name(provider_id, username);
+        let entry = keyring::Entry::new(SERVICE_NAME, &entry_name)
             .context("Failed to create keyring entry")?;
-        
-        let json = entry.get_password()
-            .context("Failed to retrieve credentials from keyring. Please configure credentials first.")?;
-        
-        let credentials: Credentials = serde_json::from_str(&json)
-            .context("Failed to parse credentials from keyring")?;
-        
-        Ok(credentials)
+        let json = entry
+            .get_password()
+            .context("Failed to retrieve credentials from keyring")?;
+        serde_json::from_str(&json).context("Failed to parse credentials from keyring")
     }
 
-    fn delete_from_keyring(&self) -> Result<()> {
-        let entry = keyring::Entry::new(SERVICE_NAME, USERNAME)
+    /// Delete one account's credentials from the system keyring by `(provider_id, username)`
+    pub fn delete_account_secret(provider_id: &str, username: &str) -> Result<()> {
+        let entry_name = Self::keyed_entry_name(provider_id, username);
+        let entry = keyring::Entry::new(SERVICE_NAME, &entry_name)
             .context("Failed to create keyring entry")?;
-        
-        entry.delete_credential()
-            .context("Failed to delete credentials from keyring")?;
-        
+        entry.delete_credential().context("Failed to delete credentials from keyring")?;
         Ok(())
     }
 
-    // ============ Encrypted File Operations ============
+    /// One-time migration: move the legacy single-entry credentials (stored under
+    /// whichever backend is currently active) into a `(provider_id, username)`-keyed
+    /// keyring entry, so existing installs pick up the new multi-account scheme.
+    pub fn migrate_legacy_to_keyed(
+        &self,
+        provider_id: &str,
+        username: &str,
+        current_master_password: Option<&str>,
+    ) -> Result<()> {
+        let credentials = self.load_credentials(current_master_password)?;
+        Self::save_account_secret(provider_id, username, &credentials)?;
+        self.delete_credentials()?;
+        Ok(())
+    }
 
-    fn save_to_encrypted_file(&self, credentials: &Credentials, master_password: &str) -> Result<()> {
-        // Ensure parent directory exists
-        if let Some(parent) = self.file_path.parent() {
-            fs::create_dir_all(parent)
-                .context("Failed to create credentials directory")?;
-        }
+}
 
-        // Generate salt for key derivation
-        let salt = SaltString::generate(&mut OsRng);
-        
-        // Hash password for verification
-        let password_hash = Argon2::default()
-            .hash_password(master_password.as_bytes(), &salt)
-            .map_err(|e| anyhow!("Failed to hash master password: {}", e))?
-            .to_string();
+/// Resolves a username/password pair into full IMAP/SMTP connection settings and secrets,
+/// abstracting over whether an account is hand-configured locally or provisioned by an
+/// organization directory. Modeled on Aerogramme's `login` module, where this is the one seam
+/// between "what the user typed" and "which mailbox they get."
+pub trait CredentialProvider {
+    fn resolve(&self, username: &str, password: &str) -> Result<Credentials>;
+}
 
-        // Derive encryption key from password
-        let mut key = [0u8; 32];
-        Argon2::default()
-            .hash_password_into(master_password.as_bytes(), salt.as_str().as_bytes(), &mut key)
-            .map_err(|e| anyhow!("Failed to derive encryption key: {}", e))?;
+/// The default provider: an account's settings come from whatever was previously saved locally
+/// (keyring, encrypted file, etc.) via a [`CredentialsManager`]. `username` selects the account;
+/// `password` authenticates access to that store, not the mail servers themselves.
+pub struct StaticProvider {
+    manager: CredentialsManager,
+}
 
-        // Generate random nonce
-        let mut nonce_bytes = [0u8; 12];
-        OsRng.fill_bytes(&mut nonce_bytes);
-        let nonce = Nonce::from_slice(&nonce_bytes);
+impl StaticProvider {
+    pub fn new(manager: CredentialsManager) -> Self {
+        Self { manager }
+    }
+}
 
-        // Serialize credentials
-        let json = serde_json::to_string(credentials)
-            .context("Failed to serialize credentials")?;
+impl CredentialProvider for StaticProvider {
+    fn resolve(&self, username: &str, password: &str) -> Result<Credentials> {
+        self.manager
+            .load_credentials_for(username, Some(password))
+    }
+}
 
-        // Encrypt credentials
-        let cipher = Aes256Gcm::new_from_slice(&key)
-            .map_err(|e| anyhow!("Failed to create cipher: {}", e))?;
-        let ciphertext = cipher
-            .encrypt(nonce, json.as_bytes())
-            .map_err(|_| anyhow!("Failed to encrypt credentials"))?;
+/// Connection settings for an [`LdapProvider`]: where to bind, how to find the user's entry,
+/// and which directory attributes hold their mail server settings. Attribute names are
+/// configurable since they vary across directory schemas.
+#[derive(Debug, Clone)]
+pub struct LdapProviderConfig {
+    /// e.g. `ldaps://ldap.example.org:636`
+    pub server_url: String,
+    /// Base DN to search under, e.g. `ou=people,dc=example,dc=org`
+    pub base_dn: String,
+    /// Directory attribute holding the login username, e.g. `uid`
+    pub username_attr: String,
+    /// Attribute holding the user's IMAP hostname
+    pub imap_server_attr: String,
+    /// Attribute holding the user's IMAP port; falls back to 993 if absent or unparsable
+    pub imap_port_attr: String,
+    /// Attribute holding the user's IMAP login; falls back to the resolved username if absent
+    pub imap_username_attr: String,
+    /// Attribute holding the user's SMTP hostname
+    pub smtp_server_attr: String,
+    /// Attribute holding the user's SMTP port; falls back to 587 if absent or unparsable
+    pub smtp_port_attr: String,
+    /// Attribute holding the user's SMTP login; falls back to the resolved username if absent
+    pub smtp_username_attr: String,
+}
 
-        // Zeroize sensitive data
-        key.zeroize();
+impl Default for LdapProviderConfig {
+    fn default() -> Self {
+        Self {
+            server_url: String::new(),
+            base_dn: String::new(),
+            username_attr: "uid".to_string(),
+            imap_server_attr: "mailHost".to_string(),
+            imap_port_attr: "mailHostImapPort".to_string(),
+            imap_username_attr: "mailImapUsername".to_string(),
+            smtp_server_attr: "mailHost".to_string(),
+            smtp_port_attr: "mailHostSmtpPort".to_string(),
+            smtp_username_attr: "mailSmtpUsername".to_string(),
+        }
+    }
+}
 
-        // Create encrypted data structure
-        let encrypted_data = EncryptedData {
-            salt: base64::encode(salt.as_str().as_bytes()),
-            nonce: base64::encode(&nonce_bytes),
-            ciphertext: base64::encode(&ciphertext),
-            password_hash,
-        };
+/// Looks up an account in an LDAP directory rather than local storage, so an admin can point
+/// tume at an organization's directory and end users only ever enter their username and
+/// password: server settings live in the directory entry, not in a hand-configured account.
+pub struct LdapProvider {
+    config: LdapProviderConfig,
+}
 
-        // Write to file
-        let json = serde_json::to_string(&encrypted_data)
-            .context("Failed to serialize encrypted data")?;
-        fs::write(&self.file_path, json)
-            .context("Failed to write encrypted credentials file")?;
+impl LdapProvider {
+    pub fn new(config: LdapProviderConfig) -> Self {
+        Self { config }
+    }
 
-        Ok(())
+    /// First value of a directory attribute, if the entry has it
+    fn attr(entry: &ldap3::SearchEntry, name: &str) -> Option<String> {
+        entry.attrs.get(name).and_then(|values| values.first()).cloned()
     }
 
-    fn load_from_encrypted_file(&self, master_password: &str) -> Result<Credentials> {
-        if !self.file_path.exists() {
-            return Err(anyhow!("Credentials file not found. Please configure credentials first."));
+    /// Escape a value for safe use inside an RFC 4514 LDAP distinguished name component, e.g. the
+    /// bind DN built from a user-supplied username below: backslash-escapes the characters DN
+    /// syntax treats specially (`, + " \ < > ; =` and a leading `#` or leading/trailing space) so a
+    /// crafted username can't reshape the DN it's spliced into.
+    fn escape_dn_value(value: &str) -> String {
+        let chars: Vec<char> = value.chars().collect();
+        let last = chars.len().saturating_sub(1);
+        let mut escaped = String::with_capacity(value.len());
+        for (i, &c) in chars.iter().enumerate() {
+            match c {
+                ',' | '+' | '"' | '\\' | '<' | '>' | ';' | '=' => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                '#' if i == 0 => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                ' ' if i == 0 || i == last => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                _ => escaped.push(c),
+            }
         }
-
-        // Read encrypted data
-        let encrypted_data: EncryptedData = {
-            let json = fs::read_to_string(&self.file_path)
-                .context("Failed to read encrypted credentials file")?;
-            serde_json::from_str(&json)
-                .context("Failed to parse encrypted credentials file")?
-        };
-
-        // Verify password
-        let parsed_hash = PasswordHash::new(&encrypted_data.password_hash)
-            .map_err(|e| anyhow!("Failed to parse password hash: {}", e))?;
-        Argon2::default()
-            .verify_password(master_password.as_bytes(), &parsed_hash)
-            .map_err(|_| anyhow!("Incorrect master password"))?;
-
-        // Decode base64 data
-        let salt_bytes = base64::decode(&encrypted_data.salt)
-            .context("Failed to decode salt")?;
-        let nonce_bytes = base64::decode(&encrypted_data.nonce)
-            .context("Failed to decode nonce")?;
-        let ciphertext = base64::decode(&encrypted_data.ciphertext)
-            .context("Failed to decode ciphertext")?;
-
-        // Derive decryption key
-        let mut key = [0u8; 32];
-        Argon2::default()
-            .hash_password_into(master_password.as_bytes(), &salt_bytes, &mut key)
-            .map_err(|e| anyhow!("Failed to derive decryption key: {}", e))?;
-
-        // Decrypt credentials
-        let cipher = Aes256Gcm::new_from_slice(&key)
-            .map_err(|e| anyhow!("Failed to create cipher: {}", e))?;
-        let nonce = Nonce::from_slice(&nonce_bytes);
-        let plaintext = cipher
-            .decrypt(nonce, ciphertext.as_ref())
-            .map_err(|_| anyhow!("Failed to decrypt credentials"))?;
-
-        // Zeroize key
-        key.zeroize();
-
-        // Parse credentials
-        let json = String::from_utf8(plaintext)
-            .context("Failed to parse decrypted data as UTF-8")?;
-        let credentials: Credentials = serde_json::from_str(&json)
-            .context("Failed to parse credentials JSON")?;
-
-        Ok(credentials)
+        escaped
     }
 
-    fn delete_encrypted_file(&self) -> Result<()> {
-        if self.file_path.exists() {
-            fs::remove_file(&self.file_path)
-                .context("Failed to delete encrypted credentials file")?;
+    /// Escape a value for safe use inside an RFC 4515 LDAP search filter, e.g. the `(uid=...)`
+    /// filter built from a user-supplied username below: backslash-hex-escapes `* ( ) \` and NUL
+    /// so a crafted username can't inject filter syntax.
+    fn escape_filter_value(value: &str) -> String {
+        let mut escaped = String::with_capacity(value.len());
+        for c in value.chars() {
+            match c {
+                '*' | '(' | ')' | '\\' | '\0' => escaped.push_str(&format!("\\{:02x}", c as u32)),
+                _ => escaped.push(c),
+            }
         }
-        Ok(())
+        escaped
+    }
+}
+
+impl CredentialProvider for LdapProvider {
+    fn resolve(&self, username: &str, password: &str) -> Result<Credentials> {
+        let mut conn = ldap3::LdapConn::new(&self.config.server_url)
+            .context("Failed to connect to LDAP server")?;
+
+        // The directory itself authenticates the user: a failed bind here means "wrong
+        // username or password," not a lookup failure. `username` is untrusted login input, so
+        // it's DN-escaped before being spliced into the bind DN (see Self::escape_dn_value).
+        let bind_dn = format!(
+            "{}={},{}",
+            self.config.username_attr,
+            Self::escape_dn_value(username),
+            self.config.base_dn
+        );
+        conn.simple_bind(&bind_dn, password)
+            .context("LDAP bind failed")?
+            .success()
+            .context("Incorrect username or password")?;
+
+        let filter = format!("({}={})", self.config.username_attr, Self::escape_filter_value(username));
+        let (results, _) = conn
+            .search(
+                &self.config.base_dn,
+                ldap3::Scope::Subtree,
+                &filter,
+                vec![
+                    self.config.imap_server_attr.clone(),
+                    self.config.imap_port_attr.clone(),
+                    self.config.imap_username_attr.clone(),
+                    self.config.smtp_server_attr.clone(),
+                    self.config.smtp_port_attr.clone(),
+                    self.config.smtp_username_attr.clone(),
+                ],
+            )
+            .context("LDAP search failed")?
+            .success()
+            .context("LDAP search did not complete successfully")?;
+
+        let raw_entry = results
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("No directory entry found for user '{}'", username))?;
+        let entry = ldap3::SearchEntry::construct(raw_entry);
+
+        let imap_server = Self::attr(&entry, &self.config.imap_server_attr)
+            .ok_or_else(|| anyhow!("Directory entry is missing the IMAP server attribute"))?;
+        let smtp_server = Self::attr(&entry, &self.config.smtp_server_attr)
+            .ok_or_else(|| anyhow!("Directory entry is missing the SMTP server attribute"))?;
+        let imap_port = Self::attr(&entry, &self.config.imap_port_attr)
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(993);
+        let smtp_port = Self::attr(&entry, &self.config.smtp_port_attr)
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(587);
+        let imap_username = Self::attr(&entry, &self.config.imap_username_attr)
+            .unwrap_or_else(|| username.to_string());
+        let smtp_username = Self::attr(&entry, &self.config.smtp_username_attr)
+            .unwrap_or_else(|| username.to_string());
+
+        let _ = conn.unbind();
+
+        Ok(Credentials {
+            imap_server,
+            imap_port,
+            imap_security: crate::providers::SecurityType::default(),
+            imap_username,
+            imap_password: password.to_string(),
+            smtp_server,
+            smtp_port,
+            smtp_security: crate::providers::SecurityType::default(),
+            smtp_username,
+            smtp_password: password.to_string(),
+            oauth_token: None,
+        })
     }
 }
 
@@ -473,15 +1510,40 @@ mod tests {
         Credentials {
             imap_server: "imap.example.com".to_string(),
             imap_port: 993,
+            imap_security: crate::providers::SecurityType::Tls,
             imap_username: "user@example.com".to_string(),
             imap_password: "imap_secret".to_string(),
             smtp_server: "smtp.example.com".to_string(),
             smtp_port: 587,
+            smtp_security: crate::providers::SecurityType::StartTls,
             smtp_username: "user@example.com".to_string(),
             smtp_password: "smtp_secret".to_string(),
+            oauth_token: None,
         }
     }
 
+    #[test]
+    fn test_xoauth2_sasl_string_format() {
+        let encoded = xoauth2_sasl_string("user@example.com", "ya29.fake-token");
+        let decoded = base64::decode(&encoded).unwrap();
+        let decoded = String::from_utf8(decoded).unwrap();
+        assert_eq!(decoded, "user=user@example.com\x01auth=Bearer ya29.fake-token\x01\x01");
+    }
+
+    #[test]
+    fn test_oauth_token_expiry() {
+        let token = OAuthToken {
+            access_token: "access".to_string(),
+            refresh_token: "refresh".to_string(),
+            expires_at: 1000,
+            token_url: "https://example.com/token".to_string(),
+            client_id: "client".to_string(),
+        };
+        assert!(!token.is_expired(999));
+        assert!(token.is_expired(1000));
+        assert!(token.is_expired(1001));
+    }
+
     #[test]
     fn test_encrypted_file_save_and_load() {
         // Create a temporary file path
@@ -491,8 +1553,7 @@ mod tests {
         // Clean up if exists
         let _ = std::fs::remove_file(&file_path);
 
-        let mut manager = CredentialsManager::with_backend(StorageBackend::EncryptedFile);
-        manager.file_path = file_path.clone();
+        let manager = CredentialsManager::with_encrypted_file_path(file_path.clone());
 
         let credentials = create_test_credentials();
         let master_password = "test-master-password-123";
@@ -526,8 +1587,7 @@ mod tests {
         let file_path = temp_dir.join(format!("test_tume_verify_{}.enc", std::process::id()));
         let _ = std::fs::remove_file(&file_path);
 
-        let mut manager = CredentialsManager::with_backend(StorageBackend::EncryptedFile);
-        manager.file_path = file_path.clone();
+        let manager = CredentialsManager::with_encrypted_file_path(file_path.clone());
 
         let credentials = create_test_credentials();
         let master_password = "correct-password";
@@ -551,8 +1611,7 @@ mod tests {
         let file_path = temp_dir.join(format!("test_tume_delete_{}.enc", std::process::id()));
         let _ = std::fs::remove_file(&file_path);
 
-        let mut manager = CredentialsManager::with_backend(StorageBackend::EncryptedFile);
-        manager.file_path = file_path.clone();
+        let manager = CredentialsManager::with_encrypted_file_path(file_path.clone());
 
         let credentials = create_test_credentials();
         manager.save_credentials(&credentials, Some("password"))
@@ -566,13 +1625,201 @@ mod tests {
         assert!(!manager.credentials_exist());
     }
 
+    #[test]
+    fn test_multi_account_roundtrip() {
+        let temp_dir = std::env::temp_dir();
+        let file_path = temp_dir.join(format!("test_tume_multi_{}.enc", std::process::id()));
+        let _ = std::fs::remove_file(&file_path);
+
+        let manager = CredentialsManager::with_encrypted_file_path(file_path.clone());
+        let master_password = "multi-account-password";
+
+        let mut work = create_test_credentials();
+        work.imap_server = "imap.work.example.com".to_string();
+        let mut personal = create_test_credentials();
+        personal.imap_server = "imap.personal.example.com".to_string();
+
+        manager.save_credentials_for("work", &work, Some(master_password))
+            .expect("Failed to save work account");
+        manager.save_credentials_for("personal", &personal, Some(master_password))
+            .expect("Failed to save personal account");
+
+        let mut accounts = manager.list_accounts(Some(master_password)).unwrap();
+        accounts.sort();
+        assert_eq!(accounts, vec!["personal".to_string(), "work".to_string()]);
+
+        let loaded_work = manager.load_credentials_for("work", Some(master_password))
+            .expect("Failed to load work account");
+        assert_eq!(loaded_work.imap_server, work.imap_server);
+
+        manager.delete_account("work", Some(master_password))
+            .expect("Failed to delete work account");
+
+        let remaining = manager.list_accounts(Some(master_password)).unwrap();
+        assert_eq!(remaining, vec!["personal".to_string()]);
+        assert!(manager.load_credentials_for("work", Some(master_password)).is_err());
+
+        // Clean up
+        let _ = std::fs::remove_file(&file_path);
+    }
+
     #[test]
     fn test_backend_detection() {
         let manager = CredentialsManager::new();
         // Should select either keyring or encrypted file
         assert!(
-            manager.backend() == StorageBackend::SystemKeyring 
+            manager.backend() == StorageBackend::SystemKeyring
             || manager.backend() == StorageBackend::EncryptedFile
         );
     }
+
+    #[test]
+    fn test_credentials_from_secret_refs() {
+        let imap_secret = crate::config::SecretRef::Inline { value: "imap-pass".to_string() };
+        let smtp_secret = crate::config::SecretRef::Command { command: "echo smtp-pass".to_string() };
+
+        let credentials = Credentials::from_secret_refs(
+            "imap.example.com".to_string(),
+            993,
+            crate::providers::SecurityType::Tls,
+            "user@example.com".to_string(),
+            &imap_secret,
+            "smtp.example.com".to_string(),
+            587,
+            crate::providers::SecurityType::StartTls,
+            "user@example.com".to_string(),
+            &smtp_secret,
+        )
+        .expect("should resolve both secrets");
+
+        assert_eq!(credentials.imap_password, "imap-pass");
+        assert_eq!(credentials.smtp_password, "smtp-pass");
+    }
+
+    #[test]
+    fn test_change_master_password_reuses_dek() {
+        let temp_dir = std::env::temp_dir();
+        let file_path = temp_dir.join(format!("test_tume_change_pw_{}.enc", std::process::id()));
+        let _ = std::fs::remove_file(&file_path);
+
+        let manager = CredentialsManager::with_encrypted_file_path(file_path.clone());
+        let credentials = create_test_credentials();
+
+        manager.save_credentials(&credentials, Some("old-password"))
+            .expect("Failed to save credentials");
+
+        manager.change_master_password("old-password", "new-password")
+            .expect("Failed to change master password");
+
+        // Old password no longer works
+        assert!(manager.load_credentials(Some("old-password")).is_err());
+
+        // New password decrypts to the same credentials
+        let loaded = manager.load_credentials(Some("new-password"))
+            .expect("Failed to load credentials with new password");
+        assert_eq!(loaded.imap_password, credentials.imap_password);
+        assert_eq!(loaded.smtp_password, credentials.smtp_password);
+
+        // Wrong old password is rejected
+        assert!(manager.change_master_password("wrong-password", "another-password").is_err());
+
+        let _ = std::fs::remove_file(&file_path);
+    }
+
+    #[test]
+    fn test_command_store_roundtrip() {
+        let temp_dir = std::env::temp_dir();
+        let file_path = temp_dir.join(format!("test_tume_cmd_store_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&file_path);
+
+        let config = CommandHelperConfig {
+            save_command: format!("cat > {}", file_path.display()),
+            load_command: format!("cat {}", file_path.display()),
+            delete_command: Some(format!("rm -f {}", file_path.display())),
+            attach_tty: false,
+        };
+        let manager = CredentialsManager::with_command_helper(config);
+        let credentials = create_test_credentials();
+
+        manager.save_credentials(&credentials, None)
+            .expect("Failed to save via command helper");
+        assert!(manager.credentials_exist());
+
+        let loaded = manager.load_credentials(None)
+            .expect("Failed to load via command helper");
+        assert_eq!(loaded.imap_server, credentials.imap_server);
+        assert_eq!(loaded.imap_password, credentials.imap_password);
+
+        manager.delete_credentials()
+            .expect("Failed to delete via command helper");
+
+        let _ = std::fs::remove_file(&file_path);
+    }
+
+    #[test]
+    fn test_command_store_attach_tty_uses_temp_file_handoff() {
+        let temp_dir = std::env::temp_dir();
+        let file_path = temp_dir.join(format!("test_tume_cmd_tty_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&file_path);
+
+        let config = CommandHelperConfig {
+            save_command: format!("cp \"$TUME_CREDENTIALS_FILE\" {}", file_path.display()),
+            load_command: format!("cat {} > \"$TUME_CREDENTIALS_FILE\"", file_path.display()),
+            delete_command: None,
+            attach_tty: true,
+        };
+        let manager = CredentialsManager::with_command_helper(config);
+        let credentials = create_test_credentials();
+
+        manager.save_credentials(&credentials, None)
+            .expect("Failed to save via command helper with attach_tty");
+        let loaded = manager.load_credentials(None)
+            .expect("Failed to load via command helper with attach_tty");
+        assert_eq!(loaded.imap_username, credentials.imap_username);
+
+        let _ = std::fs::remove_file(&file_path);
+    }
+
+    #[test]
+    fn test_keyed_entry_name_is_stable() {
+        let name = CredentialsManager::keyed_entry_name("gmail", "jane@gmail.com");
+        assert_eq!(name, "gmail:jane@gmail.com");
+        assert_eq!(name, CredentialsManager::keyed_entry_name("gmail", "jane@gmail.com"));
+    }
+
+    #[test]
+    fn test_escape_dn_value_escapes_rfc4514_special_characters() {
+        assert_eq!(LdapProvider::escape_dn_value("plain"), "plain");
+        assert_eq!(
+            LdapProvider::escape_dn_value("jane,ou=admins+x=\"y\"<z>;w"),
+            "jane\\,ou\\=admins\\+x\\=\\\"y\\\"\\<z\\>\\;w"
+        );
+        assert_eq!(LdapProvider::escape_dn_value("#leading"), "\\#leading");
+        assert_eq!(LdapProvider::escape_dn_value(" leading and trailing "), "\\ leading and trailing\\ ");
+    }
+
+    #[test]
+    fn test_escape_dn_value_neutralizes_dn_injection_attempt() {
+        // Without escaping, this username would splice a second RDN into the bind DN.
+        let escaped = LdapProvider::escape_dn_value("jane,ou=admins,dc=example,dc=com");
+        let bind_dn = format!("uid={},ou=people,dc=example,dc=com", escaped);
+        assert_eq!(
+            bind_dn,
+            "uid=jane\\,ou\\=admins\\,dc\\=example\\,dc\\=com,ou=people,dc=example,dc=com"
+        );
+    }
+
+    #[test]
+    fn test_escape_filter_value_escapes_rfc4515_special_characters() {
+        assert_eq!(LdapProvider::escape_filter_value("plain"), "plain");
+        assert_eq!(LdapProvider::escape_filter_value("a*b(c)d\\e\0f"), "a\\2ab\\28c\\29d\\5ce\\00f");
+    }
+
+    #[test]
+    fn test_escape_filter_value_neutralizes_filter_injection_attempt() {
+        // Without escaping, this username would close the filter early and OR in a wildcard.
+        let escaped = LdapProvider::escape_filter_value("*)(uid=*))(|(uid=*");
+        let filter = format!("(uid={})", escaped);
+        assert_eq!(filter, "(uid=\\2a\\29\\28uid=\\2a\\29\\29\\28|\\28uid=\\2a)");
+    }
 }
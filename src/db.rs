@@ -17,6 +17,45 @@ pub enum EmailStatus {
     Deleted,
 }
 
+/// Outcome of [`EmailDatabase::upsert_email`], so callers can tell a freshly-arrived message
+/// (worth a "new mail" notification) from one that was already cached and just got its
+/// server-side fields refreshed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpsertOutcome {
+    Inserted(i64),
+    Updated(i64),
+}
+
+impl UpsertOutcome {
+    pub fn id(&self) -> i64 {
+        match self {
+            UpsertOutcome::Inserted(id) | UpsertOutcome::Updated(id) => *id,
+        }
+    }
+}
+
+/// Result ordering for [`EmailDatabase::search_emails_query`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchOrder {
+    /// FTS5 bm25 relevance rank (best match first). Falls back to `Date` when a query has no
+    /// free-text terms to rank.
+    Rank,
+    /// Most recent first.
+    Date,
+}
+
+/// Tally of what [`EmailDatabase::apply_actions`] did (or, under `dry_run`, would do).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ApplyOutcome {
+    pub deleted: usize,
+    pub moved: usize,
+    pub flags_updated: usize,
+    /// `SyncAction::FetchNew`/`SyncAction::PushFlagsRemote` entries, which need server IO the
+    /// sync loop performs itself; counted here so the caller can confirm the whole plan was
+    /// accounted for.
+    pub deferred_to_server: usize,
+}
+
 impl EmailStatus {
     pub fn as_str(&self) -> &str {
         match self {
@@ -47,6 +86,9 @@ pub struct DbEmail {
     pub bcc_addresses: Option<String>,
     pub subject: String,
     pub body: String,
+    /// The `text/html` alternative body, when the message was multipart and carried one
+    /// alongside (or instead of) `body`'s `text/plain`. See [`crate::mime::parse_message`].
+    pub body_html: Option<String>,
     pub preview: String,
     pub date: String,
     pub status: EmailStatus,
@@ -56,6 +98,64 @@ pub struct DbEmail {
     pub account_id: Option<i64>,
     pub message_id: Option<String>,
     pub imap_uid: Option<u32>,
+    /// The `In-Reply-To` header, used by [`rebuild_threads`](EmailDatabase::rebuild_threads)
+    /// when `references` is absent
+    pub in_reply_to: Option<String>,
+    /// The `References` header, space-separated message-ids oldest first
+    pub references: Option<String>,
+    /// The IMAP MODSEQ this message was last seen at (CONDSTORE), used by
+    /// [`EmailDatabase::emails_modified_since`] for incremental sync
+    pub modseq: Option<i64>,
+    /// Human-readable PGP/MIME sign/encrypt status, set at parse time by
+    /// [`crate::gpg::scan_incoming`] when the message carried a signed or encrypted part
+    /// (e.g. `"Signed (verified, alice@example.com)"`, `"Encrypted (decrypted)"`); `None` for
+    /// plain messages.
+    pub pgp_status: Option<String>,
+    /// Raw `List-Id`/`List-Post`/`List-Unsubscribe` headers, see [`crate::mime::parse_message`]
+    /// and [`crate::app::Email::list_headers`]; `None` for non-list mail.
+    pub list_headers: Option<String>,
+    /// Every header the message carried, encoded as `"Name: value"` lines joined with `\n`, for
+    /// [`crate::email_sync::RuleCondition::HeaderContains`]/[`crate::email_sync::MatchField::Header`]
+    /// to read arbitrary headers without re-fetching the raw message. See
+    /// [`crate::mime::parse_message`].
+    pub headers: Option<String>,
+    /// Whether the message carried at least one MIME attachment, for
+    /// [`crate::email_sync::RuleCondition::HasAttachment`]. See [`crate::mime::parse_message`].
+    pub has_attachment: bool,
+}
+
+/// Lightweight attachment listing for [`EmailDatabase::get_attachment_manifest`] — everything
+/// `EmailDetail` needs to render a manifest without pulling attachment bodies off disk.
+#[derive(Debug, Clone)]
+pub struct AttachmentMeta {
+    pub id: i64,
+    pub email_id: i64,
+    pub filename: String,
+    pub content_type: String,
+    pub size: i64,
+}
+
+/// Database representation of a stored attachment, including its bytes. Fetched on demand by
+/// [`EmailDatabase::get_attachment`] when the user saves it to disk.
+#[derive(Debug, Clone)]
+pub struct DbAttachment {
+    pub id: i64,
+    pub email_id: i64,
+    pub filename: String,
+    pub content_type: String,
+    pub size: i64,
+    pub data: Vec<u8>,
+}
+
+/// Per-(account, folder) CONDSTORE/UIDVALIDITY bookkeeping, so a sync only has to fetch what
+/// changed since `highest_modseq` instead of re-reading the whole folder.
+#[derive(Debug, Clone)]
+pub struct FolderSyncState {
+    pub account_id: Option<i64>,
+    pub folder: String,
+    pub uidvalidity: i64,
+    pub highest_modseq: i64,
+    pub last_seen_uid: i64,
 }
 
 /// Database representation of a draft email
@@ -68,13 +168,26 @@ pub struct DbDraft {
     pub created_at: String,
     pub updated_at: String,
     pub account_id: Option<i64>,
+    /// Paths of files attached via `compose_add_attachment`, persisted newline-separated in the
+    /// `attachments` column and read back by [`App::load_draft_async`](crate::app::App::load_draft_async).
+    pub attachments: Vec<PathBuf>,
 }
 
-/// Database representation of a folder/label
+/// Database representation of a folder/label.
+///
+/// Folders are scoped per-account (`account_id`) and can nest (`parent_id`), mirroring the
+/// hierarchy an IMAP server exposes under its `LIST` response delimiter. `special_use` carries
+/// the server's special-use tag (`\Inbox`, `\Sent`, `\Drafts`, `\Archive`, `\Trash`, `\Junk`)
+/// when known, so code like [`EmailDatabase::archive_email`] can resolve the right folder
+/// without assuming a literal name.
 #[derive(Debug, Clone)]
 pub struct DbFolder {
     pub id: i64,
+    pub account_id: Option<i64>,
     pub name: String,
+    pub parent_id: Option<i64>,
+    pub delimiter: String,
+    pub special_use: Option<String>,
     pub display_order: i64,
 }
 
@@ -88,6 +201,94 @@ pub struct DbAccount {
     pub is_default: bool,
     pub color: Option<String>,
     pub display_order: i64,
+    /// [`crate::config::AccountBackend::db_tag`] for this account - `"imap"`, `"maildir"`, or
+    /// `"notmuch"`. Defaults to `"imap"` so accounts persisted before pluggable backends existed
+    /// keep working unmodified.
+    pub backend_kind: String,
+    /// The local path a non-`"imap"` `backend_kind` reads from; `None` for an IMAP account. See
+    /// [`crate::config::AccountBackend::from_db`].
+    pub backend_path: Option<String>,
+}
+
+/// Database representation of an inbox rule: "if `condition_type` matches `condition_value`,
+/// run `action_type`". Evaluated by [`EmailDatabase::apply_rules`].
+#[derive(Debug, Clone)]
+pub struct DbInboxRule {
+    pub id: i64,
+    pub name: String,
+    pub condition_type: String,
+    pub condition_value: String,
+    pub action_type: String,
+    pub action_value: Option<String>,
+    pub enabled: bool,
+    pub account_id: Option<i64>,
+    pub display_order: i64,
+    pub stop_on_match: bool,
+}
+
+/// Database representation of an address book entry, kept up to date from stored emails by
+/// [`EmailDatabase::upsert_contacts_from_email`]. Surfaced to compose autocomplete via
+/// [`EmailDatabase::search_contacts`].
+#[derive(Debug, Clone)]
+pub struct DbContact {
+    pub id: i64,
+    pub account_id: Option<i64>,
+    pub address: String,
+    pub display_name: Option<String>,
+    pub times_seen: i64,
+    pub last_seen_date: String,
+}
+
+/// Split a `From`/`To`/`Cc` header value on commas into `(display_name, address)` pairs,
+/// normalizing `"Name" <addr@x>` and bare `addr@x` forms alike. Addresses are lowercased so the
+/// same mailbox always dedupes to one contact regardless of casing.
+pub(crate) fn parse_address_list(raw: &str) -> Vec<(Option<String>, String)> {
+    raw.split(',')
+        .filter_map(|part| {
+            let part = part.trim();
+            if part.is_empty() {
+                return None;
+            }
+
+            if let (Some(start), Some(end)) = (part.find('<'), part.find('>')) {
+                if end > start {
+                    let address = part[start + 1..end].trim();
+                    if address.is_empty() {
+                        return None;
+                    }
+                    let name = part[..start].trim().trim_matches('"');
+                    let name = if name.is_empty() { None } else { Some(name.to_string()) };
+                    return Some((name, address.to_lowercase()));
+                }
+            }
+
+            Some((None, part.trim_matches('"').to_lowercase()))
+        })
+        .collect()
+}
+
+/// Whether `rule`'s condition matches `email`, per `condition_type`. Text conditions are
+/// case-insensitive substring matches; `size_over`/`size_under` compare `condition_value`
+/// (bytes) against the message body's length, the closest thing to a Sieve `:size` test this
+/// schema can evaluate without storing the original message's wire size. An unrecognized
+/// `condition_type`, or a `size_over`/`size_under` value that doesn't parse as a number, never
+/// matches.
+fn rule_matches(rule: &DbInboxRule, email: &DbEmail) -> bool {
+    match rule.condition_type.as_str() {
+        "size_over" => return rule.condition_value.parse::<usize>().is_ok_and(|n| email.body.len() > n),
+        "size_under" => return rule.condition_value.parse::<usize>().is_ok_and(|n| email.body.len() < n),
+        _ => {}
+    }
+
+    let needle = rule.condition_value.to_lowercase();
+    let haystack = match rule.condition_type.as_str() {
+        "from_contains" => &email.from_address,
+        "subject_contains" => &email.subject,
+        "to_contains" => &email.to_addresses,
+        "body_contains" => &email.body,
+        _ => return false,
+    };
+    haystack.to_lowercase().contains(&needle)
 }
 
 impl EmailDatabase {
@@ -111,6 +312,17 @@ impl EmailDatabase {
 
         let conn = db.connect().context("Failed to connect to database")?;
 
+        // WAL lets readers (the UI) and the writer (sync) proceed concurrently instead of
+        // blocking each other on every autocommit statement; NORMAL trades a little durability
+        // (a crash can lose the last few commits) for avoiding an fsync per write, which matters
+        // once sync starts inserting emails one row at a time.
+        conn.execute("PRAGMA journal_mode=WAL", ())
+            .await
+            .context("Failed to enable WAL journal mode")?;
+        conn.execute("PRAGMA synchronous=NORMAL", ())
+            .await
+            .context("Failed to set synchronous mode")?;
+
         let db = Self { conn };
         db.initialize_schema().await?;
 
@@ -145,6 +357,64 @@ impl EmailDatabase {
             .await
             .context("Failed to create emails table")?;
 
+        // Create the FTS5 index backing `search_emails`, plus the triggers that keep it in
+        // sync with `emails`. `fts_table_exists` is checked *before* the CREATE so we only run
+        // the (potentially expensive) backfill rebuild the first time the table is created.
+        let fts_table_exists = self.table_exists("emails_fts").await?;
+
+        self.conn
+            .execute(
+                "CREATE VIRTUAL TABLE IF NOT EXISTS emails_fts USING fts5(
+                    subject, body, from_address, to_addresses,
+                    content='emails', content_rowid='id'
+                )",
+                (),
+            )
+            .await
+            .context("Failed to create emails_fts virtual table")?;
+
+        self.conn
+            .execute(
+                "CREATE TRIGGER IF NOT EXISTS emails_fts_ai AFTER INSERT ON emails BEGIN
+                    INSERT INTO emails_fts(rowid, subject, body, from_address, to_addresses)
+                    VALUES (new.id, new.subject, new.body, new.from_address, new.to_addresses);
+                END",
+                (),
+            )
+            .await
+            .context("Failed to create emails_fts insert trigger")?;
+
+        self.conn
+            .execute(
+                "CREATE TRIGGER IF NOT EXISTS emails_fts_ad AFTER DELETE ON emails BEGIN
+                    INSERT INTO emails_fts(emails_fts, rowid, subject, body, from_address, to_addresses)
+                    VALUES ('delete', old.id, old.subject, old.body, old.from_address, old.to_addresses);
+                END",
+                (),
+            )
+            .await
+            .context("Failed to create emails_fts delete trigger")?;
+
+        self.conn
+            .execute(
+                "CREATE TRIGGER IF NOT EXISTS emails_fts_au AFTER UPDATE ON emails BEGIN
+                    INSERT INTO emails_fts(emails_fts, rowid, subject, body, from_address, to_addresses)
+                    VALUES ('delete', old.id, old.subject, old.body, old.from_address, old.to_addresses);
+                    INSERT INTO emails_fts(rowid, subject, body, from_address, to_addresses)
+                    VALUES (new.id, new.subject, new.body, new.from_address, new.to_addresses);
+                END",
+                (),
+            )
+            .await
+            .context("Failed to create emails_fts update trigger")?;
+
+        if !fts_table_exists {
+            self.conn
+                .execute("INSERT INTO emails_fts(emails_fts) VALUES('rebuild')", ())
+                .await
+                .context("Failed to backfill emails_fts")?;
+        }
+
         // Create drafts table
         self.conn
             .execute(
@@ -161,20 +431,80 @@ impl EmailDatabase {
             .await
             .context("Failed to create drafts table")?;
 
-        // Create folders table
+        // Create folders table. `name` is scoped per-account rather than globally unique, since
+        // two accounts can both have an "Inbox"; a fresh database gets the new shape directly.
         self.conn
             .execute(
                 "CREATE TABLE IF NOT EXISTS folders (
                     id INTEGER PRIMARY KEY AUTOINCREMENT,
-                    name TEXT NOT NULL UNIQUE,
+                    account_id INTEGER,
+                    name TEXT NOT NULL,
+                    parent_id INTEGER,
+                    delimiter TEXT NOT NULL DEFAULT '/',
+                    special_use TEXT,
                     display_order INTEGER NOT NULL DEFAULT 0,
-                    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+                    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE,
+                    FOREIGN KEY (parent_id) REFERENCES folders(id) ON DELETE SET NULL,
+                    UNIQUE(account_id, parent_id, name)
                 )",
                 (),
             )
             .await
             .context("Failed to create folders table")?;
 
+        // Migrate a pre-account-scoping folders table: SQLite can't drop the old table-level
+        // UNIQUE(name) in place, so swap in the new schema and carry the old rows over as
+        // account-less (global) folders, inferring `special_use` from the old fixed names.
+        let folders_has_account_id = self.check_column_exists("folders", "account_id").await?;
+        if !folders_has_account_id {
+            self.conn
+                .execute("ALTER TABLE folders RENAME TO folders_legacy", ())
+                .await
+                .context("Failed to rename legacy folders table")?;
+            self.conn
+                .execute(
+                    "CREATE TABLE folders (
+                        id INTEGER PRIMARY KEY AUTOINCREMENT,
+                        account_id INTEGER,
+                        name TEXT NOT NULL,
+                        parent_id INTEGER,
+                        delimiter TEXT NOT NULL DEFAULT '/',
+                        special_use TEXT,
+                        display_order INTEGER NOT NULL DEFAULT 0,
+                        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                        FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE,
+                        FOREIGN KEY (parent_id) REFERENCES folders(id) ON DELETE SET NULL,
+                        UNIQUE(account_id, parent_id, name)
+                    )",
+                    (),
+                )
+                .await
+                .context("Failed to create migrated folders table")?;
+            self.conn
+                .execute(
+                    "INSERT INTO folders (id, account_id, name, parent_id, delimiter, special_use, display_order, created_at)
+                     SELECT id, NULL, name, NULL, '/',
+                            CASE name
+                                WHEN 'inbox' THEN '\\Inbox'
+                                WHEN 'sent' THEN '\\Sent'
+                                WHEN 'drafts' THEN '\\Drafts'
+                                WHEN 'archive' THEN '\\Archive'
+                                WHEN 'trash' THEN '\\Trash'
+                                ELSE NULL
+                            END,
+                            display_order, created_at
+                     FROM folders_legacy",
+                    (),
+                )
+                .await
+                .context("Failed to copy legacy folders into migrated table")?;
+            self.conn
+                .execute("DROP TABLE folders_legacy", ())
+                .await
+                .context("Failed to drop legacy folders table")?;
+        }
+
         // Create attachments table
         self.conn
             .execute(
@@ -259,6 +589,15 @@ impl EmailDatabase {
                 .context("Failed to add account_id to drafts table")?;
         }
 
+        // Add attachments column to drafts if it doesn't exist (migration)
+        let draft_attachments_exists = self.check_column_exists("drafts", "attachments").await?;
+        if !draft_attachments_exists {
+            self.conn
+                .execute("ALTER TABLE drafts ADD COLUMN attachments TEXT", ())
+                .await
+                .context("Failed to add attachments to drafts table")?;
+        }
+
         // Add message_id column to emails if it doesn't exist (migration for deduplication)
         let message_id_column_exists = self.check_column_exists("emails", "message_id").await?;
         if !message_id_column_exists {
@@ -283,6 +622,209 @@ impl EmailDatabase {
                 .context("Failed to add imap_uid to emails table")?;
         }
 
+        // Add in_reply_to/references columns to emails if they don't exist (migration for
+        // JWZ threading, see `rebuild_threads`)
+        let in_reply_to_column_exists = self.check_column_exists("emails", "in_reply_to").await?;
+        if !in_reply_to_column_exists {
+            self.conn
+                .execute(
+                    "ALTER TABLE emails ADD COLUMN in_reply_to TEXT",
+                    (),
+                )
+                .await
+                .context("Failed to add in_reply_to to emails table")?;
+        }
+
+        let references_column_exists = self.check_column_exists("emails", "references").await?;
+        if !references_column_exists {
+            self.conn
+                .execute(
+                    "ALTER TABLE emails ADD COLUMN \"references\" TEXT",
+                    (),
+                )
+                .await
+                .context("Failed to add references to emails table")?;
+        }
+
+        // Add modseq column to emails if it doesn't exist (migration for CONDSTORE incremental sync)
+        let modseq_column_exists = self.check_column_exists("emails", "modseq").await?;
+        if !modseq_column_exists {
+            self.conn
+                .execute(
+                    "ALTER TABLE emails ADD COLUMN modseq INTEGER",
+                    (),
+                )
+                .await
+                .context("Failed to add modseq to emails table")?;
+        }
+
+        // Add body_html column to emails if it doesn't exist (migration for MIME multipart
+        // parsing, see `crate::mime::parse_message`)
+        let body_html_column_exists = self.check_column_exists("emails", "body_html").await?;
+        if !body_html_column_exists {
+            self.conn
+                .execute(
+                    "ALTER TABLE emails ADD COLUMN body_html TEXT",
+                    (),
+                )
+                .await
+                .context("Failed to add body_html to emails table")?;
+        }
+
+        // Add pgp_status column to emails if it doesn't exist (migration for PGP/MIME sign and
+        // encrypt detection, see `crate::gpg::scan_incoming`)
+        let pgp_status_column_exists = self.check_column_exists("emails", "pgp_status").await?;
+        if !pgp_status_column_exists {
+            self.conn
+                .execute(
+                    "ALTER TABLE emails ADD COLUMN pgp_status TEXT",
+                    (),
+                )
+                .await
+                .context("Failed to add pgp_status to emails table")?;
+        }
+
+        // Add list_headers column to emails if it doesn't exist (migration for mailing-list
+        // awareness, see `crate::mime::parse_message`)
+        let list_headers_column_exists = self.check_column_exists("emails", "list_headers").await?;
+        if !list_headers_column_exists {
+            self.conn
+                .execute(
+                    "ALTER TABLE emails ADD COLUMN list_headers TEXT",
+                    (),
+                )
+                .await
+                .context("Failed to add list_headers to emails table")?;
+        }
+
+        // Add headers/has_attachment columns to emails if they don't exist (migration for the
+        // richer rule-condition engine, see `crate::email_sync::RuleCondition`)
+        let headers_column_exists = self.check_column_exists("emails", "headers").await?;
+        if !headers_column_exists {
+            self.conn
+                .execute(
+                    "ALTER TABLE emails ADD COLUMN headers TEXT",
+                    (),
+                )
+                .await
+                .context("Failed to add headers to emails table")?;
+        }
+
+        let has_attachment_column_exists = self.check_column_exists("emails", "has_attachment").await?;
+        if !has_attachment_column_exists {
+            self.conn
+                .execute(
+                    "ALTER TABLE emails ADD COLUMN has_attachment INTEGER NOT NULL DEFAULT 0",
+                    (),
+                )
+                .await
+                .context("Failed to add has_attachment to emails table")?;
+        }
+
+        // Add display_order/stop_on_match columns to inbox_rules if they don't exist
+        // (migration for the rule execution engine, see `apply_rules`)
+        let rule_display_order_exists = self.check_column_exists("inbox_rules", "display_order").await?;
+        if !rule_display_order_exists {
+            self.conn
+                .execute(
+                    "ALTER TABLE inbox_rules ADD COLUMN display_order INTEGER NOT NULL DEFAULT 0",
+                    (),
+                )
+                .await
+                .context("Failed to add display_order to inbox_rules table")?;
+        }
+
+        let rule_stop_on_match_exists = self.check_column_exists("inbox_rules", "stop_on_match").await?;
+        if !rule_stop_on_match_exists {
+            self.conn
+                .execute(
+                    "ALTER TABLE inbox_rules ADD COLUMN stop_on_match INTEGER NOT NULL DEFAULT 0",
+                    (),
+                )
+                .await
+                .context("Failed to add stop_on_match to inbox_rules table")?;
+        }
+
+        // Add backend_kind/backend_path columns to accounts if they don't exist (migration for
+        // pluggable Maildir/notmuch backends, see `crate::backend`)
+        let backend_kind_column_exists = self.check_column_exists("accounts", "backend_kind").await?;
+        if !backend_kind_column_exists {
+            self.conn
+                .execute(
+                    "ALTER TABLE accounts ADD COLUMN backend_kind TEXT NOT NULL DEFAULT 'imap'",
+                    (),
+                )
+                .await
+                .context("Failed to add backend_kind to accounts table")?;
+        }
+
+        let backend_path_column_exists = self.check_column_exists("accounts", "backend_path").await?;
+        if !backend_path_column_exists {
+            self.conn
+                .execute(
+                    "ALTER TABLE accounts ADD COLUMN backend_path TEXT",
+                    (),
+                )
+                .await
+                .context("Failed to add backend_path to accounts table")?;
+        }
+
+        // Create the per-(account, folder) CONDSTORE/UIDVALIDITY bookkeeping table
+        self.conn
+            .execute(
+                "CREATE TABLE IF NOT EXISTS folder_sync_state (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    account_id INTEGER,
+                    folder TEXT NOT NULL,
+                    uidvalidity INTEGER NOT NULL DEFAULT 0,
+                    highest_modseq INTEGER NOT NULL DEFAULT 0,
+                    last_seen_uid INTEGER NOT NULL DEFAULT 0,
+                    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE
+                )",
+                (),
+            )
+            .await
+            .context("Failed to create folder_sync_state table")?;
+
+        self.conn
+            .execute(
+                "CREATE UNIQUE INDEX IF NOT EXISTS idx_folder_sync_state_account_folder
+                 ON folder_sync_state(account_id, folder)",
+                (),
+            )
+            .await
+            .context("Failed to create folder_sync_state index")?;
+
+        // Create contacts table: the address book used for compose autocomplete, kept up to
+        // date by `upsert_contacts_from_email` as emails are ingested rather than maintained by
+        // hand.
+        self.conn
+            .execute(
+                "CREATE TABLE IF NOT EXISTS contacts (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    account_id INTEGER,
+                    address TEXT NOT NULL,
+                    display_name TEXT,
+                    times_seen INTEGER NOT NULL DEFAULT 0,
+                    last_seen_date TEXT NOT NULL,
+                    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE
+                )",
+                (),
+            )
+            .await
+            .context("Failed to create contacts table")?;
+
+        self.conn
+            .execute(
+                "CREATE UNIQUE INDEX IF NOT EXISTS idx_contacts_account_address
+                 ON contacts(account_id, address)",
+                (),
+            )
+            .await
+            .context("Failed to create contacts index")?;
+
         // Create indexes for better query performance
         self.conn
             .execute(
@@ -332,6 +874,32 @@ impl EmailDatabase {
             .await
             .context("Failed to create imap_uid index")?;
 
+        // Before the unique index below can be created, collapse any duplicates that snuck in
+        // while inserts were plain INSERTs (e.g. the same message synced twice), keeping the
+        // oldest row of each (account_id, message_id) pair.
+        self.conn
+            .execute(
+                "DELETE FROM emails WHERE message_id IS NOT NULL AND id NOT IN (
+                    SELECT MIN(id) FROM emails
+                    WHERE message_id IS NOT NULL
+                    GROUP BY account_id, message_id
+                 )",
+                (),
+            )
+            .await
+            .context("Failed to remove duplicate emails before adding unique index")?;
+
+        // SQLite can't add a table-level UNIQUE constraint after the fact, so this is enforced
+        // as a unique index instead; it's the conflict target `upsert_email` resolves against.
+        self.conn
+            .execute(
+                "CREATE UNIQUE INDEX IF NOT EXISTS idx_emails_account_message_unique
+                 ON emails(account_id, message_id) WHERE message_id IS NOT NULL",
+                (),
+            )
+            .await
+            .context("Failed to create account/message_id unique index")?;
+
         // Initialize default folders if they don't exist
         self.initialize_default_folders().await?;
 
@@ -365,21 +933,51 @@ impl EmailDatabase {
         Ok(false)
     }
 
+    /// Check whether `table` already exists in the database
+    async fn table_exists(&self, table: &str) -> Result<bool> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+                libsql::params![table],
+            )
+            .await
+            .context("Failed to query sqlite_master")?;
+
+        Ok(rows.next().await?.is_some())
+    }
+
     /// Initialize default folders (inbox, sent, drafts, trash, archive)
     async fn initialize_default_folders(&self) -> Result<()> {
         let default_folders = vec![
-            ("inbox", 0),
-            ("sent", 1),
-            ("drafts", 2),
-            ("archive", 3),
-            ("trash", 4),
+            ("inbox", 0, "\\Inbox"),
+            ("sent", 1, "\\Sent"),
+            ("drafts", 2, "\\Drafts"),
+            ("archive", 3, "\\Archive"),
+            ("trash", 4, "\\Trash"),
         ];
 
-        for (name, order) in default_folders {
+        for (name, order, special_use) in default_folders {
+            // `INSERT OR IGNORE` can't rely on the (account_id, parent_id, name) unique index
+            // here: SQLite never treats two NULLs as equal, so it wouldn't catch a duplicate
+            // global (account_id IS NULL) folder. Check explicitly instead.
+            let mut existing = self
+                .conn
+                .query(
+                    "SELECT id FROM folders WHERE account_id IS NULL AND parent_id IS NULL AND name = ?1",
+                    libsql::params![name],
+                )
+                .await
+                .context("Failed to check for existing default folder")?;
+            if existing.next().await?.is_some() {
+                continue;
+            }
+
             self.conn
                 .execute(
-                    "INSERT OR IGNORE INTO folders (name, display_order) VALUES (?1, ?2)",
-                    libsql::params![name, order],
+                    "INSERT INTO folders (account_id, name, parent_id, delimiter, special_use, display_order)
+                     VALUES (NULL, ?1, NULL, '/', ?2, ?3)",
+                    libsql::params![name, special_use, order],
                 )
                 .await
                 .context("Failed to insert default folder")?;
@@ -388,22 +986,225 @@ impl EmailDatabase {
         Ok(())
     }
 
-    /// Insert a new email into the database
-    pub async fn insert_email(&self, email: &DbEmail) -> Result<i64> {
-        self.conn
-            .execute(
-                "INSERT INTO emails (
-                    from_address, to_addresses, cc_addresses, bcc_addresses, 
-                    subject, body, preview, date, status, is_flagged, 
-                    folder, thread_id, account_id, message_id, imap_uid
-                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
-                libsql::params![
-                    email.from_address.as_str(),
-                    email.to_addresses.as_str(),
-                    email.cc_addresses.as_deref(),
-                    email.bcc_addresses.as_deref(),
+    /// Parse every participant out of `email`'s `From`/`To`/`Cc` fields and upsert them into the
+    /// address book: bump `times_seen` and advance `last_seen_date` for addresses already known,
+    /// insert new ones at `times_seen = 1`. Best-effort display names never overwrite one already
+    /// on file with a blank one.
+    async fn upsert_contacts_from_email(&self, email: &DbEmail) -> Result<()> {
+        let mut participants = parse_address_list(&email.from_address);
+        participants.extend(parse_address_list(&email.to_addresses));
+        if let Some(cc) = &email.cc_addresses {
+            participants.extend(parse_address_list(cc));
+        }
+
+        for (display_name, address) in participants {
+            self.upsert_contact(&address, display_name.as_deref(), email.account_id, &email.date)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Record one sighting of `address` for `account_id`, creating the contact if it's new.
+    async fn upsert_contact(
+        &self,
+        address: &str,
+        display_name: Option<&str>,
+        account_id: Option<i64>,
+        seen_date: &str,
+    ) -> Result<()> {
+        let mut rows = if let Some(account_id) = account_id {
+            self.conn
+                .query(
+                    "SELECT id FROM contacts WHERE account_id = ?1 AND address = ?2",
+                    libsql::params![account_id, address],
+                )
+                .await
+        } else {
+            self.conn
+                .query(
+                    "SELECT id FROM contacts WHERE account_id IS NULL AND address = ?1",
+                    libsql::params![address],
+                )
+                .await
+        }
+        .context("Failed to check for an existing contact")?;
+        let existing_id: Option<i64> = match rows.next().await? {
+            Some(row) => Some(row.get(0)?),
+            None => None,
+        };
+
+        if let Some(id) = existing_id {
+            self.conn
+                .execute(
+                    "UPDATE contacts SET
+                        times_seen = times_seen + 1,
+                        last_seen_date = MAX(last_seen_date, ?1),
+                        display_name = COALESCE(display_name, ?2)
+                     WHERE id = ?3",
+                    libsql::params![seen_date, display_name, id],
+                )
+                .await
+                .context("Failed to update contact")?;
+        } else {
+            self.conn
+                .execute(
+                    "INSERT INTO contacts (account_id, address, display_name, times_seen, last_seen_date)
+                     VALUES (?1, ?2, ?3, 1, ?4)",
+                    libsql::params![account_id, address, display_name, seen_date],
+                )
+                .await
+                .context("Failed to insert contact")?;
+        }
+
+        Ok(())
+    }
+
+    /// Look up a single contact by exact address, for resolving a display name when rendering a
+    /// message list. Matches either scoped to `account_id` or the account-less address book.
+    pub async fn get_contact(&self, address: &str) -> Result<Option<DbContact>> {
+        let address = address.to_lowercase();
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT id, account_id, address, display_name, times_seen, last_seen_date
+                 FROM contacts WHERE address = ?1
+                 ORDER BY times_seen DESC LIMIT 1",
+                libsql::params![address.as_str()],
+            )
+            .await
+            .context("Failed to query contact")?;
+
+        match rows.next().await? {
+            Some(row) => Ok(Some(DbContact {
+                id: row.get(0)?,
+                account_id: row.get(1)?,
+                address: row.get(2)?,
+                display_name: row.get(3)?,
+                times_seen: row.get(4)?,
+                last_seen_date: row.get(5)?,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    /// Suggest contacts for compose autocomplete: addresses or display names containing
+    /// `needle` anywhere, not just as a prefix (case-insensitive), ranked by a recency-weighted
+    /// frequency score so someone emailed often and recently outranks someone emailed often but
+    /// long ago.
+    pub async fn search_contacts(&self, needle: &str, limit: i64) -> Result<Vec<DbContact>> {
+        let pattern = format!("%{}%", needle.replace('%', "\\%").replace('_', "\\_"));
+
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT id, account_id, address, display_name, times_seen, last_seen_date
+                 FROM contacts
+                 WHERE address LIKE ?1 ESCAPE '\\' OR display_name LIKE ?1 ESCAPE '\\'
+                 ORDER BY times_seen / (1.0 + MAX(0.0, julianday('now') - julianday(last_seen_date)) / 30.0) DESC
+                 LIMIT ?2",
+                libsql::params![pattern, limit],
+            )
+            .await
+            .context("Failed to search contacts")?;
+
+        let mut contacts = Vec::new();
+        while let Some(row) = rows.next().await? {
+            contacts.push(DbContact {
+                id: row.get(0)?,
+                account_id: row.get(1)?,
+                address: row.get(2)?,
+                display_name: row.get(3)?,
+                times_seen: row.get(4)?,
+                last_seen_date: row.get(5)?,
+            });
+        }
+
+        Ok(contacts)
+    }
+
+    /// List every contact in the address book, alphabetically by display name (falling back to
+    /// address), for the Contacts view.
+    pub async fn list_contacts(&self) -> Result<Vec<DbContact>> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT id, account_id, address, display_name, times_seen, last_seen_date
+                 FROM contacts
+                 ORDER BY COALESCE(display_name, address) COLLATE NOCASE ASC",
+                libsql::params![],
+            )
+            .await
+            .context("Failed to list contacts")?;
+
+        let mut contacts = Vec::new();
+        while let Some(row) = rows.next().await? {
+            contacts.push(DbContact {
+                id: row.get(0)?,
+                account_id: row.get(1)?,
+                address: row.get(2)?,
+                display_name: row.get(3)?,
+                times_seen: row.get(4)?,
+                last_seen_date: row.get(5)?,
+            });
+        }
+
+        Ok(contacts)
+    }
+
+    /// Manually add a contact from the Contacts view's "add" action, as opposed to
+    /// [`Self::upsert_contact`] which records a sighting during mail sync.
+    pub async fn add_contact(&self, address: &str, display_name: Option<&str>) -> Result<i64> {
+        let address = address.to_lowercase();
+        self.conn
+            .execute(
+                "INSERT INTO contacts (account_id, address, display_name, times_seen, last_seen_date)
+                 VALUES (NULL, ?1, ?2, 0, '')",
+                libsql::params![address.as_str(), display_name],
+            )
+            .await
+            .context("Failed to add contact")?;
+
+        let mut rows = self
+            .conn
+            .query("SELECT last_insert_rowid()", libsql::params![])
+            .await
+            .context("Failed to read new contact id")?;
+        let id: i64 = rows
+            .next()
+            .await?
+            .context("last_insert_rowid() returned no row")?
+            .get(0)?;
+        Ok(id)
+    }
+
+    /// Remove a contact from the address book by id, for the Contacts view's "delete" action.
+    pub async fn delete_contact(&self, id: i64) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM contacts WHERE id = ?1", libsql::params![id])
+            .await
+            .context("Failed to delete contact")?;
+        Ok(())
+    }
+
+    /// Insert a new email into the database
+    pub async fn insert_email(&self, email: &DbEmail) -> Result<i64> {
+        self.conn
+            .execute(
+                "INSERT INTO emails (
+                    from_address, to_addresses, cc_addresses, bcc_addresses,
+                    subject, body, body_html, preview, date, status, is_flagged,
+                    folder, thread_id, account_id, message_id, imap_uid,
+                    in_reply_to, \"references\", modseq, pgp_status, list_headers, headers, has_attachment
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23)",
+                libsql::params![
+                    email.from_address.as_str(),
+                    email.to_addresses.as_str(),
+                    email.cc_addresses.as_deref(),
+                    email.bcc_addresses.as_deref(),
                     email.subject.as_str(),
                     email.body.as_str(),
+                    email.body_html.as_deref(),
                     email.preview.as_str(),
                     email.date.as_str(),
                     email.status.as_str(),
@@ -413,21 +1214,202 @@ impl EmailDatabase {
                     email.account_id,
                     email.message_id.as_deref(),
                     email.imap_uid,
+                    email.in_reply_to.as_deref(),
+                    email.references.as_deref(),
+                    email.modseq,
+                    email.pgp_status.as_deref(),
+                    email.list_headers.as_deref(),
+                    email.headers.as_deref(),
+                    email.has_attachment as i64,
                 ],
             )
             .await
             .context("Failed to insert email")?;
 
+        self.upsert_contacts_from_email(email).await?;
+
         Ok(self.conn.last_insert_rowid())
     }
 
+    /// Insert many emails in a single transaction, e.g. when ingesting a freshly-synced folder.
+    ///
+    /// Wrapping the batch in `BEGIN IMMEDIATE ... COMMIT` means one fsync for the whole batch
+    /// instead of one per message, and that a failure partway through leaves the table exactly
+    /// as it was before the call rather than half-synced. Returns the inserted row ids in the
+    /// same order as `emails`.
+    pub async fn insert_emails_batch(&self, emails: &[DbEmail]) -> Result<Vec<i64>> {
+        if emails.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.conn
+            .execute("BEGIN IMMEDIATE", ())
+            .await
+            .context("Failed to start batch insert transaction")?;
+
+        let mut ids = Vec::with_capacity(emails.len());
+        for email in emails {
+            let result = self
+                .conn
+                .execute(
+                    "INSERT INTO emails (
+                        from_address, to_addresses, cc_addresses, bcc_addresses,
+                        subject, body, body_html, preview, date, status, is_flagged,
+                        folder, thread_id, account_id, message_id, imap_uid,
+                        in_reply_to, \"references\", modseq, pgp_status, list_headers, headers, has_attachment
+                     ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23)",
+                    libsql::params![
+                        email.from_address.as_str(),
+                        email.to_addresses.as_str(),
+                        email.cc_addresses.as_deref(),
+                        email.bcc_addresses.as_deref(),
+                        email.subject.as_str(),
+                        email.body.as_str(),
+                        email.body_html.as_deref(),
+                        email.preview.as_str(),
+                        email.date.as_str(),
+                        email.status.as_str(),
+                        email.is_flagged as i64,
+                        email.folder.as_str(),
+                        email.thread_id.as_deref(),
+                        email.account_id,
+                        email.message_id.as_deref(),
+                        email.imap_uid,
+                        email.in_reply_to.as_deref(),
+                        email.references.as_deref(),
+                        email.modseq,
+                        email.pgp_status.as_deref(),
+                        email.list_headers.as_deref(),
+                        email.headers.as_deref(),
+                        email.has_attachment as i64,
+                    ],
+                )
+                .await;
+
+            match result {
+                Ok(_) => ids.push(self.conn.last_insert_rowid()),
+                Err(e) => {
+                    self.conn
+                        .execute("ROLLBACK", ())
+                        .await
+                        .context("Failed to roll back batch insert transaction")?;
+                    return Err(e).context("Failed to insert email in batch");
+                }
+            }
+
+            if let Err(e) = self.upsert_contacts_from_email(email).await {
+                self.conn
+                    .execute("ROLLBACK", ())
+                    .await
+                    .context("Failed to roll back batch insert transaction")?;
+                return Err(e);
+            }
+        }
+
+        self.conn
+            .execute("COMMIT", ())
+            .await
+            .context("Failed to commit batch insert transaction")?;
+
+        Ok(ids)
+    }
+
+    /// Insert `email`, or update it in place if a row with the same `(account_id, message_id)`
+    /// already exists (per `idx_emails_account_message_unique`).
+    ///
+    /// Only server-side fields are refreshed on conflict (`imap_uid`, `folder`, `thread_id`,
+    /// `in_reply_to`, `references`, `modseq`, `pgp_status`, `list_headers`, `headers`,
+    /// `has_attachment`, `body_html`); `status` and `is_flagged` are left untouched so a
+    /// re-sync can't clobber a message the user already read or flagged locally. Emails without
+    /// a `message_id` can't be deduplicated and are always plainly inserted.
+    pub async fn upsert_email(&self, email: &DbEmail) -> Result<UpsertOutcome> {
+        let Some(message_id) = email.message_id.as_deref() else {
+            return Ok(UpsertOutcome::Inserted(self.insert_email(email).await?));
+        };
+
+        let mut rows = if let Some(account_id) = email.account_id {
+            self.conn
+                .query(
+                    "SELECT id FROM emails WHERE account_id = ?1 AND message_id = ?2",
+                    libsql::params![account_id, message_id],
+                )
+                .await
+        } else {
+            self.conn
+                .query(
+                    "SELECT id FROM emails WHERE account_id IS NULL AND message_id = ?1",
+                    libsql::params![message_id],
+                )
+                .await
+        }
+        .context("Failed to check for an existing email before upsert")?;
+        let existing_id: Option<i64> = match rows.next().await? {
+            Some(row) => Some(row.get(0)?),
+            None => None,
+        };
+
+        self.conn
+            .execute(
+                "INSERT INTO emails (
+                    from_address, to_addresses, cc_addresses, bcc_addresses,
+                    subject, body, body_html, preview, date, status, is_flagged,
+                    folder, thread_id, account_id, message_id, imap_uid,
+                    in_reply_to, \"references\", modseq, pgp_status, list_headers, headers, has_attachment
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23)
+                 ON CONFLICT(account_id, message_id) WHERE message_id IS NOT NULL DO UPDATE SET
+                    imap_uid = excluded.imap_uid,
+                    folder = excluded.folder,
+                    thread_id = excluded.thread_id,
+                    in_reply_to = excluded.in_reply_to,
+                    \"references\" = excluded.\"references\",
+                    modseq = excluded.modseq,
+                    pgp_status = excluded.pgp_status,
+                    list_headers = excluded.list_headers,
+                    headers = excluded.headers,
+                    has_attachment = excluded.has_attachment,
+                    body_html = excluded.body_html",
+                libsql::params![
+                    email.from_address.as_str(),
+                    email.to_addresses.as_str(),
+                    email.cc_addresses.as_deref(),
+                    email.bcc_addresses.as_deref(),
+                    email.subject.as_str(),
+                    email.body.as_str(),
+                    email.body_html.as_deref(),
+                    email.preview.as_str(),
+                    email.date.as_str(),
+                    email.status.as_str(),
+                    email.is_flagged as i64,
+                    email.folder.as_str(),
+                    email.thread_id.as_deref(),
+                    email.account_id,
+                    email.message_id.as_deref(),
+                    email.imap_uid,
+                    email.in_reply_to.as_deref(),
+                    email.references.as_deref(),
+                    email.modseq,
+                    email.pgp_status.as_deref(),
+                    email.list_headers.as_deref(),
+                    email.headers.as_deref(),
+                    email.has_attachment as i64,
+                ],
+            )
+            .await
+            .context("Failed to upsert email")?;
+
+        Ok(match existing_id {
+            Some(id) => UpsertOutcome::Updated(id),
+            None => UpsertOutcome::Inserted(self.conn.last_insert_rowid()),
+        })
+    }
+
     /// Get all emails from a specific folder
     pub async fn get_emails_by_folder(&self, folder: &str) -> Result<Vec<DbEmail>> {
         let mut rows = self
             .conn
             .query(
                 "SELECT id, from_address, to_addresses, cc_addresses, bcc_addresses,
-                        subject, body, preview, date, status, is_flagged, folder, thread_id, account_id, message_id, imap_uid
+                        subject, body, body_html, preview, date, status, is_flagged, folder, thread_id, account_id, message_id, imap_uid, in_reply_to, \"references\", modseq, pgp_status, list_headers, headers, has_attachment
                  FROM emails
                  WHERE folder = ?1 AND status != 'deleted'
                  ORDER BY date DESC",
@@ -446,15 +1428,23 @@ impl EmailDatabase {
                 bcc_addresses: row.get(4)?,
                 subject: row.get(5)?,
                 body: row.get(6)?,
-                preview: row.get(7)?,
-                date: row.get(8)?,
-                status: EmailStatus::from_str(&row.get::<String>(9)?),
-                is_flagged: row.get::<i64>(10)? != 0,
-                folder: row.get(11)?,
-                thread_id: row.get(12)?,
-                account_id: row.get(13)?,
-                message_id: row.get(14)?,
-                imap_uid: row.get(15)?,
+                body_html: row.get(7)?,
+                preview: row.get(8)?,
+                date: row.get(9)?,
+                status: EmailStatus::from_str(&row.get::<String>(10)?),
+                is_flagged: row.get::<i64>(11)? != 0,
+                folder: row.get(12)?,
+                thread_id: row.get(13)?,
+                account_id: row.get(14)?,
+                message_id: row.get(15)?,
+                imap_uid: row.get(16)?,
+                in_reply_to: row.get(17)?,
+                references: row.get(18)?,
+                modseq: row.get(19)?,
+                pgp_status: row.get(20)?,
+                list_headers: row.get(21)?,
+                headers: row.get(22)?,
+                has_attachment: row.get::<i64>(23)? != 0,
             });
         }
 
@@ -467,7 +1457,7 @@ impl EmailDatabase {
             .conn
             .query(
                 "SELECT id, from_address, to_addresses, cc_addresses, bcc_addresses,
-                        subject, body, preview, date, status, is_flagged, folder, thread_id, account_id, message_id, imap_uid
+                        subject, body, body_html, preview, date, status, is_flagged, folder, thread_id, account_id, message_id, imap_uid, in_reply_to, \"references\", modseq, pgp_status, list_headers, headers, has_attachment
                  FROM emails
                  WHERE id = ?1",
                 libsql::params![id],
@@ -484,15 +1474,125 @@ impl EmailDatabase {
                 bcc_addresses: row.get(4)?,
                 subject: row.get(5)?,
                 body: row.get(6)?,
-                preview: row.get(7)?,
-                date: row.get(8)?,
-                status: EmailStatus::from_str(&row.get::<String>(9)?),
-                is_flagged: row.get::<i64>(10)? != 0,
-                folder: row.get(11)?,
-                thread_id: row.get(12)?,
-                account_id: row.get(13)?,
-                message_id: row.get(14)?,
-                imap_uid: row.get(15)?,
+                body_html: row.get(7)?,
+                preview: row.get(8)?,
+                date: row.get(9)?,
+                status: EmailStatus::from_str(&row.get::<String>(10)?),
+                is_flagged: row.get::<i64>(11)? != 0,
+                folder: row.get(12)?,
+                thread_id: row.get(13)?,
+                account_id: row.get(14)?,
+                message_id: row.get(15)?,
+                imap_uid: row.get(16)?,
+                in_reply_to: row.get(17)?,
+                references: row.get(18)?,
+                modseq: row.get(19)?,
+                pgp_status: row.get(20)?,
+                list_headers: row.get(21)?,
+                headers: row.get(22)?,
+                has_attachment: row.get::<i64>(23)? != 0,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Store the attachments a message was parsed with (see [`crate::mime::parse_message`])
+    /// against `email_id`. Called once, right after the email itself is inserted.
+    pub async fn insert_attachments(
+        &self,
+        email_id: i64,
+        attachments: &[crate::mime::ParsedAttachment],
+    ) -> Result<()> {
+        if attachments.is_empty() {
+            return Ok(());
+        }
+
+        self.conn
+            .execute("BEGIN IMMEDIATE", ())
+            .await
+            .context("Failed to start attachment insert transaction")?;
+
+        for attachment in attachments {
+            let result = self
+                .conn
+                .execute(
+                    "INSERT INTO attachments (email_id, filename, content_type, size, data)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    libsql::params![
+                        email_id,
+                        attachment.filename.as_str(),
+                        attachment.content_type.as_str(),
+                        attachment.size(),
+                        attachment.data.clone(),
+                    ],
+                )
+                .await;
+
+            if let Err(e) = result {
+                self.conn.execute("ROLLBACK", ()).await.ok();
+                return Err(e).context("Failed to insert attachment");
+            }
+        }
+
+        self.conn
+            .execute("COMMIT", ())
+            .await
+            .context("Failed to commit attachment insert transaction")?;
+
+        Ok(())
+    }
+
+    /// List the attachments on a message, without pulling their bytes off disk. Used by
+    /// `EmailDetail` to render a manifest the user can pick from.
+    pub async fn get_attachment_manifest(&self, email_id: i64) -> Result<Vec<AttachmentMeta>> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT id, email_id, filename, content_type, size
+                 FROM attachments
+                 WHERE email_id = ?1
+                 ORDER BY id ASC",
+                libsql::params![email_id],
+            )
+            .await
+            .context("Failed to query attachment manifest")?;
+
+        let mut attachments = Vec::new();
+        while let Some(row) = rows.next().await? {
+            attachments.push(AttachmentMeta {
+                id: row.get(0)?,
+                email_id: row.get(1)?,
+                filename: row.get(2)?,
+                content_type: row.get(3)?,
+                size: row.get(4)?,
+            });
+        }
+
+        Ok(attachments)
+    }
+
+    /// Fetch one attachment's bytes, e.g. to save it to the downloads directory.
+    pub async fn get_attachment(&self, attachment_id: i64) -> Result<Option<DbAttachment>> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT id, email_id, filename, content_type, size, data
+                 FROM attachments
+                 WHERE id = ?1",
+                libsql::params![attachment_id],
+            )
+            .await
+            .context("Failed to query attachment")?;
+
+        if let Some(row) = rows.next().await? {
+            Ok(Some(DbAttachment {
+                id: row.get(0)?,
+                email_id: row.get(1)?,
+                filename: row.get(2)?,
+                content_type: row.get(3)?,
+                size: row.get(4)?,
+                data: row.get(5)?,
             }))
         } else {
             Ok(None)
@@ -565,22 +1665,39 @@ impl EmailDatabase {
     /// Archive email
     pub async fn archive_email(&self, id: i64) -> Result<()> {
         self.update_email_status(id, EmailStatus::Archived).await?;
-        self.move_email_to_folder(id, "archive").await?;
+
+        let mut rows = self
+            .conn
+            .query("SELECT account_id FROM emails WHERE id = ?1", libsql::params![id])
+            .await
+            .context("Failed to look up email's account for archiving")?;
+        let account_id: Option<i64> = match rows.next().await? {
+            Some(row) => row.get(0)?,
+            None => None,
+        };
+
+        let archive_folder = self
+            .resolve_special_use_folder(account_id, "\\Archive")
+            .await?
+            .unwrap_or_else(|| "archive".to_string());
+        self.move_email_to_folder(id, &archive_folder).await?;
         Ok(())
     }
 
     /// Save a draft
     pub async fn save_draft(&self, draft: &DbDraft) -> Result<i64> {
+        let attachments = Self::join_attachment_paths(&draft.attachments);
         if draft.id == 0 {
             // Insert new draft
             self.conn
                 .execute(
-                    "INSERT INTO drafts (recipients, subject, body, account_id) VALUES (?1, ?2, ?3, ?4)",
+                    "INSERT INTO drafts (recipients, subject, body, account_id, attachments) VALUES (?1, ?2, ?3, ?4, ?5)",
                     libsql::params![
                         draft.recipients.as_str(),
                         draft.subject.as_str(),
                         draft.body.as_str(),
                         draft.account_id,
+                        attachments,
                     ],
                 )
                 .await
@@ -590,8 +1707,8 @@ impl EmailDatabase {
             // Update existing draft
             self.conn
                 .execute(
-                    "UPDATE drafts SET recipients = ?1, subject = ?2, body = ?3, account_id = ?4, updated_at = CURRENT_TIMESTAMP WHERE id = ?5",
-                    libsql::params![draft.recipients.as_str(), draft.subject.as_str(), draft.body.as_str(), draft.account_id, draft.id],
+                    "UPDATE drafts SET recipients = ?1, subject = ?2, body = ?3, account_id = ?4, attachments = ?5, updated_at = CURRENT_TIMESTAMP WHERE id = ?6",
+                    libsql::params![draft.recipients.as_str(), draft.subject.as_str(), draft.body.as_str(), draft.account_id, attachments, draft.id],
                 )
                 .await
                 .context("Failed to update draft")?;
@@ -604,7 +1721,7 @@ impl EmailDatabase {
         let mut rows = self
             .conn
             .query(
-                "SELECT id, recipients, subject, body, created_at, updated_at, account_id
+                "SELECT id, recipients, subject, body, created_at, updated_at, account_id, attachments
                  FROM drafts
                  ORDER BY updated_at DESC",
                 (),
@@ -614,6 +1731,7 @@ impl EmailDatabase {
 
         let mut drafts = Vec::new();
         while let Some(row) = rows.next().await? {
+            let attachments: Option<String> = row.get(7)?;
             drafts.push(DbDraft {
                 id: row.get(0)?,
                 recipients: row.get(1)?,
@@ -622,12 +1740,32 @@ impl EmailDatabase {
                 created_at: row.get(4)?,
                 updated_at: row.get(5)?,
                 account_id: row.get(6)?,
+                attachments: Self::split_attachment_paths(attachments.as_deref()),
             });
         }
 
         Ok(drafts)
     }
 
+    /// Join draft attachment paths into the newline-separated form stored in the `attachments`
+    /// column; the inverse of [`Self::split_attachment_paths`].
+    fn join_attachment_paths(paths: &[PathBuf]) -> String {
+        paths
+            .iter()
+            .map(|p| p.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Parse the newline-separated `attachments` column back into paths, treating a missing or
+    /// empty value as no attachments.
+    fn split_attachment_paths(raw: Option<&str>) -> Vec<PathBuf> {
+        match raw {
+            Some(raw) if !raw.is_empty() => raw.lines().map(PathBuf::from).collect(),
+            _ => Vec::new(),
+        }
+    }
+
     /// Delete a draft
     pub async fn delete_draft(&self, id: i64) -> Result<()> {
         self.conn
@@ -643,7 +1781,8 @@ impl EmailDatabase {
         let mut rows = self
             .conn
             .query(
-                "SELECT id, name, display_order FROM folders ORDER BY display_order",
+                "SELECT id, account_id, name, parent_id, delimiter, special_use, display_order
+                 FROM folders ORDER BY display_order",
                 (),
             )
             .await
@@ -653,72 +1792,404 @@ impl EmailDatabase {
         while let Some(row) = rows.next().await? {
             folders.push(DbFolder {
                 id: row.get(0)?,
-                name: row.get(1)?,
-                display_order: row.get(2)?,
+                account_id: row.get(1)?,
+                name: row.get(2)?,
+                parent_id: row.get(3)?,
+                delimiter: row.get(4)?,
+                special_use: row.get(5)?,
+                display_order: row.get(6)?,
             });
         }
 
         Ok(folders)
     }
 
-    /// Search emails by query string (searches in subject, body, and from address)
-    pub async fn search_emails(&self, query: &str) -> Result<Vec<DbEmail>> {
-        let search_pattern = format!("%{}%", query);
-        let mut rows = self
-            .conn
-            .query(
-                "SELECT id, from_address, to_addresses, cc_addresses, bcc_addresses,
-                        subject, body, preview, date, status, is_flagged, folder, thread_id, account_id, message_id, imap_uid
-                 FROM emails
-                 WHERE (subject LIKE ?1 OR body LIKE ?1 OR from_address LIKE ?1)
-                   AND status != 'deleted'
-                 ORDER BY date DESC",
-                libsql::params![search_pattern.as_str()],
-            )
-            .await
-            .context("Failed to search emails")?;
+    /// Get the folder tree for one account (or the account-less/global folders when `None`),
+    /// in `display_order`.
+    pub async fn get_folders_by_account(&self, account_id: Option<i64>) -> Result<Vec<DbFolder>> {
+        let mut rows = if let Some(account_id) = account_id {
+            self.conn
+                .query(
+                    "SELECT id, account_id, name, parent_id, delimiter, special_use, display_order
+                     FROM folders WHERE account_id = ?1 ORDER BY display_order",
+                    libsql::params![account_id],
+                )
+                .await
+        } else {
+            self.conn
+                .query(
+                    "SELECT id, account_id, name, parent_id, delimiter, special_use, display_order
+                     FROM folders WHERE account_id IS NULL ORDER BY display_order",
+                    (),
+                )
+                .await
+        }
+        .context("Failed to query folders by account")?;
 
-        let mut emails = Vec::new();
+        let mut folders = Vec::new();
         while let Some(row) = rows.next().await? {
-            emails.push(DbEmail {
+            folders.push(DbFolder {
                 id: row.get(0)?,
-                from_address: row.get(1)?,
-                to_addresses: row.get(2)?,
-                cc_addresses: row.get(3)?,
-                bcc_addresses: row.get(4)?,
-                subject: row.get(5)?,
-                body: row.get(6)?,
-                preview: row.get(7)?,
-                date: row.get(8)?,
-                status: EmailStatus::from_str(&row.get::<String>(9)?),
-                is_flagged: row.get::<i64>(10)? != 0,
-                folder: row.get(11)?,
-                thread_id: row.get(12)?,
-                account_id: row.get(13)?,
-                message_id: row.get(14)?,
-                imap_uid: row.get(15)?,
+                account_id: row.get(1)?,
+                name: row.get(2)?,
+                parent_id: row.get(3)?,
+                delimiter: row.get(4)?,
+                special_use: row.get(5)?,
+                display_order: row.get(6)?,
             });
         }
 
-        Ok(emails)
+        Ok(folders)
     }
 
-    /// Get all emails from a specific folder and account
-    pub async fn get_emails_by_folder_and_account(&self, folder: &str, account_id: Option<i64>) -> Result<Vec<DbEmail>> {
-        let query = if account_id.is_some() {
-            "SELECT id, from_address, to_addresses, cc_addresses, bcc_addresses,
-                    subject, body, preview, date, status, is_flagged, folder, thread_id, account_id, message_id, imap_uid
-             FROM emails
-             WHERE folder = ?1 AND account_id = ?2 AND status != 'deleted'
-             ORDER BY date DESC"
-        } else {
-            "SELECT id, from_address, to_addresses, cc_addresses, bcc_addresses,
-                    subject, body, preview, date, status, is_flagged, folder, thread_id, account_id, message_id, imap_uid
-             FROM emails
-             WHERE folder = ?1 AND account_id IS NULL AND status != 'deleted'
-             ORDER BY date DESC"
-        };
-
+    /// Upsert the mailboxes an IMAP `LIST` returned for `account_id`, keyed by name (folders are
+    /// cached flat; IMAP's delimiter-separated names aren't turned into `parent_id` nesting
+    /// here). Each tuple is `(name, delimiter, special_use)`. Returns the account's full,
+    /// up-to-date folder set in `display_order` afterward.
+    pub async fn sync_folders_from_imap(
+        &self,
+        account_id: i64,
+        folders: &[(String, String, Option<String>)],
+    ) -> Result<Vec<DbFolder>> {
+        for (order, (name, delimiter, special_use)) in folders.iter().enumerate() {
+            let mut rows = self
+                .conn
+                .query(
+                    "SELECT id FROM folders WHERE account_id = ?1 AND parent_id IS NULL AND name = ?2",
+                    libsql::params![account_id, name.as_str()],
+                )
+                .await
+                .context("Failed to check for an existing folder")?;
+            let existing_id: Option<i64> = match rows.next().await? {
+                Some(row) => Some(row.get(0)?),
+                None => None,
+            };
+
+            if let Some(id) = existing_id {
+                self.conn
+                    .execute(
+                        "UPDATE folders SET delimiter = ?1, special_use = ?2, display_order = ?3 WHERE id = ?4",
+                        libsql::params![delimiter.as_str(), special_use.as_deref(), order as i64, id],
+                    )
+                    .await
+                    .context("Failed to update folder")?;
+            } else {
+                self.conn
+                    .execute(
+                        "INSERT INTO folders (account_id, name, parent_id, delimiter, special_use, display_order)
+                         VALUES (?1, ?2, NULL, ?3, ?4, ?5)",
+                        libsql::params![account_id, name.as_str(), delimiter.as_str(), special_use.as_deref(), order as i64],
+                    )
+                    .await
+                    .context("Failed to insert folder")?;
+            }
+        }
+
+        self.get_folders_by_account(Some(account_id)).await
+    }
+
+    /// Create a folder (optionally nested under `folder.parent_id`) and return its id.
+    pub async fn create_folder(&self, folder: &DbFolder) -> Result<i64> {
+        self.conn
+            .execute(
+                "INSERT INTO folders (account_id, name, parent_id, delimiter, special_use, display_order)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                libsql::params![
+                    folder.account_id,
+                    folder.name.as_str(),
+                    folder.parent_id,
+                    folder.delimiter.as_str(),
+                    folder.special_use.as_deref(),
+                    folder.display_order,
+                ],
+            )
+            .await
+            .context("Failed to create folder")?;
+
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Rename `folder_id` to `new_name`, cascading the rename to every cached email filed under
+    /// its old name for the same account.
+    pub async fn rename_folder(&self, folder_id: i64, new_name: &str) -> Result<()> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT account_id, name FROM folders WHERE id = ?1",
+                libsql::params![folder_id],
+            )
+            .await
+            .context("Failed to look up folder to rename")?;
+        let Some(row) = rows.next().await? else {
+            return Err(anyhow::anyhow!("Folder {folder_id} not found"));
+        };
+        let account_id: Option<i64> = row.get(0)?;
+        let old_name: String = row.get(1)?;
+
+        self.conn
+            .execute(
+                "UPDATE folders SET name = ?1 WHERE id = ?2",
+                libsql::params![new_name, folder_id],
+            )
+            .await
+            .context("Failed to rename folder")?;
+
+        let update_emails = if account_id.is_some() {
+            "UPDATE emails SET folder = ?1 WHERE folder = ?2 AND account_id = ?3"
+        } else {
+            "UPDATE emails SET folder = ?1 WHERE folder = ?2 AND account_id IS NULL"
+        };
+        if let Some(acc_id) = account_id {
+            self.conn
+                .execute(update_emails, libsql::params![new_name, old_name.as_str(), acc_id])
+                .await
+        } else {
+            self.conn
+                .execute(update_emails, libsql::params![new_name, old_name.as_str()])
+                .await
+        }
+        .context("Failed to cascade folder rename to emails")?;
+
+        Ok(())
+    }
+
+    /// Delete a folder, re-homing its children under its own parent and leaving any emails
+    /// filed under its name as-is (the folder name becomes a stale label rather than vanishing
+    /// emails).
+    pub async fn delete_folder(&self, folder_id: i64) -> Result<()> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT parent_id FROM folders WHERE id = ?1",
+                libsql::params![folder_id],
+            )
+            .await
+            .context("Failed to look up folder to delete")?;
+        let Some(row) = rows.next().await? else {
+            return Err(anyhow::anyhow!("Folder {folder_id} not found"));
+        };
+        let parent_id: Option<i64> = row.get(0)?;
+
+        self.conn
+            .execute(
+                "UPDATE folders SET parent_id = ?1 WHERE parent_id = ?2",
+                libsql::params![parent_id, folder_id],
+            )
+            .await
+            .context("Failed to re-home child folders")?;
+
+        self.conn
+            .execute("DELETE FROM folders WHERE id = ?1", libsql::params![folder_id])
+            .await
+            .context("Failed to delete folder")?;
+
+        Ok(())
+    }
+
+    /// Resolve the folder name tagged `special_use` for `account_id` (or the account-less
+    /// folders when `None`), e.g. `\Archive`. `None` if the account has no such folder.
+    pub(crate) async fn resolve_special_use_folder(&self, account_id: Option<i64>, special_use: &str) -> Result<Option<String>> {
+        let mut rows = if let Some(account_id) = account_id {
+            self.conn
+                .query(
+                    "SELECT name FROM folders WHERE account_id = ?1 AND special_use = ?2",
+                    libsql::params![account_id, special_use],
+                )
+                .await
+        } else {
+            self.conn
+                .query(
+                    "SELECT name FROM folders WHERE account_id IS NULL AND special_use = ?1",
+                    libsql::params![special_use],
+                )
+                .await
+        }
+        .context("Failed to resolve special-use folder")?;
+
+        match rows.next().await? {
+            Some(row) => Ok(Some(row.get(0)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Search emails with the FTS5-backed `emails_fts` index, ranked by `bm25` relevance.
+    ///
+    /// `query` is compiled through [`crate::search::compile_query`], which supports bare
+    /// (prefix-matched) terms, `field:value` column filters, quoted phrases, and `AND`/`OR`/`NOT`.
+    pub async fn search_emails(&self, query: &str) -> Result<Vec<DbEmail>> {
+        let match_expr = crate::search::compile_query(query);
+        if match_expr.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT e.id, e.from_address, e.to_addresses, e.cc_addresses, e.bcc_addresses,
+                        e.subject, e.body, e.body_html, e.preview, e.date, e.status, e.is_flagged, e.folder,
+                        e.thread_id, e.account_id, e.message_id, e.imap_uid, e.in_reply_to, e.\"references\", e.modseq, e.pgp_status,
+                        e.list_headers, e.headers, e.has_attachment
+                 FROM emails_fts
+                 JOIN emails e ON e.id = emails_fts.rowid
+                 WHERE emails_fts MATCH ?1
+                   AND e.status != 'deleted'
+                 ORDER BY bm25(emails_fts)",
+                libsql::params![match_expr.as_str()],
+            )
+            .await
+            .context("Failed to search emails")?;
+
+        let mut emails = Vec::new();
+        while let Some(row) = rows.next().await? {
+            emails.push(DbEmail {
+                id: row.get(0)?,
+                from_address: row.get(1)?,
+                to_addresses: row.get(2)?,
+                cc_addresses: row.get(3)?,
+                bcc_addresses: row.get(4)?,
+                subject: row.get(5)?,
+                body: row.get(6)?,
+                body_html: row.get(7)?,
+                preview: row.get(8)?,
+                date: row.get(9)?,
+                status: EmailStatus::from_str(&row.get::<String>(10)?),
+                is_flagged: row.get::<i64>(11)? != 0,
+                folder: row.get(12)?,
+                thread_id: row.get(13)?,
+                account_id: row.get(14)?,
+                message_id: row.get(15)?,
+                imap_uid: row.get(16)?,
+                in_reply_to: row.get(17)?,
+                references: row.get(18)?,
+                modseq: row.get(19)?,
+                pgp_status: row.get(20)?,
+                list_headers: row.get(21)?,
+                headers: row.get(22)?,
+                has_attachment: row.get::<i64>(23)? != 0,
+            });
+        }
+
+        Ok(emails)
+    }
+
+    /// Run a [structured query DSL](crate::search::parse_query) search: free text is matched via
+    /// FTS5, `is:`/`folder:`/`status:`/`before:`/`after:` become plain `WHERE` predicates. A
+    /// query with only structured predicates and no free text skips FTS5 entirely. `order`
+    /// chooses bm25 relevance vs. `date` ordering; relevance only applies when there's free text
+    /// to rank, so a structured-only query is always date-ordered regardless of `order`.
+    pub async fn search_emails_query(&self, query: &str, order: SearchOrder) -> Result<Vec<DbEmail>> {
+        let parsed = crate::search::parse_query(query);
+
+        let is_flagged_param = parsed.is_flagged.map(|b| b as i64);
+        let folder_param = parsed.folder.as_deref();
+        let status_param = parsed.status.as_deref();
+        let before_param = parsed.before.as_deref();
+        let after_param = parsed.after.as_deref();
+
+        let mut rows = if parsed.fts_match.is_empty() {
+            self.conn
+                .query(
+                    "SELECT e.id, e.from_address, e.to_addresses, e.cc_addresses, e.bcc_addresses,
+                            e.subject, e.body, e.body_html, e.preview, e.date, e.status, e.is_flagged, e.folder,
+                            e.thread_id, e.account_id, e.message_id, e.imap_uid, e.in_reply_to, e.\"references\", e.modseq, e.pgp_status,
+                            e.list_headers, e.headers, e.has_attachment
+                     FROM emails e
+                     WHERE e.status != 'deleted'
+                       AND (?1 IS NULL OR e.is_flagged = ?1)
+                       AND (?2 IS NULL OR e.folder = ?2)
+                       AND (?3 IS NULL OR e.status = ?3)
+                       AND (?4 IS NULL OR e.date < ?4)
+                       AND (?5 IS NULL OR e.date > ?5)
+                     ORDER BY e.date DESC",
+                    libsql::params![is_flagged_param, folder_param, status_param, before_param, after_param],
+                )
+                .await
+        } else {
+            let order_clause = match order {
+                SearchOrder::Rank => "bm25(emails_fts)",
+                SearchOrder::Date => "e.date DESC",
+            };
+            let sql = format!(
+                "SELECT e.id, e.from_address, e.to_addresses, e.cc_addresses, e.bcc_addresses,
+                        e.subject, e.body, e.body_html, e.preview, e.date, e.status, e.is_flagged, e.folder,
+                        e.thread_id, e.account_id, e.message_id, e.imap_uid, e.in_reply_to, e.\"references\", e.modseq, e.pgp_status,
+                        e.list_headers, e.headers, e.has_attachment
+                 FROM emails_fts
+                 JOIN emails e ON e.id = emails_fts.rowid
+                 WHERE emails_fts MATCH ?1
+                   AND e.status != 'deleted'
+                   AND (?2 IS NULL OR e.is_flagged = ?2)
+                   AND (?3 IS NULL OR e.folder = ?3)
+                   AND (?4 IS NULL OR e.status = ?4)
+                   AND (?5 IS NULL OR e.date < ?5)
+                   AND (?6 IS NULL OR e.date > ?6)
+                 ORDER BY {order_clause}"
+            );
+            self.conn
+                .query(
+                    &sql,
+                    libsql::params![
+                        parsed.fts_match.as_str(),
+                        is_flagged_param,
+                        folder_param,
+                        status_param,
+                        before_param,
+                        after_param
+                    ],
+                )
+                .await
+        }
+        .context("Failed to search emails")?;
+
+        let mut emails = Vec::new();
+        while let Some(row) = rows.next().await? {
+            emails.push(DbEmail {
+                id: row.get(0)?,
+                from_address: row.get(1)?,
+                to_addresses: row.get(2)?,
+                cc_addresses: row.get(3)?,
+                bcc_addresses: row.get(4)?,
+                subject: row.get(5)?,
+                body: row.get(6)?,
+                body_html: row.get(7)?,
+                preview: row.get(8)?,
+                date: row.get(9)?,
+                status: EmailStatus::from_str(&row.get::<String>(10)?),
+                is_flagged: row.get::<i64>(11)? != 0,
+                folder: row.get(12)?,
+                thread_id: row.get(13)?,
+                account_id: row.get(14)?,
+                message_id: row.get(15)?,
+                imap_uid: row.get(16)?,
+                in_reply_to: row.get(17)?,
+                references: row.get(18)?,
+                modseq: row.get(19)?,
+                pgp_status: row.get(20)?,
+                list_headers: row.get(21)?,
+                headers: row.get(22)?,
+                has_attachment: row.get::<i64>(23)? != 0,
+            });
+        }
+
+        Ok(emails)
+    }
+
+    /// Get all emails from a specific folder and account
+    pub async fn get_emails_by_folder_and_account(&self, folder: &str, account_id: Option<i64>) -> Result<Vec<DbEmail>> {
+        let query = if account_id.is_some() {
+            "SELECT id, from_address, to_addresses, cc_addresses, bcc_addresses,
+                    subject, body, body_html, preview, date, status, is_flagged, folder, thread_id, account_id, message_id, imap_uid, in_reply_to, \"references\", modseq, pgp_status, list_headers, headers, has_attachment
+             FROM emails
+             WHERE folder = ?1 AND account_id = ?2 AND status != 'deleted'
+             ORDER BY date DESC"
+        } else {
+            "SELECT id, from_address, to_addresses, cc_addresses, bcc_addresses,
+                    subject, body, body_html, preview, date, status, is_flagged, folder, thread_id, account_id, message_id, imap_uid, in_reply_to, \"references\", modseq, pgp_status, list_headers, headers, has_attachment
+             FROM emails
+             WHERE folder = ?1 AND account_id IS NULL AND status != 'deleted'
+             ORDER BY date DESC"
+        };
+
         let mut rows = if let Some(acc_id) = account_id {
             self.conn
                 .query(query, libsql::params![folder, acc_id])
@@ -741,15 +2212,23 @@ impl EmailDatabase {
                 bcc_addresses: row.get(4)?,
                 subject: row.get(5)?,
                 body: row.get(6)?,
-                preview: row.get(7)?,
-                date: row.get(8)?,
-                status: EmailStatus::from_str(&row.get::<String>(9)?),
-                is_flagged: row.get::<i64>(10)? != 0,
-                folder: row.get(11)?,
-                thread_id: row.get(12)?,
-                account_id: row.get(13)?,
-                message_id: row.get(14)?,
-                imap_uid: row.get(15)?,
+                body_html: row.get(7)?,
+                preview: row.get(8)?,
+                date: row.get(9)?,
+                status: EmailStatus::from_str(&row.get::<String>(10)?),
+                is_flagged: row.get::<i64>(11)? != 0,
+                folder: row.get(12)?,
+                thread_id: row.get(13)?,
+                account_id: row.get(14)?,
+                message_id: row.get(15)?,
+                imap_uid: row.get(16)?,
+                in_reply_to: row.get(17)?,
+                references: row.get(18)?,
+                modseq: row.get(19)?,
+                pgp_status: row.get(20)?,
+                list_headers: row.get(21)?,
+                headers: row.get(22)?,
+                has_attachment: row.get::<i64>(23)? != 0,
             });
         }
 
@@ -762,8 +2241,8 @@ impl EmailDatabase {
             // Insert new account
             self.conn
                 .execute(
-                    "INSERT INTO accounts (name, email, provider, is_default, color, display_order)
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    "INSERT INTO accounts (name, email, provider, is_default, color, display_order, backend_kind, backend_path)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
                     libsql::params![
                         account.name.as_str(),
                         account.email.as_str(),
@@ -771,6 +2250,8 @@ impl EmailDatabase {
                         account.is_default as i64,
                         account.color.as_deref(),
                         account.display_order,
+                        account.backend_kind.as_str(),
+                        account.backend_path.as_deref(),
                     ],
                 )
                 .await
@@ -780,8 +2261,9 @@ impl EmailDatabase {
             // Update existing account
             self.conn
                 .execute(
-                    "UPDATE accounts SET name = ?1, email = ?2, provider = ?3, is_default = ?4, 
-                     color = ?5, display_order = ?6, updated_at = CURRENT_TIMESTAMP WHERE id = ?7",
+                    "UPDATE accounts SET name = ?1, email = ?2, provider = ?3, is_default = ?4,
+                     color = ?5, display_order = ?6, backend_kind = ?7, backend_path = ?8,
+                     updated_at = CURRENT_TIMESTAMP WHERE id = ?9",
                     libsql::params![
                         account.name.as_str(),
                         account.email.as_str(),
@@ -789,6 +2271,8 @@ impl EmailDatabase {
                         account.is_default as i64,
                         account.color.as_deref(),
                         account.display_order,
+                        account.backend_kind.as_str(),
+                        account.backend_path.as_deref(),
                         account.id,
                     ],
                 )
@@ -803,7 +2287,7 @@ impl EmailDatabase {
         let mut rows = self
             .conn
             .query(
-                "SELECT id, name, email, provider, is_default, color, display_order
+                "SELECT id, name, email, provider, is_default, color, display_order, backend_kind, backend_path
                  FROM accounts
                  ORDER BY display_order",
                 (),
@@ -821,6 +2305,8 @@ impl EmailDatabase {
                 is_default: row.get::<i64>(4)? != 0,
                 color: row.get(5)?,
                 display_order: row.get(6)?,
+                backend_kind: row.get(7)?,
+                backend_path: row.get(8)?,
             });
         }
 
@@ -832,7 +2318,7 @@ impl EmailDatabase {
         let mut rows = self
             .conn
             .query(
-                "SELECT id, name, email, provider, is_default, color, display_order
+                "SELECT id, name, email, provider, is_default, color, display_order, backend_kind, backend_path
                  FROM accounts
                  WHERE id = ?1",
                 libsql::params![id],
@@ -849,6 +2335,8 @@ impl EmailDatabase {
                 is_default: row.get::<i64>(4)? != 0,
                 color: row.get(5)?,
                 display_order: row.get(6)?,
+                backend_kind: row.get(7)?,
+                backend_path: row.get(8)?,
             }))
         } else {
             Ok(None)
@@ -864,6 +2352,191 @@ impl EmailDatabase {
         Ok(())
     }
 
+    /// Get the enabled rules for `account_id` (or the account-less rules when `None`), in the
+    /// order they should be evaluated.
+    pub async fn get_enabled_rules(&self, account_id: Option<i64>) -> Result<Vec<DbInboxRule>> {
+        let mut rows = if let Some(account_id) = account_id {
+            self.conn
+                .query(
+                    "SELECT id, name, condition_type, condition_value, action_type, action_value,
+                            enabled, account_id, display_order, stop_on_match
+                     FROM inbox_rules WHERE account_id = ?1 AND enabled = 1
+                     ORDER BY display_order",
+                    libsql::params![account_id],
+                )
+                .await
+        } else {
+            self.conn
+                .query(
+                    "SELECT id, name, condition_type, condition_value, action_type, action_value,
+                            enabled, account_id, display_order, stop_on_match
+                     FROM inbox_rules WHERE account_id IS NULL AND enabled = 1
+                     ORDER BY display_order",
+                    (),
+                )
+                .await
+        }
+        .context("Failed to query inbox rules")?;
+
+        let mut rules = Vec::new();
+        while let Some(row) = rows.next().await? {
+            rules.push(DbInboxRule {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                condition_type: row.get(2)?,
+                condition_value: row.get(3)?,
+                action_type: row.get(4)?,
+                action_value: row.get(5)?,
+                enabled: row.get::<i64>(6)? != 0,
+                account_id: row.get(7)?,
+                display_order: row.get(8)?,
+                stop_on_match: row.get::<i64>(9)? != 0,
+            });
+        }
+
+        Ok(rules)
+    }
+
+    /// Evaluate `account_id`'s enabled rules against `email_id` in `display_order`, running
+    /// every matched rule's action. All actions run inside one transaction, so a message is
+    /// never left with e.g. its folder moved but its flag unset because a later action failed.
+    /// A rule with `stop_on_match` set ends evaluation after it matches.
+    pub async fn apply_rules(&self, email_id: i64, account_id: Option<i64>) -> Result<usize> {
+        let rules = self.get_enabled_rules(account_id).await?;
+        if rules.is_empty() {
+            return Ok(0);
+        }
+
+        let Some(email) = self.get_email_by_id(email_id).await? else {
+            return Ok(0);
+        };
+
+        self.conn
+            .execute("BEGIN IMMEDIATE", ())
+            .await
+            .context("Failed to start rule application transaction")?;
+
+        let mut applied = 0;
+        for rule in &rules {
+            if !rule_matches(rule, &email) {
+                continue;
+            }
+
+            if let Err(e) = self.run_rule_action(email_id, rule).await {
+                self.conn
+                    .execute("ROLLBACK", ())
+                    .await
+                    .context("Failed to roll back rule application transaction")?;
+                return Err(e).context("Failed to apply inbox rule");
+            }
+            applied += 1;
+
+            if rule.stop_on_match {
+                break;
+            }
+        }
+
+        self.conn
+            .execute("COMMIT", ())
+            .await
+            .context("Failed to commit rule application transaction")?;
+
+        Ok(applied)
+    }
+
+    /// Run the single action a matched rule specifies, reusing the same primitives the UI uses.
+    async fn run_rule_action(&self, email_id: i64, rule: &DbInboxRule) -> Result<()> {
+        match rule.action_type.as_str() {
+            "move_to_folder" => {
+                let folder = rule
+                    .action_value
+                    .as_deref()
+                    .context("move_to_folder rule is missing a target folder")?;
+                self.move_email_to_folder(email_id, folder).await
+            }
+            "set_flag" => {
+                self.toggle_email_flag(email_id).await.map(|_| ())
+            }
+            "mark_read" => self.update_email_status(email_id, EmailStatus::Read).await,
+            "archive" => self.archive_email(email_id).await,
+            "discard" => self.delete_email(email_id).await,
+            other => Err(anyhow::anyhow!("Unknown inbox rule action type: {other}")),
+        }
+    }
+
+    /// Execute the purely-local actions from a [`crate::sync::plan_sync`] plan inside one
+    /// transaction, so a partially-applied sync never leaves a message in a half-moved or
+    /// half-flagged state. `SyncAction::FetchNew` and `SyncAction::PushFlagsRemote` need to talk
+    /// to the server, so the sync loop handles those itself; this only counts them here.
+    /// With `dry_run` set, the same counts are computed but nothing is written.
+    pub async fn apply_actions(&self, actions: &[crate::sync::SyncAction], dry_run: bool) -> Result<ApplyOutcome> {
+        use crate::sync::SyncAction;
+
+        let mut outcome = ApplyOutcome::default();
+        if dry_run {
+            for action in actions {
+                match action {
+                    SyncAction::DeleteLocal { .. } => outcome.deleted += 1,
+                    SyncAction::MoveLocal { .. } => outcome.moved += 1,
+                    SyncAction::UpdateFlags { .. } => outcome.flags_updated += 1,
+                    SyncAction::FetchNew { .. } | SyncAction::PushFlagsRemote { .. } => {
+                        outcome.deferred_to_server += 1
+                    }
+                }
+            }
+            return Ok(outcome);
+        }
+
+        self.conn
+            .execute("BEGIN IMMEDIATE", ())
+            .await
+            .context("Failed to start apply_actions transaction")?;
+
+        for action in actions {
+            let result = match action {
+                SyncAction::DeleteLocal { id } => self.delete_email(*id).await.map(|_| outcome.deleted += 1),
+                SyncAction::MoveLocal { id, folder } => {
+                    self.move_email_to_folder(*id, folder).await.map(|_| outcome.moved += 1)
+                }
+                SyncAction::UpdateFlags { id, flagged, status } => {
+                    self.apply_flags(*id, *flagged, *status).await.map(|_| outcome.flags_updated += 1)
+                }
+                SyncAction::FetchNew { .. } | SyncAction::PushFlagsRemote { .. } => {
+                    outcome.deferred_to_server += 1;
+                    Ok(())
+                }
+            };
+
+            if let Err(e) = result {
+                self.conn
+                    .execute("ROLLBACK", ())
+                    .await
+                    .context("Failed to roll back apply_actions transaction")?;
+                return Err(e).context("Failed to apply sync action");
+            }
+        }
+
+        self.conn
+            .execute("COMMIT", ())
+            .await
+            .context("Failed to commit apply_actions transaction")?;
+
+        Ok(outcome)
+    }
+
+    /// Set `id`'s flag and status to exactly `flagged`/`status`, the way a remote flag pull
+    /// needs to (as opposed to [`toggle_email_flag`](Self::toggle_email_flag), which flips it).
+    async fn apply_flags(&self, id: i64, flagged: bool, status: EmailStatus) -> Result<()> {
+        self.conn
+            .execute(
+                "UPDATE emails SET is_flagged = ?1, status = ?2 WHERE id = ?3",
+                libsql::params![flagged as i64, status.as_str(), id],
+            )
+            .await
+            .context("Failed to apply remote flags")?;
+        Ok(())
+    }
+
     /// Clear all emails from the inbox folder (for development/testing)
     pub async fn clear_inbox(&self) -> Result<()> {
         self.conn
@@ -896,52 +2569,588 @@ impl EmailDatabase {
             Ok(false)
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::path::PathBuf;
-    use std::sync::atomic::{AtomicU64, Ordering};
+    /// Recompute `thread_id` for every message on `account_id` (or accountless messages when
+    /// `None`) using JWZ threading over `message_id`/`in_reply_to`/`references`. Returns how
+    /// many messages were assigned a thread.
+    pub async fn rebuild_threads(&self, account_id: Option<i64>) -> Result<usize> {
+        let query = if account_id.is_some() {
+            "SELECT id, message_id, in_reply_to, \"references\" FROM emails WHERE account_id = ?1"
+        } else {
+            "SELECT id, message_id, in_reply_to, \"references\" FROM emails WHERE account_id IS NULL"
+        };
 
-    static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+        let mut rows = if let Some(acc_id) = account_id {
+            self.conn.query(query, libsql::params![acc_id]).await
+        } else {
+            self.conn.query(query, ()).await
+        }
+        .context("Failed to query emails for threading")?;
 
-    async fn create_test_db() -> Result<EmailDatabase> {
-        let id = TEST_COUNTER.fetch_add(1, Ordering::SeqCst);
-        let path = PathBuf::from(format!("/tmp/test_tume_{}_{}.db", std::process::id(), id));
-        // Clean up if exists
-        let _ = std::fs::remove_file(&path);
-        EmailDatabase::new(Some(path)).await
+        let mut messages = Vec::new();
+        while let Some(row) = rows.next().await? {
+            messages.push(crate::threading::ThreadableMessage {
+                id: row.get(0)?,
+                message_id: row.get(1)?,
+                in_reply_to: row.get(2)?,
+                references: row.get(3)?,
+            });
+        }
+
+        let assignments = crate::threading::compute_threads(&messages);
+        for (id, thread_id) in &assignments {
+            self.conn
+                .execute(
+                    "UPDATE emails SET thread_id = ?1 WHERE id = ?2",
+                    libsql::params![thread_id.as_str(), *id],
+                )
+                .await
+                .context("Failed to update thread_id")?;
+        }
+
+        Ok(assignments.len())
     }
 
-    #[tokio::test]
-    async fn test_database_initialization() {
-        let db = create_test_db().await.unwrap();
-        let folders = db.get_folders().await.unwrap();
-        assert_eq!(folders.len(), 5);
-        assert_eq!(folders[0].name, "inbox");
+    /// Alias for [`rebuild_threads`](Self::rebuild_threads): recompute `thread_id` for every
+    /// message in `account_id` (backfilling after a bulk import, or once threading columns are
+    /// first populated for existing data).
+    pub async fn recompute_threads(&self, account_id: Option<i64>) -> Result<usize> {
+        self.rebuild_threads(account_id).await
     }
 
-    #[tokio::test]
-    async fn test_insert_and_get_email() {
-        let db = create_test_db().await.unwrap();
+    /// Get every message in a thread, oldest first.
+    pub async fn get_thread(&self, thread_id: &str) -> Result<Vec<DbEmail>> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT id, from_address, to_addresses, cc_addresses, bcc_addresses,
+                        subject, body, body_html, preview, date, status, is_flagged, folder, thread_id, account_id, message_id, imap_uid, in_reply_to, \"references\", modseq, pgp_status, list_headers, headers, has_attachment
+                 FROM emails
+                 WHERE thread_id = ?1 AND status != 'deleted'
+                 ORDER BY date ASC",
+                libsql::params![thread_id],
+            )
+            .await
+            .context("Failed to query thread")?;
 
-        let email = DbEmail {
-            id: 0,
-            from_address: "test@example.com".to_string(),
-            to_addresses: "recipient@example.com".to_string(),
-            cc_addresses: None,
-            bcc_addresses: None,
-            subject: "Test Subject".to_string(),
-            body: "Test body content".to_string(),
-            preview: "Test body content".to_string(),
-            date: "2026-01-12 12:00".to_string(),
+        let mut emails = Vec::new();
+        while let Some(row) = rows.next().await? {
+            emails.push(DbEmail {
+                id: row.get(0)?,
+                from_address: row.get(1)?,
+                to_addresses: row.get(2)?,
+                cc_addresses: row.get(3)?,
+                bcc_addresses: row.get(4)?,
+                subject: row.get(5)?,
+                body: row.get(6)?,
+                body_html: row.get(7)?,
+                preview: row.get(8)?,
+                date: row.get(9)?,
+                status: EmailStatus::from_str(&row.get::<String>(10)?),
+                is_flagged: row.get::<i64>(11)? != 0,
+                folder: row.get(12)?,
+                thread_id: row.get(13)?,
+                account_id: row.get(14)?,
+                message_id: row.get(15)?,
+                imap_uid: row.get(16)?,
+                in_reply_to: row.get(17)?,
+                references: row.get(18)?,
+                modseq: row.get(19)?,
+                pgp_status: row.get(20)?,
+                list_headers: row.get(21)?,
+                headers: row.get(22)?,
+                has_attachment: row.get::<i64>(23)? != 0,
+            });
+        }
+
+        Ok(emails)
+    }
+
+    /// Get the stored CONDSTORE/UIDVALIDITY state for `(account_id, folder)`, if a sync has
+    /// ever completed for it.
+    pub async fn get_sync_state(&self, account_id: Option<i64>, folder: &str) -> Result<Option<FolderSyncState>> {
+        let query = if account_id.is_some() {
+            "SELECT account_id, folder, uidvalidity, highest_modseq, last_seen_uid
+             FROM folder_sync_state WHERE account_id = ?1 AND folder = ?2"
+        } else {
+            "SELECT account_id, folder, uidvalidity, highest_modseq, last_seen_uid
+             FROM folder_sync_state WHERE account_id IS NULL AND folder = ?1"
+        };
+
+        let mut rows = if let Some(acc_id) = account_id {
+            self.conn.query(query, libsql::params![acc_id, folder]).await
+        } else {
+            self.conn.query(query, libsql::params![folder]).await
+        }
+        .context("Failed to query folder sync state")?;
+
+        if let Some(row) = rows.next().await? {
+            Ok(Some(FolderSyncState {
+                account_id: row.get(0)?,
+                folder: row.get(1)?,
+                uidvalidity: row.get(2)?,
+                highest_modseq: row.get(3)?,
+                last_seen_uid: row.get(4)?,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Insert or update the CONDSTORE/UIDVALIDITY state for `state.folder`.
+    pub async fn upsert_sync_state(&self, state: &FolderSyncState) -> Result<()> {
+        let exists = self.get_sync_state(state.account_id, &state.folder).await?.is_some();
+
+        if exists {
+            let query = if state.account_id.is_some() {
+                "UPDATE folder_sync_state
+                 SET uidvalidity = ?1, highest_modseq = ?2, last_seen_uid = ?3, updated_at = CURRENT_TIMESTAMP
+                 WHERE account_id = ?4 AND folder = ?5"
+            } else {
+                "UPDATE folder_sync_state
+                 SET uidvalidity = ?1, highest_modseq = ?2, last_seen_uid = ?3, updated_at = CURRENT_TIMESTAMP
+                 WHERE account_id IS NULL AND folder = ?4"
+            };
+
+            if let Some(acc_id) = state.account_id {
+                self.conn
+                    .execute(query, libsql::params![state.uidvalidity, state.highest_modseq, state.last_seen_uid, acc_id, state.folder.as_str()])
+                    .await
+            } else {
+                self.conn
+                    .execute(query, libsql::params![state.uidvalidity, state.highest_modseq, state.last_seen_uid, state.folder.as_str()])
+                    .await
+            }
+            .context("Failed to update folder sync state")?;
+        } else {
+            self.conn
+                .execute(
+                    "INSERT INTO folder_sync_state (account_id, folder, uidvalidity, highest_modseq, last_seen_uid)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    libsql::params![state.account_id, state.folder.as_str(), state.uidvalidity, state.highest_modseq, state.last_seen_uid],
+                )
+                .await
+                .context("Failed to insert folder sync state")?;
+        }
+
+        Ok(())
+    }
+
+    /// Get every message in `folder` whose `modseq` increased past `modseq`, for a
+    /// `CHANGEDSINCE <modseq>` incremental sync.
+    pub async fn emails_modified_since(&self, account_id: Option<i64>, folder: &str, modseq: i64) -> Result<Vec<DbEmail>> {
+        let query = if account_id.is_some() {
+            "SELECT id, from_address, to_addresses, cc_addresses, bcc_addresses,
+                    subject, body, body_html, preview, date, status, is_flagged, folder, thread_id, account_id, message_id, imap_uid, in_reply_to, \"references\", modseq, pgp_status, list_headers, headers, has_attachment
+             FROM emails
+             WHERE folder = ?1 AND account_id = ?2 AND modseq > ?3"
+        } else {
+            "SELECT id, from_address, to_addresses, cc_addresses, bcc_addresses,
+                    subject, body, body_html, preview, date, status, is_flagged, folder, thread_id, account_id, message_id, imap_uid, in_reply_to, \"references\", modseq, pgp_status, list_headers, headers, has_attachment
+             FROM emails
+             WHERE folder = ?1 AND account_id IS NULL AND modseq > ?2"
+        };
+
+        let mut rows = if let Some(acc_id) = account_id {
+            self.conn.query(query, libsql::params![folder, acc_id, modseq]).await
+        } else {
+            self.conn.query(query, libsql::params![folder, modseq]).await
+        }
+        .context("Failed to query modified emails")?;
+
+        let mut emails = Vec::new();
+        while let Some(row) = rows.next().await? {
+            emails.push(DbEmail {
+                id: row.get(0)?,
+                from_address: row.get(1)?,
+                to_addresses: row.get(2)?,
+                cc_addresses: row.get(3)?,
+                bcc_addresses: row.get(4)?,
+                subject: row.get(5)?,
+                body: row.get(6)?,
+                body_html: row.get(7)?,
+                preview: row.get(8)?,
+                date: row.get(9)?,
+                status: EmailStatus::from_str(&row.get::<String>(10)?),
+                is_flagged: row.get::<i64>(11)? != 0,
+                folder: row.get(12)?,
+                thread_id: row.get(13)?,
+                account_id: row.get(14)?,
+                message_id: row.get(15)?,
+                imap_uid: row.get(16)?,
+                in_reply_to: row.get(17)?,
+                references: row.get(18)?,
+                modseq: row.get(19)?,
+                pgp_status: row.get(20)?,
+                list_headers: row.get(21)?,
+                headers: row.get(22)?,
+                has_attachment: row.get::<i64>(23)? != 0,
+            });
+        }
+
+        Ok(emails)
+    }
+
+    /// Drop every cached message for `(account_id, folder)` and reset its sync state, because
+    /// a changed UIDVALIDITY means the server's UIDs are no longer comparable to ours.
+    pub async fn invalidate_folder(&self, account_id: Option<i64>, folder: &str) -> Result<()> {
+        let (delete_emails, delete_state) = if account_id.is_some() {
+            (
+                "DELETE FROM emails WHERE folder = ?1 AND account_id = ?2",
+                "DELETE FROM folder_sync_state WHERE folder = ?1 AND account_id = ?2",
+            )
+        } else {
+            (
+                "DELETE FROM emails WHERE folder = ?1 AND account_id IS NULL",
+                "DELETE FROM folder_sync_state WHERE folder = ?1 AND account_id IS NULL",
+            )
+        };
+
+        if let Some(acc_id) = account_id {
+            self.conn.execute(delete_emails, libsql::params![folder, acc_id]).await
+        } else {
+            self.conn.execute(delete_emails, libsql::params![folder]).await
+        }
+        .context("Failed to drop cached emails during folder invalidation")?;
+
+        if let Some(acc_id) = account_id {
+            self.conn.execute(delete_state, libsql::params![folder, acc_id]).await
+        } else {
+            self.conn.execute(delete_state, libsql::params![folder]).await
+        }
+        .context("Failed to reset folder sync state during invalidation")?;
+
+        Ok(())
+    }
+
+    /// Alias for [`upsert_sync_state`](Self::upsert_sync_state): insert or update the
+    /// CONDSTORE/UIDVALIDITY bookkeeping for `state.folder`.
+    pub async fn save_sync_state(&self, state: &FolderSyncState) -> Result<()> {
+        self.upsert_sync_state(state).await
+    }
+
+    /// If `server_uidvalidity` doesn't match what's stored for `(account_id, folder)`, the
+    /// server has renumbered UIDs (RFC 3501 §2.3.1.1) and every cached UID/MODSEQ for that
+    /// folder is meaningless; drop the cache and sync state so the next sync starts fresh.
+    /// Returns whether a reset happened.
+    pub async fn reset_folder_on_uidvalidity_change(
+        &self,
+        account_id: Option<i64>,
+        folder: &str,
+        server_uidvalidity: i64,
+    ) -> Result<bool> {
+        let stale = match self.get_sync_state(account_id, folder).await? {
+            Some(state) => state.uidvalidity != server_uidvalidity,
+            None => false,
+        };
+
+        if stale {
+            self.invalidate_folder(account_id, folder).await?;
+        }
+
+        Ok(stale)
+    }
+
+    /// Check for a cached message by its stable IMAP UID rather than `message_id`, which a
+    /// server may reuse or omit entirely.
+    pub async fn email_exists_by_account_folder_uid(
+        &self,
+        account_id: Option<i64>,
+        folder: &str,
+        imap_uid: u32,
+    ) -> Result<bool> {
+        let query = if account_id.is_some() {
+            "SELECT COUNT(*) FROM emails WHERE account_id = ?1 AND folder = ?2 AND imap_uid = ?3"
+        } else {
+            "SELECT COUNT(*) FROM emails WHERE account_id IS NULL AND folder = ?1 AND imap_uid = ?2"
+        };
+
+        let mut rows = if let Some(acc_id) = account_id {
+            self.conn.query(query, libsql::params![acc_id, folder, imap_uid]).await
+        } else {
+            self.conn.query(query, libsql::params![folder, imap_uid]).await
+        }
+        .context("Failed to check for existing email by account/folder/uid")?;
+
+        let count: i64 = rows.next().await?.map(|r| r.get(0)).transpose()?.unwrap_or(0);
+        Ok(count > 0)
+    }
+
+    /// The highest cached `imap_uid` for `(account_id, folder)`, so the sync loop can issue a
+    /// bounded `UID FETCH <max+1>:*` instead of re-fetching the whole folder. `None` when the
+    /// folder has no cached messages with a UID yet.
+    pub async fn get_max_uid(&self, account_id: Option<i64>, folder: &str) -> Result<Option<u32>> {
+        let query = if account_id.is_some() {
+            "SELECT MAX(imap_uid) FROM emails WHERE account_id = ?1 AND folder = ?2"
+        } else {
+            "SELECT MAX(imap_uid) FROM emails WHERE account_id IS NULL AND folder = ?1"
+        };
+
+        let mut rows = if let Some(acc_id) = account_id {
+            self.conn.query(query, libsql::params![acc_id, folder]).await
+        } else {
+            self.conn.query(query, libsql::params![folder]).await
+        }
+        .context("Failed to query max imap_uid")?;
+
+        match rows.next().await? {
+            Some(row) => Ok(row.get(0)?),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    async fn create_test_db() -> Result<EmailDatabase> {
+        let id = TEST_COUNTER.fetch_add(1, Ordering::SeqCst);
+        let path = PathBuf::from(format!("/tmp/test_tume_{}_{}.db", std::process::id(), id));
+        // Clean up if exists
+        let _ = std::fs::remove_file(&path);
+        EmailDatabase::new(Some(path)).await
+    }
+
+    #[tokio::test]
+    async fn test_database_initialization() {
+        let db = create_test_db().await.unwrap();
+        let folders = db.get_folders().await.unwrap();
+        assert_eq!(folders.len(), 5);
+        assert_eq!(folders[0].name, "inbox");
+    }
+
+    #[tokio::test]
+    async fn test_create_folder_and_get_folders_by_account() {
+        let db = create_test_db().await.unwrap();
+
+        assert!(db.get_folders_by_account(Some(1)).await.unwrap().is_empty());
+
+        let id = db
+            .create_folder(&DbFolder {
+                id: 0,
+                account_id: Some(1),
+                name: "Projects".to_string(),
+                parent_id: None,
+                delimiter: "/".to_string(),
+                special_use: None,
+                display_order: 0,
+            })
+            .await
+            .unwrap();
+
+        let folders = db.get_folders_by_account(Some(1)).await.unwrap();
+        assert_eq!(folders.len(), 1);
+        assert_eq!(folders[0].id, id);
+        assert_eq!(folders[0].name, "Projects");
+
+        // Global default folders aren't scoped to this account.
+        assert_eq!(db.get_folders_by_account(None).await.unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_sync_folders_from_imap_upserts_by_name() {
+        let db = create_test_db().await.unwrap();
+
+        let folders = db
+            .sync_folders_from_imap(
+                1,
+                &[
+                    ("INBOX".to_string(), "/".to_string(), None),
+                    ("Sent".to_string(), "/".to_string(), Some("\\Sent".to_string())),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(folders.len(), 2);
+        assert_eq!(folders[1].special_use.as_deref(), Some("\\Sent"));
+
+        // Re-syncing with a changed attribute updates the existing row instead of duplicating it
+        let folders = db
+            .sync_folders_from_imap(
+                1,
+                &[
+                    ("INBOX".to_string(), "/".to_string(), Some("\\Inbox".to_string())),
+                    ("Sent".to_string(), "/".to_string(), Some("\\Sent".to_string())),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(folders.len(), 2);
+        assert_eq!(folders[0].special_use.as_deref(), Some("\\Inbox"));
+    }
+
+    #[tokio::test]
+    async fn test_rename_folder_cascades_to_emails() {
+        let db = create_test_db().await.unwrap();
+
+        let folder_id = db
+            .create_folder(&DbFolder {
+                id: 0,
+                account_id: Some(1),
+                name: "Old".to_string(),
+                parent_id: None,
+                delimiter: "/".to_string(),
+                special_use: None,
+                display_order: 0,
+            })
+            .await
+            .unwrap();
+
+        let email = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "a@example.com".to_string(),
+            to_addresses: "b@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Hi".to_string(),
+            body: "Body".to_string(),
+            preview: "Body".to_string(),
+            date: "2026-01-12 10:00".to_string(),
+            status: EmailStatus::Unread,
+            is_flagged: false,
+            folder: "Old".to_string(),
+            thread_id: None,
+            account_id: Some(1),
+            message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+        let email_id = db.insert_email(&email).await.unwrap();
+
+        db.rename_folder(folder_id, "New").await.unwrap();
+
+        let renamed = db.get_email_by_id(email_id).await.unwrap().unwrap();
+        assert_eq!(renamed.folder, "New");
+    }
+
+    #[tokio::test]
+    async fn test_delete_folder_rehomes_children() {
+        let db = create_test_db().await.unwrap();
+
+        let parent_id = db
+            .create_folder(&DbFolder {
+                id: 0,
+                account_id: Some(1),
+                name: "Parent".to_string(),
+                parent_id: None,
+                delimiter: "/".to_string(),
+                special_use: None,
+                display_order: 0,
+            })
+            .await
+            .unwrap();
+        let child_id = db
+            .create_folder(&DbFolder {
+                id: 0,
+                account_id: Some(1),
+                name: "Child".to_string(),
+                parent_id: Some(parent_id),
+                delimiter: "/".to_string(),
+                special_use: None,
+                display_order: 1,
+            })
+            .await
+            .unwrap();
+
+        db.delete_folder(parent_id).await.unwrap();
+
+        let folders = db.get_folders_by_account(Some(1)).await.unwrap();
+        let child = folders.into_iter().find(|f| f.id == child_id).unwrap();
+        assert_eq!(child.parent_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_archive_email_uses_special_use_folder_when_present() {
+        let db = create_test_db().await.unwrap();
+
+        db.create_folder(&DbFolder {
+            id: 0,
+            account_id: Some(1),
+            name: "Old Mail".to_string(),
+            parent_id: None,
+            delimiter: "/".to_string(),
+            special_use: Some("\\Archive".to_string()),
+            display_order: 0,
+        })
+        .await
+        .unwrap();
+
+        let email = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "a@example.com".to_string(),
+            to_addresses: "b@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Hi".to_string(),
+            body: "Body".to_string(),
+            preview: "Body".to_string(),
+            date: "2026-01-12 10:00".to_string(),
+            status: EmailStatus::Unread,
+            is_flagged: false,
+            folder: "INBOX".to_string(),
+            thread_id: None,
+            account_id: Some(1),
+            message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+        let email_id = db.insert_email(&email).await.unwrap();
+
+        db.archive_email(email_id).await.unwrap();
+
+        let archived = db.get_email_by_id(email_id).await.unwrap().unwrap();
+        assert_eq!(archived.folder, "Old Mail");
+        assert_eq!(archived.status, EmailStatus::Archived);
+    }
+
+    #[tokio::test]
+    async fn test_insert_and_get_email() {
+        let db = create_test_db().await.unwrap();
+
+        let email = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "test@example.com".to_string(),
+            to_addresses: "recipient@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Test Subject".to_string(),
+            body: "Test body content".to_string(),
+            preview: "Test body content".to_string(),
+            date: "2026-01-12 12:00".to_string(),
             status: EmailStatus::Unread,
             is_flagged: false,
             folder: "inbox".to_string(),
             thread_id: None,
             account_id: None,
             message_id: Some("<test123@example.com>".to_string()),
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
         };
 
         let id = db.insert_email(&email).await.unwrap();
@@ -955,12 +3164,60 @@ mod tests {
         assert_eq!(retrieved.message_id, Some("<test123@example.com>".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_insert_emails_batch_returns_ids_in_order() {
+        let db = create_test_db().await.unwrap();
+
+        let make = |subject: &str| DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "test@example.com".to_string(),
+            to_addresses: "recipient@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: subject.to_string(),
+            body: "Body".to_string(),
+            preview: "Body".to_string(),
+            date: "2026-01-12 12:00".to_string(),
+            status: EmailStatus::Unread,
+            is_flagged: false,
+            folder: "inbox".to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+        let emails = vec![make("First"), make("Second"), make("Third")];
+
+        let ids = db.insert_emails_batch(&emails).await.unwrap();
+        assert_eq!(ids.len(), 3);
+        assert!(ids.windows(2).all(|w| w[1] > w[0]));
+
+        let inbox = db.get_emails_by_folder("inbox").await.unwrap();
+        assert_eq!(inbox.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_insert_emails_batch_empty_slice_is_a_noop() {
+        let db = create_test_db().await.unwrap();
+        let ids = db.insert_emails_batch(&[]).await.unwrap();
+        assert!(ids.is_empty());
+    }
+
     #[tokio::test]
     async fn test_get_emails_by_folder() {
         let db = create_test_db().await.unwrap();
 
         let email1 = DbEmail {
             id: 0,
+            body_html: None,
             from_address: "test1@example.com".to_string(),
             to_addresses: "recipient@example.com".to_string(),
             cc_addresses: None,
@@ -975,10 +3232,19 @@ mod tests {
             thread_id: None,
             account_id: None,
             message_id: Some("<test1@example.com>".to_string()),
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
         };
 
         let email2 = DbEmail {
             id: 0,
+            body_html: None,
             from_address: "test2@example.com".to_string(),
             to_addresses: "recipient@example.com".to_string(),
             cc_addresses: None,
@@ -993,6 +3259,14 @@ mod tests {
             thread_id: None,
             account_id: None,
             message_id: Some("<test2@example.com>".to_string()),
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
         };
 
         db.insert_email(&email1).await.unwrap();
@@ -1013,6 +3287,7 @@ mod tests {
 
         let email = DbEmail {
             id: 0,
+            body_html: None,
             from_address: "test@example.com".to_string(),
             to_addresses: "recipient@example.com".to_string(),
             cc_addresses: None,
@@ -1027,6 +3302,14 @@ mod tests {
             thread_id: None,
             account_id: None,
             message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
         };
 
         let id = db.insert_email(&email).await.unwrap();
@@ -1042,6 +3325,7 @@ mod tests {
 
         let email = DbEmail {
             id: 0,
+            body_html: None,
             from_address: "test@example.com".to_string(),
             to_addresses: "recipient@example.com".to_string(),
             cc_addresses: None,
@@ -1056,6 +3340,14 @@ mod tests {
             thread_id: None,
             account_id: None,
             message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
         };
 
         let id = db.insert_email(&email).await.unwrap();
@@ -1078,69 +3370,535 @@ mod tests {
             created_at: String::new(),
             updated_at: String::new(),
             account_id: None,
+            attachments: vec![PathBuf::from("/tmp/report.pdf"), PathBuf::from("/tmp/notes.txt")],
+        };
+
+        let id = db.save_draft(&draft).await.unwrap();
+        assert!(id > 0);
+
+        let drafts = db.get_drafts().await.unwrap();
+        assert_eq!(drafts.len(), 1);
+        assert_eq!(drafts[0].subject, "Draft subject");
+        assert_eq!(
+            drafts[0].attachments,
+            vec![PathBuf::from("/tmp/report.pdf"), PathBuf::from("/tmp/notes.txt")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_emails() {
+        let db = create_test_db().await.unwrap();
+
+        let email1 = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "alice@example.com".to_string(),
+            to_addresses: "recipient@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Meeting notes".to_string(),
+            body: "Important meeting discussion".to_string(),
+            preview: "Important meeting discussion".to_string(),
+            date: "2026-01-12 12:00".to_string(),
+            status: EmailStatus::Unread,
+            is_flagged: false,
+            folder: "inbox".to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+
+        let email2 = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "bob@example.com".to_string(),
+            to_addresses: "recipient@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Project update".to_string(),
+            body: "The project is progressing well".to_string(),
+            preview: "The project is progressing well".to_string(),
+            date: "2026-01-12 13:00".to_string(),
+            status: EmailStatus::Read,
+            is_flagged: false,
+            folder: "inbox".to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+
+        db.insert_email(&email1).await.unwrap();
+        db.insert_email(&email2).await.unwrap();
+
+        let results = db.search_emails("meeting").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].subject, "Meeting notes");
+
+        let results = db.search_emails("project").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].subject, "Project update");
+
+        let results = db.search_emails("alice").await.unwrap();
+        assert_eq!(results.len(), 1);
+
+        let results = db.search_emails("from:bob").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].subject, "Project update");
+
+        let results = db.search_emails("subject:meeting").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].subject, "Meeting notes");
+    }
+
+    #[tokio::test]
+    async fn test_search_emails_query_combines_text_and_structured_predicates() {
+        let db = create_test_db().await.unwrap();
+
+        let flagged = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "alice@example.com".to_string(),
+            to_addresses: "recipient@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Meeting notes".to_string(),
+            body: "Important meeting discussion".to_string(),
+            preview: "Important meeting discussion".to_string(),
+            date: "2026-01-12 12:00".to_string(),
+            status: EmailStatus::Unread,
+            is_flagged: true,
+            folder: "inbox".to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+        let unflagged = DbEmail {
+            is_flagged: false,
+            subject: "Meeting cancelled".to_string(),
+            body: "Our meeting is off".to_string(),
+            preview: "Our meeting is off".to_string(),
+            date: "2026-01-12 13:00".to_string(),
+            ..flagged.clone()
+        };
+
+        db.insert_email(&flagged).await.unwrap();
+        db.insert_email(&unflagged).await.unwrap();
+
+        let results = db
+            .search_emails_query("meeting is:flagged", SearchOrder::Rank)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].subject, "Meeting notes");
+    }
+
+    #[tokio::test]
+    async fn test_search_emails_query_structured_only_skips_fts_and_orders_by_date() {
+        let db = create_test_db().await.unwrap();
+
+        let older = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "alice@example.com".to_string(),
+            to_addresses: "recipient@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Older".to_string(),
+            body: "Body".to_string(),
+            preview: "Body".to_string(),
+            date: "2026-01-10 12:00".to_string(),
+            status: EmailStatus::Unread,
+            is_flagged: false,
+            folder: "inbox".to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+        let newer = DbEmail {
+            subject: "Newer".to_string(),
+            date: "2026-01-12 12:00".to_string(),
+            ..older.clone()
+        };
+
+        db.insert_email(&older).await.unwrap();
+        db.insert_email(&newer).await.unwrap();
+
+        let results = db
+            .search_emails_query("folder:inbox", SearchOrder::Rank)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].subject, "Newer");
+        assert_eq!(results[1].subject, "Older");
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_threads_and_get_thread() {
+        let db = create_test_db().await.unwrap();
+
+        let root = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "alice@example.com".to_string(),
+            to_addresses: "bob@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Lunch?".to_string(),
+            body: "Want to grab lunch?".to_string(),
+            preview: "Want to grab lunch?".to_string(),
+            date: "2026-01-12 12:00".to_string(),
+            status: EmailStatus::Unread,
+            is_flagged: false,
+            folder: "inbox".to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id: Some("<root@example.com>".to_string()),
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+
+        let reply = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "bob@example.com".to_string(),
+            to_addresses: "alice@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Re: Lunch?".to_string(),
+            body: "Sure, noon?".to_string(),
+            preview: "Sure, noon?".to_string(),
+            date: "2026-01-12 13:00".to_string(),
+            status: EmailStatus::Unread,
+            is_flagged: false,
+            folder: "inbox".to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id: Some("<reply@example.com>".to_string()),
+            imap_uid: None,
+            in_reply_to: Some("<root@example.com>".to_string()),
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+
+        let unrelated = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "carol@example.com".to_string(),
+            to_addresses: "bob@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Invoice".to_string(),
+            body: "Please find the invoice attached".to_string(),
+            preview: "Please find the invoice attached".to_string(),
+            date: "2026-01-12 14:00".to_string(),
+            status: EmailStatus::Unread,
+            is_flagged: false,
+            folder: "inbox".to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id: Some("<invoice@example.com>".to_string()),
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+
+        db.insert_email(&root).await.unwrap();
+        db.insert_email(&reply).await.unwrap();
+        db.insert_email(&unrelated).await.unwrap();
+
+        let assigned = db.rebuild_threads(None).await.unwrap();
+        assert_eq!(assigned, 3);
+
+        let thread = db.get_thread("<root@example.com>").await.unwrap();
+        assert_eq!(thread.len(), 2);
+        assert_eq!(thread[0].subject, "Lunch?");
+        assert_eq!(thread[1].subject, "Re: Lunch?");
+
+        let other_thread = db.get_thread("<invoice@example.com>").await.unwrap();
+        assert_eq!(other_thread.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sync_state_roundtrip_and_modified_since() {
+        let db = create_test_db().await.unwrap();
+
+        assert!(db.get_sync_state(None, "INBOX").await.unwrap().is_none());
+
+        db.upsert_sync_state(&FolderSyncState {
+            account_id: None,
+            folder: "INBOX".to_string(),
+            uidvalidity: 100,
+            highest_modseq: 5,
+            last_seen_uid: 42,
+        })
+        .await
+        .unwrap();
+
+        let state = db.get_sync_state(None, "INBOX").await.unwrap().unwrap();
+        assert_eq!(state.uidvalidity, 100);
+        assert_eq!(state.highest_modseq, 5);
+        assert_eq!(state.last_seen_uid, 42);
+
+        // Upsert again with a newer modseq should update the same row, not insert a second one
+        db.upsert_sync_state(&FolderSyncState {
+            account_id: None,
+            folder: "INBOX".to_string(),
+            uidvalidity: 100,
+            highest_modseq: 9,
+            last_seen_uid: 50,
+        })
+        .await
+        .unwrap();
+        let state = db.get_sync_state(None, "INBOX").await.unwrap().unwrap();
+        assert_eq!(state.highest_modseq, 9);
+
+        let old = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "a@example.com".to_string(),
+            to_addresses: "b@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Old".to_string(),
+            body: "old body".to_string(),
+            preview: "old body".to_string(),
+            date: "2026-01-12 10:00".to_string(),
+            status: EmailStatus::Read,
+            is_flagged: false,
+            folder: "INBOX".to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id: Some("<old@example.com>".to_string()),
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: Some(3),
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+        let mut fresh = old.clone();
+        fresh.message_id = Some("<fresh@example.com>".to_string());
+        fresh.subject = "Fresh".to_string();
+        fresh.modseq = Some(12);
+
+        db.insert_email(&old).await.unwrap();
+        db.insert_email(&fresh).await.unwrap();
+
+        let modified = db.emails_modified_since(None, "INBOX", 9).await.unwrap();
+        assert_eq!(modified.len(), 1);
+        assert_eq!(modified[0].subject, "Fresh");
+
+        db.invalidate_folder(None, "INBOX").await.unwrap();
+        assert!(db.get_sync_state(None, "INBOX").await.unwrap().is_none());
+        assert!(db.get_emails_by_folder("INBOX").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_email_exists_and_max_uid_by_account_folder() {
+        let db = create_test_db().await.unwrap();
+
+        assert!(!db.email_exists_by_account_folder_uid(None, "INBOX", 7).await.unwrap());
+        assert_eq!(db.get_max_uid(None, "INBOX").await.unwrap(), None);
+
+        let email = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "a@example.com".to_string(),
+            to_addresses: "b@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Hi".to_string(),
+            body: "Body".to_string(),
+            preview: "Body".to_string(),
+            date: "2026-01-12 10:00".to_string(),
+            status: EmailStatus::Unread,
+            is_flagged: false,
+            folder: "INBOX".to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id: None,
+            imap_uid: Some(7),
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
         };
+        db.insert_email(&email).await.unwrap();
 
-        let id = db.save_draft(&draft).await.unwrap();
-        assert!(id > 0);
+        assert!(db.email_exists_by_account_folder_uid(None, "INBOX", 7).await.unwrap());
+        assert!(!db.email_exists_by_account_folder_uid(None, "INBOX", 8).await.unwrap());
+        assert_eq!(db.get_max_uid(None, "INBOX").await.unwrap(), Some(7));
+    }
 
-        let drafts = db.get_drafts().await.unwrap();
-        assert_eq!(drafts.len(), 1);
-        assert_eq!(drafts[0].subject, "Draft subject");
+    #[tokio::test]
+    async fn test_reset_folder_on_uidvalidity_change() {
+        let db = create_test_db().await.unwrap();
+
+        db.save_sync_state(&FolderSyncState {
+            account_id: None,
+            folder: "INBOX".to_string(),
+            uidvalidity: 100,
+            highest_modseq: 5,
+            last_seen_uid: 42,
+        })
+        .await
+        .unwrap();
+
+        // Same UIDVALIDITY: nothing to reset
+        let reset = db.reset_folder_on_uidvalidity_change(None, "INBOX", 100).await.unwrap();
+        assert!(!reset);
+        assert!(db.get_sync_state(None, "INBOX").await.unwrap().is_some());
+
+        // Server reports a different UIDVALIDITY: cache and sync state must be dropped
+        let reset = db.reset_folder_on_uidvalidity_change(None, "INBOX", 200).await.unwrap();
+        assert!(reset);
+        assert!(db.get_sync_state(None, "INBOX").await.unwrap().is_none());
     }
 
     #[tokio::test]
-    async fn test_search_emails() {
+    async fn test_apply_actions_applies_local_actions_and_defers_server_ones() {
         let db = create_test_db().await.unwrap();
 
-        let email1 = DbEmail {
+        let kept = DbEmail {
             id: 0,
-            from_address: "alice@example.com".to_string(),
-            to_addresses: "recipient@example.com".to_string(),
+            body_html: None,
+            from_address: "a@example.com".to_string(),
+            to_addresses: "b@example.com".to_string(),
             cc_addresses: None,
             bcc_addresses: None,
-            subject: "Meeting notes".to_string(),
-            body: "Important meeting discussion".to_string(),
-            preview: "Important meeting discussion".to_string(),
-            date: "2026-01-12 12:00".to_string(),
+            subject: "Keep".to_string(),
+            body: "Body".to_string(),
+            preview: "Body".to_string(),
+            date: "2026-01-12 10:00".to_string(),
             status: EmailStatus::Unread,
             is_flagged: false,
             folder: "inbox".to_string(),
             thread_id: None,
             account_id: None,
             message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
         };
+        let removed = DbEmail { subject: "Remove".to_string(), ..kept.clone() };
 
-        let email2 = DbEmail {
+        let keep_id = db.insert_email(&kept).await.unwrap();
+        let remove_id = db.insert_email(&removed).await.unwrap();
+
+        let actions = vec![
+            crate::sync::SyncAction::UpdateFlags { id: keep_id, flagged: true, status: EmailStatus::Read },
+            crate::sync::SyncAction::DeleteLocal { id: remove_id },
+            crate::sync::SyncAction::FetchNew { uid: 99 },
+        ];
+
+        let outcome = db.apply_actions(&actions, false).await.unwrap();
+        assert_eq!(outcome.flags_updated, 1);
+        assert_eq!(outcome.deleted, 1);
+        assert_eq!(outcome.deferred_to_server, 1);
+
+        let stored = db.get_email_by_id(keep_id).await.unwrap().unwrap();
+        assert!(stored.is_flagged);
+        assert_eq!(stored.status, EmailStatus::Read);
+
+        let removed_stored = db.get_email_by_id(remove_id).await.unwrap().unwrap();
+        assert_eq!(removed_stored.status, EmailStatus::Deleted);
+    }
+
+    #[tokio::test]
+    async fn test_apply_actions_dry_run_does_not_write() {
+        let db = create_test_db().await.unwrap();
+
+        let email = DbEmail {
             id: 0,
-            from_address: "bob@example.com".to_string(),
-            to_addresses: "recipient@example.com".to_string(),
+            body_html: None,
+            from_address: "a@example.com".to_string(),
+            to_addresses: "b@example.com".to_string(),
             cc_addresses: None,
             bcc_addresses: None,
-            subject: "Project update".to_string(),
-            body: "The project is progressing well".to_string(),
-            preview: "The project is progressing well".to_string(),
-            date: "2026-01-12 13:00".to_string(),
-            status: EmailStatus::Read,
+            subject: "Hi".to_string(),
+            body: "Body".to_string(),
+            preview: "Body".to_string(),
+            date: "2026-01-12 10:00".to_string(),
+            status: EmailStatus::Unread,
             is_flagged: false,
             folder: "inbox".to_string(),
             thread_id: None,
             account_id: None,
             message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
         };
+        let id = db.insert_email(&email).await.unwrap();
 
-        db.insert_email(&email1).await.unwrap();
-        db.insert_email(&email2).await.unwrap();
-
-        let results = db.search_emails("meeting").await.unwrap();
-        assert_eq!(results.len(), 1);
-        assert_eq!(results[0].subject, "Meeting notes");
-
-        let results = db.search_emails("project").await.unwrap();
-        assert_eq!(results.len(), 1);
-        assert_eq!(results[0].subject, "Project update");
+        let actions = vec![crate::sync::SyncAction::DeleteLocal { id }];
+        let outcome = db.apply_actions(&actions, true).await.unwrap();
+        assert_eq!(outcome.deleted, 1);
 
-        let results = db.search_emails("alice").await.unwrap();
-        assert_eq!(results.len(), 1);
+        let stored = db.get_email_by_id(id).await.unwrap().unwrap();
+        assert_eq!(stored.status, EmailStatus::Unread); // dry run: nothing actually changed
     }
 
     #[tokio::test]
@@ -1149,6 +3907,7 @@ mod tests {
 
         let email = DbEmail {
             id: 0,
+            body_html: None,
             from_address: "test@example.com".to_string(),
             to_addresses: "me@example.com".to_string(),
             cc_addresses: None,
@@ -1163,6 +3922,14 @@ mod tests {
             thread_id: None,
             account_id: None,
             message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
         };
 
         db.insert_email(&email).await.unwrap();
@@ -1182,6 +3949,7 @@ mod tests {
         // Create an email with a message_id
         let email1 = DbEmail {
             id: 0,
+            body_html: None,
             from_address: "test@example.com".to_string(),
             to_addresses: "me@example.com".to_string(),
             cc_addresses: None,
@@ -1196,6 +3964,14 @@ mod tests {
             thread_id: None,
             account_id: None,
             message_id: Some("<unique-123@example.com>".to_string()),
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
         };
 
         // Insert the email
@@ -1214,6 +3990,65 @@ mod tests {
         assert_eq!(emails.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_upsert_email_inserts_then_updates_in_place() {
+        let db = create_test_db().await.unwrap();
+
+        let email = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "test@example.com".to_string(),
+            to_addresses: "me@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Original".to_string(),
+            body: "Body".to_string(),
+            preview: "Body".to_string(),
+            date: "2026-01-12 12:00".to_string(),
+            status: EmailStatus::Unread,
+            is_flagged: false,
+            folder: "INBOX".to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id: Some("<dup@example.com>".to_string()),
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: Some(1),
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+
+        let first = db.upsert_email(&email).await.unwrap();
+        let UpsertOutcome::Inserted(id) = first else {
+            panic!("expected an insert on first upsert");
+        };
+
+        // The user reads and flags the message locally before the next sync sees it again.
+        db.update_email_status(id, EmailStatus::Read).await.unwrap();
+        db.toggle_email_flag(id).await.unwrap();
+
+        let mut resynced = email.clone();
+        resynced.folder = "Archive".to_string();
+        resynced.modseq = Some(2);
+        resynced.status = EmailStatus::Unread; // what the server thinks, should be ignored
+        resynced.is_flagged = false; // ditto
+
+        let second = db.upsert_email(&resynced).await.unwrap();
+        assert_eq!(second, UpsertOutcome::Updated(id));
+
+        let stored = db.get_email_by_id(id).await.unwrap().unwrap();
+        assert_eq!(stored.folder, "Archive");
+        assert_eq!(stored.modseq, Some(2));
+        assert_eq!(stored.status, EmailStatus::Read);
+        assert!(stored.is_flagged);
+
+        let all = db.get_emails_by_folder("Archive").await.unwrap();
+        assert_eq!(all.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_sync_deduplication_workflow() {
         let db = create_test_db().await.unwrap();
@@ -1221,6 +4056,7 @@ mod tests {
         // Simulate first sync - insert 3 emails
         let email1 = DbEmail {
             id: 0,
+            body_html: None,
             from_address: "sender1@example.com".to_string(),
             to_addresses: "me@example.com".to_string(),
             cc_addresses: None,
@@ -1235,10 +4071,19 @@ mod tests {
             thread_id: None,
             account_id: None,
             message_id: Some("<msg1@server.com>".to_string()),
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
         };
 
         let email2 = DbEmail {
             id: 0,
+            body_html: None,
             from_address: "sender2@example.com".to_string(),
             to_addresses: "me@example.com".to_string(),
             cc_addresses: None,
@@ -1253,10 +4098,19 @@ mod tests {
             thread_id: None,
             account_id: None,
             message_id: Some("<msg2@server.com>".to_string()),
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
         };
 
         let email3 = DbEmail {
             id: 0,
+            body_html: None,
             from_address: "sender3@example.com".to_string(),
             to_addresses: "me@example.com".to_string(),
             cc_addresses: None,
@@ -1271,6 +4125,14 @@ mod tests {
             thread_id: None,
             account_id: None,
             message_id: Some("<msg3@server.com>".to_string()),
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
         };
 
         db.insert_email(&email1).await.unwrap();
@@ -1298,6 +4160,7 @@ mod tests {
         // Insert only the new emails
         let email4 = DbEmail {
             id: 0,
+            body_html: None,
             from_address: "sender4@example.com".to_string(),
             to_addresses: "me@example.com".to_string(),
             cc_addresses: None,
@@ -1312,10 +4175,19 @@ mod tests {
             thread_id: None,
             account_id: None,
             message_id: Some("<msg4@server.com>".to_string()),
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
         };
 
         let email5 = DbEmail {
             id: 0,
+            body_html: None,
             from_address: "sender5@example.com".to_string(),
             to_addresses: "me@example.com".to_string(),
             cc_addresses: None,
@@ -1330,6 +4202,14 @@ mod tests {
             thread_id: None,
             account_id: None,
             message_id: Some("<msg5@server.com>".to_string()),
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
         };
 
         db.insert_email(&email4).await.unwrap();
@@ -1345,4 +4225,348 @@ mod tests {
             .collect();
         assert_eq!(message_ids.len(), 5);
     }
+
+    async fn insert_test_rule(
+        db: &EmailDatabase,
+        condition_type: &str,
+        condition_value: &str,
+        action_type: &str,
+        action_value: Option<&str>,
+        display_order: i64,
+        stop_on_match: bool,
+    ) {
+        db.conn
+            .execute(
+                "INSERT INTO inbox_rules (
+                    name, condition_type, condition_value, action_type, action_value,
+                    enabled, account_id, display_order, stop_on_match
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, 1, NULL, ?6, ?7)",
+                libsql::params![
+                    "test rule",
+                    condition_type,
+                    condition_value,
+                    action_type,
+                    action_value,
+                    display_order,
+                    stop_on_match as i64,
+                ],
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_apply_rules_matches_and_runs_action() {
+        let db = create_test_db().await.unwrap();
+        insert_test_rule(&db, "from_contains", "newsletter@", "move_to_folder", Some("Promotions"), 0, false).await;
+
+        let email = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "newsletter@shop.example.com".to_string(),
+            to_addresses: "me@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "This week's deals".to_string(),
+            body: "Body".to_string(),
+            preview: "Body".to_string(),
+            date: "2026-01-12 12:00".to_string(),
+            status: EmailStatus::Unread,
+            is_flagged: false,
+            folder: "inbox".to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+        let id = db.insert_email(&email).await.unwrap();
+
+        let applied = db.apply_rules(id, None).await.unwrap();
+        assert_eq!(applied, 1);
+
+        let stored = db.get_email_by_id(id).await.unwrap().unwrap();
+        assert_eq!(stored.folder, "Promotions");
+    }
+
+    #[tokio::test]
+    async fn test_apply_rules_stops_on_first_match_when_flagged() {
+        let db = create_test_db().await.unwrap();
+        insert_test_rule(&db, "subject_contains", "invoice", "archive", None, 0, true).await;
+        insert_test_rule(&db, "from_contains", "billing@", "set_flag", None, 1, false).await;
+
+        let email = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "billing@example.com".to_string(),
+            to_addresses: "me@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Your invoice is ready".to_string(),
+            body: "Body".to_string(),
+            preview: "Body".to_string(),
+            date: "2026-01-12 12:00".to_string(),
+            status: EmailStatus::Unread,
+            is_flagged: false,
+            folder: "inbox".to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+        let id = db.insert_email(&email).await.unwrap();
+
+        let applied = db.apply_rules(id, None).await.unwrap();
+        assert_eq!(applied, 1);
+
+        let stored = db.get_email_by_id(id).await.unwrap().unwrap();
+        assert_eq!(stored.status, EmailStatus::Archived);
+        assert!(!stored.is_flagged); // second rule never ran, since the first stopped evaluation
+    }
+
+    #[tokio::test]
+    async fn test_apply_rules_with_no_rules_is_a_noop() {
+        let db = create_test_db().await.unwrap();
+        let email = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "someone@example.com".to_string(),
+            to_addresses: "me@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Hello".to_string(),
+            body: "Body".to_string(),
+            preview: "Body".to_string(),
+            date: "2026-01-12 12:00".to_string(),
+            status: EmailStatus::Unread,
+            is_flagged: false,
+            folder: "inbox".to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+        let id = db.insert_email(&email).await.unwrap();
+
+        let applied = db.apply_rules(id, None).await.unwrap();
+        assert_eq!(applied, 0);
+    }
+
+    #[tokio::test]
+    async fn test_apply_rules_size_over_discards_large_message() {
+        let db = create_test_db().await.unwrap();
+        insert_test_rule(&db, "size_over", "10", "discard", None, 0, false).await;
+
+        let email = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "someone@example.com".to_string(),
+            to_addresses: "me@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Big attachment".to_string(),
+            body: "This body is longer than ten bytes".to_string(),
+            preview: "This body".to_string(),
+            date: "2026-01-12 12:00".to_string(),
+            status: EmailStatus::Unread,
+            is_flagged: false,
+            folder: "inbox".to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+        let id = db.insert_email(&email).await.unwrap();
+
+        let applied = db.apply_rules(id, None).await.unwrap();
+        assert_eq!(applied, 1);
+        assert!(db.get_email_by_id(id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_apply_rules_size_under_does_not_match_large_message() {
+        let db = create_test_db().await.unwrap();
+        insert_test_rule(&db, "size_under", "10", "discard", None, 0, false).await;
+
+        let email = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "someone@example.com".to_string(),
+            to_addresses: "me@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Big attachment".to_string(),
+            body: "This body is longer than ten bytes".to_string(),
+            preview: "This body".to_string(),
+            date: "2026-01-12 12:00".to_string(),
+            status: EmailStatus::Unread,
+            is_flagged: false,
+            folder: "inbox".to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+        let id = db.insert_email(&email).await.unwrap();
+
+        let applied = db.apply_rules(id, None).await.unwrap();
+        assert_eq!(applied, 0);
+        assert!(db.get_email_by_id(id).await.unwrap().is_some());
+    }
+
+    #[test]
+    fn test_parse_address_list_splits_and_normalizes() {
+        let parsed = parse_address_list("Alice <Alice@Example.com>, bob@example.com, \"Carol\" <carol@example.com>");
+        assert_eq!(
+            parsed,
+            vec![
+                (Some("Alice".to_string()), "alice@example.com".to_string()),
+                (None, "bob@example.com".to_string()),
+                (Some("Carol".to_string()), "carol@example.com".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_address_list_ignores_blank_entries() {
+        assert_eq!(parse_address_list(""), Vec::new());
+        assert_eq!(parse_address_list(" , "), Vec::new());
+    }
+
+    #[tokio::test]
+    async fn test_insert_email_upserts_contacts_and_bumps_times_seen() {
+        let db = create_test_db().await.unwrap();
+
+        let email = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "Alice <alice@example.com>".to_string(),
+            to_addresses: "me@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Hi".to_string(),
+            body: "Body".to_string(),
+            preview: "Body".to_string(),
+            date: "2026-01-10 09:00".to_string(),
+            status: EmailStatus::Unread,
+            is_flagged: false,
+            folder: "inbox".to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+        db.insert_email(&email).await.unwrap();
+
+        let contact = db.get_contact("alice@example.com").await.unwrap().unwrap();
+        assert_eq!(contact.display_name.as_deref(), Some("Alice"));
+        assert_eq!(contact.times_seen, 1);
+
+        let mut second = email.clone();
+        second.date = "2026-01-12 09:00".to_string();
+        db.insert_email(&second).await.unwrap();
+
+        let contact = db.get_contact("alice@example.com").await.unwrap().unwrap();
+        assert_eq!(contact.times_seen, 2);
+        assert_eq!(contact.last_seen_date, "2026-01-12 09:00");
+    }
+
+    #[tokio::test]
+    async fn test_search_contacts_matches_prefix_on_address_or_name() {
+        let db = create_test_db().await.unwrap();
+
+        let email = DbEmail {
+            id: 0,
+            body_html: None,
+            from_address: "Alice Smith <alice@example.com>".to_string(),
+            to_addresses: "bob@example.com".to_string(),
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject: "Hi".to_string(),
+            body: "Body".to_string(),
+            preview: "Body".to_string(),
+            date: "2026-01-10 09:00".to_string(),
+            status: EmailStatus::Unread,
+            is_flagged: false,
+            folder: "inbox".to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
+        };
+        db.insert_email(&email).await.unwrap();
+
+        let by_address = db.search_contacts("ali", 10).await.unwrap();
+        assert_eq!(by_address.len(), 1);
+        assert_eq!(by_address[0].address, "alice@example.com");
+
+        let by_name = db.search_contacts("Alice", 10).await.unwrap();
+        assert_eq!(by_name.len(), 1);
+
+        assert!(db.search_contacts("nobody", 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_add_list_delete_contact() {
+        let db = create_test_db().await.unwrap();
+
+        let id = db.add_contact("carol@example.com", Some("Carol")).await.unwrap();
+
+        let contacts = db.list_contacts().await.unwrap();
+        assert_eq!(contacts.len(), 1);
+        assert_eq!(contacts[0].id, id);
+        assert_eq!(contacts[0].address, "carol@example.com");
+        assert_eq!(contacts[0].display_name.as_deref(), Some("Carol"));
+
+        db.delete_contact(id).await.unwrap();
+        assert!(db.list_contacts().await.unwrap().is_empty());
+    }
 }
@@ -9,12 +9,188 @@
 /// - ✅ Inbox rules engine (fully implemented)
 /// - ✅ IMAP email fetching (working implementation)
 /// - ✅ SMTP email sending (working implementation)
-/// - ⏳ Folder management (requires IMAP integration)
-/// - ⏳ OAuth2 support (not started - needed for Gmail/Outlook)
+/// - ✅ Folder listing via IMAP `LIST`, cached in the database (see [`ImapClient::list_folders`])
+/// - ✅ OAuth2 support via XOAUTH2 for both IMAP and SMTP (used automatically when credentials
+///   carry an oauth_token), with expired access tokens refreshed transparently before each call
+/// - ✅ Secrets may be stored in the system keyring and resolved lazily via
+///   `Credentials::from_secret_refs` instead of being kept in plaintext config
+/// - ✅ Offline-first sync into a local Maildir mirror (see [`crate::maildir`]), with a
+///   fall back to the mirror's cached copy when the server is unreachable
+/// - ✅ Push sync via IMAP `IDLE` (see [`ImapClient::watch_idle`]), falling back to polling for
+///   servers that don't advertise the capability
+/// - ✅ Granular live updates via [`ImapClient::watch`]'s [`SyncEvent`] stream, distinguishing new
+///   mail from expunges and flag-only changes instead of forcing a refetch on every wakeup
+/// - ✅ Threaded replies: outgoing `In-Reply-To`/`References` headers (see
+///   [`SmtpClient::send_threaded_mime_email`]) so a reply stays in the same JWZ conversation
+///   (see [`crate::threading`]) instead of starting a new one
+/// - ✅ An account's outgoing mail can be routed through an explicit
+///   [`crate::config::SendBackend`] override instead of its receiving credentials - a distinct
+///   SMTP server or a local `sendmail`-style command (see [`SendTransport`]), the only way a
+///   local-only `Maildir`/`Notmuch` account can send mail at all
 
 use crate::credentials::Credentials;
 use crate::db::{DbEmail, EmailStatus as DbEmailStatus};
-use anyhow::{Result, anyhow, Context};
+use anyhow::{Result, anyhow, bail, Context};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_stream::{Stream, wrappers::ReceiverStream};
+
+/// How long a single `IDLE` command is left open before we tear it down and reissue a fresh
+/// one, comfortably inside the ~29-minute timeout most servers enforce on an idle connection.
+const IDLE_REISSUE_INTERVAL: Duration = Duration::from_secs(20 * 60);
+
+/// `imap::Authenticator` implementation for the XOAUTH2 SASL mechanism
+///
+/// Hands back the pre-built initial response; the `imap` crate takes care of the
+/// rest of the `AUTHENTICATE XOAUTH2` exchange.
+struct XOAuth2Authenticator {
+    sasl_string: String,
+}
+
+impl imap::Authenticator for XOAuth2Authenticator {
+    type Response = String;
+
+    fn process(&self, _data: &[u8]) -> Self::Response {
+        self.sasl_string.clone()
+    }
+}
+
+/// Open a TCP+TLS connection to the IMAP server, honoring the account's [`SecurityType`]:
+/// implicit TLS connects straight into TLS, STARTTLS connects in plaintext and upgrades.
+///
+/// [`SecurityType`]: crate::providers::SecurityType
+fn imap_connect(credentials: &Credentials) -> Result<imap::Client<native_tls::TlsStream<std::net::TcpStream>>> {
+    let domain = &credentials.imap_server;
+    let port = credentials.imap_port;
+
+    let tls = native_tls::TlsConnector::builder()
+        .build()
+        .context("Failed to build TLS connector")?;
+
+    match credentials.imap_security {
+        crate::providers::SecurityType::Tls => imap::connect((domain.as_str(), port), domain, &tls)
+            .context(format!("Failed to connect to {}:{}", domain, port)),
+        crate::providers::SecurityType::StartTls => {
+            imap::connect_starttls((domain.as_str(), port), domain, &tls)
+                .context(format!("Failed to connect (STARTTLS) to {}:{}", domain, port))
+        }
+    }
+}
+
+/// Build an SMTP transport honoring the account's [`SecurityType`]: implicit TLS relays
+/// straight into TLS (typically port 465), STARTTLS connects in plaintext and upgrades
+/// (typically port 587).
+///
+/// [`SecurityType`]: crate::providers::SecurityType
+fn smtp_transport_builder(credentials: &Credentials) -> Result<lettre::transport::smtp::SmtpTransportBuilder> {
+    use lettre::SmtpTransport;
+
+    match credentials.smtp_security {
+        crate::providers::SecurityType::Tls => SmtpTransport::relay(&credentials.smtp_server)
+            .context("Failed to create SMTP transport"),
+        crate::providers::SecurityType::StartTls => {
+            SmtpTransport::starttls_relay(&credentials.smtp_server)
+                .context("Failed to create SMTP transport (STARTTLS)")
+        }
+    }
+}
+
+/// Build the lettre `Credentials`/`Mechanism` pair for an SMTP AUTH exchange, using XOAUTH2
+/// (access token as the "password") when the account carries an OAuth2 token and falling
+/// back to plain AUTH PLAIN/LOGIN otherwise.
+fn smtp_credentials(
+    credentials: &Credentials,
+) -> (
+    lettre::transport::smtp::authentication::Credentials,
+    lettre::transport::smtp::authentication::Mechanism,
+) {
+    use lettre::transport::smtp::authentication::{Credentials as LettreCredentials, Mechanism};
+
+    match &credentials.oauth_token {
+        Some(token) => (
+            LettreCredentials::new(credentials.smtp_username.clone(), token.access_token.clone()),
+            Mechanism::Xoauth2,
+        ),
+        None => (
+            LettreCredentials::new(credentials.smtp_username.clone(), credentials.smtp_password.clone()),
+            Mechanism::Plain,
+        ),
+    }
+}
+
+/// If `credentials` carries an OAuth2 token that has expired, refresh it before use and
+/// return a copy with the renewed token; otherwise return an unmodified copy.
+///
+/// The refreshed token isn't persisted back to `CredentialsManager` here - `ImapClient`/
+/// `SmtpClient` only hold `Credentials` by value for the duration of one call, so the next
+/// call simply refreshes again. Wiring persistence through is left for a future chunk.
+async fn with_fresh_oauth_token(credentials: &Credentials) -> Result<Credentials> {
+    let Some(token) = &credentials.oauth_token else {
+        return Ok(credentials.clone());
+    };
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    if !token.is_expired(now) {
+        return Ok(credentials.clone());
+    }
+
+    let refreshed = crate::oauth::refresh_access_token(&token.token_url, &token.client_id, &token.refresh_token)
+        .await
+        .context("Failed to refresh OAuth2 access token")?;
+
+    let mut credentials = credentials.clone();
+    credentials.oauth_token = Some(refreshed);
+    Ok(credentials)
+}
+
+/// Like [`with_fresh_oauth_token`], but refreshes unconditionally regardless of the token's
+/// recorded expiry. Used to recover from an auth failure the expiry check didn't predict (clock
+/// skew, or the provider revoking the token early) by forcing one retry with a brand-new token.
+async fn with_forcibly_refreshed_oauth_token(credentials: &Credentials) -> Result<Credentials> {
+    let Some(token) = &credentials.oauth_token else {
+        return Ok(credentials.clone());
+    };
+
+    let refreshed = crate::oauth::refresh_access_token(&token.token_url, &token.client_id, &token.refresh_token)
+        .await
+        .context("Failed to refresh OAuth2 access token")?;
+
+    let mut credentials = credentials.clone();
+    credentials.oauth_token = Some(refreshed);
+    Ok(credentials)
+}
+
+/// Whether a blocking IMAP/SMTP call's error looks like an authentication failure, so a caller
+/// holding an OAuth2 token knows it's worth a forced refresh-and-retry rather than giving up.
+fn is_auth_failure(err: &anyhow::Error) -> bool {
+    let message = err.to_string().to_lowercase();
+    message.contains("xoauth2") || message.contains("authentication failed") || message.contains("auth error")
+}
+
+/// Authenticate an IMAP session, using XOAUTH2 when the account has an OAuth2 token
+/// and falling back to plain LOGIN otherwise.
+fn imap_login<T: std::io::Read + std::io::Write>(
+    client: imap::Client<T>,
+    credentials: &Credentials,
+) -> Result<imap::Session<T>> {
+    if let Some(token) = &credentials.oauth_token {
+        let sasl_string = crate::credentials::xoauth2_sasl_string(&credentials.imap_username, &token.access_token);
+        client
+            .authenticate("XOAUTH2", &XOAuth2Authenticator { sasl_string })
+            .map_err(|e| anyhow!("IMAP XOAUTH2 authentication failed: {:?}", e.0))
+    } else {
+        client
+            .login(&credentials.imap_username, &credentials.imap_password)
+            .map_err(|e| anyhow!("IMAP login failed: {:?}", e.0))
+    }
+}
 
 /// Inbox rule for automatic filtering and organization
 #[derive(Debug, Clone)]
@@ -24,6 +200,48 @@ pub struct InboxRule {
     pub condition: RuleCondition,
     pub action: RuleAction,
     pub enabled: bool,
+    /// Scope this rule to one account by name (a key into [`EmailSyncManager`]'s account map),
+    /// or `None` to run it against every account's mail.
+    pub account: Option<String>,
+}
+
+/// Which part of a message a [`RuleCondition::Regex`] or [`RuleCondition::HeaderContains`]-style
+/// check reads. `Header` names an arbitrary MIME header (case-insensitively) looked up in
+/// [`DbEmail::headers`].
+#[derive(Debug, Clone)]
+pub enum MatchField {
+    From,
+    To,
+    Subject,
+    Body,
+    Header(String),
+}
+
+impl MatchField {
+    /// Pull the text this field refers to out of `email`. Always `None` for `Header`, which
+    /// callers look up themselves in [`DbEmail::headers`] via `find_header`.
+    fn read<'a>(&self, email: &'a DbEmail) -> Option<&'a str> {
+        match self {
+            MatchField::From => Some(&email.from_address),
+            MatchField::To => Some(&email.to_addresses),
+            MatchField::Subject => Some(&email.subject),
+            MatchField::Body => Some(&email.body),
+            MatchField::Header(_) => None,
+        }
+    }
+}
+
+/// Find the value of header `name` (case-insensitive) in `headers`, encoded as `"Name: value"`
+/// lines joined with `\n` by [`crate::mime::parse_message`].
+fn find_header<'a>(headers: &'a str, name: &str) -> Option<&'a str> {
+    headers.lines().find_map(|line| {
+        let (header_name, value) = line.split_once(':')?;
+        if header_name.trim().eq_ignore_ascii_case(name) {
+            Some(value.trim())
+        } else {
+            None
+        }
+    })
 }
 
 /// Condition for inbox rules
@@ -35,6 +253,31 @@ pub enum RuleCondition {
     FromEquals(String),
     And(Box<RuleCondition>, Box<RuleCondition>),
     Or(Box<RuleCondition>, Box<RuleCondition>),
+    /// N-ary generalization of [`Self::And`] for rules with more than two clauses.
+    All(Vec<RuleCondition>),
+    /// N-ary generalization of [`Self::Or`] for rules with more than two clauses.
+    Any(Vec<RuleCondition>),
+    /// Invert a condition, e.g. `Not(Box::new(FromContains("newsletter")))`.
+    Not(Box<RuleCondition>),
+    /// `field` matches `pattern` as a regular expression (via the `regex` crate, compiled each
+    /// time this is evaluated). An invalid `pattern` never matches rather than erroring, since a
+    /// rule condition has no way to surface a compile failure to the user at match time.
+    /// [`EmailSyncManager::apply_rules`]/[`EmailSyncManager::apply_rules_batch`] instead go
+    /// through [`CompiledCondition`], which compiles each pattern in the tree once per rule
+    /// rather than once per email.
+    Regex { field: MatchField, pattern: String },
+    /// An arbitrary MIME header (case-insensitive name) contains `value` (case-insensitive
+    /// substring match), read from [`DbEmail::headers`].
+    HeaderContains(String, String),
+    /// An arbitrary MIME header (case-insensitive name) matches `regex`. Sugar over
+    /// `Regex { field: MatchField::Header(name), pattern: regex }`.
+    HeaderMatches { name: String, regex: String },
+    /// Sugar over `Regex { field: MatchField::Subject, pattern }`.
+    SubjectRegex(String),
+    /// Sugar over `Regex { field: MatchField::Body, pattern }`.
+    BodyRegex(String),
+    /// The message carried at least one MIME attachment, see [`DbEmail::has_attachment`].
+    HasAttachment,
 }
 
 impl RuleCondition {
@@ -59,6 +302,137 @@ impl RuleCondition {
             RuleCondition::Or(left, right) => {
                 left.matches(email) || right.matches(email)
             }
+            RuleCondition::All(conditions) => conditions.iter().all(|c| c.matches(email)),
+            RuleCondition::Any(conditions) => conditions.iter().any(|c| c.matches(email)),
+            RuleCondition::Not(inner) => !inner.matches(email),
+            RuleCondition::Regex { field, pattern } => {
+                let Ok(re) = regex::Regex::new(pattern) else {
+                    return false;
+                };
+                let text = match field {
+                    MatchField::Header(name) => email.headers.as_deref().and_then(|h| find_header(h, name)),
+                    other => other.read(email),
+                };
+                match text {
+                    Some(text) => re.is_match(text),
+                    None => false,
+                }
+            }
+            RuleCondition::HeaderContains(name, value) => {
+                match email.headers.as_deref().and_then(|h| find_header(h, name)) {
+                    Some(header_value) => header_value.to_lowercase().contains(&value.to_lowercase()),
+                    None => false,
+                }
+            }
+            RuleCondition::HeaderMatches { name, regex } => {
+                let Ok(re) = regex::Regex::new(regex) else {
+                    return false;
+                };
+                match email.headers.as_deref().and_then(|h| find_header(h, name)) {
+                    Some(value) => re.is_match(value),
+                    None => false,
+                }
+            }
+            RuleCondition::SubjectRegex(pattern) => {
+                regex::Regex::new(pattern).is_ok_and(|re| re.is_match(&email.subject))
+            }
+            RuleCondition::BodyRegex(pattern) => {
+                regex::Regex::new(pattern).is_ok_and(|re| re.is_match(&email.body))
+            }
+            RuleCondition::HasAttachment => email.has_attachment,
+        }
+    }
+}
+
+/// A [`RuleCondition`] tree with every regex pre-compiled, built once by
+/// [`EmailSyncManager::compiled_condition`] and cached there per rule, so
+/// [`EmailSyncManager::apply_rules`]/[`EmailSyncManager::apply_rules_batch`] compile each pattern
+/// once per rule rather than once per email when scanning a large mailbox. Mirrors
+/// [`RuleCondition`] one-for-one; an invalid regex compiles to `None` and then never matches,
+/// same as [`RuleCondition::matches`]'s behavior for a bad pattern.
+#[derive(Debug)]
+enum CompiledCondition {
+    FromContains(String),
+    SubjectContains(String),
+    BodyContains(String),
+    FromEquals(String),
+    And(Box<CompiledCondition>, Box<CompiledCondition>),
+    Or(Box<CompiledCondition>, Box<CompiledCondition>),
+    All(Vec<CompiledCondition>),
+    Any(Vec<CompiledCondition>),
+    Not(Box<CompiledCondition>),
+    Regex { field: MatchField, compiled: Option<regex::Regex> },
+    HeaderContains(String, String),
+    HeaderMatches { name: String, compiled: Option<regex::Regex> },
+    SubjectRegex(Option<regex::Regex>),
+    BodyRegex(Option<regex::Regex>),
+    HasAttachment,
+}
+
+impl CompiledCondition {
+    fn compile(condition: &RuleCondition) -> Self {
+        match condition {
+            RuleCondition::FromContains(pattern) => Self::FromContains(pattern.clone()),
+            RuleCondition::SubjectContains(pattern) => Self::SubjectContains(pattern.clone()),
+            RuleCondition::BodyContains(pattern) => Self::BodyContains(pattern.clone()),
+            RuleCondition::FromEquals(addr) => Self::FromEquals(addr.clone()),
+            RuleCondition::And(left, right) => {
+                Self::And(Box::new(Self::compile(left)), Box::new(Self::compile(right)))
+            }
+            RuleCondition::Or(left, right) => {
+                Self::Or(Box::new(Self::compile(left)), Box::new(Self::compile(right)))
+            }
+            RuleCondition::All(conditions) => Self::All(conditions.iter().map(Self::compile).collect()),
+            RuleCondition::Any(conditions) => Self::Any(conditions.iter().map(Self::compile).collect()),
+            RuleCondition::Not(inner) => Self::Not(Box::new(Self::compile(inner))),
+            RuleCondition::Regex { field, pattern } => {
+                Self::Regex { field: field.clone(), compiled: regex::Regex::new(pattern).ok() }
+            }
+            RuleCondition::HeaderContains(name, value) => Self::HeaderContains(name.clone(), value.clone()),
+            RuleCondition::HeaderMatches { name, regex } => {
+                Self::HeaderMatches { name: name.clone(), compiled: regex::Regex::new(regex).ok() }
+            }
+            RuleCondition::SubjectRegex(pattern) => Self::SubjectRegex(regex::Regex::new(pattern).ok()),
+            RuleCondition::BodyRegex(pattern) => Self::BodyRegex(regex::Regex::new(pattern).ok()),
+            RuleCondition::HasAttachment => Self::HasAttachment,
+        }
+    }
+
+    fn matches(&self, email: &DbEmail) -> bool {
+        match self {
+            Self::FromContains(pattern) => email.from_address.to_lowercase().contains(&pattern.to_lowercase()),
+            Self::SubjectContains(pattern) => email.subject.to_lowercase().contains(&pattern.to_lowercase()),
+            Self::BodyContains(pattern) => email.body.to_lowercase().contains(&pattern.to_lowercase()),
+            Self::FromEquals(addr) => email.from_address.to_lowercase() == addr.to_lowercase(),
+            Self::And(left, right) => left.matches(email) && right.matches(email),
+            Self::Or(left, right) => left.matches(email) || right.matches(email),
+            Self::All(conditions) => conditions.iter().all(|c| c.matches(email)),
+            Self::Any(conditions) => conditions.iter().any(|c| c.matches(email)),
+            Self::Not(inner) => !inner.matches(email),
+            Self::Regex { field, compiled } => {
+                let Some(re) = compiled else { return false };
+                let text = match field {
+                    MatchField::Header(name) => email.headers.as_deref().and_then(|h| find_header(h, name)),
+                    other => other.read(email),
+                };
+                text.is_some_and(|text| re.is_match(text))
+            }
+            Self::HeaderContains(name, value) => {
+                match email.headers.as_deref().and_then(|h| find_header(h, name)) {
+                    Some(header_value) => header_value.to_lowercase().contains(&value.to_lowercase()),
+                    None => false,
+                }
+            }
+            Self::HeaderMatches { name, compiled } => {
+                let Some(re) = compiled else { return false };
+                match email.headers.as_deref().and_then(|h| find_header(h, name)) {
+                    Some(value) => re.is_match(value),
+                    None => false,
+                }
+            }
+            Self::SubjectRegex(compiled) => compiled.as_ref().is_some_and(|re| re.is_match(&email.subject)),
+            Self::BodyRegex(compiled) => compiled.as_ref().is_some_and(|re| re.is_match(&email.body)),
+            Self::HasAttachment => email.has_attachment,
         }
     }
 }
@@ -71,6 +445,274 @@ pub enum RuleAction {
     Flag,
     Delete,
     Archive,
+    /// Forward the message on to `to` as-is. Executed over
+    /// [`EmailSyncManager::outbound_transport`] rather than against the IMAP server, see
+    /// [`EmailSyncManager::execute_outbound_action`].
+    Forward { to: String },
+    /// Reply to the message's sender with `body` as a templated auto-response. Executed over
+    /// [`EmailSyncManager::outbound_transport`], see [`EmailSyncManager::execute_outbound_action`].
+    ReplyWithTemplate { body: String },
+}
+
+/// Quote `value` as a Sieve (RFC 5228 §2.4.2) quoted-string: wrap in `"..."`, backslash-escaping
+/// any embedded `\` or `"` so the value can't break out of the string.
+fn sieve_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl RuleCondition {
+    /// Render this condition as a Sieve (RFC 5228 §5) test, e.g. `header :contains "from"
+    /// "alice"`, for [`compile_rules_to_sieve`].
+    pub fn to_sieve(&self) -> String {
+        match self {
+            RuleCondition::FromContains(pattern) => {
+                format!("header :contains \"from\" {}", sieve_quote(pattern))
+            }
+            RuleCondition::SubjectContains(pattern) => {
+                format!("header :contains \"subject\" {}", sieve_quote(pattern))
+            }
+            RuleCondition::BodyContains(pattern) => format!("body :contains {}", sieve_quote(pattern)),
+            RuleCondition::FromEquals(addr) => format!("address :is \"from\" {}", sieve_quote(addr)),
+            RuleCondition::And(left, right) => format!("allof({}, {})", left.to_sieve(), right.to_sieve()),
+            RuleCondition::Or(left, right) => format!("anyof({}, {})", left.to_sieve(), right.to_sieve()),
+            RuleCondition::All(conditions) => {
+                format!("allof({})", conditions.iter().map(|c| c.to_sieve()).collect::<Vec<_>>().join(", "))
+            }
+            RuleCondition::Any(conditions) => {
+                format!("anyof({})", conditions.iter().map(|c| c.to_sieve()).collect::<Vec<_>>().join(", "))
+            }
+            RuleCondition::Not(inner) => format!("not {}", inner.to_sieve()),
+            RuleCondition::Regex { field, pattern } => match field {
+                MatchField::From => format!("header :regex \"from\" {}", sieve_quote(pattern)),
+                MatchField::To => format!("header :regex \"to\" {}", sieve_quote(pattern)),
+                MatchField::Subject => format!("header :regex \"subject\" {}", sieve_quote(pattern)),
+                MatchField::Body => format!("body :regex {}", sieve_quote(pattern)),
+                MatchField::Header(name) => format!("header :regex {} {}", sieve_quote(name), sieve_quote(pattern)),
+            },
+            RuleCondition::HeaderContains(name, value) => {
+                format!("header :contains {} {}", sieve_quote(name), sieve_quote(value))
+            }
+            RuleCondition::HeaderMatches { name, regex } => {
+                format!("header :regex {} {}", sieve_quote(name), sieve_quote(regex))
+            }
+            RuleCondition::SubjectRegex(pattern) => format!("header :regex \"subject\" {}", sieve_quote(pattern)),
+            RuleCondition::BodyRegex(pattern) => format!("body :regex {}", sieve_quote(pattern)),
+            RuleCondition::HasAttachment => {
+                "header :mime :anychild :contains \"content-disposition\" \"attachment\"".to_string()
+            }
+        }
+    }
+
+    /// Whether this condition (recursively) needs the `regex` Sieve extension.
+    fn uses_regex(&self) -> bool {
+        match self {
+            RuleCondition::Regex { .. }
+            | RuleCondition::HeaderMatches { .. }
+            | RuleCondition::SubjectRegex(_)
+            | RuleCondition::BodyRegex(_) => true,
+            RuleCondition::Not(inner) => inner.uses_regex(),
+            RuleCondition::And(left, right) | RuleCondition::Or(left, right) => {
+                left.uses_regex() || right.uses_regex()
+            }
+            RuleCondition::All(conditions) | RuleCondition::Any(conditions) => {
+                conditions.iter().any(|c| c.uses_regex())
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether this condition (recursively) needs the `mime` Sieve extension.
+    fn uses_mime(&self) -> bool {
+        match self {
+            RuleCondition::HasAttachment => true,
+            RuleCondition::Not(inner) => inner.uses_mime(),
+            RuleCondition::And(left, right) | RuleCondition::Or(left, right) => {
+                left.uses_mime() || right.uses_mime()
+            }
+            RuleCondition::All(conditions) | RuleCondition::Any(conditions) => {
+                conditions.iter().any(|c| c.uses_mime())
+            }
+            _ => false,
+        }
+    }
+}
+
+impl RuleAction {
+    /// Render this action as a Sieve (RFC 5228 §4) command, for [`compile_rules_to_sieve`].
+    /// `Flag`/`MarkAsRead` need the `imap4flags` extension's `setflag`; `Archive` has no
+    /// standalone Sieve verb, so (like [`ImapClient::apply_actions`]'s `Archive` case) it's
+    /// modeled as filing into a folder named `Archive`. `Forward`/`ReplyWithTemplate` are
+    /// only executed locally via [`EmailSyncManager::execute_outbound_action`] - a Sieve script
+    /// runs server-side, so they're rendered with their closest standalone Sieve equivalents
+    /// (`redirect`/`vacation`) for completeness rather than round-tripped exactly.
+    pub fn to_sieve(&self) -> String {
+        match self {
+            RuleAction::MoveToFolder(folder) => format!("fileinto {};", sieve_quote(folder)),
+            RuleAction::Archive => format!("fileinto {};", sieve_quote("Archive")),
+            RuleAction::MarkAsRead => "setflag \"\\\\Seen\";".to_string(),
+            RuleAction::Flag => "setflag \"\\\\Flagged\";".to_string(),
+            RuleAction::Delete => "discard;".to_string(),
+            RuleAction::Forward { to } => format!("redirect {};", sieve_quote(to)),
+            RuleAction::ReplyWithTemplate { body } => format!("vacation {};", sieve_quote(body)),
+        }
+    }
+}
+
+impl InboxRule {
+    /// Render this rule as a Sieve `if` block, for [`compile_rules_to_sieve`].
+    fn to_sieve(&self) -> String {
+        format!("if {} {{\n    {}\n}}", self.condition.to_sieve(), self.action.to_sieve())
+    }
+}
+
+/// Compile every enabled rule in `rules` into one Sieve (RFC 5228) script, in order (every
+/// matching rule's action runs, same as [`EmailSyncManager::apply_rules`]), with the `require` statements its actions
+/// need so it's ready for [`ManageSieveClient::put_script`]. Disabled rules are skipped.
+pub fn compile_rules_to_sieve(rules: &[InboxRule]) -> String {
+    let mut requires = vec!["fileinto"];
+    let needs_flags = rules
+        .iter()
+        .any(|r| r.enabled && matches!(r.action, RuleAction::Flag | RuleAction::MarkAsRead));
+    if needs_flags {
+        requires.push("imap4flags");
+    }
+    if rules.iter().any(|r| r.enabled && r.condition.uses_regex()) {
+        requires.push("regex");
+    }
+    if rules.iter().any(|r| r.enabled && r.condition.uses_mime()) {
+        requires.push("mime");
+    }
+    if rules.iter().any(|r| r.enabled && matches!(r.action, RuleAction::ReplyWithTemplate { .. })) {
+        requires.push("vacation");
+    }
+
+    let require_line = format!(
+        "require [{}];\n",
+        requires.iter().map(|r| sieve_quote(r)).collect::<Vec<_>>().join(", ")
+    );
+
+    let body = rules
+        .iter()
+        .filter(|r| r.enabled)
+        .map(InboxRule::to_sieve)
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    format!("{}\n{}\n", require_line, body)
+}
+
+/// ManageSieve (RFC 5804) client: uploads a compiled Sieve script (see
+/// [`compile_rules_to_sieve`]) to the mail server so the user's inbox rules keep running even
+/// when the app is closed. ManageSieve has no crates.io client, so - like the rest of this
+/// module's protocol handling - it's a small hand-rolled line protocol over a TLS socket rather
+/// than a new dependency.
+pub struct ManageSieveClient {
+    stream: native_tls::TlsStream<std::net::TcpStream>,
+}
+
+impl ManageSieveClient {
+    /// Connect to `host:port` (servers conventionally listen on 4190), read the greeting, and
+    /// authenticate with `user`/`password` via SASL PLAIN (RFC 4616) - the one mechanism every
+    /// ManageSieve server is required to support.
+    pub fn connect(host: &str, port: u16, user: &str, password: &str) -> Result<Self> {
+        let tcp = std::net::TcpStream::connect((host, port))
+            .context(format!("Failed to connect to {}:{}", host, port))?;
+        let tls = native_tls::TlsConnector::builder()
+            .build()
+            .context("Failed to build TLS connector")?;
+        let stream = tls
+            .connect(host, tcp)
+            .context("Failed to establish TLS connection")?;
+
+        let mut client = Self { stream };
+        client.read_response().context("Failed to read ManageSieve greeting")?;
+
+        let initial_response = base64::encode(format!("\0{}\0{}", user, password).as_bytes());
+        client.send_line(&format!("AUTHENTICATE \"PLAIN\" \"{}\"", initial_response))?;
+        client.read_response().context("ManageSieve AUTHENTICATE failed")?;
+
+        Ok(client)
+    }
+
+    /// `PUTSCRIPT` - upload `script` under `name`, replacing any existing script of that name.
+    pub fn put_script(&mut self, name: &str, script: &str) -> Result<()> {
+        self.send_line(&format!("PUTSCRIPT {} {{{}+}}", sieve_quote(name), script.len()))?;
+        self.send_raw(script.as_bytes())?;
+        self.send_raw(b"\r\n")?;
+        self.read_response().context("PUTSCRIPT failed")?;
+        Ok(())
+    }
+
+    /// `SETACTIVE` - make the script named `name` the one the server runs on incoming mail.
+    pub fn set_active(&mut self, name: &str) -> Result<()> {
+        self.send_line(&format!("SETACTIVE {}", sieve_quote(name)))?;
+        self.read_response().context("SETACTIVE failed")?;
+        Ok(())
+    }
+
+    /// `LISTSCRIPTS` - the scripts stored on the server, with the currently-active one flagged.
+    pub fn list_scripts(&mut self) -> Result<Vec<(String, bool)>> {
+        self.send_line("LISTSCRIPTS")?;
+        let lines = self.read_response().context("LISTSCRIPTS failed")?;
+
+        Ok(lines
+            .iter()
+            .filter_map(|line| {
+                let name = line.split('"').nth(1)?;
+                let is_active = line.to_uppercase().ends_with("ACTIVE");
+                Some((name.to_string(), is_active))
+            })
+            .collect())
+    }
+
+    /// Compile `rules` to Sieve and upload+activate it in one step, under the conventional name
+    /// `"tume"` - the whole-ruleset path [`EmailSyncManager::apply_rules`]'s client-side
+    /// evaluation falls back to when the app isn't running.
+    pub fn upload_rules(&mut self, rules: &[InboxRule]) -> Result<()> {
+        let script = compile_rules_to_sieve(rules);
+        self.put_script("tume", &script)?;
+        self.set_active("tume")
+    }
+
+    fn send_line(&mut self, line: &str) -> Result<()> {
+        use std::io::Write;
+        self.stream
+            .write_all(format!("{}\r\n", line).as_bytes())
+            .context("Failed to write to ManageSieve connection")
+    }
+
+    fn send_raw(&mut self, bytes: &[u8]) -> Result<()> {
+        use std::io::Write;
+        self.stream.write_all(bytes).context("Failed to write to ManageSieve connection")
+    }
+
+    /// Read response lines until the tagged `OK`/`NO`/`BYE` line, returning everything before it.
+    /// Good enough for the fixed-shape responses `PUTSCRIPT`/`SETACTIVE`/`LISTSCRIPTS` return;
+    /// multi-line string literals (`{N+}`) in a response are passed through as opaque lines
+    /// since none of the commands above need to parse one back out.
+    fn read_response(&mut self) -> Result<Vec<String>> {
+        use std::io::{BufRead, BufReader};
+        let mut reader = BufReader::new(&mut self.stream);
+        let mut lines = Vec::new();
+
+        loop {
+            let mut line = String::new();
+            let bytes_read = reader.read_line(&mut line).context("Failed to read from ManageSieve connection")?;
+            if bytes_read == 0 {
+                return Err(anyhow!("ManageSieve connection closed unexpectedly"));
+            }
+            let trimmed = line.trim_end().to_string();
+            let upper = trimmed.to_uppercase();
+
+            if upper.starts_with("OK") {
+                return Ok(lines);
+            }
+            if upper.starts_with("NO") || upper.starts_with("BYE") {
+                return Err(anyhow!("ManageSieve command failed: {}", trimmed));
+            }
+            lines.push(trimmed);
+        }
+    }
 }
 
 /// Status of email sync operation
@@ -80,6 +722,16 @@ pub enum SyncStatus {
     Error(String),
 }
 
+/// One mailbox as reported by the server's IMAP `LIST` command.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImapFolder {
+    pub name: String,
+    pub delimiter: String,
+    /// RFC 6154 special-use attribute (`\Sent`, `\Drafts`, `\Trash`, ...), when the server
+    /// advertises one for this mailbox.
+    pub special_use: Option<String>,
+}
+
 /// IMAP email fetcher
 #[derive(Clone, Debug)]
 pub struct ImapClient {
@@ -92,17 +744,34 @@ impl ImapClient {
         Self { credentials }
     }
 
-    /// Fetch emails from IMAP server
-    pub async fn fetch_emails(&self, folder: &str, limit: Option<usize>) -> Result<Vec<DbEmail>> {
-        let credentials = self.credentials.clone();
+    /// Fetch emails from IMAP server, along with any attachments each message carried.
+    pub async fn fetch_emails(
+        &self,
+        folder: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<(DbEmail, Vec<crate::mime::ParsedAttachment>)>> {
+        let credentials = with_fresh_oauth_token(&self.credentials).await?;
         let folder = folder.to_string();
-        
+
         // Use spawn_blocking to run blocking IMAP operations in a thread pool
-        tokio::task::spawn_blocking(move || {
-            Self::fetch_emails_blocking(&credentials, &folder, limit)
+        let result = tokio::task::spawn_blocking({
+            let credentials = credentials.clone();
+            let folder = folder.clone();
+            move || Self::fetch_emails_blocking(&credentials, &folder, limit)
         })
         .await
-        .context("Task join error")?
+        .context("Task join error")?;
+
+        match result {
+            // The expiry check let an invalid token through; force a refresh and retry once.
+            Err(e) if credentials.oauth_token.is_some() && is_auth_failure(&e) => {
+                let credentials = with_forcibly_refreshed_oauth_token(&credentials).await?;
+                tokio::task::spawn_blocking(move || Self::fetch_emails_blocking(&credentials, &folder, limit))
+                    .await
+                    .context("Task join error")?
+            }
+            other => other,
+        }
     }
 
     /// Blocking IMAP fetch implementation
@@ -110,22 +779,12 @@ impl ImapClient {
         credentials: &Credentials,
         folder: &str,
         limit: Option<usize>,
-    ) -> Result<Vec<DbEmail>> {
-        // Connect to IMAP server with TLS
-        let domain = &credentials.imap_server;
-        let port = credentials.imap_port;
-        
-        let tls = native_tls::TlsConnector::builder()
-            .build()
-            .context("Failed to build TLS connector")?;
-        
-        let client = imap::connect((domain.as_str(), port), domain, &tls)
-            .context(format!("Failed to connect to {}:{}", domain, port))?;
+    ) -> Result<Vec<(DbEmail, Vec<crate::mime::ParsedAttachment>)>> {
+        // Connect to IMAP server, honoring the account's security type
+        let client = imap_connect(credentials)?;
 
-        // Login
-        let mut session = client
-            .login(&credentials.imap_username, &credentials.imap_password)
-            .map_err(|e| anyhow!("IMAP login failed: {:?}", e.0))?;
+        // Login (XOAUTH2 when the account carries an OAuth2 token, plain LOGIN otherwise)
+        let mut session = imap_login(client, credentials)?;
 
         // Select mailbox
         session.select(folder)
@@ -155,9 +814,9 @@ impl ImapClient {
                     for fetch in messages.iter() {
                         if let Some(body) = fetch.body() {
                             match Self::parse_email(body, fetch.flags(), folder) {
-                                Ok(email) => emails.push(email),
+                                Ok(parsed) => emails.push(parsed),
                                 Err(e) => {
-                                    eprintln!("Failed to parse email {}: {}", msg_id, e);
+                                    log::warn!("Failed to parse email {}: {}", msg_id, e);
                                     continue;
                                 }
                             }
@@ -165,7 +824,7 @@ impl ImapClient {
                     }
                 }
                 Err(e) => {
-                    eprintln!("Failed to fetch message {}: {}", msg_id, e);
+                    log::warn!("Failed to fetch message {}: {}", msg_id, e);
                     continue;
                 }
             }
@@ -177,8 +836,134 @@ impl ImapClient {
         Ok(emails)
     }
 
-    /// Parse email from raw RFC822 bytes
-    fn parse_email(body: &[u8], flags: &[imap::types::Flag], folder: &str) -> Result<DbEmail> {
+    /// UID-based incremental sync: reads `UIDVALIDITY`/`UIDNEXT` off the `SELECT` response and,
+    /// unless `UIDVALIDITY` changed since `state` was last persisted (which invalidates every
+    /// cached UID and forces a full resync from 0), only `UID FETCH`es mail past
+    /// `state.last_seen_uid` plus a cheap `UID FETCH 1:* (FLAGS)` pass so
+    /// [`crate::sync::plan_sync`] can reconcile read/flag changes - instead of [`Self::fetch_emails`]'s
+    /// `SEARCH ALL` plus full-body refetch of every message on every sync. Returns the new
+    /// messages plus the updated cursor to persist via `EmailDatabase::upsert_sync_state`.
+    pub async fn fetch_incremental(
+        &self,
+        folder: &str,
+        state: &crate::db::FolderSyncState,
+    ) -> Result<IncrementalSyncResult> {
+        let credentials = with_fresh_oauth_token(&self.credentials).await?;
+        let folder = folder.to_string();
+        let state = state.clone();
+
+        tokio::task::spawn_blocking(move || Self::fetch_incremental_blocking(&credentials, &folder, &state))
+            .await
+            .context("Task join error")?
+    }
+
+    /// Blocking half of [`Self::fetch_incremental`].
+    fn fetch_incremental_blocking(
+        credentials: &Credentials,
+        folder: &str,
+        state: &crate::db::FolderSyncState,
+    ) -> Result<IncrementalSyncResult> {
+        let client = imap_connect(credentials)?;
+        let mut session = imap_login(client, credentials)?;
+
+        let mailbox = session.select(folder).context(format!("Failed to select folder: {}", folder))?;
+        let uidvalidity = mailbox.uid_validity.unwrap_or(0) as i64;
+        let uidnext = mailbox.uid_next.unwrap_or(1) as i64;
+
+        // A changed UIDVALIDITY means the server renumbered UIDs (e.g. the folder was recreated);
+        // every cached UID is meaningless, so resync from scratch instead of trusting them.
+        let cache_valid = state.uidvalidity != 0 && state.uidvalidity == uidvalidity;
+        let last_seen_uid = if cache_valid { state.last_seen_uid.max(0) as u32 } else { 0 };
+
+        let new_uids: Vec<u32> = session
+            .uid_search(format!("UID {}:*", last_seen_uid + 1))
+            .context("Failed to UID search for new mail")?
+            .into_iter()
+            .filter(|uid| *uid > last_seen_uid)
+            .collect();
+
+        let mut new_messages = Vec::new();
+        let mut highest_uid = last_seen_uid;
+        for uid in &new_uids {
+            let fetched = session
+                .uid_fetch(uid.to_string(), "(FLAGS RFC822)")
+                .context("Failed to UID fetch new message")?;
+            for fetch in fetched.iter() {
+                if let Some(body) = fetch.body() {
+                    match Self::parse_email(body, fetch.flags(), folder) {
+                        Ok((mut email, attachments)) => {
+                            email.imap_uid = Some(*uid);
+                            highest_uid = highest_uid.max(*uid);
+                            new_messages.push((email, attachments));
+                        }
+                        Err(e) => log::warn!("Failed to parse new message (UID {}): {}", uid, e),
+                    }
+                }
+            }
+        }
+
+        // Cheap flags-only pass over every UID the server has, for `plan_sync` to reconcile
+        // read/flag changes (and deletions) against the local cache; skipped on a full resync
+        // since `new_messages` above already covers the whole folder in that case.
+        let remote_flags = if cache_valid {
+            Self::fetch_remote_flags(&mut session)?
+        } else {
+            Vec::new()
+        };
+
+        session.logout().ok();
+
+        Ok(IncrementalSyncResult {
+            new_messages,
+            remote_flags,
+            state: crate::db::FolderSyncState {
+                account_id: state.account_id,
+                folder: folder.to_string(),
+                uidvalidity,
+                highest_modseq: state.highest_modseq,
+                last_seen_uid: highest_uid.max((uidnext.saturating_sub(1)).max(0) as u32) as i64,
+            },
+        })
+    }
+
+    /// `UID FETCH 1:* (FLAGS)` over the whole folder, as a [`crate::sync::RemoteMsgMeta`]
+    /// snapshot for [`crate::sync::plan_sync`].
+    fn fetch_remote_flags<T: std::io::Read + std::io::Write>(
+        session: &mut imap::Session<T>,
+    ) -> Result<Vec<crate::sync::RemoteMsgMeta>> {
+        let all_uids: Vec<u32> = session.uid_search("ALL").context("Failed to UID search for flags reconciliation")?.into_iter().collect();
+        if all_uids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let uid_set = all_uids.iter().map(u32::to_string).collect::<Vec<_>>().join(",");
+        let fetched = session.uid_fetch(uid_set, "FLAGS").context("Failed to UID fetch flags")?;
+
+        Ok(fetched
+            .iter()
+            .filter_map(|fetch| {
+                let uid = fetch.uid?;
+                let flags = fetch.flags();
+                Some(crate::sync::RemoteMsgMeta {
+                    uid,
+                    flagged: flags.iter().any(|f| matches!(f, imap::types::Flag::Flagged)),
+                    status: if flags.iter().any(|f| matches!(f, imap::types::Flag::Seen)) {
+                        DbEmailStatus::Read
+                    } else {
+                        DbEmailStatus::Unread
+                    },
+                })
+            })
+            .collect())
+    }
+
+    /// Parse email from raw RFC822 bytes into a [`DbEmail`] plus whatever attachments the
+    /// message carried. See [`crate::mime::parse_message`] for the MIME decoding itself.
+    fn parse_email(
+        body: &[u8],
+        flags: &[imap::types::Flag],
+        folder: &str,
+    ) -> Result<(DbEmail, Vec<crate::mime::ParsedAttachment>)> {
         let parsed = mail_parser::MessageParser::default()
             .parse(body)
             .ok_or_else(|| anyhow!("Failed to parse email"))?;
@@ -202,11 +987,14 @@ impl ImapClient {
             .unwrap_or("(No Subject)")
             .to_string();
 
-        let body_text = parsed
-            .body_text(0)
-            .or_else(|| parsed.body_html(0))
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| "".to_string());
+        let message_id = parsed.message_id().map(|s| s.to_string());
+
+        let mime = crate::mime::parse_message(body).unwrap_or_default();
+        let body_text = mime
+            .text_plain
+            .clone()
+            .or_else(|| mime.text_html.clone())
+            .unwrap_or_default();
 
         let preview = body_text
             .lines()
@@ -232,7 +1020,7 @@ impl ImapClient {
         let is_unread = !flags.iter().any(|f| matches!(f, imap::types::Flag::Seen));
         let is_flagged = flags.iter().any(|f| matches!(f, imap::types::Flag::Flagged));
 
-        Ok(DbEmail {
+        let email = DbEmail {
             id: 0,
             from_address: from,
             to_addresses: to,
@@ -240,6 +1028,7 @@ impl ImapClient {
             bcc_addresses: None,
             subject,
             body: body_text,
+            body_html: mime.text_html.clone(),
             preview,
             date,
             status: if is_unread { DbEmailStatus::Unread } else { DbEmailStatus::Read },
@@ -247,71 +1036,823 @@ impl ImapClient {
             folder: folder.to_string(),
             thread_id: None,
             account_id: None,
-        })
+            message_id,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: mime.pgp_status.clone(),
+            list_headers: mime.list_headers.clone(),
+            headers: mime.headers.clone(),
+            has_attachment: mime.has_attachment,
+        };
+
+        Ok((email, mime.attachments))
     }
 
-    /// Connect to IMAP server and test connection
-    pub async fn test_connection(&self) -> Result<()> {
-        let credentials = self.credentials.clone();
-        
-        tokio::task::spawn_blocking(move || {
-            let domain = &credentials.imap_server;
-            let port = credentials.imap_port;
-            
-            let tls = native_tls::TlsConnector::builder()
-                .build()
-                .context("Failed to build TLS connector")?;
-            
-            let client = imap::connect((domain.as_str(), port), domain, &tls)
-                .context(format!("Failed to connect to {}:{}", domain, port))?;
-
-            let mut session = client
-                .login(&credentials.imap_username, &credentials.imap_password)
-                .map_err(|e| anyhow!("IMAP login failed: {:?}", e.0))?;
+    /// Whether the server advertises the `IDLE` capability (RFC 2177). Callers fall back to
+    /// polling (see [`EmailSyncManager::sync`]) when it doesn't.
+    pub async fn supports_idle(&self) -> Result<bool> {
+        let credentials = with_fresh_oauth_token(&self.credentials).await?;
 
+        tokio::task::spawn_blocking(move || {
+            let client = imap_connect(&credentials)?;
+            let mut session = imap_login(client, &credentials)?;
+            let has_idle = session.capabilities().context("Failed to read capabilities")?.has_str("IDLE");
             session.logout().ok();
-            Ok(())
+            Ok(has_idle)
         })
         .await
         .context("Task join error")?
     }
 
-    /// Sync a specific folder
-    pub async fn sync_folder(&self, folder: &str) -> Result<usize> {
-        let emails = self.fetch_emails(folder, None).await?;
-        Ok(emails.len())
+    /// Hold a single IMAP connection open on `folder` and block until either new mail arrives
+    /// (an `IDLE` `EXISTS`/`RECENT` untagged response) or `stop` is set, re-issuing `IDLE` every
+    /// [`IDLE_REISSUE_INTERVAL`] in between to respect server timeouts. Returns the new messages
+    /// fetched past `last_uid`, or `None` if `stop` fired before any arrived.
+    pub async fn watch_idle(
+        &self,
+        folder: &str,
+        last_uid: u32,
+        stop: Arc<AtomicBool>,
+    ) -> Result<Option<IdleBatch>> {
+        let credentials = with_fresh_oauth_token(&self.credentials).await?;
+        let folder = folder.to_string();
+
+        tokio::task::spawn_blocking(move || Self::watch_idle_blocking(&credentials, &folder, last_uid, &stop))
+            .await
+            .context("Task join error")?
     }
-}
 
-/// SMTP email sender (stub implementation)
-#[derive(Clone, Debug)]
-pub struct SmtpClient {
-    credentials: Credentials,
-}
+    /// Blocking half of [`Self::watch_idle`]: one connection, looping `IDLE` until new mail
+    /// shows up or `stop` fires.
+    fn watch_idle_blocking(
+        credentials: &Credentials,
+        folder: &str,
+        last_uid: u32,
+        stop: &AtomicBool,
+    ) -> Result<Option<IdleBatch>> {
+        let client = imap_connect(credentials)?;
+        let mut session = imap_login(client, credentials)?;
+        session.select(folder).context(format!("Failed to select folder: {}", folder))?;
+
+        loop {
+            if stop.load(Ordering::Relaxed) {
+                session.logout().ok();
+                return Ok(None);
+            }
 
-impl SmtpClient {
-    /// Create a new SMTP client with credentials
-    pub fn new(credentials: Credentials) -> Self {
-        Self { credentials }
+            {
+                let mut idle = session.idle().context("Failed to start IDLE")?;
+                idle.set_keepalive(IDLE_REISSUE_INTERVAL);
+                idle.wait_keepalive().context("IDLE wait failed")?;
+            }
+
+            if stop.load(Ordering::Relaxed) {
+                session.logout().ok();
+                return Ok(None);
+            }
+
+            let new_uids: Vec<u32> = session
+                .uid_search(format!("UID {}:*", last_uid + 1))
+                .context("Failed to UID search for new mail")?
+                .into_iter()
+                .filter(|uid| *uid > last_uid)
+                .collect();
+
+            if new_uids.is_empty() {
+                // The keepalive interval elapsed with nothing new; reissue IDLE and keep waiting.
+                continue;
+            }
+
+            let mut messages = Vec::new();
+            for uid in &new_uids {
+                let fetched = session.uid_fetch(uid.to_string(), "(FLAGS RFC822)")
+                    .context("Failed to UID fetch new message")?;
+                for fetch in fetched.iter() {
+                    if let Some(body) = fetch.body() {
+                        match Self::parse_email(body, fetch.flags(), folder) {
+                            Ok((mut email, attachments)) => {
+                                email.imap_uid = Some(*uid);
+                                messages.push((email, attachments));
+                            }
+                            Err(e) => log::warn!("Failed to parse IDLE-pushed message (UID {}): {}", uid, e),
+                        }
+                    }
+                }
+            }
+
+            session.logout().ok();
+            return Ok(Some(IdleBatch {
+                last_uid: new_uids.into_iter().max().unwrap_or(last_uid),
+                messages,
+            }));
+        }
     }
 
-    /// Send an email via SMTP
-    pub async fn send_email(
+    /// Like [`Self::watch_idle`], but instead of blocking for one batch and returning, this opens
+    /// a single dedicated connection on a `spawn_blocking` thread that stays alive for the
+    /// duration of the stream, re-issuing `IDLE` every [`IDLE_REISSUE_INTERVAL`] per RFC 2177,
+    /// and reports every observed change as a [`SyncEvent`] over a bounded channel: new mail as
+    /// `NewMessage`, deletions as `Expunge`, and flag-only changes (e.g. marked read/flagged from
+    /// another client) as `FlagsChanged` - so callers don't need to refetch the whole folder to
+    /// notice those. Falls back to polling every `poll_interval` when the server doesn't
+    /// advertise `IDLE` (see [`Self::supports_idle`]). The stream ends once `stop` fires or the
+    /// receiving end is dropped.
+    pub async fn watch(
         &self,
-        to: &str,
-        subject: &str,
-        body: &str,
+        folder: &str,
+        last_uid: u32,
+        stop: Arc<AtomicBool>,
+        poll_interval: Duration,
+    ) -> Result<impl Stream<Item = SyncEvent>> {
+        let credentials = with_fresh_oauth_token(&self.credentials).await?;
+        let has_idle = self.supports_idle().await?;
+        let folder = folder.to_string();
+
+        let (tx, rx) = mpsc::channel(32);
+        tokio::task::spawn_blocking(move || {
+            Self::watch_blocking(&credentials, &folder, last_uid, has_idle, poll_interval, &stop, tx)
+        });
+
+        Ok(ReceiverStream::new(rx))
+    }
+
+    /// Blocking half of [`Self::watch`]: one connection, looping `IDLE` (or sleeping
+    /// `poll_interval` when `has_idle` is false) until `stop` fires or `tx` closes, sending a
+    /// [`SyncEvent`] for each new message, expunge, and flag change observed along the way.
+    fn watch_blocking(
+        credentials: &Credentials,
+        folder: &str,
+        mut last_uid: u32,
+        has_idle: bool,
+        poll_interval: Duration,
+        stop: &AtomicBool,
+        tx: mpsc::Sender<SyncEvent>,
+    ) {
+        let client = match imap_connect(credentials) {
+            Ok(client) => client,
+            Err(e) => { log::warn!("watch: failed to connect: {}", e); return; }
+        };
+        let mut session = match imap_login(client, credentials) {
+            Ok(session) => session,
+            Err(e) => { log::warn!("watch: failed to log in: {}", e); return; }
+        };
+        if let Err(e) = session.select(folder) {
+            log::warn!("watch: failed to select folder {}: {}", folder, e);
+            return;
+        }
+
+        while !stop.load(Ordering::Relaxed) {
+            if has_idle {
+                let idled = (|| -> Result<()> {
+                    let mut idle = session.idle().context("Failed to start IDLE")?;
+                    idle.set_keepalive(IDLE_REISSUE_INTERVAL);
+                    idle.wait_keepalive().context("IDLE wait failed")?;
+                    Ok(())
+                })();
+                if let Err(e) = idled {
+                    log::warn!("watch: IDLE wait failed: {}", e);
+                    break;
+                }
+            } else {
+                std::thread::sleep(poll_interval);
+            }
+
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+
+            // Drain whatever untagged EXPUNGE/FETCH responses piled up while we were idling.
+            for response in session.unsolicited_responses.try_iter() {
+                let event = match response {
+                    imap::types::UnsolicitedResponse::Expunge(seq) => Some(SyncEvent::Expunge { seq }),
+                    imap::types::UnsolicitedResponse::Fetch { uid: Some(uid), flags, .. } => {
+                        Some(SyncEvent::FlagsChanged {
+                            uid,
+                            is_unread: !flags.iter().any(|f| matches!(f, imap::types::Flag::Seen)),
+                            is_flagged: flags.iter().any(|f| matches!(f, imap::types::Flag::Flagged)),
+                        })
+                    }
+                    _ => None,
+                };
+                if let Some(event) = event {
+                    if tx.blocking_send(event).is_err() {
+                        session.logout().ok();
+                        return;
+                    }
+                }
+            }
+
+            // EXISTS only tells us the mailbox grew; fetch the actual new UIDs past `last_uid`.
+            let new_uids: Vec<u32> = match session.uid_search(format!("UID {}:*", last_uid + 1)) {
+                Ok(uids) => uids.into_iter().filter(|uid| *uid > last_uid).collect(),
+                Err(e) => { log::warn!("watch: UID search failed: {}", e); continue; }
+            };
+
+            for uid in new_uids {
+                let fetched = match session.uid_fetch(uid.to_string(), "(FLAGS RFC822)") {
+                    Ok(fetched) => fetched,
+                    Err(e) => { log::warn!("watch: UID fetch failed for {}: {}", uid, e); continue; }
+                };
+                for fetch in fetched.iter() {
+                    if let Some(body) = fetch.body() {
+                        match Self::parse_email(body, fetch.flags(), folder) {
+                            Ok((mut email, attachments)) => {
+                                email.imap_uid = Some(uid);
+                                last_uid = last_uid.max(uid);
+                                if tx.blocking_send(SyncEvent::NewMessage { email, attachments }).is_err() {
+                                    session.logout().ok();
+                                    return;
+                                }
+                            }
+                            Err(e) => log::warn!("watch: failed to parse pushed message (UID {}): {}", uid, e),
+                        }
+                    }
+                }
+            }
+        }
+
+        session.logout().ok();
+    }
+
+    /// List every mailbox the account exposes, via IMAP `LIST`.
+    pub async fn list_folders(&self) -> Result<Vec<ImapFolder>> {
+        let credentials = with_fresh_oauth_token(&self.credentials).await?;
+
+        tokio::task::spawn_blocking(move || Self::list_folders_blocking(&credentials))
+            .await
+            .context("Task join error")?
+    }
+
+    /// Blocking `LIST "" "*"` implementation, mapping each returned mailbox name/delimiter/
+    /// attributes into an [`ImapFolder`]. `pub(crate)` (rather than private like the other
+    /// `_blocking` helpers) so [`crate::backend::ImapBackend`] can call it directly - that trait
+    /// is itself synchronous, so it has no `.await` point to hand off to `spawn_blocking` with.
+    pub(crate) fn list_folders_blocking(credentials: &Credentials) -> Result<Vec<ImapFolder>> {
+        let client = imap_connect(credentials)?;
+        let mut session = imap_login(client, credentials)?;
+
+        let names = session.list(Some(""), Some("*")).context("Failed to LIST folders")?;
+
+        let folders = names
+            .iter()
+            .map(|name| {
+                let special_use = name.attributes().iter().find_map(|attr| match attr {
+                    imap::types::NameAttribute::Custom(value) if value.starts_with('\\') => {
+                        Some(value.to_string())
+                    }
+                    _ => None,
+                });
+
+                ImapFolder {
+                    name: name.name().to_string(),
+                    delimiter: name.delimiter().unwrap_or("/").to_string(),
+                    special_use,
+                }
+            })
+            .collect();
+
+        session.logout().ok();
+        Ok(folders)
+    }
+
+    /// Connect to IMAP server and test connection
+    pub async fn test_connection(&self) -> Result<()> {
+        let credentials = with_fresh_oauth_token(&self.credentials).await?;
+
+        tokio::task::spawn_blocking(move || {
+            let client = imap_connect(&credentials)?;
+
+            let mut session = imap_login(client, &credentials)?;
+
+            session.logout().ok();
+            Ok(())
+        })
+        .await
+        .context("Task join error")?
+    }
+
+    /// Sync a specific folder
+    pub async fn sync_folder(&self, folder: &str) -> Result<usize> {
+        let emails = self.fetch_emails(folder, None).await?;
+        Ok(emails.len())
+    }
+
+    /// Sync a folder into a local [`MaildirMirror`], fetching only UIDs the mirror doesn't
+    /// already have and dropping UIDs the server no longer reports (expunged messages).
+    ///
+    /// Returns how many messages were fetched, how many flag updates were applied, and how
+    /// many stale messages were dropped from the mirror.
+    pub async fn sync_folder_to_mirror(
+        &self,
+        folder: &str,
+        mirror: &crate::maildir::MaildirMirror,
+    ) -> Result<MirrorSyncResult> {
+        let credentials = with_fresh_oauth_token(&self.credentials).await?;
+        let folder = folder.to_string();
+        let cached_uids = mirror.cached_uids()?;
+
+        let (server_uids, fetched): (Vec<u32>, Vec<(u32, Vec<u8>, Vec<crate::maildir::MaildirFlag>)>) =
+            tokio::task::spawn_blocking({
+                let cached_uids = cached_uids.clone();
+                move || Self::sync_folder_to_mirror_blocking(&credentials, &folder, &cached_uids)
+            })
+            .await
+            .context("Task join error")??;
+
+        let (to_fetch, to_remove) = crate::maildir::diff_uids(&server_uids, &cached_uids);
+        let to_fetch: HashSet<u32> = to_fetch.into_iter().collect();
+
+        let mut stored = 0;
+        let mut flags_updated = 0;
+        for (uid, raw, flags) in fetched {
+            if to_fetch.contains(&uid) {
+                mirror.store(uid, &raw, &flags)?;
+                stored += 1;
+            } else {
+                mirror.apply_flags(uid, &flags)?;
+                flags_updated += 1;
+            }
+        }
+
+        for uid in &to_remove {
+            mirror.remove(*uid)?;
+        }
+
+        Ok(MirrorSyncResult {
+            fetched: stored,
+            flags_updated,
+            removed: to_remove.len(),
+        })
+    }
+
+    /// Blocking half of [`Self::sync_folder_to_mirror`]: UID-search the folder, then UID-fetch
+    /// flags for everything (cheap) plus the full RFC822 body for UIDs not already cached.
+    fn sync_folder_to_mirror_blocking(
+        credentials: &Credentials,
+        folder: &str,
+        cached_uids: &HashSet<u32>,
+    ) -> Result<(Vec<u32>, Vec<(u32, Vec<u8>, Vec<crate::maildir::MaildirFlag>)>)> {
+        let client = imap_connect(credentials)?;
+        let mut session = imap_login(client, credentials)?;
+
+        session.select(folder)
+            .context(format!("Failed to select folder: {}", folder))?;
+
+        let server_uids: Vec<u32> = session.uid_search("ALL")
+            .context("Failed to UID search messages")?
+            .into_iter()
+            .collect();
+
+        let mut fetched = Vec::new();
+        for uid in &server_uids {
+            let query = if cached_uids.contains(uid) { "FLAGS" } else { "(FLAGS RFC822)" };
+            let messages = session.uid_fetch(uid.to_string(), query)
+                .context("Failed to UID fetch message")?;
+
+            for message in messages.iter() {
+                let flags = Self::maildir_flags(message.flags());
+                let raw = message.body().unwrap_or_default().to_vec();
+                fetched.push((*uid, raw, flags));
+            }
+        }
+
+        session.logout().ok();
+        Ok((server_uids, fetched))
+    }
+
+    /// Translate IMAP flags to the subset [`crate::maildir::MaildirFlag`] tracks.
+    fn maildir_flags(flags: &[imap::types::Flag]) -> Vec<crate::maildir::MaildirFlag> {
+        use crate::maildir::MaildirFlag;
+
+        flags
+            .iter()
+            .filter_map(|flag| match flag {
+                imap::types::Flag::Seen => Some(MaildirFlag::Seen),
+                imap::types::Flag::Answered => Some(MaildirFlag::Answered),
+                imap::types::Flag::Flagged => Some(MaildirFlag::Flagged),
+                imap::types::Flag::Deleted => Some(MaildirFlag::Deleted),
+                imap::types::Flag::Draft => Some(MaildirFlag::Draft),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Async wrapper around [`Self::fetch_by_uid_blocking`], for [`EmailSyncManager::apply_rules_and_execute`].
+    pub async fn fetch_by_uid(
+        &self,
+        folder: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<(u32, DbEmail, Vec<crate::mime::ParsedAttachment>, Vec<u8>)>> {
+        let credentials = with_fresh_oauth_token(&self.credentials).await?;
+        let folder = folder.to_string();
+
+        tokio::task::spawn_blocking(move || Self::fetch_by_uid_blocking(&credentials, &folder, limit))
+            .await
+            .context("Task join error")?
+    }
+
+    /// Blocking: every message in `folder` (capped to `limit`, most recent first) addressed by
+    /// IMAP UID rather than sequence number, so the caller has a stable ref to hand back to
+    /// [`Self::set_flag_blocking`]/[`Self::move_message_blocking`]/[`Self::delete_message_blocking`].
+    /// Used by [`crate::backend::ImapBackend`] instead of [`Self::fetch_emails_blocking`], which
+    /// never surfaces the UID it fetched each message under. Also returns each message's raw
+    /// RFC822 bytes, for [`EmailSyncManager::apply_rules_and_execute`] to hand off to a
+    /// [`crate::maildir::MaildirStore`] when a rule archives it.
+    pub(crate) fn fetch_by_uid_blocking(
+        credentials: &Credentials,
+        folder: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<(u32, DbEmail, Vec<crate::mime::ParsedAttachment>, Vec<u8>)>> {
+        let client = imap_connect(credentials)?;
+        let mut session = imap_login(client, credentials)?;
+
+        session.select(folder)
+            .context(format!("Failed to select folder: {}", folder))?;
+
+        let mut uids: Vec<u32> = session.uid_search("ALL")
+            .context("Failed to UID search messages")?
+            .into_iter()
+            .collect();
+        uids.sort_unstable();
+        uids.reverse(); // Most recent first
+        if let Some(limit) = limit {
+            uids.truncate(limit);
+        }
+
+        let mut emails = Vec::new();
+        for uid in uids {
+            match session.uid_fetch(uid.to_string(), "(FLAGS RFC822)") {
+                Ok(messages) => {
+                    for fetch in messages.iter() {
+                        if let Some(body) = fetch.body() {
+                            match Self::parse_email(body, fetch.flags(), folder) {
+                                Ok((mut email, attachments)) => {
+                                    email.imap_uid = Some(uid);
+                                    emails.push((uid, email, attachments, body.to_vec()));
+                                }
+                                Err(e) => log::warn!("Failed to parse email (UID {}): {}", uid, e),
+                            }
+                        }
+                    }
+                }
+                Err(e) => log::warn!("Failed to UID fetch message {}: {}", uid, e),
+            }
+        }
+
+        session.logout().ok();
+        Ok(emails)
+    }
+
+    /// Blocking: fetch one message by IMAP UID (flags + full body), for
+    /// [`crate::backend::ImapBackend::fetch_body`].
+    pub(crate) fn fetch_one_by_uid_blocking(
+        credentials: &Credentials,
+        folder: &str,
+        uid: u32,
+    ) -> Result<(DbEmail, Vec<crate::mime::ParsedAttachment>)> {
+        let client = imap_connect(credentials)?;
+        let mut session = imap_login(client, credentials)?;
+
+        session.select(folder)
+            .context(format!("Failed to select folder: {}", folder))?;
+
+        let messages = session.uid_fetch(uid.to_string(), "(FLAGS RFC822)")
+            .context("Failed to UID fetch message")?;
+        let fetch = messages.iter().next()
+            .ok_or_else(|| anyhow!("No message with UID {} in {}", uid, folder))?;
+        let body = fetch.body()
+            .ok_or_else(|| anyhow!("Message UID {} in {} had no body", uid, folder))?;
+        let (mut email, attachments) = Self::parse_email(body, fetch.flags(), folder)?;
+        email.imap_uid = Some(uid);
+
+        session.logout().ok();
+        Ok((email, attachments))
+    }
+
+    /// Blocking: `STORE` a single flag on `uid` in `folder`, adding it if `value` is set and
+    /// removing it otherwise. `flag_name` is the bare flag name without its leading `\`
+    /// (e.g. `"Seen"`, `"Flagged"`).
+    pub(crate) fn set_flag_blocking(
+        credentials: &Credentials,
+        folder: &str,
+        uid: u32,
+        flag_name: &str,
+        value: bool,
+    ) -> Result<()> {
+        let client = imap_connect(credentials)?;
+        let mut session = imap_login(client, credentials)?;
+
+        session.select(folder)
+            .context(format!("Failed to select folder: {}", folder))?;
+
+        let sign = if value { "+" } else { "-" };
+        session.uid_store(uid.to_string(), format!("{}FLAGS (\\{})", sign, flag_name))
+            .context("Failed to STORE flag")?;
+
+        session.logout().ok();
+        Ok(())
+    }
+
+    /// Blocking: `COPY` `uid` into `dest_folder`, then mark it `\Deleted` and `EXPUNGE` it out
+    /// of `folder` - the portable way to move a message without relying on a server
+    /// advertising the IMAP `MOVE` extension.
+    pub(crate) fn move_message_blocking(
+        credentials: &Credentials,
+        folder: &str,
+        uid: u32,
+        dest_folder: &str,
+    ) -> Result<()> {
+        let client = imap_connect(credentials)?;
+        let mut session = imap_login(client, credentials)?;
+
+        session.select(folder)
+            .context(format!("Failed to select folder: {}", folder))?;
+        session.uid_copy(uid.to_string(), dest_folder)
+            .context("Failed to COPY message to destination folder")?;
+        session.uid_store(uid.to_string(), "+FLAGS (\\Deleted)")
+            .context("Failed to mark source message deleted")?;
+        session.expunge()
+            .context("Failed to EXPUNGE source folder")?;
+
+        session.logout().ok();
+        Ok(())
+    }
+
+    /// Blocking: mark `uid` `\Deleted` and `EXPUNGE` it out of `folder`.
+    pub(crate) fn delete_message_blocking(credentials: &Credentials, folder: &str, uid: u32) -> Result<()> {
+        let client = imap_connect(credentials)?;
+        let mut session = imap_login(client, credentials)?;
+
+        session.select(folder)
+            .context(format!("Failed to select folder: {}", folder))?;
+        session.uid_store(uid.to_string(), "+FLAGS (\\Deleted)")
+            .context("Failed to mark message deleted")?;
+        session.expunge()
+            .context("Failed to EXPUNGE folder")?;
+
+        session.logout().ok();
+        Ok(())
+    }
+
+    /// Translate a matched [`EmailSyncManager::apply_rules`] action into IMAP commands against
+    /// `uid` in `folder`, for [`EmailSyncManager::apply_rules_and_execute`]. `MoveToFolder`/
+    /// `Archive` use `UID MOVE` when the server advertises the `MOVE` capability, falling back to
+    /// `UID COPY` + `\Deleted` + `EXPUNGE` (the same portable sequence as [`Self::move_message_blocking`])
+    /// otherwise.
+    pub async fn apply_actions(&self, folder: &str, uid: u32, actions: &[RuleAction]) -> Result<()> {
+        let credentials = with_fresh_oauth_token(&self.credentials).await?;
+        let folder = folder.to_string();
+        let actions = actions.to_vec();
+
+        tokio::task::spawn_blocking(move || Self::apply_actions_blocking(&credentials, &folder, uid, &actions))
+            .await
+            .context("Task join error")?
+    }
+
+    /// Blocking half of [`Self::apply_actions`].
+    fn apply_actions_blocking(
+        credentials: &Credentials,
+        folder: &str,
+        uid: u32,
+        actions: &[RuleAction],
     ) -> Result<()> {
-        let credentials = self.credentials.clone();
+        let client = imap_connect(credentials)?;
+        let mut session = imap_login(client, credentials)?;
+
+        session.select(folder).context(format!("Failed to select folder: {}", folder))?;
+        let supports_move = session.capabilities().context("Failed to read capabilities")?.has_str("MOVE");
+
+        for action in actions {
+            match action {
+                RuleAction::Flag => {
+                    session.uid_store(uid.to_string(), "+FLAGS (\\Flagged)").context("Failed to STORE \\Flagged")?;
+                }
+                RuleAction::MarkAsRead => {
+                    session.uid_store(uid.to_string(), "+FLAGS (\\Seen)").context("Failed to STORE \\Seen")?;
+                }
+                RuleAction::MoveToFolder(dest) => Self::move_uid(&mut session, uid, dest, supports_move)?,
+                RuleAction::Archive => Self::move_uid(&mut session, uid, "Archive", supports_move)?,
+                RuleAction::Delete => {
+                    session.uid_store(uid.to_string(), "+FLAGS (\\Deleted)").context("Failed to mark message deleted")?;
+                    session.expunge().context("Failed to EXPUNGE folder")?;
+                }
+                // Outbound actions don't touch the mailbox; `EmailSyncManager::execute_outbound_action`
+                // sends them over SMTP instead.
+                RuleAction::Forward { .. } | RuleAction::ReplyWithTemplate { .. } => {}
+            }
+        }
+
+        session.logout().ok();
+        Ok(())
+    }
+
+    /// `UID MOVE` `uid` into `dest`, or the `UID COPY` + `\Deleted` + `EXPUNGE` fallback when
+    /// `supports_move` is false.
+    fn move_uid<T: std::io::Read + std::io::Write>(
+        session: &mut imap::Session<T>,
+        uid: u32,
+        dest: &str,
+        supports_move: bool,
+    ) -> Result<()> {
+        if supports_move {
+            session.uid_mv(uid.to_string(), dest).context("Failed to UID MOVE message")?;
+        } else {
+            session.uid_copy(uid.to_string(), dest).context("Failed to COPY message to destination folder")?;
+            session.uid_store(uid.to_string(), "+FLAGS (\\Deleted)").context("Failed to mark source message deleted")?;
+            session.expunge().context("Failed to EXPUNGE source folder")?;
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of [`ImapClient::sync_folder_to_mirror`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MirrorSyncResult {
+    pub fetched: usize,
+    pub flags_updated: usize,
+    pub removed: usize,
+}
+
+/// Outcome of [`ImapClient::fetch_incremental`].
+#[derive(Debug)]
+pub struct IncrementalSyncResult {
+    /// Mail past the previous cursor's `last_seen_uid`, already fetched and parsed.
+    pub new_messages: Vec<(DbEmail, Vec<crate::mime::ParsedAttachment>)>,
+    /// A flags-only snapshot of the whole folder for [`crate::sync::plan_sync`] to reconcile
+    /// read/flag changes against the local cache; empty when `state` forced a full resync, since
+    /// `new_messages` already covers the whole folder in that case.
+    pub remote_flags: Vec<crate::sync::RemoteMsgMeta>,
+    /// The cursor to persist via `EmailDatabase::upsert_sync_state` for the next call.
+    pub state: crate::db::FolderSyncState,
+}
+
+/// One batch of new mail discovered by [`ImapClient::watch_idle`]/[`EmailSyncManager::watch_idle`].
+#[derive(Debug)]
+pub struct IdleBatch {
+    /// Highest UID seen in this batch; callers pass this back in as `last_uid` for the next
+    /// call so already-seen mail isn't re-announced.
+    pub last_uid: u32,
+    pub messages: Vec<(DbEmail, Vec<crate::mime::ParsedAttachment>)>,
+}
+
+/// One incremental change observed during a live [`ImapClient::watch`] session.
+#[derive(Debug)]
+pub enum SyncEvent {
+    /// A new message arrived, already fetched and parsed.
+    NewMessage {
+        email: DbEmail,
+        attachments: Vec<crate::mime::ParsedAttachment>,
+    },
+    /// The message at IMAP sequence number `seq` was expunged (deleted) from the folder. Per
+    /// RFC 3501, `EXPUNGE` reports a sequence number rather than a UID.
+    Expunge { seq: u32 },
+    /// The message at UID `uid` had its flags changed (e.g. marked read/flagged from another
+    /// client), without being a new message.
+    FlagsChanged {
+        uid: u32,
+        is_unread: bool,
+        is_flagged: bool,
+    },
+}
+
+/// `In-Reply-To` header for threaded replies (see [`SmtpClient::send_threaded_mime_email`]).
+/// lettre ships typed headers for the common cases (`ContentType`, `Subject`, ...) but not this
+/// one, so it's implemented the same way lettre's own docs show for a custom header.
+#[derive(Clone)]
+struct InReplyToHeader(String);
+
+impl lettre::message::header::Header for InReplyToHeader {
+    fn name() -> lettre::message::header::HeaderName {
+        lettre::message::header::HeaderName::new_from_ascii_str("In-Reply-To")
+    }
+
+    fn parse(s: &str) -> std::result::Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self(s.to_string()))
+    }
+
+    fn display(&self) -> lettre::message::header::HeaderValue {
+        lettre::message::header::HeaderValue::new(Self::name(), self.0.clone())
+    }
+}
+
+/// `References` header for threaded replies; same rationale as [`InReplyToHeader`].
+#[derive(Clone)]
+struct ReferencesHeader(String);
+
+impl lettre::message::header::Header for ReferencesHeader {
+    fn name() -> lettre::message::header::HeaderName {
+        lettre::message::header::HeaderName::new_from_ascii_str("References")
+    }
+
+    fn parse(s: &str) -> std::result::Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self(s.to_string()))
+    }
+
+    fn display(&self) -> lettre::message::header::HeaderValue {
+        lettre::message::header::HeaderValue::new(Self::name(), self.0.clone())
+    }
+}
+
+/// SMTP email sender
+#[derive(Clone, Debug)]
+pub struct SmtpClient {
+    credentials: Credentials,
+}
+
+impl SmtpClient {
+    /// Create a new SMTP client with credentials
+    pub fn new(credentials: Credentials) -> Self {
+        Self { credentials }
+    }
+
+    /// Send an email via SMTP, as a plain-text message
+    pub async fn send_email(
+        &self,
+        to: &str,
+        subject: &str,
+        body: &str,
+    ) -> Result<()> {
+        self.send_mime_email(to, subject, "text/plain; charset=utf-8", body).await
+    }
+
+    /// Send an email via SMTP with an explicit `Content-Type`, so a PGP/MIME signed or
+    /// encrypted body (see [`crate::gpg::build_outgoing_body`]) goes out with its multipart
+    /// structure intact instead of being flattened to plain text.
+    pub async fn send_mime_email(
+        &self,
+        to: &str,
+        subject: &str,
+        content_type: &str,
+        body: &str,
+    ) -> Result<()> {
+        self.send_threaded_mime_email(to, subject, content_type, body, None, None).await
+    }
+
+    /// Same as [`Self::send_mime_email`], but threading the outgoing message onto an existing
+    /// conversation via `In-Reply-To`/`References` (see [`App::begin_reply`]) when the compose
+    /// draft came from a reply rather than a fresh message.
+    pub async fn send_threaded_mime_email(
+        &self,
+        to: &str,
+        subject: &str,
+        content_type: &str,
+        body: &str,
+        in_reply_to: Option<&str>,
+        references: Option<&str>,
+    ) -> Result<()> {
+        let credentials = with_fresh_oauth_token(&self.credentials).await?;
         let to = to.to_string();
         let subject = subject.to_string();
+        let content_type = content_type.to_string();
         let body = body.to_string();
-        
-        tokio::task::spawn_blocking(move || {
-            Self::send_email_blocking(&credentials, &to, &subject, &body)
+        let in_reply_to = in_reply_to.map(str::to_string);
+        let references = references.map(str::to_string);
+
+        let result = tokio::task::spawn_blocking({
+            let credentials = credentials.clone();
+            let to = to.clone();
+            let subject = subject.clone();
+            let content_type = content_type.clone();
+            let body = body.clone();
+            let in_reply_to = in_reply_to.clone();
+            let references = references.clone();
+            move || {
+                Self::send_email_blocking(
+                    &credentials,
+                    &to,
+                    &subject,
+                    &content_type,
+                    &body,
+                    in_reply_to.as_deref(),
+                    references.as_deref(),
+                )
+            }
         })
         .await
-        .context("Task join error")?
+        .context("Task join error")?;
+
+        match result {
+            // The expiry check let an invalid token through; force a refresh and retry once.
+            Err(e) if credentials.oauth_token.is_some() && is_auth_failure(&e) => {
+                let credentials = with_forcibly_refreshed_oauth_token(&credentials).await?;
+                tokio::task::spawn_blocking(move || {
+                    Self::send_email_blocking(
+                        &credentials,
+                        &to,
+                        &subject,
+                        &content_type,
+                        &body,
+                        in_reply_to.as_deref(),
+                        references.as_deref(),
+                    )
+                })
+                .await
+                .context("Task join error")?
+            }
+            other => other,
+        }
     }
 
     /// Blocking SMTP send implementation
@@ -319,115 +1860,622 @@ impl SmtpClient {
         credentials: &Credentials,
         to: &str,
         subject: &str,
+        content_type: &str,
         body: &str,
+        in_reply_to: Option<&str>,
+        references: Option<&str>,
     ) -> Result<()> {
         use lettre::message::header::ContentType;
-        use lettre::transport::smtp::authentication::Credentials as LettreCredentials;
-        use lettre::{Message, SmtpTransport, Transport};
+        use lettre::{Message, Transport};
 
         // Build email message
-        let email = Message::builder()
+        let mut builder = Message::builder()
             .from(credentials.smtp_username.parse()?)
             .to(to.parse()?)
             .subject(subject)
-            .header(ContentType::TEXT_PLAIN)
+            .header(content_type.parse::<ContentType>().context("Invalid content type")?);
+
+        if let Some(in_reply_to) = in_reply_to {
+            builder = builder.header(InReplyToHeader(in_reply_to.to_string()));
+        }
+        if let Some(references) = references {
+            builder = builder.header(ReferencesHeader(references.to_string()));
+        }
+
+        let email = builder
             .body(body.to_string())
             .context("Failed to build email")?;
 
-        // Configure SMTP transport
-        let creds = LettreCredentials::new(
-            credentials.smtp_username.clone(),
-            credentials.smtp_password.clone(),
-        );
+        // Configure SMTP transport (XOAUTH2 when the account has an OAuth2 token, plain
+        // AUTH PLAIN otherwise)
+        let (creds, mechanism) = smtp_credentials(credentials);
+
+        let mailer = smtp_transport_builder(credentials)?
+            .credentials(creds)
+            .authentication(vec![mechanism])
+            .port(credentials.smtp_port)
+            .build();
+
+        // Send email
+        mailer
+            .send(&email)
+            .context("Failed to send email via SMTP")?;
+
+        Ok(())
+    }
+
+    /// Test SMTP connection
+    pub async fn test_connection(&self) -> Result<()> {
+        let credentials = with_fresh_oauth_token(&self.credentials).await?;
+
+        tokio::task::spawn_blocking(move || {
+            use lettre::Transport;
+
+            let (creds, mechanism) = smtp_credentials(&credentials);
+
+            let mailer = smtp_transport_builder(&credentials)?
+                .credentials(creds)
+                .authentication(vec![mechanism])
+                .port(credentials.smtp_port)
+                .build();
+
+            mailer
+                .test_connection()
+                .context("SMTP connection test failed")?;
+
+            Ok(())
+        })
+        .await
+        .context("Task join error")?
+    }
+}
+
+/// SMTP client for an account's explicit [`crate::config::SendBackend::Smtp`] override. Same
+/// AUTH PLAIN send path as [`SmtpClient`], but keyed on the override's own host/login/secret
+/// instead of [`Credentials`], and able to express [`crate::config::SendEncryption::None`] (a
+/// bare unencrypted relay, e.g. a trusted local MTA) that [`crate::providers::SecurityType`] has
+/// no variant for.
+#[derive(Clone, Debug)]
+pub struct SendBackendSmtpClient {
+    host: String,
+    port: u16,
+    login: String,
+    password: String,
+    encryption: crate::config::SendEncryption,
+    from: String,
+}
+
+impl SendBackendSmtpClient {
+    pub fn new(
+        host: String,
+        port: u16,
+        login: String,
+        password: String,
+        encryption: crate::config::SendEncryption,
+        from: String,
+    ) -> Self {
+        Self { host, port, login, password, encryption, from }
+    }
+
+    /// Same as [`SmtpClient::send_threaded_mime_email`], but against this override's own
+    /// host/login/secret rather than account credentials.
+    pub async fn send_threaded_mime_email(
+        &self,
+        to: &str,
+        subject: &str,
+        content_type: &str,
+        body: &str,
+        in_reply_to: Option<&str>,
+        references: Option<&str>,
+    ) -> Result<()> {
+        let this = self.clone();
+        let to = to.to_string();
+        let subject = subject.to_string();
+        let content_type = content_type.to_string();
+        let body = body.to_string();
+        let in_reply_to = in_reply_to.map(str::to_string);
+        let references = references.map(str::to_string);
+
+        tokio::task::spawn_blocking(move || {
+            this.send_blocking(&to, &subject, &content_type, &body, in_reply_to.as_deref(), references.as_deref())
+        })
+        .await
+        .context("Task join error")?
+    }
+
+    fn send_blocking(
+        &self,
+        to: &str,
+        subject: &str,
+        content_type: &str,
+        body: &str,
+        in_reply_to: Option<&str>,
+        references: Option<&str>,
+    ) -> Result<()> {
+        use lettre::message::header::ContentType;
+        use lettre::transport::smtp::authentication::{Credentials as LettreCredentials, Mechanism};
+        use lettre::{Message, SmtpTransport, Transport};
+
+        let mut builder = Message::builder()
+            .from(self.from.parse()?)
+            .to(to.parse()?)
+            .subject(subject)
+            .header(content_type.parse::<ContentType>().context("Invalid content type")?);
+
+        if let Some(in_reply_to) = in_reply_to {
+            builder = builder.header(InReplyToHeader(in_reply_to.to_string()));
+        }
+        if let Some(references) = references {
+            builder = builder.header(ReferencesHeader(references.to_string()));
+        }
+
+        let email = builder.body(body.to_string()).context("Failed to build email")?;
+
+        let transport = match self.encryption {
+            crate::config::SendEncryption::SslTls => {
+                SmtpTransport::relay(&self.host).context("Failed to create SMTP transport")?
+            }
+            crate::config::SendEncryption::StartTls => SmtpTransport::starttls_relay(&self.host)
+                .context("Failed to create SMTP transport (STARTTLS)")?,
+            crate::config::SendEncryption::None => SmtpTransport::builder_dangerous(&self.host),
+        };
+
+        let mailer = transport
+            .credentials(LettreCredentials::new(self.login.clone(), self.password.clone()))
+            .authentication(vec![Mechanism::Plain])
+            .port(self.port)
+            .build();
+
+        mailer.send(&email).context("Failed to send email via SMTP")?;
+
+        Ok(())
+    }
+}
+
+/// Pipes the outgoing MIME message to a local command's stdin, the way `sendmail -t`/msmtp are
+/// normally invoked - [`crate::config::SendBackend::Sendmail`]'s escape hatch for a host that
+/// already has outgoing mail handled by a local MTA, with no SMTP connection of tume's own.
+#[derive(Clone, Debug)]
+pub struct SendmailClient {
+    command: String,
+    from: String,
+}
+
+impl SendmailClient {
+    pub fn new(command: String, from: String) -> Self {
+        Self { command, from }
+    }
+
+    /// Same as [`SmtpClient::send_threaded_mime_email`], but handing the built MIME message to
+    /// `self.command`'s stdin instead of opening an SMTP connection.
+    pub async fn send_threaded_mime_email(
+        &self,
+        to: &str,
+        subject: &str,
+        content_type: &str,
+        body: &str,
+        in_reply_to: Option<&str>,
+        references: Option<&str>,
+    ) -> Result<()> {
+        let this = self.clone();
+        let to = to.to_string();
+        let subject = subject.to_string();
+        let content_type = content_type.to_string();
+        let body = body.to_string();
+        let in_reply_to = in_reply_to.map(str::to_string);
+        let references = references.map(str::to_string);
+
+        tokio::task::spawn_blocking(move || {
+            this.send_blocking(&to, &subject, &content_type, &body, in_reply_to.as_deref(), references.as_deref())
+        })
+        .await
+        .context("Task join error")?
+    }
+
+    fn send_blocking(
+        &self,
+        to: &str,
+        subject: &str,
+        content_type: &str,
+        body: &str,
+        in_reply_to: Option<&str>,
+        references: Option<&str>,
+    ) -> Result<()> {
+        use lettre::message::header::ContentType;
+        use lettre::Message;
+        use std::io::Write;
+
+        let mut builder = Message::builder()
+            .from(self.from.parse()?)
+            .to(to.parse()?)
+            .subject(subject)
+            .header(content_type.parse::<ContentType>().context("Invalid content type")?);
+
+        if let Some(in_reply_to) = in_reply_to {
+            builder = builder.header(InReplyToHeader(in_reply_to.to_string()));
+        }
+        if let Some(references) = references {
+            builder = builder.header(ReferencesHeader(references.to_string()));
+        }
+
+        let email = builder.body(body.to_string()).context("Failed to build email")?;
+
+        let mut child = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to spawn sendmail command {:?}", self.command))?;
+
+        child
+            .stdin
+            .take()
+            .expect("spawned with piped stdin")
+            .write_all(&email.formatted())
+            .context("Failed to write message to sendmail command's stdin")?;
+
+        let output = child.wait_with_output().context("Failed to wait for sendmail command")?;
+        if !output.status.success() {
+            bail!(
+                "sendmail command {:?} exited with {}: {}",
+                self.command,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Where one account's outgoing mail actually goes: the credentials-derived SMTP server
+/// ([`SmtpClient`], used by [`EmailSyncManager`] when [`crate::config::Account::send_backend`]
+/// is unset), or an explicit override - a distinct SMTP server or a local sendmail-style
+/// command. Parallel to [`crate::backend::MailBackend`] on the receiving side: one enum built
+/// per send, rather than `dyn`.
+#[derive(Clone, Debug)]
+pub enum SendTransport {
+    Smtp(SmtpClient),
+    Override(SendBackendSmtpClient),
+    Sendmail(SendmailClient),
+}
+
+impl SendTransport {
+    pub async fn send_threaded_mime_email(
+        &self,
+        to: &str,
+        subject: &str,
+        content_type: &str,
+        body: &str,
+        in_reply_to: Option<&str>,
+        references: Option<&str>,
+    ) -> Result<()> {
+        match self {
+            SendTransport::Smtp(client) => {
+                client.send_threaded_mime_email(to, subject, content_type, body, in_reply_to, references).await
+            }
+            SendTransport::Override(client) => {
+                client.send_threaded_mime_email(to, subject, content_type, body, in_reply_to, references).await
+            }
+            SendTransport::Sendmail(client) => {
+                client.send_threaded_mime_email(to, subject, content_type, body, in_reply_to, references).await
+            }
+        }
+    }
+}
+
+/// Resolve `account`'s explicit [`crate::config::SendBackend`] override into a [`SendTransport`],
+/// for an account that doesn't just send through the credentials-derived SMTP server
+/// [`EmailSyncManager`] already holds - including a local-only (`Maildir`/`Notmuch`) account,
+/// which has no credentials at all. Resolves the configured secret (a blocking keyring/command
+/// lookup, see [`crate::config::Account::resolve_secret`]), so call this via `spawn_blocking`,
+/// not directly on an async task.
+pub fn send_transport_for_account(account: &crate::config::Account) -> Result<SendTransport> {
+    match &account.send_backend {
+        Some(crate::config::SendBackend::Smtp { host, port, login, encryption, .. }) => {
+            let password = account.resolve_secret().context("Failed to resolve SMTP secret")?;
+            Ok(SendTransport::Override(SendBackendSmtpClient::new(
+                host.clone(),
+                *port,
+                login.clone(),
+                password,
+                encryption.clone(),
+                account.email.clone(),
+            )))
+        }
+        Some(crate::config::SendBackend::Sendmail { command }) => {
+            Ok(SendTransport::Sendmail(SendmailClient::new(command.clone(), account.email.clone())))
+        }
+        None => Err(anyhow!("Account {:?} has no send_backend configured", account.name)),
+    }
+}
+
+/// Categorized outcome of a pre-flight connectivity/credential check, so the wizard can
+/// show "wrong password" vs. "server unreachable" instead of a raw error string.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationError {
+    /// The domain's DNS records could not be resolved
+    DnsFailure(String),
+    /// The TCP connection to the server timed out, was refused, or never completed
+    ConnectionFailure(String),
+    /// The TLS/STARTTLS handshake failed
+    TlsFailure(String),
+    /// The server rejected the LOGIN/AUTHENTICATE attempt
+    AuthRejected(String),
+    /// Anything that doesn't fit the categories above
+    Other(String),
+}
+
+impl ValidationError {
+    /// Actionable guidance shown to the user alongside the raw error
+    pub fn guidance(&self) -> &'static str {
+        match self {
+            ValidationError::DnsFailure(_) => {
+                "Could not resolve the server address. Check the hostname for typos."
+            }
+            ValidationError::ConnectionFailure(_) => {
+                "Could not reach the server. Check your network connection and port."
+            }
+            ValidationError::TlsFailure(_) => {
+                "The TLS handshake failed. Check the security type (TLS vs STARTTLS) and port."
+            }
+            ValidationError::AuthRejected(_) => {
+                "Login was rejected. Double check the username/password - this provider may require an app-specific password."
+            }
+            ValidationError::Other(_) => "An unexpected error occurred while validating the connection.",
+        }
+    }
+
+    /// Classify a lower-level connection error by inspecting its message, since the
+    /// underlying `imap`/`lettre` error types don't expose a stable category of their own.
+    fn classify(leg: &str, err: &anyhow::Error) -> Self {
+        let message = format!("{leg}: {err:#}");
+        let lower = message.to_lowercase();
+
+        if lower.contains("dns") || lower.contains("resolve") || lower.contains("lookup") {
+            ValidationError::DnsFailure(message)
+        } else if lower.contains("timed out")
+            || lower.contains("timeout")
+            || lower.contains("connection refused")
+            || lower.contains("failed to connect")
+        {
+            ValidationError::ConnectionFailure(message)
+        } else if lower.contains("tls") || lower.contains("ssl") || lower.contains("certificate") || lower.contains("handshake")
+        {
+            ValidationError::TlsFailure(message)
+        } else if lower.contains("login") || lower.contains("auth") || lower.contains("credential") || lower.contains("password")
+        {
+            ValidationError::AuthRejected(message)
+        } else {
+            ValidationError::Other(message)
+        }
+    }
+}
 
-        let mailer = SmtpTransport::relay(&credentials.smtp_server)
-            .context("Failed to create SMTP transport")?
-            .credentials(creds)
-            .port(credentials.smtp_port)
-            .build();
+/// Outcome of validating one leg (IMAP or SMTP) of an account's connection settings
+#[derive(Debug, Clone, PartialEq)]
+pub enum LegResult {
+    Ok,
+    Failed(ValidationError),
+}
 
-        // Send email
-        mailer
-            .send(&email)
-            .context("Failed to send email via SMTP")?;
+/// Structured pre-flight result covering both legs of an account's connection settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationResult {
+    pub imap: LegResult,
+    pub smtp: LegResult,
+}
 
-        Ok(())
+impl ValidationResult {
+    /// Whether both legs succeeded
+    pub fn is_ok(&self) -> bool {
+        matches!(self.imap, LegResult::Ok) && matches!(self.smtp, LegResult::Ok)
     }
+}
 
-    /// Test SMTP connection
-    pub async fn test_connection(&self) -> Result<()> {
-        let credentials = self.credentials.clone();
-        
-        tokio::task::spawn_blocking(move || {
-            use lettre::transport::smtp::authentication::Credentials as LettreCredentials;
-            use lettre::{SmtpTransport, Transport};
+/// Validate a full set of credentials before saving them: opens the IMAP connection
+/// (honoring [`SecurityType`] via implicit TLS or a STARTTLS upgrade), logs in, then opens
+/// the SMTP connection and authenticates - without sending any mail.
+///
+/// [`SecurityType`]: crate::providers::SecurityType
+pub async fn validate_credentials(credentials: &Credentials) -> ValidationResult {
+    let imap = match ImapClient::new(credentials.clone()).test_connection().await {
+        Ok(()) => LegResult::Ok,
+        Err(e) => LegResult::Failed(ValidationError::classify("IMAP", &e)),
+    };
+
+    let smtp = match SmtpClient::new(credentials.clone()).test_connection().await {
+        Ok(()) => LegResult::Ok,
+        Err(e) => LegResult::Failed(ValidationError::classify("SMTP", &e)),
+    };
+
+    ValidationResult { imap, smtp }
+}
 
-            let creds = LettreCredentials::new(
-                credentials.smtp_username.clone(),
-                credentials.smtp_password.clone(),
-            );
+/// Where [`EmailSyncManager::execute_outbound_action`] sends a `Forward`/`ReplyWithTemplate`
+/// action's message. `Smtp` is the real path, connecting to the account's configured server;
+/// `Directory` writes the composed message into a directory instead, so `execute_outbound_action`'s
+/// outbound behavior can be asserted in unit tests without a live server.
+#[derive(Clone, Debug)]
+pub enum OutboundTransport {
+    Smtp,
+    Directory { dir: PathBuf, next_id: Arc<AtomicU64> },
+}
 
-            let mailer = SmtpTransport::relay(&credentials.smtp_server)
-                .context("Failed to create SMTP transport")?
-                .credentials(creds)
-                .port(credentials.smtp_port)
-                .build();
+impl OutboundTransport {
+    /// A `Directory` transport rooted at `dir`, for tests.
+    pub fn directory(dir: impl Into<PathBuf>) -> Self {
+        Self::Directory { dir: dir.into(), next_id: Arc::new(AtomicU64::new(0)) }
+    }
+}
 
-            mailer
-                .test_connection()
-                .context("SMTP connection test failed")?;
+impl Default for OutboundTransport {
+    fn default() -> Self {
+        Self::Smtp
+    }
+}
 
-            Ok(())
-        })
-        .await
-        .context("Task join error")?
+/// Compose a minimal RFC822 message and write it to `dir` under a monotonically-numbered
+/// filename, for [`OutboundTransport::Directory`].
+fn write_outbound_message(
+    dir: &Path,
+    next_id: &AtomicU64,
+    to: &str,
+    subject: &str,
+    body: &str,
+    in_reply_to: Option<&str>,
+) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create outbound transport directory at {:?}", dir))?;
+
+    let mut message = format!("To: {}\r\nSubject: {}\r\n", to, subject);
+    if let Some(in_reply_to) = in_reply_to {
+        message.push_str(&format!("In-Reply-To: {}\r\n", in_reply_to));
     }
+    message.push_str("\r\n");
+    message.push_str(body);
+
+    let id = next_id.fetch_add(1, Ordering::Relaxed);
+    let path = dir.join(format!("{}.eml", id));
+    std::fs::write(&path, message).with_context(|| format!("Failed to write outbound message to {:?}", path))?;
+    Ok(())
 }
 
-/// Email sync manager that coordinates IMAP/SMTP operations and inbox rules
+/// Email sync manager that coordinates IMAP/SMTP operations and inbox rules across every
+/// configured account, keyed by account name (the same key used in [`crate::config::Config::accounts`]).
 #[derive(Clone, Debug)]
 pub struct EmailSyncManager {
-    imap_client: Option<ImapClient>,
-    smtp_client: Option<SmtpClient>,
+    accounts: HashMap<String, (ImapClient, SmtpClient)>,
+    /// Which account the account-unscoped operations (`imap_client`, `list_folders`,
+    /// `watch`/`watch_idle`, `is_configured`, ...) act on - the mailbox currently shown in the
+    /// UI. `sync`/`test_connections`/`send_mime_email`/`apply_rules_and_execute` take an explicit
+    /// account name instead, since those route to a specific server regardless of what's on
+    /// screen.
+    active_account: Option<String>,
     // Note: Vec is used for simplicity. For large rule sets, consider HashMap<i64, InboxRule>
     // for O(1) lookups in remove_rule, update_rule, and set_rule_enabled operations.
     rules: Vec<InboxRule>,
+    /// Per-rule [`CompiledCondition`] cache, keyed by [`InboxRule::id`], so
+    /// [`Self::apply_rules`]/[`Self::apply_rules_batch`] compile each rule's regexes once rather
+    /// than once per email. Invalidated in [`Self::add_rule`]/[`Self::update_rule`]/
+    /// [`Self::remove_rule`] since the condition a given id maps to may have changed.
+    compiled_conditions: Arc<std::sync::Mutex<HashMap<i64, Arc<CompiledCondition>>>>,
+    /// Where [`Self::apply_rules_and_execute`] archives messages an `Archive` action matched.
+    /// `None` until [`Self::with_maildir_store`] attaches one.
+    maildir_store: Option<Arc<std::sync::Mutex<crate::maildir::MaildirStore>>>,
+    /// Where [`Self::execute_outbound_action`] sends `Forward`/`ReplyWithTemplate` actions.
+    /// Defaults to [`OutboundTransport::Smtp`]; swap in [`OutboundTransport::directory`] via
+    /// [`Self::with_outbound_transport`] for tests.
+    outbound_transport: OutboundTransport,
 }
 
 impl EmailSyncManager {
-    /// Create a new sync manager with credentials
-    pub fn new(credentials: Option<Credentials>) -> Self {
-        let (imap_client, smtp_client) = if let Some(creds) = credentials {
-            (
-                Some(ImapClient::new(creds.clone())),
-                Some(SmtpClient::new(creds)),
-            )
-        } else {
-            (None, None)
-        };
-
+    /// Create a sync manager with no accounts configured yet; add one via [`Self::add_account`].
+    pub fn new() -> Self {
         Self {
-            imap_client,
-            smtp_client,
+            accounts: HashMap::new(),
+            active_account: None,
             rules: Vec::new(),
+            compiled_conditions: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            maildir_store: None,
+            outbound_transport: OutboundTransport::default(),
+        }
+    }
+
+    /// Convenience over [`Self::new`] + [`Self::add_account`] for the common single-account case.
+    pub fn with_account(name: impl Into<String>, credentials: Credentials) -> Self {
+        let mut manager = Self::new();
+        manager.add_account(name, credentials);
+        manager
+    }
+
+    /// Attach `store` so [`Self::apply_rules_and_execute`] archives messages an `Archive` action
+    /// matches into a real on-disk Maildir, for interop with mutt/neomutt/meli.
+    pub fn with_maildir_store(mut self, store: crate::maildir::MaildirStore) -> Self {
+        self.maildir_store = Some(Arc::new(std::sync::Mutex::new(store)));
+        self
+    }
+
+    /// Swap in a non-default [`OutboundTransport`] for `Forward`/`ReplyWithTemplate` actions,
+    /// e.g. [`OutboundTransport::directory`] so tests can assert on outbound sends without a
+    /// live server.
+    pub fn with_outbound_transport(mut self, transport: OutboundTransport) -> Self {
+        self.outbound_transport = transport;
+        self
+    }
+
+    /// Register `name` as an account, replacing it if already present. The first account added
+    /// becomes [`Self::active_account`]; later ones leave it untouched.
+    pub fn add_account(&mut self, name: impl Into<String>, credentials: Credentials) {
+        let name = name.into();
+        self.accounts.insert(
+            name.clone(),
+            (ImapClient::new(credentials.clone()), SmtpClient::new(credentials)),
+        );
+        if self.active_account.is_none() {
+            self.active_account = Some(name);
         }
     }
 
+    /// Drop `name` from the account map. If it was [`Self::active_account`], the active account
+    /// falls back to an arbitrary remaining one (or `None` if it was the last).
+    pub fn remove_account(&mut self, name: &str) {
+        self.accounts.remove(name);
+        if self.active_account.as_deref() == Some(name) {
+            self.active_account = self.accounts.keys().next().cloned();
+        }
+    }
+
+    /// Switch which account the account-unscoped operations (`imap_client`, `list_folders`,
+    /// `watch`/`watch_idle`, ...) act on - the mailbox currently shown in the UI.
+    pub fn set_active_account(&mut self, name: &str) -> Result<()> {
+        if !self.accounts.contains_key(name) {
+            return Err(anyhow!("No account named {:?} configured", name));
+        }
+        self.active_account = Some(name.to_string());
+        Ok(())
+    }
+
+    fn imap_for(&self, account: &str) -> Result<&ImapClient> {
+        self.accounts
+            .get(account)
+            .map(|(client, _)| client)
+            .ok_or_else(|| anyhow!("No account named {:?} configured", account))
+    }
+
+    fn smtp_for(&self, account: &str) -> Result<&SmtpClient> {
+        self.accounts
+            .get(account)
+            .map(|(_, client)| client)
+            .ok_or_else(|| anyhow!("No account named {:?} configured", account))
+    }
+
+    fn active_imap(&self) -> Result<&ImapClient> {
+        let account = self.active_account.as_deref()
+            .ok_or_else(|| anyhow!("No credentials configured. Please set up email credentials first."))?;
+        self.imap_for(account)
+    }
+
     // ============ Inbox Rules Management ============
 
     /// Add an inbox rule
     pub fn add_rule(&mut self, rule: InboxRule) {
+        self.compiled_conditions.lock().unwrap().remove(&rule.id);
         self.rules.push(rule);
     }
 
     /// Remove a rule by ID
     pub fn remove_rule(&mut self, rule_id: i64) {
         self.rules.retain(|r| r.id != rule_id);
+        self.compiled_conditions.lock().unwrap().remove(&rule_id);
     }
 
     /// Update an existing rule
     pub fn update_rule(&mut self, rule: InboxRule) {
         if let Some(existing) = self.rules.iter_mut().find(|r| r.id == rule.id) {
+            self.compiled_conditions.lock().unwrap().remove(&rule.id);
             *existing = rule;
         }
     }
@@ -444,12 +2492,27 @@ impl EmailSyncManager {
         }
     }
 
-    /// Apply rules to an email and return actions to perform
-    pub fn apply_rules(&self, email: &DbEmail) -> Vec<RuleAction> {
+    /// The compiled form of `rule`'s condition, compiling and caching it in `compiled_conditions`
+    /// on first use rather than recompiling its regexes for every email.
+    fn compiled_condition(&self, rule: &InboxRule) -> Arc<CompiledCondition> {
+        let mut cache = self.compiled_conditions.lock().unwrap();
+        cache
+            .entry(rule.id)
+            .or_insert_with(|| Arc::new(CompiledCondition::compile(&rule.condition)))
+            .clone()
+    }
+
+    /// Apply rules scoped to `account` (or global, unscoped rules) to an email and return
+    /// actions to perform.
+    pub fn apply_rules(&self, account: &str, email: &DbEmail) -> Vec<RuleAction> {
         let mut actions = Vec::new();
 
         for rule in &self.rules {
-            if rule.enabled && rule.condition.matches(email) {
+            let in_scope = match rule.account.as_deref() {
+                Some(scoped) => scoped == account,
+                None => true,
+            };
+            if rule.enabled && in_scope && self.compiled_condition(rule).matches(email) {
                 actions.push(rule.action.clone());
             }
         }
@@ -457,28 +2520,27 @@ impl EmailSyncManager {
         actions
     }
 
-    /// Apply all enabled rules to a batch of emails
-    pub fn apply_rules_batch(&self, emails: &[DbEmail]) -> Vec<(usize, Vec<RuleAction>)> {
+    /// Apply all enabled, in-scope rules to a batch of emails fetched from `account`. Each
+    /// matched entry is tagged with `account` so a unified inbox merging several accounts'
+    /// batches together still knows which server to fan its actions back out to.
+    pub fn apply_rules_batch(&self, account: &str, emails: &[DbEmail]) -> Vec<(usize, Vec<RuleAction>, String)> {
         emails
             .iter()
             .enumerate()
-            .map(|(idx, email)| (idx, self.apply_rules(email)))
-            .filter(|(_, actions)| !actions.is_empty())
+            .map(|(idx, email)| (idx, self.apply_rules(account, email), account.to_string()))
+            .filter(|(_, actions, _)| !actions.is_empty())
             .collect()
     }
 
     // ============ IMAP/SMTP Operations ============
 
-    /// Perform full email sync from IMAP inbox
-    pub async fn sync(&self, folder: &str, limit: Option<usize>) -> Result<SyncStatus> {
-        if self.imap_client.is_none() {
-            return Ok(SyncStatus::Error(
-                "No credentials configured. Please set up email credentials first.".to_string()
-            ));
-        }
+    /// Perform full email sync of `folder` from `account`'s IMAP inbox.
+    pub async fn sync(&self, account: &str, folder: &str, limit: Option<usize>) -> Result<SyncStatus> {
+        let client = match self.imap_for(account) {
+            Ok(client) => client,
+            Err(e) => return Ok(SyncStatus::Error(e.to_string())),
+        };
 
-        let client = self.imap_client.as_ref().unwrap();
-        
         match client.fetch_emails(folder, limit).await {
             Ok(emails) => {
                 let count = emails.len();
@@ -488,31 +2550,269 @@ impl EmailSyncManager {
         }
     }
 
-    /// Get IMAP client for direct operations
+    /// Get the active account's IMAP client for direct operations
     pub fn imap_client(&self) -> Option<&ImapClient> {
-        self.imap_client.as_ref()
+        self.active_account.as_ref().and_then(|name| self.accounts.get(name)).map(|(client, _)| client)
     }
 
-    /// Test both IMAP and SMTP connections
-    pub async fn test_connections(&self) -> Result<(bool, bool)> {
-        let imap_ok = if let Some(ref client) = self.imap_client {
-            client.test_connection().await.is_ok()
-        } else {
-            false
+    /// List every mailbox the active account exposes via IMAP `LIST`, for populating
+    /// [`View::FolderList`](crate::app::View::FolderList).
+    pub async fn list_folders(&self) -> Result<Vec<ImapFolder>> {
+        self.active_imap()?.list_folders().await
+    }
+
+    /// Whether the active account's server supports IMAP `IDLE`, see [`ImapClient::supports_idle`].
+    pub async fn supports_idle(&self) -> Result<bool> {
+        self.active_imap()?.supports_idle().await
+    }
+
+    /// Block until new mail arrives in `folder` on the active account (or `stop` fires), see
+    /// [`ImapClient::watch_idle`].
+    pub async fn watch_idle(&self, folder: &str, last_uid: u32, stop: Arc<AtomicBool>) -> Result<Option<IdleBatch>> {
+        self.active_imap()?.watch_idle(folder, last_uid, stop).await
+    }
+
+    /// Stream granular [`SyncEvent`]s (new mail, expunges, flag changes) from `folder` on the
+    /// active account until `stop` fires, see [`ImapClient::watch`].
+    pub async fn watch(
+        &self,
+        folder: &str,
+        last_uid: u32,
+        stop: Arc<AtomicBool>,
+        poll_interval: Duration,
+    ) -> Result<impl Stream<Item = SyncEvent>> {
+        self.active_imap()?.watch(folder, last_uid, stop, poll_interval).await
+    }
+
+    /// UID-based incremental sync of `folder` on the active account, see
+    /// [`ImapClient::fetch_incremental`].
+    pub async fn fetch_incremental(
+        &self,
+        folder: &str,
+        state: &crate::db::FolderSyncState,
+    ) -> Result<IncrementalSyncResult> {
+        self.active_imap()?.fetch_incremental(folder, state).await
+    }
+
+    /// Perform `actions` against `uid` in `folder` on `account`'s server, see
+    /// [`ImapClient::apply_actions`].
+    pub async fn apply_actions(&self, account: &str, folder: &str, uid: u32, actions: &[RuleAction]) -> Result<()> {
+        self.imap_for(account)?.apply_actions(folder, uid, actions).await
+    }
+
+    /// Fetch `folder` (capped to `limit`) from `account`, run the rule engine over every
+    /// message, and execute the resulting actions against that account's server in one pass.
+    /// An `Archive` action also hands the raw message to [`Self::with_maildir_store`]'s store, if
+    /// one is attached, so the archive survives locally in a standard on-disk Maildir. A
+    /// `Forward`/`ReplyWithTemplate` action is sent over [`Self::execute_outbound_action`].
+    /// Returns the number of messages at least one rule matched.
+    pub async fn apply_rules_and_execute(&self, account: &str, folder: &str, limit: Option<usize>) -> Result<usize> {
+        let client = self.imap_for(account)?;
+
+        let messages = client.fetch_by_uid(folder, limit).await?;
+
+        let mut matched = 0;
+        for (uid, email, _attachments, raw) in &messages {
+            let actions = self.apply_rules(account, email);
+            if actions.is_empty() {
+                continue;
+            }
+            client.apply_actions(folder, *uid, &actions).await?;
+            for action in &actions {
+                if matches!(action, RuleAction::Forward { .. } | RuleAction::ReplyWithTemplate { .. }) {
+                    self.execute_outbound_action(account, email, action).await?;
+                }
+            }
+            if actions.iter().any(|action| matches!(action, RuleAction::Archive)) {
+                self.archive_to_maildir(raw)?;
+            }
+            matched += 1;
+        }
+
+        Ok(matched)
+    }
+
+    /// Write `raw_message` into the attached [`Self::with_maildir_store`] store, if any. A no-op
+    /// when no store is attached, so callers don't need to special-case the unconfigured case.
+    fn archive_to_maildir(&self, raw_message: &[u8]) -> Result<()> {
+        if let Some(store) = &self.maildir_store {
+            store.lock().unwrap().archive(raw_message)?;
+        }
+        Ok(())
+    }
+
+    /// Send `action` (`Forward`/`ReplyWithTemplate`) for `email` over [`Self::outbound_transport`].
+    /// A no-op for any other action, so callers can run every matched action through this
+    /// unconditionally. The forward keeps the original sender/subject in its body; the reply
+    /// threads onto the original message via `In-Reply-To`.
+    async fn execute_outbound_action(&self, account: &str, email: &DbEmail, action: &RuleAction) -> Result<()> {
+        let (to, subject, body) = match action {
+            RuleAction::Forward { to } => (
+                to.clone(),
+                format!("Fwd: {}", email.subject),
+                format!(
+                    "---------- Forwarded message ----------\nFrom: {}\nSubject: {}\n\n{}",
+                    email.from_address, email.subject, email.body
+                ),
+            ),
+            RuleAction::ReplyWithTemplate { body } => {
+                (email.from_address.clone(), format!("Re: {}", email.subject), body.clone())
+            }
+            _ => return Ok(()),
         };
 
-        let smtp_ok = if let Some(ref client) = self.smtp_client {
-            client.test_connection().await.is_ok()
-        } else {
-            false
+        match &self.outbound_transport {
+            OutboundTransport::Smtp => {
+                self.smtp_for(account)?
+                    .send_threaded_mime_email(
+                        &to,
+                        &subject,
+                        "text/plain; charset=utf-8",
+                        &body,
+                        email.message_id.as_deref(),
+                        email.references.as_deref(),
+                    )
+                    .await
+            }
+            OutboundTransport::Directory { dir, next_id } => {
+                write_outbound_message(dir, next_id, &to, &subject, &body, email.message_id.as_deref())
+            }
+        }
+    }
+
+    /// Push-sync daemon: loop [`Self::watch_idle`] on `account`/`folder` until `stop` fires,
+    /// running every pushed batch through [`Self::apply_rules_batch`], executing the matched
+    /// actions against the server, and sending the matched subset over `tx`. Lets a caller react
+    /// to new mail the instant the server pushes it rather than polling on an interval; each
+    /// `watch_idle` call already re-issues `IDLE` every [`IDLE_REISSUE_INTERVAL`] internally to
+    /// respect the ~30 minute server timeout. Returns once `stop` fires or `tx`'s receiver is
+    /// dropped.
+    pub async fn idle_watch(
+        &self,
+        account: &str,
+        folder: &str,
+        mut last_uid: u32,
+        stop: Arc<AtomicBool>,
+        tx: mpsc::Sender<Vec<(DbEmail, Vec<RuleAction>)>>,
+    ) -> Result<()> {
+        let client = self.imap_for(account)?;
+
+        while !stop.load(Ordering::Relaxed) {
+            let batch = match client.watch_idle(folder, last_uid, stop.clone()).await? {
+                Some(batch) => batch,
+                None => break,
+            };
+            last_uid = batch.last_uid;
+
+            let emails: Vec<DbEmail> = batch.messages.into_iter().map(|(email, _)| email).collect();
+            let matched = self.apply_rules_batch(account, &emails);
+            if matched.is_empty() {
+                continue;
+            }
+
+            let mut notified = Vec::with_capacity(matched.len());
+            for (idx, actions, _account) in matched {
+                if let Some(uid) = emails[idx].imap_uid {
+                    client.apply_actions(folder, uid, &actions).await?;
+                }
+                for action in &actions {
+                    if matches!(action, RuleAction::Forward { .. } | RuleAction::ReplyWithTemplate { .. }) {
+                        self.execute_outbound_action(account, &emails[idx], action).await?;
+                    }
+                }
+                notified.push((emails[idx].clone(), actions));
+            }
+
+            if tx.send(notified).await.is_err() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sync the active account's folder into its local Maildir mirror, falling back to the
+    /// mirror's cached copy (offline reading) if the server can't be reached.
+    pub async fn sync_folder_offline_first(
+        &self,
+        folder: &str,
+        mirror: &crate::maildir::MaildirMirror,
+    ) -> Result<SyncStatus> {
+        let client = match self.active_imap() {
+            Ok(client) => client,
+            Err(e) => return Ok(SyncStatus::Error(e.to_string())),
         };
 
+        match client.sync_folder_to_mirror(folder, mirror).await {
+            Ok(result) => Ok(SyncStatus::Success { fetched: result.fetched, sent: 0 }),
+            Err(e) => Ok(SyncStatus::Error(format!(
+                "Sync failed, reading cached copy instead: {}", e
+            ))),
+        }
+    }
+
+    /// Read whatever is currently mirrored for `folder`, for offline reading/search when the
+    /// server is unreachable (or a caller just wants to avoid the network round-trip).
+    pub fn read_cached_folder(
+        &self,
+        folder: &str,
+        mirror: &crate::maildir::MaildirMirror,
+    ) -> Result<Vec<(DbEmail, Vec<crate::mime::ParsedAttachment>)>> {
+        mirror.load_cached(folder)
+    }
+
+    /// Send an outgoing message from `account`, with whatever `Content-Type`
+    /// [`crate::gpg::build_outgoing_body`] produced (plain text, or a PGP/MIME signed/encrypted
+    /// multipart body).
+    pub async fn send_mime_email(
+        &self,
+        account: &str,
+        to: &str,
+        subject: &str,
+        content_type: &str,
+        body: &str,
+    ) -> Result<()> {
+        self.smtp_for(account)?.send_mime_email(to, subject, content_type, body).await
+    }
+
+    /// Same as [`Self::send_mime_email`], threaded onto an existing conversation via
+    /// `In-Reply-To`/`References`, see [`SmtpClient::send_threaded_mime_email`].
+    pub async fn send_threaded_mime_email(
+        &self,
+        account: &str,
+        to: &str,
+        subject: &str,
+        content_type: &str,
+        body: &str,
+        in_reply_to: Option<&str>,
+        references: Option<&str>,
+    ) -> Result<()> {
+        self.smtp_for(account)?
+            .send_threaded_mime_email(to, subject, content_type, body, in_reply_to, references)
+            .await
+    }
+
+    /// Test both IMAP and SMTP connections for `account`
+    pub async fn test_connections(&self, account: &str) -> Result<(bool, bool)> {
+        let Some((imap, smtp)) = self.accounts.get(account) else {
+            return Ok((false, false));
+        };
+
+        let imap_ok = imap.test_connection().await.is_ok();
+        let smtp_ok = smtp.test_connection().await.is_ok();
+
         Ok((imap_ok, smtp_ok))
     }
 
-    /// Check if credentials are configured
+    /// Whether any account is configured
     pub fn is_configured(&self) -> bool {
-        self.imap_client.is_some() && self.smtp_client.is_some()
+        !self.accounts.is_empty()
+    }
+}
+
+impl Default for EmailSyncManager {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -524,12 +2824,15 @@ mod tests {
         Credentials {
             imap_server: "imap.example.com".to_string(),
             imap_port: 993,
+            imap_security: crate::providers::SecurityType::Tls,
             imap_username: "user@example.com".to_string(),
             imap_password: "password".to_string(),
             smtp_server: "smtp.example.com".to_string(),
             smtp_port: 587,
+            smtp_security: crate::providers::SecurityType::StartTls,
             smtp_username: "user@example.com".to_string(),
             smtp_password: "password".to_string(),
+            oauth_token: None,
         }
     }
 
@@ -550,6 +2853,7 @@ mod tests {
             bcc_addresses: None,
             subject: subject.to_string(),
             body: body.to_string(),
+            body_html: None,
             preview: body.chars().take(100).collect(),
             date,
             status: DbEmailStatus::Unread,
@@ -557,9 +2861,35 @@ mod tests {
             folder: "inbox".to_string(),
             thread_id: None,
             account_id: None,
+            message_id: None,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: None,
+            list_headers: None,
+            headers: None,
+            has_attachment: false,
         }
     }
 
+    #[test]
+    fn test_parse_email_rules_match_decoded_subject_and_body() {
+        let raw = b"From: a@example.com\r\n\
+Subject: =?ISO-8859-1?Q?Caf=E9?=\r\n\
+Content-Type: text/plain; charset=windows-1252\r\n\
+Content-Transfer-Encoding: quoted-printable\r\n\
+\r\n\
+Your receipt total is =80100\r\n";
+
+        let (email, _attachments) = ImapClient::parse_email(raw, &[], "inbox").unwrap();
+        assert_eq!(email.subject, "Café");
+        assert!(email.body.contains("€100"));
+
+        assert!(RuleCondition::SubjectContains("Café".to_string()).matches(&email));
+        assert!(RuleCondition::BodyRegex(r"€\d+".to_string()).matches(&email));
+    }
+
     #[tokio::test]
     async fn test_imap_client_connection_requires_valid_server() {
         let client = ImapClient::new(create_test_credentials());
@@ -580,10 +2910,10 @@ mod tests {
 
     #[test]
     fn test_sync_manager_configured() {
-        let manager = EmailSyncManager::new(Some(create_test_credentials()));
+        let manager = EmailSyncManager::with_account("test", create_test_credentials());
         assert!(manager.is_configured());
 
-        let manager_no_creds = EmailSyncManager::new(None);
+        let manager_no_creds = EmailSyncManager::new();
         assert!(!manager_no_creds.is_configured());
     }
 
@@ -645,9 +2975,144 @@ mod tests {
         assert!(!condition.matches(&email));
     }
 
+    #[test]
+    fn test_rule_condition_all_and_any() {
+        let email = create_test_email("alice@example.com", "Test", "Body");
+
+        let all = RuleCondition::All(vec![
+            RuleCondition::FromContains("alice".to_string()),
+            RuleCondition::SubjectContains("test".to_string()),
+        ]);
+        assert!(all.matches(&email));
+
+        let all_with_miss = RuleCondition::All(vec![
+            RuleCondition::FromContains("alice".to_string()),
+            RuleCondition::SubjectContains("party".to_string()),
+        ]);
+        assert!(!all_with_miss.matches(&email));
+
+        let any = RuleCondition::Any(vec![
+            RuleCondition::FromContains("bob".to_string()),
+            RuleCondition::SubjectContains("test".to_string()),
+        ]);
+        assert!(any.matches(&email));
+    }
+
+    #[test]
+    fn test_rule_condition_subject_and_body_regex() {
+        let email = create_test_email("alice@example.com", "Weekly Newsletter #42", "Unsubscribe below");
+
+        assert!(RuleCondition::SubjectRegex(r"Newsletter #\d+".to_string()).matches(&email));
+        assert!(!RuleCondition::SubjectRegex(r"Invoice #\d+".to_string()).matches(&email));
+        assert!(RuleCondition::BodyRegex("(?i)unsubscribe".to_string()).matches(&email));
+    }
+
+    #[test]
+    fn test_rule_condition_header_matches() {
+        let mut email = create_test_email("alice@example.com", "Test", "Body");
+        email.headers = Some("List-Id: <announce.example.com>\nFrom: alice@example.com".to_string());
+
+        let condition = RuleCondition::HeaderMatches {
+            name: "List-Id".to_string(),
+            regex: r"announce\.".to_string(),
+        };
+        assert!(condition.matches(&email));
+
+        let condition = RuleCondition::HeaderMatches {
+            name: "List-Id".to_string(),
+            regex: r"digest\.".to_string(),
+        };
+        assert!(!condition.matches(&email));
+    }
+
+    #[test]
+    fn test_apply_rules_reuses_compiled_condition_across_calls() {
+        let mut manager = EmailSyncManager::with_account("test", create_test_credentials());
+        manager.add_rule(InboxRule {
+            id: 1,
+            name: "Digests".to_string(),
+            condition: RuleCondition::SubjectRegex(r"Digest #\d+".to_string()),
+            action: RuleAction::Archive,
+            enabled: true,
+            account: None,
+        });
+
+        let matching = create_test_email("news@example.com", "Digest #7", "Body");
+        let non_matching = create_test_email("news@example.com", "Hello", "Body");
+
+        let actions = manager.apply_rules("test", &matching);
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(actions[0], RuleAction::Archive));
+        assert!(manager.apply_rules("test", &non_matching).is_empty());
+        // Second call against the same rule id reuses the cached compiled condition.
+        let actions = manager.apply_rules("test", &matching);
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(actions[0], RuleAction::Archive));
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push(format!("tume-outbound-test-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        path
+    }
+
+    #[tokio::test]
+    async fn test_execute_outbound_action_forward_writes_to_directory() {
+        let dir = temp_dir("forward");
+        let manager = EmailSyncManager::with_account("test", create_test_credentials())
+            .with_outbound_transport(OutboundTransport::directory(&dir));
+        let email = create_test_email("alice@example.com", "Quarterly report", "See attached.");
+
+        manager
+            .execute_outbound_action("test", &email, &RuleAction::Forward { to: "bob@example.com".to_string() })
+            .await
+            .unwrap();
+
+        let written = std::fs::read_to_string(dir.join("0.eml")).unwrap();
+        assert!(written.contains("To: bob@example.com"));
+        assert!(written.contains("Subject: Fwd: Quarterly report"));
+        assert!(written.contains("From: alice@example.com"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_execute_outbound_action_reply_numbers_files_and_threads() {
+        let dir = temp_dir("reply");
+        let manager = EmailSyncManager::with_account("test", create_test_credentials())
+            .with_outbound_transport(OutboundTransport::directory(&dir));
+        let mut email = create_test_email("alice@example.com", "Question", "When is the deadline?");
+        email.message_id = Some("<abc123@example.com>".to_string());
+
+        let action = RuleAction::ReplyWithTemplate { body: "Thanks, we'll get back to you.".to_string() };
+        manager.execute_outbound_action("test", &email, &action).await.unwrap();
+        manager.execute_outbound_action("test", &email, &action).await.unwrap();
+
+        let first = std::fs::read_to_string(dir.join("0.eml")).unwrap();
+        assert!(first.contains("To: alice@example.com"));
+        assert!(first.contains("Subject: Re: Question"));
+        assert!(first.contains("In-Reply-To: <abc123@example.com>"));
+        assert!(first.contains("Thanks, we'll get back to you."));
+        assert!(dir.join("1.eml").exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_execute_outbound_action_ignores_other_actions() {
+        let dir = temp_dir("noop");
+        let manager = EmailSyncManager::with_account("test", create_test_credentials())
+            .with_outbound_transport(OutboundTransport::directory(&dir));
+        let email = create_test_email("alice@example.com", "Hi", "Body");
+
+        manager.execute_outbound_action("test", &email, &RuleAction::Archive).await.unwrap();
+        assert!(!dir.exists());
+    }
+
     #[test]
     fn test_sync_manager_add_rule() {
-        let mut manager = EmailSyncManager::new(Some(create_test_credentials()));
+        let mut manager = EmailSyncManager::with_account("test", create_test_credentials());
         
         let rule = InboxRule {
             id: 1,
@@ -655,6 +3120,7 @@ mod tests {
             condition: RuleCondition::FromContains("newsletter".to_string()),
             action: RuleAction::MoveToFolder("newsletters".to_string()),
             enabled: true,
+            account: None,
         };
         
         manager.add_rule(rule);
@@ -663,7 +3129,7 @@ mod tests {
 
     #[test]
     fn test_sync_manager_remove_rule() {
-        let mut manager = EmailSyncManager::new(Some(create_test_credentials()));
+        let mut manager = EmailSyncManager::with_account("test", create_test_credentials());
         
         let rule = InboxRule {
             id: 1,
@@ -671,6 +3137,7 @@ mod tests {
             condition: RuleCondition::FromContains("test".to_string()),
             action: RuleAction::Flag,
             enabled: true,
+            account: None,
         };
         
         manager.add_rule(rule);
@@ -682,7 +3149,7 @@ mod tests {
 
     #[test]
     fn test_sync_manager_apply_rules() {
-        let mut manager = EmailSyncManager::new(Some(create_test_credentials()));
+        let mut manager = EmailSyncManager::with_account("test", create_test_credentials());
         
         let rule = InboxRule {
             id: 1,
@@ -690,12 +3157,13 @@ mod tests {
             condition: RuleCondition::SubjectContains("important".to_string()),
             action: RuleAction::Flag,
             enabled: true,
+            account: None,
         };
         
         manager.add_rule(rule);
         
         let email = create_test_email("alice@example.com", "Important Meeting", "Body");
-        let actions = manager.apply_rules(&email);
+        let actions = manager.apply_rules("test", &email);
         
         assert_eq!(actions.len(), 1);
         assert!(matches!(actions[0], RuleAction::Flag));
@@ -703,7 +3171,7 @@ mod tests {
 
     #[test]
     fn test_sync_manager_disabled_rule() {
-        let mut manager = EmailSyncManager::new(Some(create_test_credentials()));
+        let mut manager = EmailSyncManager::with_account("test", create_test_credentials());
         
         let rule = InboxRule {
             id: 1,
@@ -711,12 +3179,13 @@ mod tests {
             condition: RuleCondition::FromContains("test".to_string()),
             action: RuleAction::Flag,
             enabled: false,  // Disabled
+            account: None,
         };
         
         manager.add_rule(rule);
         
         let email = create_test_email("test@example.com", "Test", "Body");
-        let actions = manager.apply_rules(&email);
+        let actions = manager.apply_rules("test", &email);
         
         // Should not apply disabled rule
         assert_eq!(actions.len(), 0);
@@ -724,7 +3193,7 @@ mod tests {
 
     #[test]
     fn test_sync_manager_multiple_rules() {
-        let mut manager = EmailSyncManager::new(Some(create_test_credentials()));
+        let mut manager = EmailSyncManager::with_account("test", create_test_credentials());
         
         manager.add_rule(InboxRule {
             id: 1,
@@ -732,6 +3201,7 @@ mod tests {
             condition: RuleCondition::SubjectContains("important".to_string()),
             action: RuleAction::Flag,
             enabled: true,
+            account: None,
         });
         
         manager.add_rule(InboxRule {
@@ -740,10 +3210,11 @@ mod tests {
             condition: RuleCondition::SubjectContains("important".to_string()),
             action: RuleAction::MarkAsRead,
             enabled: true,
+            account: None,
         });
         
         let email = create_test_email("alice@example.com", "Important Meeting", "Body");
-        let actions = manager.apply_rules(&email);
+        let actions = manager.apply_rules("test", &email);
         
         // Both rules should match
         assert_eq!(actions.len(), 2);
@@ -751,7 +3222,7 @@ mod tests {
 
     #[test]
     fn test_apply_rules_batch() {
-        let mut manager = EmailSyncManager::new(Some(create_test_credentials()));
+        let mut manager = EmailSyncManager::with_account("test", create_test_credentials());
         
         manager.add_rule(InboxRule {
             id: 1,
@@ -759,6 +3230,7 @@ mod tests {
             condition: RuleCondition::FromContains("newsletter".to_string()),
             action: RuleAction::Archive,
             enabled: true,
+            account: None,
         });
         
         let emails = vec![
@@ -767,11 +3239,140 @@ mod tests {
             create_test_email("newsletter@company.com", "Updates", "Body"),
         ];
         
-        let results = manager.apply_rules_batch(&emails);
+        let results = manager.apply_rules_batch("test", &emails);
         
         // Should match emails at index 0 and 2
         assert_eq!(results.len(), 2);
         assert_eq!(results[0].0, 0);
         assert_eq!(results[1].0, 2);
     }
+
+    #[test]
+    fn test_rule_condition_to_sieve() {
+        assert_eq!(
+            RuleCondition::FromContains("alice".to_string()).to_sieve(),
+            "header :contains \"from\" \"alice\""
+        );
+        assert_eq!(
+            RuleCondition::And(
+                Box::new(RuleCondition::FromContains("alice".to_string())),
+                Box::new(RuleCondition::SubjectContains("meeting".to_string())),
+            )
+            .to_sieve(),
+            "allof(header :contains \"from\" \"alice\", header :contains \"subject\" \"meeting\")"
+        );
+    }
+
+    #[test]
+    fn test_rule_action_to_sieve() {
+        assert_eq!(RuleAction::MoveToFolder("Lists".to_string()).to_sieve(), "fileinto \"Lists\";");
+        assert_eq!(RuleAction::Flag.to_sieve(), "setflag \"\\\\Flagged\";");
+        assert_eq!(RuleAction::Delete.to_sieve(), "discard;");
+    }
+
+    #[test]
+    fn test_compile_rules_to_sieve_includes_imap4flags_only_when_needed() {
+        let flagging_rule = InboxRule {
+            id: 1,
+            name: "Flag important".to_string(),
+            condition: RuleCondition::SubjectContains("important".to_string()),
+            action: RuleAction::Flag,
+            enabled: true,
+            account: None,
+        };
+        let script = compile_rules_to_sieve(std::slice::from_ref(&flagging_rule));
+        assert!(script.contains("require [\"fileinto\", \"imap4flags\"];"));
+        assert!(script.contains("if header :contains \"subject\" \"important\""));
+        assert!(script.contains("setflag \"\\\\Flagged\";"));
+
+        let moving_rule = InboxRule {
+            id: 2,
+            name: "Move newsletters".to_string(),
+            condition: RuleCondition::FromContains("newsletter".to_string()),
+            action: RuleAction::MoveToFolder("Newsletters".to_string()),
+            enabled: true,
+            account: None,
+        };
+        let script = compile_rules_to_sieve(&[moving_rule]);
+        assert!(script.contains("require [\"fileinto\"];"));
+        assert!(!script.contains("imap4flags"));
+    }
+
+    #[test]
+    fn test_compile_rules_to_sieve_skips_disabled_rules() {
+        let rule = InboxRule {
+            id: 1,
+            name: "Disabled".to_string(),
+            condition: RuleCondition::FromContains("bob".to_string()),
+            action: RuleAction::Delete,
+            enabled: false,
+            account: None,
+        };
+        let script = compile_rules_to_sieve(&[rule]);
+        assert!(!script.contains("discard"));
+    }
+
+    #[test]
+    fn test_validation_error_classifies_dns_failure() {
+        let err = anyhow!("failed to lookup address information: nodename nor servname provided");
+        match ValidationError::classify("IMAP", &err) {
+            ValidationError::DnsFailure(_) => {}
+            other => panic!("expected DnsFailure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validation_error_classifies_auth_rejection() {
+        let err = anyhow!("IMAP login failed: authentication failed");
+        match ValidationError::classify("IMAP", &err) {
+            ValidationError::AuthRejected(_) => {}
+            other => panic!("expected AuthRejected, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validation_error_classifies_tls_failure() {
+        let err = anyhow!("TLS handshake failed: certificate verify failed");
+        match ValidationError::classify("SMTP", &err) {
+            ValidationError::TlsFailure(_) => {}
+            other => panic!("expected TlsFailure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validation_error_classifies_connection_failure() {
+        let err = anyhow!("Failed to connect to imap.example.com:993: connection refused");
+        match ValidationError::classify("IMAP", &err) {
+            ValidationError::ConnectionFailure(_) => {}
+            other => panic!("expected ConnectionFailure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validation_result_is_ok_requires_both_legs() {
+        let all_ok = ValidationResult { imap: LegResult::Ok, smtp: LegResult::Ok };
+        assert!(all_ok.is_ok());
+
+        let imap_failed = ValidationResult {
+            imap: LegResult::Failed(ValidationError::AuthRejected("bad password".to_string())),
+            smtp: LegResult::Ok,
+        };
+        assert!(!imap_failed.is_ok());
+    }
+
+    #[test]
+    fn test_validation_error_guidance_is_non_empty() {
+        assert!(!ValidationError::AuthRejected("x".to_string()).guidance().is_empty());
+        assert!(!ValidationError::DnsFailure("x".to_string()).guidance().is_empty());
+    }
+
+    #[test]
+    fn test_maildir_flags_translates_tracked_flags_only() {
+        let flags = ImapClient::maildir_flags(&[
+            imap::types::Flag::Seen,
+            imap::types::Flag::Custom("NonJunk".into()),
+            imap::types::Flag::Flagged,
+        ]);
+        assert_eq!(flags, vec![crate::maildir::MaildirFlag::Seen, crate::maildir::MaildirFlag::Flagged]);
+    }
 }
@@ -15,8 +15,8 @@ pub fn handle_events(app: &mut App) -> io::Result<()> {
 }
 
 fn handle_key_event(app: &mut App, key: KeyEvent) {
-    // Clear status message on any key press
-    app.status_message = None;
+    // Clear status message on any key press, first recording it in the notification history
+    app.clear_status_message();
 
     match app.current_view {
         View::InboxList => handle_inbox_keys(app, key),
@@ -25,6 +25,12 @@ fn handle_key_event(app: &mut App, key: KeyEvent) {
         View::CredentialsSetup => handle_credentials_setup_keys(app, key),
         View::CredentialsUnlock => handle_credentials_unlock_keys(app, key),
         View::CredentialsManagement => handle_credentials_management_keys(app, key),
+        View::CommandLine => handle_command_line_keys(app, key),
+        View::Contacts => handle_contacts_keys(app, key),
+        View::NotificationHistory => handle_notification_history_keys(app, key),
+        View::FolderList => handle_folder_list_keys(app, key),
+        View::ThreadList => handle_thread_list_keys(app, key),
+        View::AccountStatus => handle_account_status_keys(app, key),
     }
 }
 
@@ -35,83 +41,130 @@ fn handle_inbox_keys(app: &mut App, key: KeyEvent) {
         return;
     }
 
-    match key.code {
-        // Vim-style navigation
-        KeyCode::Char('j') | KeyCode::Down => app.next_email(),
-        KeyCode::Char('k') | KeyCode::Up => app.previous_email(),
-
-        // Open email
-        KeyCode::Enter | KeyCode::Char('l') => app.open_email(),
-
-        // Toggle preview panel
-        KeyCode::Char('p') => app.toggle_preview_panel(),
-
-        // Enter visual mode with Shift+V (uppercase V)
-        KeyCode::Char('V') => {
-            app.enter_visual_mode();
-        }
-
-        // Account switching (1-9)
-        KeyCode::Char(c @ '1'..='9') => {
-            let index = (c as u8 - b'1') as usize;
-            app.switch_to_account(index);
-        }
-
-        // Next/Previous account
-        KeyCode::Char(']') => app.next_account(),
-        KeyCode::Char('[') => app.prev_account(),
+    // A bare digit accumulates into a pending vim-style count (`5j`, `3d`) instead of
+    // dispatching immediately; the next motion/action key consumes it below. This supersedes
+    // account switching as a bare-digit shortcut - switch accounts with `]`/`[`/Tab or `:account N`.
+    if let KeyCode::Char(c @ '0'..='9') = key.code {
+        app.push_pending_count_digit(c);
+        return;
+    }
 
-        // Tab to cycle through accounts
-        KeyCode::Tab => app.next_account(),
+    let count = app.take_pending_count();
+
+    match app.shortcuts.resolve_inbox(&key) {
+        Some("next_email") => (0..count).for_each(|_| app.next_email()),
+        Some("previous_email") => (0..count).for_each(|_| app.previous_email()),
+        Some("open_email") => app.open_email(),
+        Some("toggle_preview") => app.toggle_preview_panel(),
+        Some("enter_visual_mode") => app.enter_visual_mode(),
+        Some("next_account") => app.next_account(),
+        Some("prev_account") => app.prev_account(),
+        Some("delete") => (0..count).for_each(|_| app.perform_action(Action::Delete)),
+        Some("archive") => (0..count).for_each(|_| app.perform_action(Action::Archive)),
+        Some("reply") => app.perform_action(Action::Reply),
+        Some("compose") => app.perform_action(Action::Compose),
+        Some("forward") => app.perform_action(Action::Forward),
+        Some("export") => app.perform_action(Action::Export),
+        Some("reply_to_list") => app.perform_action(Action::ReplyToList),
+        Some("list_unsubscribe") => app.perform_action(Action::ListUnsubscribe),
+        Some("credentials_management") => app.enter_credentials_management(),
+        Some("open_contacts") => app.enter_contacts_mode(),
+        Some("open_notification_history") => app.enter_notification_history(),
+        Some("open_folders") => app.enter_folder_list_mode(),
+        Some("open_threads") => app.enter_thread_list_mode(),
+        Some("open_account_status") => app.enter_account_status(),
+        Some("cycle_listing_style") => app.cycle_listing_style(),
+        Some("enter_command_mode") => app.enter_command_mode(),
+        Some("quit") => app.quit(),
+        _ => {}
+    }
+}
 
-        // Actions
-        KeyCode::Char('d') => app.perform_action(Action::Delete),
-        KeyCode::Char('a') => app.perform_action(Action::Archive),
-        KeyCode::Char('r') => app.perform_action(Action::Reply),
-        KeyCode::Char('c') => app.perform_action(Action::Compose),
-        KeyCode::Char('f') => app.perform_action(Action::Forward),
+fn handle_account_status_keys(app: &mut App, key: KeyEvent) {
+    match app.shortcuts.resolve_account_status(&key) {
+        Some("exit_account_status") => app.exit_account_status(),
+        _ => {}
+    }
+}
 
-        // Credentials management
-        KeyCode::Char('m') => app.enter_credentials_management(),
+fn handle_notification_history_keys(app: &mut App, key: KeyEvent) {
+    match app.shortcuts.resolve_notification_history(&key) {
+        Some("next_notification") => app.notification_history_next(),
+        Some("previous_notification") => app.notification_history_previous(),
+        Some("exit_notification_history") => app.exit_notification_history(),
+        _ => {}
+    }
+}
 
-        // Quit
-        KeyCode::Char('q') => app.quit(),
+fn handle_folder_list_keys(app: &mut App, key: KeyEvent) {
+    match app.shortcuts.resolve_folder_list(&key) {
+        Some("next_folder") => app.folder_list_next(),
+        Some("previous_folder") => app.folder_list_previous(),
+        Some("select_folder") => app.folder_list_select(),
+        Some("exit_folder_list") => app.exit_folder_list_mode(),
+        _ => {}
+    }
+}
 
+fn handle_thread_list_keys(app: &mut App, key: KeyEvent) {
+    match app.shortcuts.resolve_thread_list(&key) {
+        Some("next_thread") => app.thread_list_next(),
+        Some("previous_thread") => app.thread_list_previous(),
+        Some("select_thread") => app.thread_list_select(),
+        Some("thread_list_back") => app.thread_list_back(),
         _ => {}
     }
 }
 
 fn handle_visual_mode_keys(app: &mut App, key: KeyEvent) {
-    match key.code {
-        // Vim-style navigation (extend selection)
-        KeyCode::Char('j') | KeyCode::Down => app.next_email(),
-        KeyCode::Char('k') | KeyCode::Up => app.previous_email(),
-
-        // Batch actions
-        KeyCode::Char('d') => app.perform_batch_action(Action::Delete),
-        KeyCode::Char('a') => app.perform_batch_action(Action::Archive),
+    if let KeyCode::Char(c @ '0'..='9') = key.code {
+        app.push_pending_count_digit(c);
+        return;
+    }
 
-        // Exit visual mode
-        KeyCode::Esc | KeyCode::Char('v') | KeyCode::Char('V') => app.exit_visual_mode(),
+    let count = app.take_pending_count();
 
+    match app.shortcuts.resolve_visual(&key) {
+        Some("next_email") => (0..count).for_each(|_| app.next_email()),
+        Some("previous_email") => (0..count).for_each(|_| app.previous_email()),
+        Some("batch_delete") => app.perform_batch_action(Action::Delete),
+        Some("batch_archive") => app.perform_batch_action(Action::Archive),
+        Some("batch_export") => app.perform_batch_action(Action::Export),
+        Some("exit_visual_mode") => app.exit_visual_mode(),
         _ => {}
     }
 }
 
 fn handle_detail_keys(app: &mut App, key: KeyEvent) {
-    match key.code {
-        // Go back
-        KeyCode::Char('h') | KeyCode::Esc => app.close_email(),
-
-        // Actions (same as inbox)
-        KeyCode::Char('d') => app.perform_action(Action::Delete),
-        KeyCode::Char('a') => app.perform_action(Action::Archive),
-        KeyCode::Char('r') => app.perform_action(Action::Reply),
-        KeyCode::Char('f') => app.perform_action(Action::Forward),
-
-        // Quit
-        KeyCode::Char('q') => app.quit(),
+    if app.detail_link_follow_mode {
+        handle_link_follow_keys(app, key, App::detail_link_follow_digit, App::detail_link_follow_confirm, App::detail_exit_link_follow_mode);
+        return;
+    }
 
+    // The detail view shows a single email, so a count prefix has nothing to repeat over, but
+    // the buffer is still drained here so a count typed before switching views doesn't leak
+    // into the next view's first keypress.
+    if let KeyCode::Char(c @ '0'..='9') = key.code {
+        app.push_pending_count_digit(c);
+        return;
+    }
+    app.take_pending_count();
+
+    match app.shortcuts.resolve_detail(&key) {
+        Some("close_email") => app.close_email(),
+        Some("delete") => app.perform_action(Action::Delete),
+        Some("archive") => app.perform_action(Action::Archive),
+        Some("reply") => app.perform_action(Action::Reply),
+        Some("forward") => app.perform_action(Action::Forward),
+        Some("export") => app.perform_action(Action::Export),
+        Some("reply_to_list") => app.perform_action(Action::ReplyToList),
+        Some("list_unsubscribe") => app.perform_action(Action::ListUnsubscribe),
+        Some("toggle_html_view") => app.toggle_html_view(),
+        Some("toggle_html_source") => app.toggle_html_source(),
+        Some("save_attachment") => app.save_selected_attachment(),
+        Some("follow_link") => app.detail_enter_link_follow_mode(),
+        Some("enter_command_mode") => app.enter_command_mode(),
+        Some("quit") => app.quit(),
         _ => {}
     }
 }
@@ -119,6 +172,28 @@ fn handle_detail_keys(app: &mut App, key: KeyEvent) {
 fn handle_compose_keys(app: &mut App, key: KeyEvent) {
     use crate::app::ComposeMode;
 
+    let prompt_open = app
+        .compose_state
+        .as_ref()
+        .map(|c| c.attachment_prompt.is_some())
+        .unwrap_or(false);
+
+    if prompt_open {
+        handle_compose_attachment_prompt_keys(app, key);
+        return;
+    }
+
+    let link_follow_mode = app
+        .compose_state
+        .as_ref()
+        .map(|c| c.link_follow_mode)
+        .unwrap_or(false);
+
+    if link_follow_mode {
+        handle_link_follow_keys(app, key, App::compose_link_follow_digit, App::compose_link_follow_confirm, App::compose_exit_link_follow_mode);
+        return;
+    }
+
     if let Some(ref compose) = app.compose_state {
         match compose.mode {
             ComposeMode::Normal => handle_compose_normal_keys(app, key),
@@ -127,28 +202,56 @@ fn handle_compose_keys(app: &mut App, key: KeyEvent) {
     }
 }
 
-fn handle_compose_normal_keys(app: &mut App, key: KeyEvent) {
+/// Shared digit-accumulate/confirm/cancel handling for follow-link mode, used by both the
+/// compose Markdown preview and the message reading view - the two just point at different
+/// `App` methods for where the digits/links live.
+fn handle_link_follow_keys(
+    app: &mut App,
+    key: KeyEvent,
+    digit: fn(&mut App, char),
+    confirm: fn(&mut App),
+    cancel: fn(&mut App),
+) {
     match key.code {
-        // Enter insert mode
-        KeyCode::Char('i') => app.compose_enter_insert_mode(),
-
-        // Navigation
-        KeyCode::Char('j') | KeyCode::Down => app.compose_next_field(),
-        KeyCode::Char('k') | KeyCode::Up => app.compose_previous_field(),
-
-        // Clear current field
-        KeyCode::Char('d') => app.compose_clear_field(),
-
-        // Toggle preview
-        KeyCode::Char('p') => app.compose_toggle_preview(),
-
-        // Save draft
-        KeyCode::Char('w') => app.save_current_draft(),
+        KeyCode::Char(c) if c.is_ascii_digit() => digit(app, c),
+        KeyCode::Enter => confirm(app),
+        KeyCode::Esc => cancel(app),
+        _ => {}
+    }
+}
 
-        // Exit compose mode
-        KeyCode::Esc => app.exit_compose_mode(),
-        KeyCode::Char('q') => app.exit_compose_mode(),
+fn handle_compose_normal_keys(app: &mut App, key: KeyEvent) {
+    match app.shortcuts.resolve_compose_normal(&key) {
+        Some("enter_insert_mode") => app.compose_enter_insert_mode(),
+        Some("next_field") => app.compose_next_field(),
+        Some("previous_field") => app.compose_previous_field(),
+        Some("clear_field") => app.compose_clear_field(),
+        Some("add_attachment") => app.compose_start_attachment_prompt(),
+        Some("next_attachment") => app.compose_next_attachment(),
+        Some("previous_attachment") => app.compose_previous_attachment(),
+        Some("toggle_preview") => app.compose_toggle_preview(),
+        Some("save_draft") => app.save_current_draft(),
+        Some("launch_editor") => app.request_external_editor(),
+        Some("toggle_sign") => app.compose_toggle_sign(),
+        Some("toggle_encrypt") => app.compose_toggle_encrypt(),
+        Some("follow_link") => app.compose_enter_link_follow_mode(),
+        Some("send") => app.request_send_email(),
+        Some("open_contacts") => app.enter_contacts_mode_from_compose(),
+        Some("exit_compose") => app.exit_compose_mode(),
+        _ => {}
+    }
+}
 
+/// Keys for the `Attachments` field's path-entry prompt, opened by `a`; mirrors
+/// `handle_contacts_add_keys`.
+fn handle_compose_attachment_prompt_keys(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Enter => app.compose_confirm_attachment_prompt(),
+        KeyCode::Esc => app.compose_cancel_attachment_prompt(),
+        KeyCode::Char(c) => app.compose_attachment_prompt_insert_char(c),
+        KeyCode::Backspace => app.compose_attachment_prompt_delete_char(),
+        KeyCode::Left => app.compose_attachment_prompt_cursor_left(),
+        KeyCode::Right => app.compose_attachment_prompt_cursor_right(),
         _ => {}
     }
 }
@@ -159,14 +262,23 @@ fn handle_compose_insert_keys(app: &mut App, key: KeyEvent) {
         KeyCode::Esc => app.compose_exit_insert_mode(),
 
         // Text input
-        KeyCode::Char(c) => app.compose_insert_char(c),
+        KeyCode::Char(c) => {
+            app.compose_insert_char(c);
+            app.compose_update_completions();
+        }
 
         // Backspace
-        KeyCode::Backspace => app.compose_delete_char(),
+        KeyCode::Backspace => {
+            app.compose_delete_char();
+            app.compose_update_completions();
+        }
 
         // Enter (newline for body only)
         KeyCode::Enter => app.compose_insert_newline(),
 
+        // Cycle contact completion candidates for the recipients field
+        KeyCode::Tab => app.compose_cycle_completion(),
+
         // Cursor movement
         KeyCode::Left => app.compose_move_cursor_left(),
         KeyCode::Right => app.compose_move_cursor_right(),
@@ -175,7 +287,67 @@ fn handle_compose_insert_keys(app: &mut App, key: KeyEvent) {
     }
 }
 
+fn handle_contacts_keys(app: &mut App, key: KeyEvent) {
+    let adding = app
+        .contacts_state
+        .as_ref()
+        .map(|s| s.add_state.is_some())
+        .unwrap_or(false);
+
+    if adding {
+        handle_contacts_add_keys(app, key);
+        return;
+    }
+
+    match app.shortcuts.resolve_contacts(&key) {
+        Some("next_contact") => app.contacts_next(),
+        Some("previous_contact") => app.contacts_previous(),
+        Some("insert_contact") => app.contacts_insert_selected(),
+        Some("add_contact") => app.contacts_start_add(),
+        Some("delete_contact") => app.contacts_delete_selected(),
+        Some("exit_contacts") => app.exit_contacts_mode(),
+        _ => {}
+    }
+}
+
+fn handle_contacts_add_keys(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Enter => app.contacts_confirm_add(),
+        KeyCode::Esc => app.contacts_cancel_add(),
+        KeyCode::Char(c) => app.contacts_add_insert_char(c),
+        KeyCode::Backspace => app.contacts_add_delete_char(),
+        KeyCode::Left => app.contacts_add_cursor_left(),
+        KeyCode::Right => app.contacts_add_cursor_right(),
+        _ => {}
+    }
+}
+
 fn handle_credentials_setup_keys(app: &mut App, key: KeyEvent) {
+    // Check if we're still picking a backend kind (the very first step)
+    let in_backend_selection = app.credentials_setup_state
+        .as_ref()
+        .map(|s| s.backend_selection_mode)
+        .unwrap_or(false);
+
+    if in_backend_selection {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                app.credentials_setup_next_backend();
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                app.credentials_setup_prev_backend();
+            }
+            KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right => {
+                app.credentials_setup_select_backend();
+            }
+            KeyCode::Esc | KeyCode::Char('q') => {
+                app.credentials_setup_cancel();
+            }
+            _ => {}
+        }
+        return;
+    }
+
     // Check if we're in provider selection mode
     let in_provider_selection = app.credentials_setup_state
         .as_ref()
@@ -198,6 +370,11 @@ fn handle_credentials_setup_keys(app: &mut App, key: KeyEvent) {
                 app.credentials_setup_select_provider();
             }
 
+            // Cycle where credentials will be stored (system keyring vs encrypted file)
+            KeyCode::Char('b') => {
+                app.credentials_setup_toggle_backend();
+            }
+
             // Cancel
             KeyCode::Esc | KeyCode::Char('q') => {
                 app.credentials_setup_cancel();
@@ -257,6 +434,32 @@ fn handle_credentials_setup_normal_keys(app: &mut App, key: KeyEvent) {
             app.credentials_setup_toggle_password_visibility();
         }
 
+        // Test connection before saving
+        KeyCode::Char('t') => {
+            app.credentials_setup_validate();
+        }
+
+        // Authorize with the provider (OAuth2 providers only)
+        KeyCode::Char('o') => {
+            app.credentials_setup_start_oauth();
+        }
+
+        // Toggle manual OAuth2 entry for a provider with no built-in preset
+        KeyCode::Char('O') => {
+            app.credentials_setup_toggle_custom_oauth2();
+        }
+
+        // Authorize via device code, for providers with no local browser to redirect to
+        KeyCode::Char('D') => {
+            if app.credentials_setup_state
+                .as_ref()
+                .map(|s| s.supports_device_code())
+                .unwrap_or(false)
+            {
+                app.credentials_setup_start_oauth_device();
+            }
+        }
+
         // Save
         KeyCode::Enter => {
             app.credentials_setup_save();
@@ -349,3 +552,45 @@ fn handle_credentials_management_keys(app: &mut App, key: KeyEvent) {
         _ => {}
     }
 }
+
+fn handle_command_line_keys(app: &mut App, key: KeyEvent) {
+    match key.code {
+        // Run the command
+        KeyCode::Enter => {
+            app.command_line_submit();
+        }
+
+        // Cancel
+        KeyCode::Esc => {
+            app.exit_command_mode();
+        }
+
+        // History navigation
+        KeyCode::Up => {
+            app.command_line_history_prev();
+        }
+        KeyCode::Down => {
+            app.command_line_history_next();
+        }
+
+        // Text input
+        KeyCode::Char(c) => {
+            app.command_line_insert_char(c);
+        }
+
+        // Backspace
+        KeyCode::Backspace => {
+            app.command_line_delete_char();
+        }
+
+        // Cursor movement
+        KeyCode::Left => {
+            app.command_line_cursor_left();
+        }
+        KeyCode::Right => {
+            app.command_line_cursor_right();
+        }
+
+        _ => {}
+    }
+}
@@ -0,0 +1,412 @@
+//! Configurable key bindings: resolves `(view, KeyEvent)` into a named action instead of
+//! `events.rs` matching literal `KeyCode`s, so users can remap keys per-view without recompiling.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Raw action-name -> key-spec-string map for one view, as loaded from TOML
+/// (e.g. `next_email = ["j", "Down"]`). Resolved into [`Shortcuts`] at startup by
+/// [`Shortcuts::from_config`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ShortcutContextConfig {
+    #[serde(flatten)]
+    pub bindings: HashMap<String, Vec<String>>,
+}
+
+/// User-facing shortcut overrides, grouped by view, as stored in the config file. Any action
+/// not mentioned here keeps its built-in binding; see [`Shortcuts::from_config`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ShortcutsConfig {
+    #[serde(default)]
+    pub inbox: ShortcutContextConfig,
+    #[serde(default)]
+    pub detail: ShortcutContextConfig,
+    #[serde(default)]
+    pub compose_normal: ShortcutContextConfig,
+    #[serde(default)]
+    pub visual: ShortcutContextConfig,
+    #[serde(default)]
+    pub contacts: ShortcutContextConfig,
+    #[serde(default)]
+    pub notification_history: ShortcutContextConfig,
+    #[serde(default)]
+    pub folder_list: ShortcutContextConfig,
+    #[serde(default)]
+    pub thread_list: ShortcutContextConfig,
+    #[serde(default)]
+    pub account_status: ShortcutContextConfig,
+}
+
+/// One parsed key combination, e.g. `j`, `Ctrl-d`, `Shift-V`, `Enter`. Shift is folded into the
+/// character's case rather than tracked as a separate modifier, since that's how crossterm
+/// itself reports letter keys (`Char('V')`, not `Char('v')` + `SHIFT`) - an explicit `Shift-`
+/// prefix is just a readability aid when writing the uppercase letter out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct KeySpec {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeySpec {
+    /// Modifiers that distinguish bindings from one another; Shift is excluded since it's
+    /// already baked into the `KeyCode::Char` case.
+    fn significant() -> KeyModifiers {
+        KeyModifiers::CONTROL | KeyModifiers::ALT
+    }
+
+    fn parse(spec: &str) -> Option<Self> {
+        let mut parts: Vec<&str> = spec.split('-').collect();
+        let key_part = parts.pop()?;
+
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in parts {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" => modifiers |= KeyModifiers::ALT,
+                "shift" => {} // folded into the char's case below
+                _ => return None,
+            }
+        }
+
+        let code = match key_part {
+            "Enter" => KeyCode::Enter,
+            "Esc" | "Escape" => KeyCode::Esc,
+            "Tab" => KeyCode::Tab,
+            "BackTab" => KeyCode::BackTab,
+            "Backspace" => KeyCode::Backspace,
+            "Left" => KeyCode::Left,
+            "Right" => KeyCode::Right,
+            "Up" => KeyCode::Up,
+            "Down" => KeyCode::Down,
+            single if single.chars().count() == 1 => KeyCode::Char(single.chars().next()?),
+            _ => return None,
+        };
+
+        Some(KeySpec { code, modifiers: modifiers & Self::significant() })
+    }
+
+    fn from_event(key: &KeyEvent) -> Self {
+        KeySpec {
+            code: key.code,
+            modifiers: key.modifiers & Self::significant(),
+        }
+    }
+}
+
+/// Resolved, queryable key-binding table: `(view, KeySpec) -> action name`. Built once at
+/// startup by merging [`ShortcutsConfig`] (user overrides) over the built-in defaults, then
+/// consulted by `events::handle_key_event` to turn a live key press into an action name.
+#[derive(Debug, Clone, Default)]
+pub struct Shortcuts {
+    inbox: HashMap<KeySpec, String>,
+    detail: HashMap<KeySpec, String>,
+    compose_normal: HashMap<KeySpec, String>,
+    visual: HashMap<KeySpec, String>,
+    contacts: HashMap<KeySpec, String>,
+    notification_history: HashMap<KeySpec, String>,
+    folder_list: HashMap<KeySpec, String>,
+    thread_list: HashMap<KeySpec, String>,
+    account_status: HashMap<KeySpec, String>,
+}
+
+impl Shortcuts {
+    /// Built-in bindings, matching the `KeyCode` matches `events.rs` used to hardcode.
+    pub fn defaults() -> Self {
+        Self {
+            inbox: Self::context_defaults(&[
+                ("j", "next_email"), ("Down", "next_email"),
+                ("k", "previous_email"), ("Up", "previous_email"),
+                ("Enter", "open_email"), ("l", "open_email"),
+                ("p", "toggle_preview"),
+                ("Shift-V", "enter_visual_mode"),
+                ("]", "next_account"),
+                ("[", "prev_account"),
+                ("Tab", "next_account"),
+                ("d", "delete"),
+                ("a", "archive"),
+                ("r", "reply"),
+                ("c", "compose"),
+                ("f", "forward"),
+                ("x", "export"),
+                ("Shift-R", "reply_to_list"),
+                ("u", "list_unsubscribe"),
+                ("m", "credentials_management"),
+                ("b", "open_contacts"),
+                ("n", "open_notification_history"),
+                ("g", "open_folders"),
+                ("t", "open_threads"),
+                ("s", "open_account_status"),
+                ("Shift-L", "cycle_listing_style"),
+                (":", "enter_command_mode"),
+                ("q", "quit"),
+            ]),
+            detail: Self::context_defaults(&[
+                ("h", "close_email"), ("Esc", "close_email"),
+                ("d", "delete"),
+                ("a", "archive"),
+                ("r", "reply"),
+                ("f", "forward"),
+                ("x", "export"),
+                ("Shift-R", "reply_to_list"),
+                ("u", "list_unsubscribe"),
+                ("t", "toggle_html_view"),
+                ("p", "toggle_html_source"),
+                ("s", "save_attachment"),
+                ("o", "follow_link"),
+                (":", "enter_command_mode"),
+                ("q", "quit"),
+            ]),
+            compose_normal: Self::context_defaults(&[
+                ("i", "enter_insert_mode"),
+                ("j", "next_field"), ("Down", "next_field"),
+                ("k", "previous_field"), ("Up", "previous_field"),
+                ("d", "clear_field"),
+                ("a", "add_attachment"),
+                ("Shift-J", "next_attachment"),
+                ("Shift-K", "previous_attachment"),
+                ("p", "toggle_preview"),
+                ("w", "save_draft"),
+                ("e", "launch_editor"),
+                ("s", "toggle_sign"),
+                ("Shift-E", "toggle_encrypt"),
+                ("o", "follow_link"),
+                ("b", "open_contacts"),
+                ("Enter", "send"),
+                ("Esc", "exit_compose"), ("q", "exit_compose"),
+            ]),
+            visual: Self::context_defaults(&[
+                ("j", "next_email"), ("Down", "next_email"),
+                ("k", "previous_email"), ("Up", "previous_email"),
+                ("d", "batch_delete"),
+                ("a", "batch_archive"),
+                ("x", "batch_export"),
+                ("Esc", "exit_visual_mode"), ("v", "exit_visual_mode"), ("Shift-V", "exit_visual_mode"),
+            ]),
+            contacts: Self::context_defaults(&[
+                ("j", "next_contact"), ("Down", "next_contact"),
+                ("k", "previous_contact"), ("Up", "previous_contact"),
+                ("Enter", "insert_contact"),
+                ("a", "add_contact"),
+                ("d", "delete_contact"),
+                ("Esc", "exit_contacts"), ("q", "exit_contacts"),
+            ]),
+            notification_history: Self::context_defaults(&[
+                ("j", "next_notification"), ("Down", "next_notification"),
+                ("k", "previous_notification"), ("Up", "previous_notification"),
+                ("Esc", "exit_notification_history"), ("q", "exit_notification_history"),
+            ]),
+            folder_list: Self::context_defaults(&[
+                ("j", "next_folder"), ("Down", "next_folder"),
+                ("k", "previous_folder"), ("Up", "previous_folder"),
+                ("Enter", "select_folder"),
+                ("Esc", "exit_folder_list"), ("q", "exit_folder_list"),
+            ]),
+            thread_list: Self::context_defaults(&[
+                ("j", "next_thread"), ("Down", "next_thread"),
+                ("k", "previous_thread"), ("Up", "previous_thread"),
+                ("Enter", "select_thread"), ("l", "select_thread"),
+                ("Esc", "thread_list_back"), ("h", "thread_list_back"), ("q", "thread_list_back"),
+            ]),
+            account_status: Self::context_defaults(&[
+                ("Esc", "exit_account_status"), ("q", "exit_account_status"), ("h", "exit_account_status"),
+            ]),
+        }
+    }
+
+    fn context_defaults(pairs: &[(&str, &str)]) -> HashMap<KeySpec, String> {
+        pairs
+            .iter()
+            .filter_map(|(spec, action)| Some((KeySpec::parse(spec)?, action.to_string())))
+            .collect()
+    }
+
+    /// Merge a user's `ShortcutsConfig` over the built-in defaults: each action the user
+    /// specifies replaces its default key(s) entirely, actions they don't mention keep theirs.
+    pub fn from_config(config: &ShortcutsConfig) -> Self {
+        let mut shortcuts = Self::defaults();
+        Self::apply_overrides(&mut shortcuts.inbox, &config.inbox);
+        Self::apply_overrides(&mut shortcuts.detail, &config.detail);
+        Self::apply_overrides(&mut shortcuts.compose_normal, &config.compose_normal);
+        Self::apply_overrides(&mut shortcuts.visual, &config.visual);
+        Self::apply_overrides(&mut shortcuts.contacts, &config.contacts);
+        Self::apply_overrides(&mut shortcuts.notification_history, &config.notification_history);
+        Self::apply_overrides(&mut shortcuts.folder_list, &config.folder_list);
+        Self::apply_overrides(&mut shortcuts.thread_list, &config.thread_list);
+        Self::apply_overrides(&mut shortcuts.account_status, &config.account_status);
+        shortcuts
+    }
+
+    fn apply_overrides(table: &mut HashMap<KeySpec, String>, overrides: &ShortcutContextConfig) {
+        for (action, specs) in &overrides.bindings {
+            table.retain(|_, existing_action| existing_action != action);
+            for spec in specs {
+                if let Some(key_spec) = KeySpec::parse(spec) {
+                    table.insert(key_spec, action.clone());
+                }
+            }
+        }
+    }
+
+    /// The action name bound to `key` in the inbox view, if any
+    pub fn resolve_inbox(&self, key: &KeyEvent) -> Option<&str> {
+        self.inbox.get(&KeySpec::from_event(key)).map(String::as_str)
+    }
+
+    /// The action name bound to `key` in the email detail view, if any
+    pub fn resolve_detail(&self, key: &KeyEvent) -> Option<&str> {
+        self.detail.get(&KeySpec::from_event(key)).map(String::as_str)
+    }
+
+    /// The action name bound to `key` in compose normal mode, if any
+    pub fn resolve_compose_normal(&self, key: &KeyEvent) -> Option<&str> {
+        self.compose_normal.get(&KeySpec::from_event(key)).map(String::as_str)
+    }
+
+    /// The action name bound to `key` in visual mode, if any
+    pub fn resolve_visual(&self, key: &KeyEvent) -> Option<&str> {
+        self.visual.get(&KeySpec::from_event(key)).map(String::as_str)
+    }
+
+    /// The action name bound to `key` in the Contacts view, if any
+    pub fn resolve_contacts(&self, key: &KeyEvent) -> Option<&str> {
+        self.contacts.get(&KeySpec::from_event(key)).map(String::as_str)
+    }
+
+    /// The action name bound to `key` in the NotificationHistory view, if any
+    pub fn resolve_notification_history(&self, key: &KeyEvent) -> Option<&str> {
+        self.notification_history.get(&KeySpec::from_event(key)).map(String::as_str)
+    }
+
+    /// The action name bound to `key` in the FolderList view, if any
+    pub fn resolve_folder_list(&self, key: &KeyEvent) -> Option<&str> {
+        self.folder_list.get(&KeySpec::from_event(key)).map(String::as_str)
+    }
+
+    /// The action name bound to `key` in the ThreadList view, if any
+    pub fn resolve_thread_list(&self, key: &KeyEvent) -> Option<&str> {
+        self.thread_list.get(&KeySpec::from_event(key)).map(String::as_str)
+    }
+
+    /// The action name bound to `key` in the AccountStatus view, if any
+    pub fn resolve_account_status(&self, key: &KeyEvent) -> Option<&str> {
+        self.account_status.get(&KeySpec::from_event(key)).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_default_inbox_bindings_resolve() {
+        let shortcuts = Shortcuts::defaults();
+        assert_eq!(shortcuts.resolve_inbox(&key(KeyCode::Char('j'), KeyModifiers::NONE)), Some("next_email"));
+        assert_eq!(shortcuts.resolve_inbox(&key(KeyCode::Char('V'), KeyModifiers::SHIFT)), Some("enter_visual_mode"));
+        assert_eq!(shortcuts.resolve_inbox(&key(KeyCode::Char('z'), KeyModifiers::NONE)), None);
+    }
+
+    #[test]
+    fn test_inbox_shift_l_cycles_listing_style() {
+        let shortcuts = Shortcuts::defaults();
+        assert_eq!(
+            shortcuts.resolve_inbox(&key(KeyCode::Char('L'), KeyModifiers::SHIFT)),
+            Some("cycle_listing_style")
+        );
+    }
+
+    #[test]
+    fn test_compose_normal_attachment_bindings() {
+        let shortcuts = Shortcuts::defaults();
+        assert_eq!(
+            shortcuts.resolve_compose_normal(&key(KeyCode::Char('a'), KeyModifiers::NONE)),
+            Some("add_attachment")
+        );
+        assert_eq!(
+            shortcuts.resolve_compose_normal(&key(KeyCode::Char('J'), KeyModifiers::SHIFT)),
+            Some("next_attachment")
+        );
+        assert_eq!(
+            shortcuts.resolve_compose_normal(&key(KeyCode::Char('K'), KeyModifiers::SHIFT)),
+            Some("previous_attachment")
+        );
+    }
+
+    #[test]
+    fn test_detail_html_view_bindings() {
+        let shortcuts = Shortcuts::defaults();
+        assert_eq!(
+            shortcuts.resolve_detail(&key(KeyCode::Char('t'), KeyModifiers::NONE)),
+            Some("toggle_html_view")
+        );
+        assert_eq!(
+            shortcuts.resolve_detail(&key(KeyCode::Char('p'), KeyModifiers::NONE)),
+            Some("toggle_html_source")
+        );
+    }
+
+    #[test]
+    fn test_user_override_replaces_default_binding() {
+        let mut config = ShortcutsConfig::default();
+        config.inbox.bindings.insert("quit".to_string(), vec!["Ctrl-c".to_string()]);
+        let shortcuts = Shortcuts::from_config(&config);
+
+        // The rebound key now triggers the action
+        assert_eq!(
+            shortcuts.resolve_inbox(&key(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some("quit")
+        );
+        // The old default key no longer does
+        assert_eq!(shortcuts.resolve_inbox(&key(KeyCode::Char('q'), KeyModifiers::NONE)), None);
+        // Unrelated actions are untouched
+        assert_eq!(shortcuts.resolve_inbox(&key(KeyCode::Char('j'), KeyModifiers::NONE)), Some("next_email"));
+    }
+
+    #[test]
+    fn test_keyspec_parse_rejects_unknown_modifier() {
+        assert!(KeySpec::parse("Meta-x").is_none());
+    }
+
+    #[test]
+    fn test_default_contacts_bindings_resolve() {
+        let shortcuts = Shortcuts::defaults();
+        assert_eq!(shortcuts.resolve_contacts(&key(KeyCode::Char('j'), KeyModifiers::NONE)), Some("next_contact"));
+        assert_eq!(shortcuts.resolve_contacts(&key(KeyCode::Enter, KeyModifiers::NONE)), Some("insert_contact"));
+        assert_eq!(shortcuts.resolve_inbox(&key(KeyCode::Char('b'), KeyModifiers::NONE)), Some("open_contacts"));
+    }
+
+    #[test]
+    fn test_default_notification_history_bindings_resolve() {
+        let shortcuts = Shortcuts::defaults();
+        assert_eq!(shortcuts.resolve_notification_history(&key(KeyCode::Char('j'), KeyModifiers::NONE)), Some("next_notification"));
+        assert_eq!(shortcuts.resolve_notification_history(&key(KeyCode::Esc, KeyModifiers::NONE)), Some("exit_notification_history"));
+        assert_eq!(shortcuts.resolve_inbox(&key(KeyCode::Char('n'), KeyModifiers::NONE)), Some("open_notification_history"));
+    }
+
+    #[test]
+    fn test_default_folder_list_bindings_resolve() {
+        let shortcuts = Shortcuts::defaults();
+        assert_eq!(shortcuts.resolve_folder_list(&key(KeyCode::Char('j'), KeyModifiers::NONE)), Some("next_folder"));
+        assert_eq!(shortcuts.resolve_folder_list(&key(KeyCode::Enter, KeyModifiers::NONE)), Some("select_folder"));
+        assert_eq!(shortcuts.resolve_inbox(&key(KeyCode::Char('g'), KeyModifiers::NONE)), Some("open_folders"));
+    }
+
+    #[test]
+    fn test_default_thread_list_bindings_resolve() {
+        let shortcuts = Shortcuts::defaults();
+        assert_eq!(shortcuts.resolve_thread_list(&key(KeyCode::Char('j'), KeyModifiers::NONE)), Some("next_thread"));
+        assert_eq!(shortcuts.resolve_thread_list(&key(KeyCode::Enter, KeyModifiers::NONE)), Some("select_thread"));
+        assert_eq!(shortcuts.resolve_inbox(&key(KeyCode::Char('t'), KeyModifiers::NONE)), Some("open_threads"));
+    }
+
+    #[test]
+    fn test_default_account_status_bindings_resolve() {
+        let shortcuts = Shortcuts::defaults();
+        assert_eq!(shortcuts.resolve_account_status(&key(KeyCode::Esc, KeyModifiers::NONE)), Some("exit_account_status"));
+        assert_eq!(shortcuts.resolve_inbox(&key(KeyCode::Char('s'), KeyModifiers::NONE)), Some("open_account_status"));
+    }
+}
@@ -0,0 +1,80 @@
+//! Link detection for the compose Markdown preview and message reading view (see
+//! `ui::render_compose`/`ui::render_email_detail`): find URLs and email addresses in body text,
+//! number them for follow-link mode, and hand off to the OS opener or a new compose draft.
+
+use anyhow::{Context, Result};
+use linkify::{LinkFinder, LinkKind};
+
+/// Where a detected link points: a URL to open with the OS's default handler, or an email
+/// address to start composing a new message to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkTarget {
+    Url(String),
+    Email(String),
+}
+
+/// A link found in body text, numbered in the order it appears (1-based, matching the digit the
+/// user types in follow-link mode).
+#[derive(Debug, Clone)]
+pub struct DetectedLink {
+    pub index: usize,
+    pub target: LinkTarget,
+}
+
+/// Scan `text` for URLs and `mailto`-able email addresses with `linkify`, which already trims
+/// the trailing punctuation a sentence tends to wrap a link in - `(https://example.com).`
+/// resolves to just `https://example.com`. Since this runs over the full unwrapped body rather
+/// than per rendered line, a link split across wrapped display lines is still found whole.
+pub fn find_links(text: &str) -> Vec<DetectedLink> {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Url, LinkKind::Email]);
+
+    finder
+        .links(text)
+        .enumerate()
+        .map(|(i, link)| {
+            let target = match link.kind() {
+                LinkKind::Email => LinkTarget::Email(link.as_str().to_string()),
+                _ => LinkTarget::Url(link.as_str().to_string()),
+            };
+            DetectedLink { index: i + 1, target }
+        })
+        .collect()
+}
+
+/// Open a URL with the OS's default handler (`xdg-open`/`open`/`start`, via the `open` crate -
+/// the same one [`crate::oauth::run_authorization_flow`] uses to launch a browser). `mailto:`
+/// targets aren't opened this way; the caller starts a new compose draft instead, since there's
+/// no "default mail client" to hand a `mailto:` URL to inside this TUI.
+pub fn open_url(url: &str) -> Result<()> {
+    open::that(url).context("Failed to open link")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_links_trims_trailing_sentence_punctuation() {
+        let links = find_links("See (https://example.com/path).");
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].index, 1);
+        assert_eq!(links[0].target, LinkTarget::Url("https://example.com/path".to_string()));
+    }
+
+    #[test]
+    fn find_links_numbers_urls_and_emails_in_order() {
+        let text = "Reach us at support@example.com or visit https://example.com for docs.";
+        let links = find_links(text);
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].index, 1);
+        assert_eq!(links[0].target, LinkTarget::Email("support@example.com".to_string()));
+        assert_eq!(links[1].index, 2);
+        assert_eq!(links[1].target, LinkTarget::Url("https://example.com".to_string()));
+    }
+
+    #[test]
+    fn find_links_empty_for_plain_text() {
+        assert!(find_links("No links here, just plain text.").is_empty());
+    }
+}
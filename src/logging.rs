@@ -0,0 +1,94 @@
+//! A minimal [`log::Log`] implementation configured from [`crate::config::LogSettings`], so
+//! diagnostics route through the `log::{error,warn,info,debug,trace}!` macros instead of raw
+//! `eprintln!` calls that would garble the TUI's alternate screen and, worse, could dump secrets
+//! straight to a config-contents debug line. [`init`] is called once, from
+//! [`crate::config::Config::load_from`], right after a config has been parsed and validated -
+//! mirroring meli's practice of initializing its logger early in the config-loading path, before
+//! anything else touches the terminal.
+
+use crate::config::LogSettings;
+use anyhow::{Context, Result};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::sync::Mutex;
+
+enum Sink {
+    Stderr,
+    File(Mutex<File>),
+}
+
+struct Logger {
+    max_level: log::LevelFilter,
+    sink: Sink,
+    redact: bool,
+    email_pattern: regex::Regex,
+}
+
+impl log::Log for Logger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= self.max_level
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let message = record.args().to_string();
+        let message = if self.redact {
+            self.email_pattern.replace_all(&message, "[redacted]").into_owned()
+        } else {
+            message
+        };
+        let line = format!("[{}] {}: {}\n", record.level(), record.target(), message);
+
+        match &self.sink {
+            Sink::Stderr => {
+                let _ = std::io::stderr().write_all(line.as_bytes());
+            }
+            Sink::File(file) => {
+                if let Ok(mut file) = file.lock() {
+                    let _ = file.write_all(line.as_bytes());
+                }
+            }
+        }
+    }
+
+    fn flush(&self) {
+        if let Sink::File(file) = &self.sink {
+            if let Ok(mut file) = file.lock() {
+                let _ = file.flush();
+            }
+        }
+    }
+}
+
+/// Install the global logger per `settings`. Safe to call more than once - `log` only ever keeps
+/// the first logger installed in a process, so later calls (e.g. a test that loads multiple
+/// configs) are silent no-ops rather than errors.
+pub fn init(settings: &LogSettings) -> Result<()> {
+    let sink = match &settings.file {
+        Some(path) => Sink::File(Mutex::new(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("Failed to open log file {:?}", path))?,
+        )),
+        None => Sink::Stderr,
+    };
+
+    let email_pattern = regex::Regex::new(r"[[:alnum:]._%+-]+@[[:alnum:].-]+\.[[:alpha:]]{2,}")
+        .expect("hardcoded email-redaction regex is valid");
+    let max_level = settings.level.to_level_filter();
+
+    log::set_max_level(max_level);
+    let _ = log::set_boxed_logger(Box::new(Logger {
+        max_level,
+        sink,
+        redact: settings.redact,
+        email_pattern,
+    }));
+
+    Ok(())
+}
@@ -0,0 +1,521 @@
+/// Local Maildir mirror for offline reading and faster re-sync.
+///
+/// Each `(account, folder)` pair gets its own standard Maildir directory
+/// (`cur`/`new`/`tmp`) under the data directory. Messages are written once on
+/// sync and keyed by IMAP UID so a later sync only has to diff the server's
+/// UID list against [`MaildirMirror::cached_uids`] instead of re-downloading
+/// the whole folder, and the TUI can fall back to [`MaildirMirror::load_cached`]
+/// for reading/search while offline.
+use crate::db::{DbEmail, EmailStatus as DbEmailStatus};
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Flags tracked on a mirrored message, matching the subset of Maildir `:2,` flags
+/// (and corresponding IMAP flags) this client cares about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MaildirFlag {
+    Seen,
+    Answered,
+    Flagged,
+    Deleted,
+    Draft,
+}
+
+impl MaildirFlag {
+    /// The single-letter code used in a Maildir filename's `:2,` flag suffix.
+    fn code(self) -> char {
+        match self {
+            MaildirFlag::Draft => 'D',
+            MaildirFlag::Flagged => 'F',
+            MaildirFlag::Answered => 'R',
+            MaildirFlag::Seen => 'S',
+            MaildirFlag::Deleted => 'T',
+        }
+    }
+
+    fn from_code(c: char) -> Option<Self> {
+        match c {
+            'D' => Some(MaildirFlag::Draft),
+            'F' => Some(MaildirFlag::Flagged),
+            'R' => Some(MaildirFlag::Answered),
+            'S' => Some(MaildirFlag::Seen),
+            'T' => Some(MaildirFlag::Deleted),
+            _ => None,
+        }
+    }
+
+    /// Render a flag set as a sorted Maildir `:2,FLAGS` suffix (letters must be
+    /// ASCII-sorted per the Maildir spec so two writers agree on a canonical name).
+    fn render(flags: &[MaildirFlag]) -> String {
+        let mut codes: Vec<char> = flags.iter().map(|f| f.code()).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        let letters: String = codes.into_iter().collect();
+        format!(":2,{}", letters)
+    }
+
+    fn parse_suffix(suffix: &str) -> Vec<MaildirFlag> {
+        suffix.chars().filter_map(MaildirFlag::from_code).collect()
+    }
+}
+
+/// A single message cached in a [`MaildirMirror`].
+pub struct MirroredMessage {
+    pub uid: u32,
+    pub flags: Vec<MaildirFlag>,
+    pub path: PathBuf,
+}
+
+/// The Maildir mirror for one account/folder pair.
+pub struct MaildirMirror {
+    root: PathBuf,
+}
+
+/// Sanitize a path component (account id, folder name) for use in the on-disk Maildir tree.
+/// `pub(crate)` so [`crate::backend::LocalMaildirBackend`] can find an account's mirror root
+/// without going through [`MaildirMirror::open`] (which also wants a specific folder).
+pub(crate) fn sanitize(component: &str) -> String {
+    component
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
+impl MaildirMirror {
+    /// Open (creating if necessary) the mirror for `account_id`/`folder` under `data_dir`.
+    pub fn open(data_dir: &Path, account_id: &str, folder: &str) -> Result<Self> {
+        let root = data_dir
+            .join("maildir")
+            .join(sanitize(account_id))
+            .join(sanitize(folder));
+
+        for sub in ["cur", "new", "tmp"] {
+            fs::create_dir_all(root.join(sub))
+                .with_context(|| format!("Failed to create Maildir {} directory at {:?}", sub, root))?;
+        }
+
+        Ok(Self { root })
+    }
+
+    /// The on-disk filename tume uses for a mirrored UID: `<uid>.tume` plus an optional
+    /// `:2,FLAGS` suffix once the message has flags set.
+    fn base_name(uid: u32) -> String {
+        format!("{}.tume", uid)
+    }
+
+    fn uid_from_name(name: &str) -> Option<u32> {
+        name.split('.').next()?.parse().ok()
+    }
+
+    fn flags_from_name(name: &str) -> Vec<MaildirFlag> {
+        name.split_once(":2,")
+            .map(|(_, suffix)| MaildirFlag::parse_suffix(suffix))
+            .unwrap_or_default()
+    }
+
+    /// List every UID currently cached in `cur` or `new`, regardless of flags.
+    pub fn cached_uids(&self) -> Result<HashSet<u32>> {
+        let mut uids = HashSet::new();
+        for sub in ["cur", "new"] {
+            let dir = self.root.join(sub);
+            for entry in fs::read_dir(&dir).with_context(|| format!("Failed to read {:?}", dir))? {
+                let entry = entry?;
+                if let Some(name) = entry.file_name().to_str() {
+                    if let Some(uid) = Self::uid_from_name(name) {
+                        uids.insert(uid);
+                    }
+                }
+            }
+        }
+        Ok(uids)
+    }
+
+    /// List every message currently cached, with its flags and path.
+    pub fn list_messages(&self) -> Result<Vec<MirroredMessage>> {
+        let mut messages = Vec::new();
+        for sub in ["cur", "new"] {
+            let dir = self.root.join(sub);
+            for entry in fs::read_dir(&dir).with_context(|| format!("Failed to read {:?}", dir))? {
+                let entry = entry?;
+                let path = entry.path();
+                if let Some(name) = entry.file_name().to_str() {
+                    if let Some(uid) = Self::uid_from_name(name) {
+                        messages.push(MirroredMessage {
+                            uid,
+                            flags: Self::flags_from_name(name),
+                            path,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(messages)
+    }
+
+    fn find_existing(&self, uid: u32) -> Option<PathBuf> {
+        for sub in ["cur", "new"] {
+            let dir = self.root.join(sub);
+            if let Ok(entries) = fs::read_dir(&dir) {
+                for entry in entries.flatten() {
+                    if let Some(name) = entry.file_name().to_str() {
+                        if Self::uid_from_name(name) == Some(uid) {
+                            return Some(entry.path());
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Write a message's raw RFC822 bytes into the mirror under `new/`, tagged with its
+    /// IMAP UID and current flags. Delivery goes through `tmp/` first per the Maildir
+    /// spec so a reader never observes a partially-written file.
+    pub fn store(&self, uid: u32, raw_message: &[u8], flags: &[MaildirFlag]) -> Result<PathBuf> {
+        if let Some(existing) = self.find_existing(uid) {
+            fs::remove_file(&existing).ok();
+        }
+
+        let name = format!("{}{}", Self::base_name(uid), MaildirFlag::render(flags));
+        let tmp_path = self.root.join("tmp").join(&name);
+        let dest_sub = if flags.is_empty() { "new" } else { "cur" };
+        let dest_path = self.root.join(dest_sub).join(&name);
+
+        fs::write(&tmp_path, raw_message).context("Failed to write message into Maildir tmp")?;
+        fs::rename(&tmp_path, &dest_path).context("Failed to move message into place")?;
+
+        Ok(dest_path)
+    }
+
+    /// Update the flags of an already-mirrored message (e.g. after the server reports a
+    /// flag change), moving it into `cur/` with the new `:2,FLAGS` suffix.
+    pub fn apply_flags(&self, uid: u32, flags: &[MaildirFlag]) -> Result<()> {
+        let existing = match self.find_existing(uid) {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        let name = format!("{}{}", Self::base_name(uid), MaildirFlag::render(flags));
+        let dest_path = self.root.join("cur").join(&name);
+        if existing != dest_path {
+            fs::rename(&existing, &dest_path).context("Failed to update Maildir flags")?;
+        }
+        Ok(())
+    }
+
+    /// Remove a message from the mirror (e.g. it was deleted or expunged server-side).
+    pub fn remove(&self, uid: u32) -> Result<()> {
+        if let Some(existing) = self.find_existing(uid) {
+            fs::remove_file(existing).context("Failed to remove mirrored message")?;
+        }
+        Ok(())
+    }
+
+    /// Load every cached message in this folder as a [`DbEmail`] plus its attachments, for
+    /// offline reading.
+    pub fn load_cached(&self, folder: &str) -> Result<Vec<(DbEmail, Vec<crate::mime::ParsedAttachment>)>> {
+        let mut emails = Vec::new();
+        for message in self.list_messages()? {
+            let raw = fs::read(&message.path)
+                .with_context(|| format!("Failed to read cached message {:?}", message.path))?;
+            if let Some(parsed) = Self::parse_cached(&raw, &message.flags, folder) {
+                emails.push(parsed);
+            }
+        }
+        Ok(emails)
+    }
+
+    /// Parse one cached message, loaded from disk by [`Self::load_cached`]. See
+    /// [`crate::mime::parse_message`] for the MIME decoding itself. `pub(crate)` so
+    /// [`crate::backend::LocalMaildirBackend`] can reuse it for single-message fetches instead
+    /// of duplicating the parse.
+    pub(crate) fn parse_cached(
+        raw: &[u8],
+        flags: &[MaildirFlag],
+        folder: &str,
+    ) -> Option<(DbEmail, Vec<crate::mime::ParsedAttachment>)> {
+        let parsed = mail_parser::MessageParser::default().parse(raw)?;
+
+        let from = parsed
+            .from()
+            .and_then(|addrs| addrs.first())
+            .and_then(|addr| addr.address())
+            .unwrap_or("unknown@unknown.com")
+            .to_string();
+
+        let to = parsed
+            .to()
+            .and_then(|addrs| addrs.first())
+            .and_then(|addr| addr.address())
+            .map(|s| s.to_string())
+            .unwrap_or_default();
+
+        let subject = parsed.subject().unwrap_or("(No Subject)").to_string();
+        let message_id = parsed.message_id().map(|s| s.to_string());
+
+        let mime = crate::mime::parse_message(raw).unwrap_or_default();
+        let body_text = mime.text_plain.clone().or_else(|| mime.text_html.clone()).unwrap_or_default();
+
+        let preview = body_text.lines().next().unwrap_or("").chars().take(100).collect::<String>();
+
+        let date = parsed
+            .date()
+            .map(|dt| format!("{}", dt))
+            .unwrap_or_else(|| {
+                let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+                format!("timestamp: {}", timestamp)
+            });
+
+        let is_unread = !flags.contains(&MaildirFlag::Seen);
+        let is_flagged = flags.contains(&MaildirFlag::Flagged);
+
+        let email = DbEmail {
+            id: 0,
+            from_address: from,
+            to_addresses: to,
+            cc_addresses: None,
+            bcc_addresses: None,
+            subject,
+            body: body_text,
+            body_html: mime.text_html.clone(),
+            preview,
+            date,
+            status: if is_unread { DbEmailStatus::Unread } else { DbEmailStatus::Read },
+            is_flagged,
+            folder: folder.to_string(),
+            thread_id: None,
+            account_id: None,
+            message_id,
+            imap_uid: None,
+            in_reply_to: None,
+            references: None,
+            modseq: None,
+            pgp_status: mime.pgp_status.clone(),
+            list_headers: mime.list_headers.clone(),
+            headers: mime.headers.clone(),
+            has_attachment: mime.has_attachment,
+        };
+
+        Some((email, mime.attachments))
+    }
+}
+
+/// Generates Maildir-unique filenames per <https://cr.yp.to/proto/maildir.html>:
+/// `<secs>.<pid>_<count>.<hostname>`. `count` increments for each name handed out within the
+/// same UNIX second and resets once the clock moves on, so two deliveries in the same second
+/// from this process never collide.
+#[derive(Debug)]
+struct UniqueNamer {
+    count: u64,
+    max_seen_unix_time: u64,
+    hostname: String,
+}
+
+impl UniqueNamer {
+    fn new() -> Self {
+        Self {
+            count: 0,
+            max_seen_unix_time: 0,
+            hostname: Self::safe_hostname(),
+        }
+    }
+
+    /// The local hostname with `/` and `:` escaped, since either would otherwise be read as a
+    /// path separator or flag delimiter in the filename it's embedded in.
+    fn safe_hostname() -> String {
+        let raw = hostname::get()
+            .map(|h| h.to_string_lossy().into_owned())
+            .unwrap_or_else(|_| "localhost".to_string());
+        raw.replace('/', "\\057").replace(':', "\\072")
+    }
+
+    fn next(&mut self) -> String {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        if secs > self.max_seen_unix_time {
+            self.max_seen_unix_time = secs;
+            self.count = 0;
+        } else {
+            self.count += 1;
+        }
+
+        format!("{}.{}_{}.{}", secs, std::process::id(), self.count, self.hostname)
+    }
+}
+
+/// A standalone on-disk Maildir that [`crate::email_sync::EmailSyncManager`] archives messages
+/// into when a [`crate::email_sync::RuleAction::Archive`] fires, independent of
+/// [`MaildirMirror`] (which mirrors a live IMAP folder keyed by UID). Filenames use the Maildir
+/// spec's unique-name scheme rather than a UID, since an archived message has no IMAP UID of its
+/// own once it's copied out to local disk.
+#[derive(Debug)]
+pub struct MaildirStore {
+    root: PathBuf,
+    namer: UniqueNamer,
+}
+
+impl MaildirStore {
+    /// Open (creating if necessary) a standalone Maildir at `root`.
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        for sub in ["cur", "new", "tmp"] {
+            fs::create_dir_all(root.join(sub))
+                .with_context(|| format!("Failed to create Maildir {} directory at {:?}", sub, root))?;
+        }
+
+        Ok(Self { root, namer: UniqueNamer::new() })
+    }
+
+    /// Deliver a new message: write into `tmp/` then rename into `new/`, per the Maildir
+    /// delivery protocol, so a reader never observes a partially-written file.
+    pub fn deliver(&mut self, raw_message: &[u8]) -> Result<PathBuf> {
+        self.write(raw_message, "new", "")
+    }
+
+    /// Archive an already-handled message straight into `cur/` tagged `:2,S`, since a message a
+    /// rule just archived isn't new mail the user still needs to triage.
+    pub fn archive(&mut self, raw_message: &[u8]) -> Result<PathBuf> {
+        self.write(raw_message, "cur", ":2,S")
+    }
+
+    fn write(&mut self, raw_message: &[u8], dest_sub: &str, suffix: &str) -> Result<PathBuf> {
+        let name = format!("{}{}", self.namer.next(), suffix);
+        let tmp_path = self.root.join("tmp").join(&name);
+        let dest_path = self.root.join(dest_sub).join(&name);
+
+        fs::write(&tmp_path, raw_message).context("Failed to write message into Maildir tmp")?;
+        fs::rename(&tmp_path, &dest_path).context("Failed to move message into place")?;
+
+        Ok(dest_path)
+    }
+}
+
+/// Given the server's current UID list and what's already cached locally, work out which
+/// UIDs need to be fetched (new on the server) and which cached UIDs are gone server-side
+/// (expunged, so the mirror should drop them).
+pub fn diff_uids(server_uids: &[u32], cached_uids: &HashSet<u32>) -> (Vec<u32>, Vec<u32>) {
+    let server_set: HashSet<u32> = server_uids.iter().copied().collect();
+
+    let mut to_fetch: Vec<u32> = server_uids
+        .iter()
+        .copied()
+        .filter(|uid| !cached_uids.contains(uid))
+        .collect();
+    to_fetch.sort_unstable();
+
+    let mut to_remove: Vec<u32> = cached_uids
+        .iter()
+        .copied()
+        .filter(|uid| !server_set.contains(uid))
+        .collect();
+    to_remove.sort_unstable();
+
+    (to_fetch, to_remove)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push(format!("tume-maildir-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        path
+    }
+
+    #[test]
+    fn test_open_creates_standard_layout() {
+        let dir = temp_dir("layout");
+        let mirror = MaildirMirror::open(&dir, "acct1", "INBOX").unwrap();
+        assert!(mirror.root.join("cur").is_dir());
+        assert!(mirror.root.join("new").is_dir());
+        assert!(mirror.root.join("tmp").is_dir());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_store_and_cached_uids() {
+        let dir = temp_dir("store");
+        let mirror = MaildirMirror::open(&dir, "acct1", "INBOX").unwrap();
+        mirror.store(42, b"Subject: hi\r\n\r\nbody", &[]).unwrap();
+
+        let uids = mirror.cached_uids().unwrap();
+        assert!(uids.contains(&42));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_apply_flags_moves_into_cur_with_suffix() {
+        let dir = temp_dir("flags");
+        let mirror = MaildirMirror::open(&dir, "acct1", "INBOX").unwrap();
+        mirror.store(7, b"Subject: hi\r\n\r\nbody", &[]).unwrap();
+        mirror.apply_flags(7, &[MaildirFlag::Seen, MaildirFlag::Flagged]).unwrap();
+
+        let messages = mirror.list_messages().unwrap();
+        let msg = messages.iter().find(|m| m.uid == 7).unwrap();
+        assert!(msg.path.starts_with(mirror.root.join("cur")));
+        assert!(msg.flags.contains(&MaildirFlag::Seen));
+        assert!(msg.flags.contains(&MaildirFlag::Flagged));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_remove_deletes_message() {
+        let dir = temp_dir("remove");
+        let mirror = MaildirMirror::open(&dir, "acct1", "INBOX").unwrap();
+        mirror.store(3, b"Subject: hi\r\n\r\nbody", &[]).unwrap();
+        mirror.remove(3).unwrap();
+
+        assert!(!mirror.cached_uids().unwrap().contains(&3));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_diff_uids_finds_new_and_stale() {
+        let cached: HashSet<u32> = [1, 2, 3].into_iter().collect();
+        let server = vec![2, 3, 4];
+
+        let (to_fetch, to_remove) = diff_uids(&server, &cached);
+        assert_eq!(to_fetch, vec![4]);
+        assert_eq!(to_remove, vec![1]);
+    }
+
+    #[test]
+    fn test_flag_render_is_sorted_and_deduped() {
+        let suffix = MaildirFlag::render(&[MaildirFlag::Seen, MaildirFlag::Answered, MaildirFlag::Seen]);
+        assert_eq!(suffix, ":2,RS");
+    }
+
+    #[test]
+    fn test_unique_namer_increments_within_same_second() {
+        let mut namer = UniqueNamer::new();
+        namer.max_seen_unix_time = u64::MAX;
+        let first = namer.next();
+        let second = namer.next();
+        assert_ne!(first, second);
+        assert!(first.ends_with(&format!("_1.{}", namer.hostname)));
+        assert!(second.ends_with(&format!("_2.{}", namer.hostname)));
+    }
+
+    #[test]
+    fn test_maildir_store_deliver_and_archive() {
+        let dir = temp_dir("store-archive");
+        let mut store = MaildirStore::open(&dir).unwrap();
+
+        let new_path = store.deliver(b"Subject: hi\r\n\r\nbody").unwrap();
+        assert!(new_path.starts_with(dir.join("new")));
+
+        let archived_path = store.archive(b"Subject: bye\r\n\r\nbody").unwrap();
+        assert!(archived_path.starts_with(dir.join("cur")));
+        assert!(archived_path.to_str().unwrap().ends_with(":2,S"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
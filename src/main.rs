@@ -1,12 +1,25 @@
 mod app;
+mod backend;
 mod credentials;
 mod config;
 mod db;
 mod email_sync;
 mod events;
+mod gpg;
+mod keymap;
+mod links;
+mod logging;
+mod maildir;
+mod mime;
+mod oauth;
 mod providers;
+mod search;
+mod sync;
 mod theme;
+mod threading;
 mod ui;
+mod vcard;
+mod wizard;
 
 use anyhow::Result;
 use crossterm::{
@@ -23,6 +36,31 @@ async fn main() -> Result<()> {
     // Parse command line arguments
     let args: Vec<String> = std::env::args().collect();
     let dev_mode = args.iter().any(|arg| arg == "--dev");
+    let config_path = args
+        .iter()
+        .position(|arg| arg == "--config")
+        .and_then(|i| args.get(i + 1))
+        .map(std::path::PathBuf::from);
+    let cli_overrides = config::ConfigOverrides {
+        default_account: args
+            .iter()
+            .position(|arg| arg == "--default-account")
+            .and_then(|i| args.get(i + 1))
+            .cloned(),
+        ..Default::default()
+    };
+
+    // First run: no config file (or an empty one) means nothing to connect to yet. Walk the
+    // user through an interactive wizard instead of leaving them to hand-edit the skeleton file
+    // `Config::load_from` just wrote; falls back to that skeleton when stdin isn't a TTY.
+    {
+        let mut config = config::Config::load_from(config_path.clone()).unwrap_or_default();
+        if config.accounts.is_empty() {
+            if let Err(e) = wizard::run_first_run_setup(&mut config) {
+                log::warn!("account wizard failed: {}", e);
+            }
+        }
+    }
 
     // Setup terminal
     enable_raw_mode()?;
@@ -32,11 +70,8 @@ async fn main() -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     // Create app state with database
-    let mut app = App::with_database(dev_mode).await.unwrap_or_else(|e| {
-        eprintln!(
-            "Warning: Failed to initialize database: {}. Using in-memory mode.",
-            e
-        );
+    let mut app = App::with_database(dev_mode, config_path, cli_overrides).await.unwrap_or_else(|e| {
+        log::warn!("Failed to initialize database: {}. Using in-memory mode.", e);
         App::new()
     });
 
@@ -46,7 +81,7 @@ async fn main() -> Result<()> {
     // Save draft before cleaning up terminal (if needed)
     if app.has_unsaved_draft() {
         if let Err(e) = app.save_draft_before_quit_async().await {
-            eprintln!("Warning: Failed to save draft before quit: {}", e);
+            log::warn!("Failed to save draft before quit: {}", e);
         }
     }
 
@@ -69,13 +104,197 @@ fn run_app<B: ratatui::backend::Backend>(
     loop {
         terminal.draw(|f| ui::draw(f, app))?;
         events::handle_events(app)?;
-        
+
         // Check for completed sync results
         app.check_sync_result();
 
+        // Check for a completed pre-flight credential validation
+        app.poll_validation_result();
+
+        // Check for a completed background folder list sync
+        app.poll_folder_list_result();
+
+        // Check for a completed OAuth2 authorization
+        app.poll_oauth_result();
+
+        // Check for a completed send from the compose view
+        app.poll_send_result();
+
+        // Check for new mail pushed by the background IDLE/poll watcher
+        app.poll_mail_watch_events();
+
+        if app.take_external_editor_request() {
+            run_external_editor(terminal, app)?;
+        }
+
         if app.should_quit {
             break;
         }
     }
     Ok(())
 }
+
+/// A `tume`-owned temp directory, scoped to this process and `0700` on unix, for handing draft
+/// contents to an external editor without leaving them world-readable in the shared system temp
+/// dir. Created atomically at the final mode via [`std::os::unix::fs::DirBuilderExt::mode`]
+/// (not `create_dir_all` then `chmod`, which leaves a window at the umask's default mode), and
+/// refuses to reuse whatever's already at this path rather than silently continuing - PIDs are
+/// low-entropy and visible via `ps`, so another user could have pre-created
+/// `tume-session-<pid>` (even as a symlink) before this process reused the pid. The owner check
+/// afterward closes the remaining race between that create and the first write into it.
+fn secure_temp_dir() -> io::Result<std::path::PathBuf> {
+    let mut dir = std::env::temp_dir();
+    dir.push(format!("tume-session-{}", std::process::id()));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+
+        std::fs::DirBuilder::new().mode(0o700).create(&dir)?;
+
+        let owner_uid = std::fs::symlink_metadata(&dir)?.uid();
+        if owner_uid != unsafe { geteuid() } {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("{:?} exists but isn't owned by the current user", dir),
+            ));
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        std::fs::create_dir_all(&dir)?;
+    }
+
+    Ok(dir)
+}
+
+#[cfg(unix)]
+extern "C" {
+    fn geteuid() -> u32;
+}
+
+/// Suspend the TUI, let `$EDITOR`/`$VISUAL` (falling back to `vi`, then `nano`) edit the draft
+/// as an RFC-822-style `To:`/`Subject:` header block followed by the body in a temp file, then
+/// restore the terminal and feed the result back into `app`. Only `main` does this, since it's
+/// the one holding the `Terminal`/raw-mode handle; `App` just flags the request.
+fn run_external_editor<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+) -> io::Result<()> {
+    let (original_recipients, original_subject, original_body) = match app.compose_state.as_ref() {
+        Some(compose) => (compose.recipients.clone(), compose.subject.clone(), compose.body.clone()),
+        None => return Ok(()),
+    };
+
+    let path = secure_temp_dir()?.join("draft.eml");
+    let original_contents = format!(
+        "To: {}\nSubject: {}\n\n{}",
+        original_recipients, original_subject, original_body
+    );
+    std::fs::write(&path, &original_contents)?;
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+
+    let result = spawn_editor(&path);
+
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen)?;
+    terminal.clear()?;
+
+    match result {
+        Ok(true) => {
+            let edited = std::fs::read_to_string(&path).unwrap_or_else(|_| original_contents.clone());
+            if edited.trim().is_empty() {
+                app.status_message = Some("External editor produced an empty file; draft unchanged".to_string());
+            } else if edited == original_contents {
+                app.status_message = Some("External editor exited without changes".to_string());
+            } else {
+                let (recipients, subject, body) = parse_edited_draft(&edited);
+                app.compose_set_from_editor(recipients, subject, body);
+                app.status_message = Some("Updated draft from external editor".to_string());
+            }
+        }
+        Ok(false) => {
+            app.status_message = Some("External editor exited with an error; draft unchanged".to_string());
+        }
+        Err(e) => {
+            app.status_message = Some(format!("Failed to launch external editor: {}", e));
+        }
+    }
+
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
+
+/// Split an edited draft back into `To:`/`Subject:` headers and body. Leading lines matching
+/// `To:`/`Subject:` (case-insensitive) are consumed as headers up to the first blank line or
+/// first non-header line, whichever comes first; everything after that is the body. A draft
+/// with no recognizable headers at all is treated as body-only, leaving To/Subject untouched.
+fn parse_edited_draft(edited: &str) -> (Option<String>, Option<String>, String) {
+    let mut recipients = None;
+    let mut subject = None;
+    let mut lines = edited.split('\n').peekable();
+
+    while let Some(line) = lines.peek() {
+        let lower = line.to_lowercase();
+        if lower.starts_with("to:") {
+            recipients = Some(line[3..].trim().to_string());
+            lines.next();
+        } else if lower.starts_with("subject:") {
+            subject = Some(line[8..].trim().to_string());
+            lines.next();
+        } else {
+            break;
+        }
+    }
+
+    // Skip a single blank separator line between the headers and the body.
+    if lines.peek().map(|l| l.is_empty()).unwrap_or(false) {
+        lines.next();
+    }
+
+    let body = lines.collect::<Vec<_>>().join("\n");
+    (recipients, subject, body)
+}
+
+/// Candidate editor commands to try in order: `$VISUAL`, then `$EDITOR`, then common fallbacks
+fn editor_candidates() -> Vec<String> {
+    let mut candidates = Vec::new();
+    for var in ["VISUAL", "EDITOR"] {
+        if let Ok(editor) = std::env::var(var) {
+            if !editor.trim().is_empty() {
+                candidates.push(editor);
+            }
+        }
+    }
+    candidates.push("vi".to_string());
+    candidates.push("nano".to_string());
+    candidates
+}
+
+/// Try each editor candidate in turn until one successfully spawns; returns `Ok(true)`/`Ok(false)`
+/// for whether the one that ran exited successfully, or `Err` if none of them could even start
+fn spawn_editor(path: &std::path::Path) -> io::Result<bool> {
+    let mut last_error = None;
+
+    for candidate in editor_candidates() {
+        let mut parts = candidate.split_whitespace();
+        let program = match parts.next() {
+            Some(p) => p,
+            None => continue,
+        };
+
+        match std::process::Command::new(program)
+            .args(parts)
+            .arg(path)
+            .status()
+        {
+            Ok(status) => return Ok(status.success()),
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No usable editor found")))
+}
@@ -0,0 +1,234 @@
+//! MIME decoding for fetched/cached RFC822 messages, built on [`mail_parser`]. Turns raw bytes
+//! into a structured [`ParsedMessage`]: the `text/plain` and `text/html` alternatives (charset
+//! decoding - via `mail_parser`'s internal `encoding_rs`-backed decoder - already applied) plus
+//! a flat list of [`ParsedAttachment`]s, instead of the flattened `body: String` that
+//! [`crate::email_sync::ImapClient::parse_email`] and [`crate::maildir::MaildirMirror`] used to
+//! produce on their own.
+//!
+//! `Content-Transfer-Encoding` (base64, quoted-printable) and the declared body/header charset
+//! (UTF-8, ISO-8859-1, Windows-1252, and everything else `encoding_rs` knows, falling back to
+//! lossy UTF-8) are decoded by `mail_parser` itself before any of this reaches [`parse_message`]
+//! or [`crate::email_sync::ImapClient::parse_email`]'s `parsed.subject()`/`parsed.from()` calls -
+//! including RFC 2047 encoded-words in `Subject`/`From`. So `RuleCondition::BodyRegex`/
+//! `SubjectContains`/etc always match against already-decoded text, not raw wire bytes.
+
+/// A single attachment pulled out of a multipart message: enough to list it in a manifest and,
+/// on request, write it to disk.
+#[derive(Debug, Clone)]
+pub struct ParsedAttachment {
+    pub filename: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+impl ParsedAttachment {
+    pub fn size(&self) -> i64 {
+        self.data.len() as i64
+    }
+}
+
+/// The decoded body and attachment manifest of one RFC822 message.
+#[derive(Debug, Clone, Default)]
+pub struct ParsedMessage {
+    pub text_plain: Option<String>,
+    pub text_html: Option<String>,
+    pub attachments: Vec<ParsedAttachment>,
+    /// Human-readable PGP/MIME sign/encrypt status, from [`crate::gpg::scan_incoming`] run over
+    /// `text_plain` (or `text_html` when there is no plain part); `None` when the message carried
+    /// no PGP framing. When the message was
+    /// encrypted, `text_plain` is already the decrypted plaintext by the time this is set.
+    pub pgp_status: Option<String>,
+    /// RFC 2369/2919 mailing-list headers (`List-Id`, `List-Post`, `List-Unsubscribe`,
+    /// `List-Archive`), encoded as `"Name: value"` lines joined with `\n` - only the headers
+    /// actually present are included. `None` for mail that carries none of them. See
+    /// [`crate::app::Email::list_header`] for how this is read back out.
+    pub list_headers: Option<String>,
+    /// Every header the message carried, encoded as `"Name: value"` lines joined with `\n`, for
+    /// `RuleCondition::HeaderContains`/`MatchField::Header` to match against an arbitrary header
+    /// without re-parsing the raw message. `None` if the message had no headers at all.
+    pub headers: Option<String>,
+    /// Whether `mail_parser` found at least one MIME attachment, for
+    /// `RuleCondition::HasAttachment`.
+    pub has_attachment: bool,
+}
+
+/// Parse raw RFC822 bytes into a [`ParsedMessage`]. Returns `None` if `mail_parser` can't make
+/// sense of `raw` at all (the same failure mode the old `body_text(0)`-only parsing had).
+pub fn parse_message(raw: &[u8]) -> Option<ParsedMessage> {
+    let parsed = mail_parser::MessageParser::default().parse(raw)?;
+
+    let mut text_plain = parsed.body_text(0).map(|s| s.to_string());
+    let text_html = parsed.body_html(0).map(|s| s.to_string());
+
+    // Sign/encrypt detection runs against whatever decoded text we have - an encrypted message's
+    // armor usually lands in `text_plain` (nothing else in the part is text `mail_parser` would
+    // surface), so check that first and fall back to the HTML alternative for inline-signed HTML
+    // mail.
+    let pgp_status = text_plain
+        .as_deref()
+        .or(text_html.as_deref())
+        .and_then(crate::gpg::scan_incoming)
+        .map(|outcome| {
+            if let crate::gpg::IncomingPgp::Decrypted { ref plaintext } = outcome {
+                text_plain = Some(plaintext.clone());
+            }
+            outcome.describe()
+        });
+
+    let attachments = parsed
+        .attachments()
+        .enumerate()
+        .map(|(i, attachment)| {
+            let filename = attachment
+                .attachment_name()
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| format!("attachment-{}", i + 1));
+            let content_type = attachment
+                .content_type()
+                .map(|ct| match ct.subtype() {
+                    Some(subtype) => format!("{}/{}", ct.ctype(), subtype),
+                    None => ct.ctype().to_string(),
+                })
+                .unwrap_or_else(|| "application/octet-stream".to_string());
+            ParsedAttachment {
+                filename,
+                content_type,
+                data: attachment.contents().to_vec(),
+            }
+        })
+        .collect();
+
+    let list_headers = encode_list_headers(&parsed);
+    let headers = encode_all_headers(&parsed);
+    let has_attachment = !attachments.is_empty();
+
+    Some(ParsedMessage {
+        text_plain,
+        text_html,
+        attachments,
+        pgp_status,
+        list_headers,
+        headers,
+        has_attachment,
+    })
+}
+
+/// Pull the `List-Id`/`List-Post`/`List-Unsubscribe`/`List-Archive` headers out of `parsed` and
+/// encode the ones that are present as `"Name: value"` lines joined with `\n`, or `None` if the
+/// message has none of them.
+fn encode_list_headers(parsed: &mail_parser::Message) -> Option<String> {
+    let header_text = |name: &str| {
+        parsed
+            .header(name)
+            .and_then(|header| header.as_text())
+            .map(|text| format!("{}: {}", name, text))
+    };
+
+    let lines: Vec<String> = ["List-Id", "List-Post", "List-Unsubscribe", "List-Archive"]
+        .into_iter()
+        .filter_map(header_text)
+        .collect();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+/// Encode every header `parsed` carried as `"Name: value"` lines joined with `\n`, for
+/// `RuleCondition::HeaderContains`/`MatchField::Header` to search later without re-parsing the
+/// raw message. `None` if the message had no headers at all.
+fn encode_all_headers(parsed: &mail_parser::Message) -> Option<String> {
+    let lines: Vec<String> = parsed
+        .headers()
+        .iter()
+        .filter_map(|header| {
+            let value = header.value().as_text()?;
+            Some(format!("{}: {}", header.name(), value))
+        })
+        .collect();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+/// Strip markup out of an HTML body for a plain-text fallback view, used by `EmailDetail`'s
+/// toggle when a message has no `text/plain` alternative to show directly.
+///
+/// This is a light touch, not a full HTML parser: it drops tags and collapses the entity
+/// references mail commonly carries, which is enough for a readable terminal view.
+pub fn html_to_text(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+
+    decode_html_entities(&out)
+        .lines()
+        .map(|line| line.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Decodes the handful of HTML entities mail commonly carries. Shared by [`html_to_text`] and
+/// `crate::ui`'s styled HTML rendering so both convert entities the same way.
+pub fn decode_html_entities(s: &str) -> String {
+    s.replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_message_decodes_quoted_printable_iso_8859_1_body() {
+        let raw = b"From: a@example.com\r\n\
+Subject: Receipt\r\n\
+Content-Type: text/plain; charset=iso-8859-1\r\n\
+Content-Transfer-Encoding: quoted-printable\r\n\
+\r\n\
+Caf=E9 money\r\n";
+
+        let parsed = parse_message(raw).unwrap();
+        assert_eq!(parsed.text_plain.as_deref(), Some("Café money"));
+    }
+
+    #[test]
+    fn test_parse_message_decodes_base64_utf8_body() {
+        let raw = b"From: a@example.com\r\n\
+Subject: Sakura\r\n\
+Content-Type: text/plain; charset=utf-8\r\n\
+Content-Transfer-Encoding: base64\r\n\
+\r\n\
+44GV44GP44KJ\r\n";
+
+        let parsed = parse_message(raw).unwrap();
+        assert_eq!(parsed.text_plain.as_deref(), Some("さくら"));
+    }
+
+    #[test]
+    fn test_subject_decodes_rfc2047_encoded_words() {
+        let raw = b"From: a@example.com\r\n\
+Subject: =?ISO-8859-1?Q?Caf=E9?=\r\n\
+\r\n\
+Body\r\n";
+
+        let parsed = mail_parser::MessageParser::default().parse(raw).unwrap();
+        assert_eq!(parsed.subject(), Some("Café"));
+    }
+}
@@ -0,0 +1,453 @@
+//! OAuth2 authorization-code-with-PKCE flow for provider presets that require `XOAUTH2`
+//! (see [`crate::providers::AuthType::OAuth2`]) instead of a plain password.
+//!
+//! [`run_authorization_flow`] drives the whole interactive exchange: it opens the provider's
+//! consent page in the user's browser, listens on a transient loopback port for the redirect,
+//! and trades the resulting code for an access/refresh token pair. [`refresh_access_token`]
+//! later renews an expired [`crate::credentials::OAuthToken`] the same way, via its stashed
+//! `refresh_token`.
+
+use crate::credentials::OAuthToken;
+use anyhow::{Context, Result, anyhow};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A PKCE verifier/challenge pair, generated fresh for each authorization attempt so the code
+/// exchange can't be hijacked by an app other than the one that started the flow.
+struct PkcePair {
+    verifier: String,
+    challenge: String,
+}
+
+impl PkcePair {
+    fn generate() -> Self {
+        let verifier = random_url_safe_token(64);
+        let digest = sha2::Sha256::digest(verifier.as_bytes());
+        let challenge = url_safe_base64(&digest);
+        Self { verifier, challenge }
+    }
+}
+
+fn random_url_safe_token(len: usize) -> String {
+    let mut bytes = vec![0u8; len];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    url_safe_base64(&bytes)
+}
+
+fn url_safe_base64(bytes: &[u8]) -> String {
+    base64::encode(bytes)
+        .replace('+', "-")
+        .replace('/', "_")
+        .trim_end_matches('=')
+        .to_string()
+}
+
+/// Run the full authorization-code-with-PKCE flow for one provider: open `auth_url` in the
+/// user's browser with a freshly generated PKCE challenge, block on a one-shot localhost
+/// listener for the redirect, then exchange the returned code for a token at `token_url`.
+///
+/// `login_hint` pre-fills the account picker with the email address the user already typed in
+/// the setup form.
+pub async fn run_authorization_flow(
+    auth_url: &str,
+    token_url: &str,
+    client_id: &str,
+    scopes: &[String],
+    login_hint: &str,
+) -> Result<OAuthToken> {
+    let pkce = PkcePair::generate();
+    let state = random_url_safe_token(16);
+
+    let listener =
+        TcpListener::bind("127.0.0.1:0").context("Failed to bind a local OAuth2 redirect listener")?;
+    let port = listener
+        .local_addr()
+        .context("Failed to read the redirect listener's port")?
+        .port();
+    let redirect_uri = format!("http://127.0.0.1:{}/callback", port);
+
+    let authorize_url = build_authorize_url(
+        auth_url,
+        client_id,
+        &redirect_uri,
+        scopes,
+        login_hint,
+        &pkce.challenge,
+        &state,
+    );
+
+    open::that(&authorize_url).context("Failed to open the authorization URL in a browser")?;
+
+    let expected_state = state.clone();
+    let (code, returned_state) = tokio::task::spawn_blocking(move || await_redirect(listener))
+        .await
+        .context("Redirect listener task panicked")??;
+
+    if returned_state != expected_state {
+        return Err(anyhow!("OAuth2 redirect state did not match; aborting authorization"));
+    }
+
+    exchange_code(token_url, client_id, &code, &redirect_uri, &pkce.verifier).await
+}
+
+/// The provider's response to starting a device-authorization-grant (RFC 8628) request: the
+/// code shown to the user plus the endpoint they type/confirm it at.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct DeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    #[serde(alias = "verification_uri_complete")]
+    pub verification_uri: String,
+    #[serde(default = "default_device_expires_in")]
+    pub expires_in: i64,
+    #[serde(default = "default_device_interval")]
+    pub interval: i64,
+}
+
+fn default_device_expires_in() -> i64 {
+    900
+}
+
+fn default_device_interval() -> i64 {
+    5
+}
+
+/// Start a device-authorization-grant flow (RFC 8628) - the alternative to
+/// [`run_authorization_flow`] for machines with no local browser to receive a redirect.
+/// `on_code` is called once the provider hands back the user code and verification URL, so the
+/// caller can surface it before this function goes on to block until the user finishes
+/// authorizing (or the code expires).
+pub async fn run_device_code_flow(
+    device_auth_url: &str,
+    token_url: &str,
+    client_id: &str,
+    scopes: &[String],
+    on_code: impl FnOnce(DeviceAuthorization),
+) -> Result<OAuthToken> {
+    let device = request_device_authorization(device_auth_url, client_id, scopes).await?;
+    on_code(device.clone());
+    poll_device_token(token_url, client_id, &device).await
+}
+
+async fn request_device_authorization(
+    device_auth_url: &str,
+    client_id: &str,
+    scopes: &[String],
+) -> Result<DeviceAuthorization> {
+    let client = reqwest::Client::new();
+    let scope = scopes.join(" ");
+    let params = [("client_id", client_id), ("scope", &scope)];
+
+    let response = client
+        .post(device_auth_url)
+        .form(&params)
+        .send()
+        .await
+        .context("Device authorization request failed")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Device authorization endpoint returned {}: {}", status, body));
+    }
+
+    response
+        .json()
+        .await
+        .context("Failed to parse the device authorization endpoint's response")
+}
+
+/// Poll the token endpoint at `device.interval`-second intervals per RFC 8628 section 3.5,
+/// backing off on `slow_down` and giving up once `device.expires_in` seconds have passed.
+async fn poll_device_token(token_url: &str, client_id: &str, device: &DeviceAuthorization) -> Result<OAuthToken> {
+    let params = [
+        ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+        ("device_code", device.device_code.as_str()),
+        ("client_id", client_id),
+    ];
+
+    let mut interval = device.interval.max(1) as u64;
+    let mut waited = 0i64;
+
+    loop {
+        tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+        waited += interval as i64;
+
+        match request_token(token_url, client_id, &params).await {
+            Ok(token) => return Ok(token),
+            Err(e) => {
+                let message = e.to_string();
+                if message.contains("slow_down") {
+                    interval += 5;
+                } else if !message.contains("authorization_pending") {
+                    return Err(e);
+                }
+            }
+        }
+
+        if waited >= device.expires_in {
+            return Err(anyhow!("Device code expired before authorization completed"));
+        }
+    }
+}
+
+/// Exchange a refresh token for a new access token, used by [`crate::email_sync`] whenever an
+/// [`OAuthToken`] has expired. Falls back to keeping the existing `refresh_token` if the
+/// provider didn't issue a new one (most don't, per RFC 6749 section 6).
+pub async fn refresh_access_token(
+    token_url: &str,
+    client_id: &str,
+    refresh_token: &str,
+) -> Result<OAuthToken> {
+    let params = [
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token),
+        ("client_id", client_id),
+    ];
+    let mut token = request_token(token_url, client_id, &params).await?;
+    if token.refresh_token.is_empty() {
+        token.refresh_token = refresh_token.to_string();
+    }
+    Ok(token)
+}
+
+fn build_authorize_url(
+    auth_url: &str,
+    client_id: &str,
+    redirect_uri: &str,
+    scopes: &[String],
+    login_hint: &str,
+    code_challenge: &str,
+    state: &str,
+) -> String {
+    let separator = if auth_url.contains('?') { "&" } else { "?" };
+    let mut url = format!(
+        "{}{}response_type=code&client_id={}&redirect_uri={}&code_challenge={}&code_challenge_method=S256&state={}",
+        auth_url,
+        separator,
+        url_encode(client_id),
+        url_encode(redirect_uri),
+        url_encode(code_challenge),
+        url_encode(state),
+    );
+    if !scopes.is_empty() {
+        url.push_str("&scope=");
+        url.push_str(&url_encode(&scopes.join(" ")));
+    }
+    if !login_hint.is_empty() {
+        url.push_str("&login_hint=");
+        url.push_str(&url_encode(login_hint));
+    }
+    url
+}
+
+/// Block waiting for exactly one redirect on `listener`, pull the authorization `code`/`state`
+/// out of its query string, and reply with a small page telling the user to return to the
+/// terminal. One connection is all this flow needs: only the first connection is accepted, and
+/// the listener is dropped when this function returns.
+fn await_redirect(listener: TcpListener) -> Result<(String, String)> {
+    let (mut stream, _) = listener
+        .accept()
+        .context("Failed to accept the OAuth2 redirect connection")?;
+
+    let mut buf = [0u8; 8192];
+    let n = stream
+        .read(&mut buf)
+        .context("Failed to read the OAuth2 redirect request")?;
+    let request = String::from_utf8_lossy(&buf[..n]);
+    let request_line = request.lines().next().unwrap_or_default();
+    let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+    let query = path.splitn(2, '?').nth(1).unwrap_or_default();
+    let params = parse_query(query);
+
+    let code = params
+        .get("code")
+        .cloned()
+        .ok_or_else(|| anyhow!("Redirect had no authorization code - {}", params.get("error").cloned().unwrap_or_default()))?;
+    let state = params.get("state").cloned().unwrap_or_default();
+
+    let body = "<html><body>Authorization complete. You can close this window and return to tume.</body></html>";
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).ok();
+
+    Ok((code, state))
+}
+
+async fn exchange_code(
+    token_url: &str,
+    client_id: &str,
+    code: &str,
+    redirect_uri: &str,
+    verifier: &str,
+) -> Result<OAuthToken> {
+    let params = [
+        ("grant_type", "authorization_code"),
+        ("code", code),
+        ("redirect_uri", redirect_uri),
+        ("client_id", client_id),
+        ("code_verifier", verifier),
+    ];
+    request_token(token_url, client_id, &params).await
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: String,
+    #[serde(default = "default_expires_in")]
+    expires_in: i64,
+}
+
+fn default_expires_in() -> i64 {
+    3600
+}
+
+async fn request_token(token_url: &str, client_id: &str, params: &[(&str, &str)]) -> Result<OAuthToken> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(token_url)
+        .form(params)
+        .send()
+        .await
+        .context("OAuth2 token request failed")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Token endpoint returned {}: {}", status, body));
+    }
+
+    let parsed: TokenResponse = response
+        .json()
+        .await
+        .context("Failed to parse the token endpoint's response")?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+
+    Ok(OAuthToken {
+        access_token: parsed.access_token,
+        refresh_token: parsed.refresh_token,
+        expires_at: now + parsed.expires_in,
+        token_url: token_url.to_string(),
+        client_id: client_id.to_string(),
+    })
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter_map(|pair| {
+            let mut parts = pair.splitn(2, '=');
+            let key = parts.next()?;
+            let value = parts.next().unwrap_or_default();
+            Some((url_decode(key), url_decode(value)))
+        })
+        .collect()
+}
+
+fn url_decode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '+' => out.push(' '),
+            '%' => {
+                let hex: String = chars.by_ref().take(2).collect();
+                match u8::from_str_radix(&hex, 16) {
+                    Ok(byte) => out.push(byte as char),
+                    Err(_) => out.push('%'),
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn url_encode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => out.push(b as char),
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pkce_challenge_is_not_the_verifier() {
+        let pair = PkcePair::generate();
+        assert_ne!(pair.verifier, pair.challenge);
+        assert!(!pair.verifier.is_empty());
+        assert!(!pair.challenge.contains('='));
+        assert!(!pair.challenge.contains('+'));
+        assert!(!pair.challenge.contains('/'));
+    }
+
+    #[test]
+    fn test_build_authorize_url_includes_pkce_and_hint() {
+        let url = build_authorize_url(
+            "https://example.com/authorize",
+            "client-123",
+            "http://127.0.0.1:9000/callback",
+            &["mail.read".to_string(), "mail.send".to_string()],
+            "user@example.com",
+            "challenge-value",
+            "state-value",
+        );
+        assert!(url.starts_with("https://example.com/authorize?"));
+        assert!(url.contains("code_challenge=challenge-value"));
+        assert!(url.contains("code_challenge_method=S256"));
+        assert!(url.contains("client_id=client-123"));
+        assert!(url.contains("login_hint=user%40example.com"));
+        assert!(url.contains("scope=mail.read%20mail.send"));
+    }
+
+    #[test]
+    fn test_parse_query_decodes_code_and_state() {
+        let params = parse_query("code=abc%2Fdef&state=xyz");
+        assert_eq!(params.get("code").unwrap(), "abc/def");
+        assert_eq!(params.get("state").unwrap(), "xyz");
+    }
+
+    #[test]
+    fn test_device_authorization_deserializes_minimal_response() {
+        let device: DeviceAuthorization = serde_json::from_str(
+            r#"{"device_code":"dc-1","user_code":"ABCD-1234","verification_uri":"https://example.com/device"}"#,
+        )
+        .unwrap();
+        assert_eq!(device.device_code, "dc-1");
+        assert_eq!(device.user_code, "ABCD-1234");
+        assert_eq!(device.verification_uri, "https://example.com/device");
+        assert_eq!(device.expires_in, 900);
+        assert_eq!(device.interval, 5);
+    }
+
+    #[test]
+    fn test_device_authorization_prefers_verification_uri_complete() {
+        let device: DeviceAuthorization = serde_json::from_str(
+            r#"{"device_code":"dc-1","user_code":"ABCD-1234","verification_uri_complete":"https://example.com/device?code=ABCD-1234","expires_in":1800,"interval":10}"#,
+        )
+        .unwrap();
+        assert_eq!(device.verification_uri, "https://example.com/device?code=ABCD-1234");
+        assert_eq!(device.expires_in, 1800);
+        assert_eq!(device.interval, 10);
+    }
+}
@@ -1,21 +1,186 @@
 /// Email provider presets for common services
-/// This module provides pre-configured IMAP and SMTP settings for popular email providers
+/// This module provides pre-configured IMAP and SMTP settings for popular email providers,
+/// loaded from an embedded data table modeled loosely on Delta Chat's provider database.
 
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 
+/// The embedded provider database, as TOML, baked into the binary at compile time
+const PROVIDERS_TOML: &str = include_str!("providers_db.toml");
+
+/// Parsed form of [`PROVIDERS_TOML`], matching the on-disk schema
+#[derive(Debug, Deserialize)]
+struct ProviderTable {
+    provider: Vec<ProviderRow>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProviderRow {
+    id: String,
+    name: String,
+    description: String,
+    #[serde(default)]
+    domains: Vec<String>,
+    imap_server: String,
+    imap_port: u16,
+    imap_security: String,
+    smtp_server: String,
+    smtp_port: u16,
+    smtp_security: String,
+    username_hint: String,
+    #[serde(default)]
+    oauth2: bool,
+    oauth2_auth_url: Option<String>,
+    oauth2_token_url: Option<String>,
+    #[serde(default)]
+    oauth2_scopes: Vec<String>,
+    #[serde(default)]
+    oauth2_client_id: String,
+    #[serde(default)]
+    oauth2_device_auth_url: Option<String>,
+    #[serde(default = "default_status")]
+    status: String,
+    #[serde(default)]
+    hints: Vec<String>,
+}
+
+fn default_status() -> String {
+    "working".to_string()
+}
+
+/// The parsed provider database, built once on first access
+static PROVIDER_TABLE: Lazy<Vec<EmailProvider>> = Lazy::new(|| {
+    let table: ProviderTable =
+        toml::from_str(PROVIDERS_TOML).expect("embedded providers_db.toml is malformed");
+
+    table.provider.into_iter().map(EmailProvider::from).collect()
+});
+
+impl From<ProviderRow> for EmailProvider {
+    fn from(row: ProviderRow) -> Self {
+        let auth = if row.oauth2 {
+            AuthType::OAuth2 {
+                auth_url: row.oauth2_auth_url.unwrap_or_default(),
+                token_url: row.oauth2_token_url.unwrap_or_default(),
+                scopes: row.oauth2_scopes,
+                client_id: row.oauth2_client_id,
+                device_auth_url: row.oauth2_device_auth_url,
+            }
+        } else {
+            AuthType::Password
+        };
+
+        EmailProvider {
+            id: row.id,
+            name: row.name,
+            description: row.description,
+            domains: row.domains,
+            imap_server: row.imap_server,
+            imap_port: row.imap_port,
+            imap_security: parse_security_type(&row.imap_security),
+            smtp_server: row.smtp_server,
+            smtp_port: row.smtp_port,
+            smtp_security: parse_security_type(&row.smtp_security),
+            username_hint: row.username_hint,
+            auth,
+            status: ProviderStatus::parse(&row.status),
+            hints: row.hints,
+        }
+    }
+}
+
+fn parse_security_type(value: &str) -> SecurityType {
+    match value {
+        "starttls" => SecurityType::StartTls,
+        _ => SecurityType::Tls,
+    }
+}
+
 /// Email provider preset with IMAP and SMTP configuration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct EmailProvider {
-    pub id: &'static str,
-    pub name: &'static str,
-    pub description: &'static str,
-    pub imap_server: &'static str,
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Domain glob patterns this preset matches (e.g. `*.onmicrosoft.com`), used by [`EmailProvider::by_email`]
+    pub domains: Vec<String>,
+    pub imap_server: String,
     pub imap_port: u16,
     pub imap_security: SecurityType,
-    pub smtp_server: &'static str,
+    pub smtp_server: String,
     pub smtp_port: u16,
     pub smtp_security: SecurityType,
-    pub username_hint: &'static str,
+    pub username_hint: String,
+    pub auth: AuthType,
+    /// Known-working status for this preset, surfaced to the user before they configure it
+    pub status: ProviderStatus,
+    /// Advisory hints shown in the UI (e.g. "enable IMAP in settings", "app-specific password required")
+    pub hints: Vec<String>,
+}
+
+/// How reliable a provider preset is known to be, mirroring Delta Chat's provider database
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ProviderStatus {
+    /// Verified to work as configured
+    Working,
+    /// Untested or known to need adjustments
+    Preparation,
+    /// Known not to work (e.g. the provider disabled IMAP/SMTP access entirely)
+    Broken,
+}
+
+impl ProviderStatus {
+    fn parse(value: &str) -> Self {
+        match value {
+            "preparation" => ProviderStatus::Preparation,
+            "broken" => ProviderStatus::Broken,
+            _ => ProviderStatus::Working,
+        }
+    }
+
+    /// Advisory message to show the user for this status, or `None` when nothing needs saying
+    pub fn advisory(&self) -> Option<&'static str> {
+        match self {
+            ProviderStatus::Working => None,
+            ProviderStatus::Preparation => {
+                Some("This provider's settings are unverified and may need adjustment")
+            }
+            ProviderStatus::Broken => {
+                Some("This provider is known not to work with standard IMAP/SMTP access")
+            }
+        }
+    }
+}
+
+/// Authentication mechanism a provider preset expects
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum AuthType {
+    /// Plain password or app-specific password, sent via IMAP LOGIN / SMTP AUTH PLAIN
+    Password,
+    /// OAuth2 authorization-code-with-PKCE flow, authenticated via XOAUTH2
+    OAuth2 {
+        auth_url: String,
+        token_url: String,
+        scopes: Vec<String>,
+        client_id: String,
+        /// Device-authorization-grant (RFC 8628) endpoint, when the provider offers one - the
+        /// alternative to `auth_url`'s browser/loopback-redirect flow for a machine with no
+        /// local browser (see [`crate::oauth::run_device_code_flow`]).
+        device_auth_url: Option<String>,
+    },
+}
+
+impl AuthType {
+    /// Whether this auth type requires the OAuth2 browser/loopback flow
+    pub fn is_oauth2(&self) -> bool {
+        matches!(self, AuthType::OAuth2 { .. })
+    }
+
+    /// Whether this provider also offers the device-authorization-grant flow (see
+    /// [`crate::oauth::run_device_code_flow`]), for a machine with no local browser.
+    pub fn supports_device_code(&self) -> bool {
+        matches!(self, AuthType::OAuth2 { device_auth_url: Some(_), .. })
+    }
 }
 
 /// Security/encryption type for connections
@@ -27,23 +192,17 @@ pub enum SecurityType {
     StartTls,
 }
 
+impl Default for SecurityType {
+    /// Implicit TLS is the safer default when a caller hasn't specified a security type
+    fn default() -> Self {
+        SecurityType::Tls
+    }
+}
+
 impl EmailProvider {
     /// Get all available provider presets
     pub fn all() -> Vec<EmailProvider> {
-        vec![
-            Self::gmail(),
-            Self::outlook(),
-            Self::yahoo(),
-            Self::protonmail(),
-            Self::icloud(),
-            Self::fastmail(),
-            Self::aol(),
-            Self::zoho(),
-            Self::gmx(),
-            Self::mailcom(),
-            Self::yandex(),
-            Self::custom(),
-        ]
+        PROVIDER_TABLE.clone()
     }
 
     /// Get a provider by its ID
@@ -51,196 +210,222 @@ impl EmailProvider {
         Self::all().into_iter().find(|p| p.id == id)
     }
 
-    /// Gmail configuration
-    pub fn gmail() -> Self {
-        EmailProvider {
-            id: "gmail",
-            name: "Gmail",
-            description: "Google Gmail - Requires app-specific password if 2FA is enabled",
-            imap_server: "imap.gmail.com",
-            imap_port: 993,
-            imap_security: SecurityType::Tls,
-            smtp_server: "smtp.gmail.com",
-            smtp_port: 587,
-            smtp_security: SecurityType::StartTls,
-            username_hint: "your.email@gmail.com",
-        }
+    /// Find the best-matching provider preset for an email address by domain glob pattern
+    pub fn by_email(email: &str) -> Option<EmailProvider> {
+        let domain = email.split('@').nth(1)?.to_lowercase();
+        Self::all()
+            .into_iter()
+            .find(|p| p.domains.iter().any(|pattern| domain_matches(pattern, &domain)))
     }
 
-    /// Microsoft Outlook/Office 365 configuration
-    pub fn outlook() -> Self {
-        EmailProvider {
-            id: "outlook",
-            name: "Outlook / Office 365",
-            description: "Microsoft Outlook.com, Hotmail, Live, and Office 365 accounts",
-            imap_server: "outlook.office365.com",
-            imap_port: 993,
-            imap_security: SecurityType::Tls,
-            smtp_server: "smtp.office365.com",
-            smtp_port: 587,
-            smtp_security: SecurityType::StartTls,
-            username_hint: "your.email@outlook.com",
-        }
+    /// Whether this preset authenticates via the OAuth2/XOAUTH2 flow rather than a password
+    pub fn supports_oauth2(&self) -> bool {
+        self.auth.is_oauth2()
     }
 
-    /// Yahoo Mail configuration
-    pub fn yahoo() -> Self {
-        EmailProvider {
-            id: "yahoo",
-            name: "Yahoo Mail",
-            description: "Yahoo Mail - Requires app-specific password",
-            imap_server: "imap.mail.yahoo.com",
-            imap_port: 993,
-            imap_security: SecurityType::Tls,
-            smtp_server: "smtp.mail.yahoo.com",
-            smtp_port: 587,
-            smtp_security: SecurityType::StartTls,
-            username_hint: "your.email@yahoo.com",
-        }
-    }
+    /// Validate that `email`/`password` actually work against this preset before saving
+    /// them: opens and authenticates both the IMAP and SMTP connections without sending
+    /// any mail, returning a structured result that distinguishes DNS failure, TCP
+    /// timeout, TLS handshake error, and auth rejection.
+    pub async fn validate(
+        &self,
+        email: &str,
+        password: &str,
+    ) -> crate::email_sync::ValidationResult {
+        let credentials = crate::credentials::Credentials {
+            imap_server: self.imap_server.clone(),
+            imap_port: self.imap_port,
+            imap_security: self.imap_security,
+            imap_username: email.to_string(),
+            imap_password: password.to_string(),
+            smtp_server: self.smtp_server.clone(),
+            smtp_port: self.smtp_port,
+            smtp_security: self.smtp_security,
+            smtp_username: email.to_string(),
+            smtp_password: password.to_string(),
+            oauth_token: None,
+        };
 
-    /// ProtonMail Bridge configuration
-    pub fn protonmail() -> Self {
-        EmailProvider {
-            id: "protonmail",
-            name: "ProtonMail Bridge",
-            description: "ProtonMail - Requires ProtonMail Bridge running locally",
-            imap_server: "127.0.0.1",
-            imap_port: 1143,
-            imap_security: SecurityType::StartTls,
-            smtp_server: "127.0.0.1",
-            smtp_port: 1025,
-            smtp_security: SecurityType::StartTls,
-            username_hint: "your.email@proton.me",
-        }
+        crate::email_sync::validate_credentials(&credentials).await
     }
 
-    /// iCloud Mail configuration
-    pub fn icloud() -> Self {
-        EmailProvider {
-            id: "icloud",
-            name: "iCloud Mail",
-            description: "Apple iCloud Mail - Requires app-specific password",
-            imap_server: "imap.mail.me.com",
-            imap_port: 993,
-            imap_security: SecurityType::Tls,
-            smtp_server: "smtp.mail.me.com",
-            smtp_port: 587,
-            smtp_security: SecurityType::StartTls,
-            username_hint: "your.email@icloud.com",
+    /// Auto-detect IMAP/SMTP settings for an email address, so a "custom" provider
+    /// can configure itself instead of making the user hunt down server settings.
+    ///
+    /// Tries, in order:
+    /// 1. `https://autoconfig.<domain>/mail/config-v1.1.xml`
+    /// 2. `https://<domain>/.well-known/autoconfig/mail/config-v1.1.xml`
+    /// 3. The central Mozilla ISPDB at `https://autoconfig.thunderbird.net/v1.1/<domain>`
+    /// 4. A DNS MX lookup, matching the MX hostname's parent domain against known presets
+    ///
+    /// Returns `None` if nothing resolves, so the caller can fall through to manual entry.
+    pub async fn discover(email: &str) -> Option<DiscoveredProvider> {
+        let domain = email.split('@').nth(1)?.to_lowercase();
+        if domain.is_empty() {
+            return None;
         }
-    }
 
-    /// Fastmail configuration
-    pub fn fastmail() -> Self {
-        EmailProvider {
-            id: "fastmail",
-            name: "Fastmail",
-            description: "Fastmail - Privacy-focused email service",
-            imap_server: "imap.fastmail.com",
-            imap_port: 993,
-            imap_security: SecurityType::Tls,
-            smtp_server: "smtp.fastmail.com",
-            smtp_port: 587,
-            smtp_security: SecurityType::StartTls,
-            username_hint: "your.email@fastmail.com",
+        let candidate_urls = [
+            format!("https://autoconfig.{domain}/mail/config-v1.1.xml"),
+            format!("https://{domain}/.well-known/autoconfig/mail/config-v1.1.xml"),
+            format!("https://autoconfig.thunderbird.net/v1.1/{domain}"),
+        ];
+
+        for url in candidate_urls {
+            if let Some(provider) = Self::fetch_autoconfig(&url, email).await {
+                return Some(provider);
+            }
         }
+
+        Self::discover_via_mx(&domain).await
     }
 
-    /// AOL Mail configuration
-    pub fn aol() -> Self {
-        EmailProvider {
-            id: "aol",
-            name: "AOL Mail",
-            description: "AOL Mail - Requires app-specific password",
-            imap_server: "imap.aol.com",
-            imap_port: 993,
-            imap_security: SecurityType::Tls,
-            smtp_server: "smtp.aol.com",
-            smtp_port: 587,
-            smtp_security: SecurityType::StartTls,
-            username_hint: "your.email@aol.com",
+    /// Fetch and parse a single Thunderbird-style autoconfig XML document
+    async fn fetch_autoconfig(url: &str, email: &str) -> Option<DiscoveredProvider> {
+        let response = reqwest::get(url).await.ok()?;
+        if !response.status().is_success() {
+            return None;
         }
+        let body = response.text().await.ok()?;
+        parse_autoconfig_xml(&body, email)
     }
 
-    /// Zoho Mail configuration
-    pub fn zoho() -> Self {
-        EmailProvider {
-            id: "zoho",
-            name: "Zoho Mail",
-            description: "Zoho Mail - Business and personal email",
-            imap_server: "imap.zoho.com",
-            imap_port: 993,
-            imap_security: SecurityType::Tls,
-            smtp_server: "smtp.zoho.com",
-            smtp_port: 587,
-            smtp_security: SecurityType::StartTls,
-            username_hint: "your.email@zoho.com",
-        }
+    /// Last-resort discovery: look up the domain's MX record and match its parent
+    /// domain against the known hardcoded presets (e.g. `mx.zoho.com` contains `imap.zoho.com`'s
+    /// `zoho.com` -> zoho). An MX host on a different domain from the preset's IMAP server, such
+    /// as `aspmx.l.google.com` for Gmail, matches no preset.
+    async fn discover_via_mx(domain: &str) -> Option<DiscoveredProvider> {
+        let resolver = trust_dns_resolver::TokioAsyncResolver::tokio_from_system_conf().ok()?;
+        let mx_lookup = resolver.mx_lookup(format!("{domain}.")).await.ok()?;
+        let mx_host = mx_lookup.iter().next()?.exchange().to_utf8().to_lowercase();
+
+        Self::all().into_iter().find_map(|preset| {
+            if !preset.imap_server.is_empty() && mx_host.contains(parent_domain(&preset.imap_server)) {
+                Some(DiscoveredProvider {
+                    imap_server: preset.imap_server,
+                    imap_port: preset.imap_port,
+                    imap_security: preset.imap_security,
+                    smtp_server: preset.smtp_server,
+                    smtp_port: preset.smtp_port,
+                    smtp_security: preset.smtp_security,
+                    username: None,
+                })
+            } else {
+                None
+            }
+        })
     }
+}
 
-    /// GMX Mail configuration
-    pub fn gmx() -> Self {
-        EmailProvider {
-            id: "gmx",
-            name: "GMX Mail",
-            description: "GMX Mail - Free email service",
-            imap_server: "imap.gmx.com",
-            imap_port: 993,
-            imap_security: SecurityType::Tls,
-            smtp_server: "smtp.gmx.com",
-            smtp_port: 587,
-            smtp_security: SecurityType::StartTls,
-            username_hint: "your.email@gmx.com",
-        }
+/// Match a domain glob pattern (`*.gmail.com`, `googlemail.com`) against a lowercased domain
+fn domain_matches(pattern: &str, domain: &str) -> bool {
+    match pattern.strip_prefix("*.") {
+        Some(suffix) => domain == suffix || domain.ends_with(&format!(".{suffix}")),
+        None => domain == pattern,
     }
+}
 
-    /// Mail.com configuration
-    pub fn mailcom() -> Self {
-        EmailProvider {
-            id: "mailcom",
-            name: "Mail.com",
-            description: "Mail.com - Free email with many domain options",
-            imap_server: "imap.mail.com",
-            imap_port: 993,
-            imap_security: SecurityType::Tls,
-            smtp_server: "smtp.mail.com",
-            smtp_port: 587,
-            smtp_security: SecurityType::StartTls,
-            username_hint: "your.email@mail.com",
-        }
+/// Return the registrable parent domain (e.g. `imap.gmail.com` -> `gmail.com`) used to
+/// match an MX hostname against a preset's IMAP server.
+fn parent_domain(host: &str) -> &str {
+    let parts: Vec<&str> = host.rsplitn(3, '.').collect();
+    if parts.len() >= 3 {
+        &host[host.len() - parts[1].len() - parts[0].len() - 1..]
+    } else {
+        host
     }
+}
 
-    /// Yandex Mail configuration
-    pub fn yandex() -> Self {
-        EmailProvider {
-            id: "yandex",
-            name: "Yandex Mail",
-            description: "Yandex Mail - Russian email service",
-            imap_server: "imap.yandex.com",
-            imap_port: 993,
-            imap_security: SecurityType::Tls,
-            smtp_server: "smtp.yandex.com",
-            smtp_port: 587,
-            smtp_security: SecurityType::StartTls,
-            username_hint: "your.email@yandex.com",
+/// A provider configuration discovered at runtime via autoconfig/MX lookup, as opposed
+/// to one of the hardcoded [`EmailProvider`] presets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoveredProvider {
+    pub imap_server: String,
+    pub imap_port: u16,
+    pub imap_security: SecurityType,
+    pub smtp_server: String,
+    pub smtp_port: u16,
+    pub smtp_security: SecurityType,
+    /// Username to log in with, after `%EMAILADDRESS%`/`%EMAILLOCALPART%` substitution,
+    /// if the autoconfig document specified one
+    pub username: Option<String>,
+}
+
+/// Parse a Thunderbird autoconfig `config-v1.1.xml` document into a [`DiscoveredProvider`]
+fn parse_autoconfig_xml(xml: &str, email: &str) -> Option<DiscoveredProvider> {
+    use quick_xml::events::Event;
+    use quick_xml::reader::Reader;
+
+    let local_part = email.split('@').next().unwrap_or_default();
+
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let (mut imap_server, mut imap_port, mut imap_security) = (None, None, None);
+    let mut imap_username_template = None;
+    let (mut smtp_server, mut smtp_port, mut smtp_security) = (None, None, None);
+
+    let mut current_server: Option<&str> = None;
+    let mut current_tag = String::new();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                if name == "incomingServer" {
+                    current_server = Some("imap");
+                } else if name == "outgoingServer" {
+                    current_server = Some("smtp");
+                }
+                current_tag = name;
+            }
+            Ok(Event::End(_)) => {
+                current_tag.clear();
+            }
+            Ok(Event::Text(e)) => {
+                let text = e.unescape().ok()?.into_owned();
+                match (current_server, current_tag.as_str()) {
+                    (Some("imap"), "hostname") => imap_server = Some(text),
+                    (Some("imap"), "port") => imap_port = text.parse().ok(),
+                    (Some("imap"), "socketType") => imap_security = parse_socket_type(&text),
+                    (Some("imap"), "username") => imap_username_template = Some(text),
+                    (Some("smtp"), "hostname") => smtp_server = Some(text),
+                    (Some("smtp"), "port") => smtp_port = text.parse().ok(),
+                    (Some("smtp"), "socketType") => smtp_security = parse_socket_type(&text),
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(_) => return None,
+            _ => {}
         }
+        buf.clear();
     }
 
-    /// Custom/Manual configuration
-    pub fn custom() -> Self {
-        EmailProvider {
-            id: "custom",
-            name: "Custom (Other Provider)",
-            description: "Manually configure IMAP and SMTP settings",
-            imap_server: "",
-            imap_port: 993,
-            imap_security: SecurityType::Tls,
-            smtp_server: "",
-            smtp_port: 587,
-            smtp_security: SecurityType::StartTls,
-            username_hint: "your.email@domain.com",
-        }
+    let username = imap_username_template.map(|template| {
+        template
+            .replace("%EMAILADDRESS%", email)
+            .replace("%EMAILLOCALPART%", local_part)
+    });
+
+    Some(DiscoveredProvider {
+        imap_server: imap_server?,
+        imap_port: imap_port.unwrap_or(993),
+        imap_security: imap_security.unwrap_or(SecurityType::Tls),
+        smtp_server: smtp_server?,
+        smtp_port: smtp_port.unwrap_or(587),
+        smtp_security: smtp_security.unwrap_or(SecurityType::StartTls),
+        username,
+    })
+}
+
+/// Map an autoconfig `<socketType>` value to our [`SecurityType`]
+fn parse_socket_type(value: &str) -> Option<SecurityType> {
+    match value {
+        "SSL" => Some(SecurityType::Tls),
+        "STARTTLS" => Some(SecurityType::StartTls),
+        _ => None,
     }
 }
 
@@ -251,10 +436,10 @@ mod tests {
     #[test]
     fn test_all_providers_are_unique() {
         let providers = EmailProvider::all();
-        let mut ids: Vec<&str> = providers.iter().map(|p| p.id).collect();
+        let mut ids: Vec<&str> = providers.iter().map(|p| p.id.as_str()).collect();
         ids.sort();
         ids.dedup();
-        
+
         // Should have same length after dedup (no duplicates)
         assert_eq!(ids.len(), providers.len());
     }
@@ -269,7 +454,7 @@ mod tests {
 
     #[test]
     fn test_gmail_config() {
-        let gmail = EmailProvider::gmail();
+        let gmail = EmailProvider::by_id("gmail").unwrap();
         assert_eq!(gmail.id, "gmail");
         assert_eq!(gmail.imap_server, "imap.gmail.com");
         assert_eq!(gmail.imap_port, 993);
@@ -279,15 +464,125 @@ mod tests {
 
     #[test]
     fn test_protonmail_uses_local_bridge() {
-        let proton = EmailProvider::protonmail();
+        let proton = EmailProvider::by_id("protonmail").unwrap();
         assert_eq!(proton.imap_server, "127.0.0.1");
         assert_eq!(proton.smtp_server, "127.0.0.1");
     }
 
     #[test]
     fn test_custom_provider_empty_servers() {
-        let custom = EmailProvider::custom();
+        let custom = EmailProvider::by_id("custom").unwrap();
         assert_eq!(custom.imap_server, "");
         assert_eq!(custom.smtp_server, "");
     }
+
+    #[test]
+    fn test_gmail_uses_oauth2() {
+        let gmail = EmailProvider::by_id("gmail").unwrap();
+        assert!(gmail.supports_oauth2());
+        assert!(gmail.auth.is_oauth2());
+    }
+
+    #[test]
+    fn test_outlook_and_yandex_use_oauth2() {
+        assert!(EmailProvider::by_id("outlook").unwrap().supports_oauth2());
+        assert!(EmailProvider::by_id("yandex").unwrap().supports_oauth2());
+    }
+
+    #[test]
+    fn test_password_providers_do_not_use_oauth2() {
+        for id in [
+            "yahoo", "protonmail", "icloud", "fastmail", "aol", "zoho", "gmx", "mailcom", "custom",
+        ] {
+            let provider = EmailProvider::by_id(id).unwrap();
+            assert!(!provider.supports_oauth2(), "{} should not use oauth2", provider.id);
+            assert_eq!(provider.auth, AuthType::Password);
+        }
+    }
+
+    #[test]
+    fn test_oauth2_scopes_present_for_gmail() {
+        match EmailProvider::by_id("gmail").unwrap().auth {
+            AuthType::OAuth2 { scopes, .. } => assert!(!scopes.is_empty()),
+            AuthType::Password => panic!("gmail should be oauth2"),
+        }
+    }
+
+    #[test]
+    fn test_by_email_matches_domain() {
+        let gmail = EmailProvider::by_email("jane@gmail.com").unwrap();
+        assert_eq!(gmail.id, "gmail");
+
+        let also_gmail = EmailProvider::by_email("jane@googlemail.com").unwrap();
+        assert_eq!(also_gmail.id, "gmail");
+    }
+
+    #[test]
+    fn test_by_email_glob_pattern() {
+        let outlook = EmailProvider::by_email("jane@contoso.onmicrosoft.com").unwrap();
+        assert_eq!(outlook.id, "outlook");
+    }
+
+    #[test]
+    fn test_by_email_unknown_domain_returns_none() {
+        assert!(EmailProvider::by_email("jane@totally-unknown-domain.example").is_none());
+    }
+
+    #[test]
+    fn test_provider_status_advisory() {
+        assert!(ProviderStatus::Working.advisory().is_none());
+        assert!(ProviderStatus::Broken.advisory().is_some());
+        assert!(ProviderStatus::Preparation.advisory().is_some());
+    }
+
+    #[test]
+    fn test_parse_autoconfig_xml() {
+        let xml = r#"
+            <clientConfig version="1.1">
+              <emailProvider id="example.com">
+                <incomingServer type="imap">
+                  <hostname>imap.example.com</hostname>
+                  <port>993</port>
+                  <socketType>SSL</socketType>
+                  <username>%EMAILADDRESS%</username>
+                </incomingServer>
+                <outgoingServer type="smtp">
+                  <hostname>smtp.example.com</hostname>
+                  <port>587</port>
+                  <socketType>STARTTLS</socketType>
+                  <username>%EMAILLOCALPART%</username>
+                </outgoingServer>
+              </emailProvider>
+            </clientConfig>
+        "#;
+
+        let discovered = parse_autoconfig_xml(xml, "jane@example.com").expect("should parse");
+        assert_eq!(discovered.imap_server, "imap.example.com");
+        assert_eq!(discovered.imap_port, 993);
+        assert_eq!(discovered.imap_security, SecurityType::Tls);
+        assert_eq!(discovered.smtp_server, "smtp.example.com");
+        assert_eq!(discovered.smtp_port, 587);
+        assert_eq!(discovered.smtp_security, SecurityType::StartTls);
+        assert_eq!(discovered.username, Some("jane@example.com".to_string()));
+    }
+
+    #[test]
+    fn test_parse_autoconfig_xml_missing_server_returns_none() {
+        let xml = r#"<clientConfig version="1.1"><emailProvider id="x"></emailProvider></clientConfig>"#;
+        assert!(parse_autoconfig_xml(xml, "jane@example.com").is_none());
+    }
+
+    #[test]
+    fn test_parent_domain() {
+        assert_eq!(parent_domain("imap.gmail.com"), "gmail.com");
+        assert_eq!(parent_domain("aspmx.l.google.com"), "google.com");
+        assert_eq!(parent_domain("example.com"), "example.com");
+    }
+
+    #[test]
+    fn test_domain_matches_glob_and_exact() {
+        assert!(domain_matches("*.onmicrosoft.com", "contoso.onmicrosoft.com"));
+        assert!(domain_matches("gmail.com", "gmail.com"));
+        assert!(!domain_matches("gmail.com", "notgmail.com"));
+    }
 }
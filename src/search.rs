@@ -0,0 +1,260 @@
+/// Query compiler for the FTS5-backed `emails_fts` virtual table.
+///
+/// Turns a user-facing search string into a SQLite FTS5 `MATCH` expression:
+/// bare words become prefix matches, `field:value` / `field:"quoted value"` scope a term to
+/// one column, quoted phrases are matched verbatim, and `AND` / `OR` / `NOT` (case-insensitive)
+/// pass straight through as FTS5's boolean operators. Every double quote found in user text is
+/// escaped by doubling it (the FTS5 in-string escape), so user input can never break out of a
+/// quoted term and inject arbitrary MATCH syntax.
+///
+/// [`parse_query`] extends the same grammar with structured predicates (`is:flagged`,
+/// `folder:inbox`, `status:unread`, `before:`/`after:` date bounds) that don't belong in the
+/// FTS5 index and are applied as plain SQL `WHERE` clauses instead; see
+/// [`EmailDatabase::search_emails_query`](crate::db::EmailDatabase::search_emails_query).
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Maps a `field:` prefix in the query DSL to its column in `emails_fts`.
+fn fts_column(field: &str) -> Option<&'static str> {
+    match field.to_ascii_lowercase().as_str() {
+        "from" => Some("from_address"),
+        "to" => Some("to_addresses"),
+        "subject" => Some("subject"),
+        "body" => Some("body"),
+        _ => None,
+    }
+}
+
+/// Double every `"` in `value` so it's safe to splice into an FTS5 quoted string.
+fn escape_fts_string(value: &str) -> String {
+    value.replace('"', "\"\"")
+}
+
+/// Read the next whitespace-delimited token, treating a `"..."` run as a single token even
+/// though it may contain spaces.
+fn next_raw_token(chars: &mut Peekable<Chars>) -> Option<String> {
+    while matches!(chars.peek(), Some(c) if c.is_whitespace()) {
+        chars.next();
+    }
+
+    let mut token = String::new();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            break;
+        }
+        token.push(c);
+        chars.next();
+        if c == '"' {
+            for inner in chars.by_ref() {
+                token.push(inner);
+                if inner == '"' {
+                    break;
+                }
+            }
+        }
+    }
+
+    if token.is_empty() { None } else { Some(token) }
+}
+
+/// Strip a single pair of enclosing double quotes, if present.
+fn unquote(value: &str) -> (&str, bool) {
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        (&value[1..value.len() - 1], true)
+    } else {
+        (value, false)
+    }
+}
+
+/// Compile one token (already split on whitespace) into its FTS5 fragment.
+fn compile_token(token: &str) -> String {
+    let upper = token.to_ascii_uppercase();
+    if upper == "AND" || upper == "OR" || upper == "NOT" {
+        return upper;
+    }
+
+    if let Some((field, value)) = token.split_once(':') {
+        if !value.is_empty() {
+            if let Some(column) = fts_column(field) {
+                let (inner, _quoted) = unquote(value);
+                return format!("{}:\"{}\"", column, escape_fts_string(inner));
+            }
+        }
+    }
+
+    let (inner, quoted) = unquote(token);
+    let escaped = escape_fts_string(inner);
+    if quoted {
+        format!("\"{}\"", escaped)
+    } else {
+        format!("\"{}\"*", escaped)
+    }
+}
+
+/// Compile a user search string into an FTS5 `MATCH` expression for `emails_fts`.
+///
+/// Returns an empty string for empty/whitespace-only input; callers should treat that as
+/// "no search filter" rather than issuing a `MATCH ''` query.
+pub fn compile_query(input: &str) -> String {
+    let mut chars = input.chars().peekable();
+    let mut parts = Vec::new();
+    while let Some(token) = next_raw_token(&mut chars) {
+        parts.push(compile_token(&token));
+    }
+    parts.join(" ")
+}
+
+/// Whether `field:` names one of the structured predicates handled outside FTS5 (as a plain SQL
+/// `WHERE` clause) rather than a text column to match against.
+fn is_structured_field(field: &str) -> bool {
+    matches!(
+        field.to_ascii_lowercase().as_str(),
+        "is" | "folder" | "status" | "before" | "after"
+    )
+}
+
+/// The structured predicates a query can carry alongside its free-text search, e.g.
+/// `is:flagged folder:inbox before:2026-01-01`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedQuery {
+    /// The FTS5 `MATCH` expression for whatever's left after structured fields are pulled out.
+    /// Empty when the query has no free-text terms.
+    pub fts_match: String,
+    pub is_flagged: Option<bool>,
+    pub folder: Option<String>,
+    pub status: Option<String>,
+    /// `date < this` (exclusive), as a raw string compared against the `emails.date` column.
+    pub before: Option<String>,
+    /// `date > this` (exclusive), as a raw string compared against the `emails.date` column.
+    pub after: Option<String>,
+}
+
+/// Parse a query DSL string into its structured predicates plus whatever free-text remains.
+///
+/// `is:flagged`, `folder:`, `status:`, `before:` and `after:` are pulled out as `WHERE`
+/// predicates; everything else (bare words, quoted phrases, `from:`/`to:`/`subject:`/`body:`,
+/// boolean operators) is compiled into `fts_match` exactly as [`compile_query`] would.
+pub fn parse_query(input: &str) -> ParsedQuery {
+    let mut chars = input.chars().peekable();
+    let mut fts_parts = Vec::new();
+    let mut parsed = ParsedQuery::default();
+
+    while let Some(token) = next_raw_token(&mut chars) {
+        if let Some((field, value)) = token.split_once(':') {
+            if !value.is_empty() && is_structured_field(field) {
+                let (inner, _quoted) = unquote(value);
+                let inner = inner.to_string();
+                match field.to_ascii_lowercase().as_str() {
+                    "is" => parsed.is_flagged = Some(inner.eq_ignore_ascii_case("flagged")),
+                    "folder" => parsed.folder = Some(inner),
+                    "status" => parsed.status = Some(inner),
+                    "before" => parsed.before = Some(inner),
+                    "after" => parsed.after = Some(inner),
+                    _ => unreachable!("is_structured_field only admits the arms above"),
+                }
+                continue;
+            }
+        }
+        fts_parts.push(compile_token(&token));
+    }
+
+    parsed.fts_match = fts_parts.join(" ");
+    parsed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bare_word_becomes_prefix_match() {
+        assert_eq!(compile_query("budget"), "\"budget\"*");
+    }
+
+    #[test]
+    fn test_quoted_phrase_is_kept_verbatim() {
+        assert_eq!(compile_query("\"quarterly report\""), "\"quarterly report\"");
+    }
+
+    #[test]
+    fn test_field_filter_maps_to_column() {
+        assert_eq!(compile_query("from:alice"), "from_address:\"alice\"");
+    }
+
+    #[test]
+    fn test_field_filter_with_quoted_value() {
+        assert_eq!(
+            compile_query("subject:\"quarterly report\""),
+            "subject:\"quarterly report\""
+        );
+    }
+
+    #[test]
+    fn test_unknown_field_falls_back_to_bare_term() {
+        assert_eq!(compile_query("unknown:alice"), "\"unknown:alice\"*");
+    }
+
+    #[test]
+    fn test_boolean_operators_pass_through_uppercased() {
+        assert_eq!(
+            compile_query("from:alice and subject:report"),
+            "from_address:\"alice\" AND subject:\"report\""
+        );
+    }
+
+    #[test]
+    fn test_not_operator() {
+        assert_eq!(compile_query("budget NOT spam"), "\"budget\"* NOT \"spam\"*");
+    }
+
+    #[test]
+    fn test_double_quotes_in_value_are_escaped() {
+        assert_eq!(compile_query("from:a\"b"), "from_address:\"a\"\"b\"");
+    }
+
+    #[test]
+    fn test_empty_query_compiles_to_empty_string() {
+        assert_eq!(compile_query(""), "");
+        assert_eq!(compile_query("   "), "");
+    }
+
+    #[test]
+    fn test_multiple_bare_terms_are_implicitly_anded_by_fts5() {
+        assert_eq!(compile_query("foo bar"), "\"foo\"* \"bar\"*");
+    }
+
+    #[test]
+    fn test_parse_query_extracts_structured_predicates() {
+        let parsed = parse_query("is:flagged folder:inbox before:2026-01-01 after:2025-01-01 status:unread");
+        assert_eq!(parsed.is_flagged, Some(true));
+        assert_eq!(parsed.folder.as_deref(), Some("inbox"));
+        assert_eq!(parsed.before.as_deref(), Some("2026-01-01"));
+        assert_eq!(parsed.after.as_deref(), Some("2025-01-01"));
+        assert_eq!(parsed.status.as_deref(), Some("unread"));
+        assert_eq!(parsed.fts_match, "");
+    }
+
+    #[test]
+    fn test_parse_query_mixes_structured_and_free_text() {
+        let parsed = parse_query("from:alice subject:\"meeting notes\" is:flagged project");
+        assert_eq!(parsed.is_flagged, Some(true));
+        assert_eq!(
+            parsed.fts_match,
+            "from_address:\"alice\" subject:\"meeting notes\" \"project\"*"
+        );
+    }
+
+    #[test]
+    fn test_parse_query_with_no_structured_fields() {
+        let parsed = parse_query("budget report");
+        assert_eq!(parsed.is_flagged, None);
+        assert_eq!(parsed.folder, None);
+        assert_eq!(parsed.fts_match, "\"budget\"* \"report\"*");
+    }
+
+    #[test]
+    fn test_parse_query_empty_input() {
+        let parsed = parse_query("");
+        assert_eq!(parsed, ParsedQuery::default());
+    }
+}
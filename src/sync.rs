@@ -0,0 +1,151 @@
+/// Two-way sync reconciliation between a remote IMAP folder and the local cache.
+///
+/// [`plan_sync`] is a pure diff over UID sets and flag state: it never touches the database,
+/// so a sync plan can be computed and rendered for the user (`dry_run`) or exercised against
+/// fixture snapshots in tests without a live IMAP server. [`EmailDatabase::apply_actions`]
+/// carries out the local side of a plan transactionally, reusing the same
+/// `move_email_to_folder`/`toggle_email_flag`/`update_email_status` primitives the rest of the
+/// app uses. `FetchNew` and `PushFlagsRemote` require talking to the server, so they're left for
+/// the sync loop to act on; `apply_actions` only executes the purely-local actions.
+use crate::db::EmailStatus;
+
+/// What we have cached locally for one message, keyed by its stable IMAP UID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalMsgMeta {
+    pub id: i64,
+    pub uid: u32,
+    pub folder: String,
+    pub flagged: bool,
+    pub status: EmailStatus,
+    /// Whether this message's flags/status changed locally since the last successful sync, and
+    /// so should win over a conflicting remote value instead of being overwritten by it.
+    pub dirty: bool,
+}
+
+/// What the remote server reports for one message in a folder, keyed by its stable IMAP UID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteMsgMeta {
+    pub uid: u32,
+    pub flagged: bool,
+    pub status: EmailStatus,
+}
+
+/// One reconciling step between the local cache and the server, produced by [`plan_sync`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncAction {
+    /// A UID the server has that the local cache doesn't: fetch the full message.
+    FetchNew { uid: u32 },
+    /// A cached message whose UID the server no longer reports: it was deleted remotely.
+    DeleteLocal { id: i64 },
+    /// A cached message the server currently has in a different folder than the one we cached
+    /// it under (e.g. moved or archived remotely).
+    MoveLocal { id: i64, folder: String },
+    /// The server's flags/status win over the (non-dirty) local copy: pull them down.
+    UpdateFlags { id: i64, flagged: bool, status: EmailStatus },
+    /// The local copy was modified since the last sync and disagrees with the server: push it.
+    PushFlagsRemote { uid: u32, flagged: bool, status: EmailStatus },
+}
+
+/// Diff `local`'s cached state for `folder` against `remote`'s current snapshot and return the
+/// actions needed to reconcile them. Doesn't read or write the database; `local` and `remote`
+/// are plain snapshots the caller gathers (from `EmailDatabase` and an IMAP `UID FETCH`
+/// respectively).
+pub fn plan_sync(folder: &str, local: &[LocalMsgMeta], remote: &[RemoteMsgMeta]) -> Vec<SyncAction> {
+    use std::collections::HashMap;
+
+    let local_by_uid: HashMap<u32, &LocalMsgMeta> = local.iter().map(|m| (m.uid, m)).collect();
+    let remote_by_uid: HashMap<u32, &RemoteMsgMeta> = remote.iter().map(|m| (m.uid, m)).collect();
+
+    let mut actions = Vec::new();
+
+    for r in remote {
+        match local_by_uid.get(&r.uid) {
+            None => actions.push(SyncAction::FetchNew { uid: r.uid }),
+            Some(l) => {
+                if l.folder != folder {
+                    actions.push(SyncAction::MoveLocal { id: l.id, folder: folder.to_string() });
+                }
+
+                let flags_differ = l.flagged != r.flagged || l.status != r.status;
+                if flags_differ {
+                    if l.dirty {
+                        actions.push(SyncAction::PushFlagsRemote { uid: r.uid, flagged: l.flagged, status: l.status });
+                    } else {
+                        actions.push(SyncAction::UpdateFlags { id: l.id, flagged: r.flagged, status: r.status });
+                    }
+                }
+            }
+        }
+    }
+
+    for l in local {
+        if !remote_by_uid.contains_key(&l.uid) {
+            actions.push(SyncAction::DeleteLocal { id: l.id });
+        }
+    }
+
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(id: i64, uid: u32, folder: &str, flagged: bool, status: EmailStatus, dirty: bool) -> LocalMsgMeta {
+        LocalMsgMeta { id, uid, folder: folder.to_string(), flagged, status, dirty }
+    }
+
+    fn remote(uid: u32, flagged: bool, status: EmailStatus) -> RemoteMsgMeta {
+        RemoteMsgMeta { uid, flagged, status }
+    }
+
+    #[test]
+    fn test_new_remote_uid_is_fetched() {
+        let actions = plan_sync("INBOX", &[], &[remote(1, false, EmailStatus::Unread)]);
+        assert_eq!(actions, vec![SyncAction::FetchNew { uid: 1 }]);
+    }
+
+    #[test]
+    fn test_missing_remote_uid_is_deleted_locally() {
+        let locals = vec![local(10, 1, "INBOX", false, EmailStatus::Unread, false)];
+        let actions = plan_sync("INBOX", &locals, &[]);
+        assert_eq!(actions, vec![SyncAction::DeleteLocal { id: 10 }]);
+    }
+
+    #[test]
+    fn test_matching_uid_with_no_changes_produces_no_actions() {
+        let locals = vec![local(10, 1, "INBOX", false, EmailStatus::Unread, false)];
+        let remotes = vec![remote(1, false, EmailStatus::Unread)];
+        assert!(plan_sync("INBOX", &locals, &remotes).is_empty());
+    }
+
+    #[test]
+    fn test_remote_flag_change_pulls_down_when_local_is_clean() {
+        let locals = vec![local(10, 1, "INBOX", false, EmailStatus::Unread, false)];
+        let remotes = vec![remote(1, true, EmailStatus::Read)];
+        let actions = plan_sync("INBOX", &locals, &remotes);
+        assert_eq!(
+            actions,
+            vec![SyncAction::UpdateFlags { id: 10, flagged: true, status: EmailStatus::Read }]
+        );
+    }
+
+    #[test]
+    fn test_dirty_local_flag_change_pushes_up_instead_of_being_overwritten() {
+        let locals = vec![local(10, 1, "INBOX", true, EmailStatus::Read, true)];
+        let remotes = vec![remote(1, false, EmailStatus::Unread)];
+        let actions = plan_sync("INBOX", &locals, &remotes);
+        assert_eq!(
+            actions,
+            vec![SyncAction::PushFlagsRemote { uid: 1, flagged: true, status: EmailStatus::Read }]
+        );
+    }
+
+    #[test]
+    fn test_remote_folder_mismatch_moves_local() {
+        let locals = vec![local(10, 1, "INBOX", false, EmailStatus::Unread, false)];
+        let remotes = vec![remote(1, false, EmailStatus::Unread)];
+        let actions = plan_sync("Archive", &locals, &remotes);
+        assert_eq!(actions, vec![SyncAction::MoveLocal { id: 10, folder: "Archive".to_string() }]);
+    }
+}
@@ -1,26 +1,27 @@
-use ratatui::style::Color;
+use ratatui::style::{Color, Modifier, Style};
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
 /// Theme color palette for the TUI
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Theme {
     pub name: String,
-    
+
     // Base colors
     pub background: ColorSpec,
     pub foreground: ColorSpec,
-    
+
     // UI Elements
     pub border: ColorSpec,
     pub border_focused: ColorSpec,
     pub title: ColorSpec,
     pub status_bar: ColorSpec,
-    pub status_bar_mode: ColorSpec,
-    
+    pub status_bar_mode: ThemeAttribute,
+
     // Text styles
     pub text_normal: ColorSpec,
     pub text_dim: ColorSpec,
-    pub text_bold: ColorSpec,
+    pub text_bold: ThemeAttribute,
     pub text_highlight: ColorSpec,
     
     // Interactive elements
@@ -40,8 +41,14 @@ pub struct Theme {
     pub email_from: ColorSpec,
     pub email_subject: ColorSpec,
     pub email_date: ColorSpec,
-    pub email_unread: ColorSpec,
-    
+    pub email_unread: ThemeAttribute,
+    /// Background for even-indexed rows in `render_inbox_list` (0-indexed); odd rows use
+    /// [`Self::email_row_odd`]. Alternating the two makes a long list easier to scan.
+    pub email_row_even: ColorSpec,
+    pub email_row_odd: ColorSpec,
+    /// Foreground for the `@` attachment-flag column prepended to a row that has attachments.
+    pub email_attachment_flag: ColorSpec,
+
     // Compose view
     pub compose_field_label: ColorSpec,
     pub compose_field_value: ColorSpec,
@@ -49,13 +56,164 @@ pub struct Theme {
     
     // Markdown preview
     pub markdown_heading: ColorSpec,
-    pub markdown_emphasis: ColorSpec,
+    pub markdown_emphasis: ThemeAttribute,
     pub markdown_link: ColorSpec,
     pub markdown_code: ColorSpec,
 }
 
+/// A single text attribute bit [`Attr`] tracks, folded into a ratatui [`Modifier`] by
+/// [`ThemeAttribute::to_style`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AttrFlag {
+    Bold,
+    Dim,
+    Italic,
+    Underline,
+    Reverse,
+}
+
+/// A bitflags-style set of [`AttrFlag`]s a [`ThemeAttribute`] applies on top of its colors.
+/// Serializes as a list of flag names (e.g. `["bold", "italic"]`) so theme files stay readable
+/// rather than exposing a raw bitmask.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Attr(u8);
+
+impl Attr {
+    pub const NONE: Attr = Attr(0);
+    pub const BOLD: Attr = Attr(1 << 0);
+    pub const DIM: Attr = Attr(1 << 1);
+    pub const ITALIC: Attr = Attr(1 << 2);
+    pub const UNDERLINE: Attr = Attr(1 << 3);
+    pub const REVERSE: Attr = Attr(1 << 4);
+
+    fn bit(flag: AttrFlag) -> Attr {
+        match flag {
+            AttrFlag::Bold => Attr::BOLD,
+            AttrFlag::Dim => Attr::DIM,
+            AttrFlag::Italic => Attr::ITALIC,
+            AttrFlag::Underline => Attr::UNDERLINE,
+            AttrFlag::Reverse => Attr::REVERSE,
+        }
+    }
+
+    pub fn contains(self, other: Attr) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Fold every set flag into a ratatui [`Modifier`] for [`ThemeAttribute::to_style`].
+    pub fn to_modifier(self) -> Modifier {
+        let mut modifier = Modifier::empty();
+        if self.contains(Attr::BOLD) {
+            modifier |= Modifier::BOLD;
+        }
+        if self.contains(Attr::DIM) {
+            modifier |= Modifier::DIM;
+        }
+        if self.contains(Attr::ITALIC) {
+            modifier |= Modifier::ITALIC;
+        }
+        if self.contains(Attr::UNDERLINE) {
+            modifier |= Modifier::UNDERLINED;
+        }
+        if self.contains(Attr::REVERSE) {
+            modifier |= Modifier::REVERSED;
+        }
+        modifier
+    }
+}
+
+impl std::ops::BitOr for Attr {
+    type Output = Attr;
+    fn bitor(self, rhs: Attr) -> Attr {
+        Attr(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for Attr {
+    fn bitor_assign(&mut self, rhs: Attr) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl FromIterator<AttrFlag> for Attr {
+    fn from_iter<I: IntoIterator<Item = AttrFlag>>(iter: I) -> Self {
+        iter.into_iter().fold(Attr::NONE, |acc, flag| acc | Attr::bit(flag))
+    }
+}
+
+/// A themeable color plus optional background and text attributes (bold/italic/underline/etc).
+/// Deserializes from either a bare [`ColorSpec`] - a color name, `#rrggbb` hex, RGB triplet, or
+/// index - which maps to `{ fg, bg: None, attrs: Attr::NONE }`, or a full `{ fg = ..., bg = ...,
+/// attrs = [...] }` table, so theme files that only ever set a color keep deserializing
+/// unchanged.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ThemeAttribute {
+    pub fg: ColorSpec,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bg: Option<ColorSpec>,
+    #[serde(default, skip_serializing_if = "attr_is_none")]
+    pub attrs: Attr,
+}
+
+fn attr_is_none(attr: &Attr) -> bool {
+    *attr == Attr::NONE
+}
+
+impl<'de> Deserialize<'de> for ThemeAttribute {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Color(ColorSpec),
+            Full {
+                fg: ColorSpec,
+                #[serde(default)]
+                bg: Option<ColorSpec>,
+                #[serde(default)]
+                attrs: Vec<AttrFlag>,
+            },
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Color(fg) => ThemeAttribute { fg, bg: None, attrs: Attr::NONE },
+            Repr::Full { fg, bg, attrs } => {
+                ThemeAttribute { fg, bg, attrs: attrs.into_iter().collect() }
+            }
+        })
+    }
+}
+
+impl From<ColorSpec> for ThemeAttribute {
+    fn from(fg: ColorSpec) -> Self {
+        ThemeAttribute { fg, bg: None, attrs: Attr::NONE }
+    }
+}
+
+impl ThemeAttribute {
+    /// Just the foreground color, for call sites that only ever wanted [`ColorSpec::to_color`]
+    /// before this type grew a background and attributes.
+    pub fn to_color(&self) -> Color {
+        self.fg.to_color()
+    }
+
+    /// The full style this attribute describes: foreground, optional background, and every
+    /// `attrs` flag folded into [`Style::add_modifier`].
+    pub fn to_style(&self) -> Style {
+        let mut style = Style::default().fg(self.fg.to_color()).add_modifier(self.attrs.to_modifier());
+        if let Some(bg) = &self.bg {
+            style = style.bg(bg.to_color());
+        }
+        style
+    }
+}
+
 /// Color specification that can be serialized/deserialized
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ColorSpec {
     /// Named color (e.g., "red", "blue", "cyan")
@@ -64,41 +222,86 @@ pub enum ColorSpec {
     Rgb(u8, u8, u8),
     /// Indexed color (0-255)
     Indexed(u8),
+    /// References another [`Theme`] field by name (e.g. `{ link = "title" }`), so that field's
+    /// color stays in sync with the one it points at. Resolved to a terminal, non-`Link`
+    /// [`ColorSpec`] by [`Theme::resolve`]; [`Self::to_color`] still handles it defensively in
+    /// case a theme is ever rendered without going through `resolve` first.
+    Link { link: String },
 }
 
 impl ColorSpec {
     pub fn to_color(&self) -> Color {
         match self {
-            ColorSpec::Named(name) => Self::parse_named_color(name),
+            ColorSpec::Named(name) => Self::named_color(name)
+                .or_else(|| match Self::parse(name) {
+                    Ok(ColorSpec::Rgb(r, g, b)) => Some(Color::Rgb(r, g, b)),
+                    _ => None,
+                })
+                .unwrap_or_else(|| {
+                // Log warning for unrecognized color names to help debug config issues
+                log::warn!("Unrecognized color name '{}', defaulting to Reset", name);
+                Color::Reset
+            }),
             ColorSpec::Rgb(r, g, b) => Color::Rgb(*r, *g, *b),
             ColorSpec::Indexed(i) => Color::Indexed(*i),
+            ColorSpec::Link { link } => {
+                log::warn!("unresolved theme color link '{}', defaulting to Reset", link);
+                Color::Reset
+            }
         }
     }
-    
-    fn parse_named_color(name: &str) -> Color {
-        match name.to_lowercase().as_str() {
-            "reset" => Color::Reset,
-            "black" => Color::Black,
-            "red" => Color::Red,
-            "green" => Color::Green,
-            "yellow" => Color::Yellow,
-            "blue" => Color::Blue,
-            "magenta" => Color::Magenta,
-            "cyan" => Color::Cyan,
-            "gray" | "grey" => Color::Gray,
-            "darkgray" | "darkgrey" => Color::DarkGray,
-            "lightred" => Color::LightRed,
-            "lightgreen" => Color::LightGreen,
-            "lightyellow" => Color::LightYellow,
-            "lightblue" => Color::LightBlue,
-            "lightmagenta" => Color::LightMagenta,
-            "lightcyan" => Color::LightCyan,
-            "white" => Color::White,
-            _ => {
-                // Log warning for unrecognized color names to help debug config issues
-                eprintln!("Warning: Unrecognized color name '{}', defaulting to Reset", name);
-                Color::Reset
+
+    /// Strictly parse a config-supplied color string into a [`ColorSpec`]: a known terminal
+    /// color name, a `#rrggbb` hex triplet, or a bare `0`-`255` 256-color index. Unlike
+    /// [`Self::to_color`] (used for the builtin presets below, which are Rust literals and can't
+    /// be malformed), this rejects anything it doesn't recognize instead of falling back to
+    /// [`Color::Reset`] - a typo in `config.toml` should fail at load, not render invisibly.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        if let Some(hex) = s.strip_prefix('#') {
+            if hex.len() == 6 {
+                if let Ok(value) = u32::from_str_radix(hex, 16) {
+                    return Ok(ColorSpec::Rgb(
+                        ((value >> 16) & 0xff) as u8,
+                        ((value >> 8) & 0xff) as u8,
+                        (value & 0xff) as u8,
+                    ));
+                }
             }
+            return Err(format!("invalid hex color {:?}: expected #rrggbb", s));
+        }
+        if let Ok(index) = s.parse::<u8>() {
+            return Ok(ColorSpec::Indexed(index));
+        }
+        if Self::named_color(s).is_some() {
+            return Ok(ColorSpec::Named(s.to_string()));
+        }
+        Err(format!(
+            "unrecognized color {:?}: expected a named color, a #rrggbb hex triplet, or a 0-255 index",
+            s
+        ))
+    }
+
+    fn named_color(name: &str) -> Option<Color> {
+        match name.to_lowercase().as_str() {
+            "reset" => Some(Color::Reset),
+            "black" => Some(Color::Black),
+            "red" => Some(Color::Red),
+            "green" => Some(Color::Green),
+            "yellow" => Some(Color::Yellow),
+            "blue" => Some(Color::Blue),
+            "magenta" => Some(Color::Magenta),
+            "cyan" => Some(Color::Cyan),
+            "gray" | "grey" => Some(Color::Gray),
+            "darkgray" | "darkgrey" => Some(Color::DarkGray),
+            "lightred" => Some(Color::LightRed),
+            "lightgreen" => Some(Color::LightGreen),
+            "lightyellow" => Some(Color::LightYellow),
+            "lightblue" => Some(Color::LightBlue),
+            "lightmagenta" => Some(Color::LightMagenta),
+            "lightcyan" => Some(Color::LightCyan),
+            "white" => Some(Color::White),
+            _ => None,
         }
     }
 }
@@ -109,6 +312,119 @@ impl Default for Theme {
     }
 }
 
+/// Minimal RGB/HSL color math backing [`Theme::generate`]: lightness shifts, hue rotation, linear
+/// sRGB mixing, and WCAG contrast. Scoped to just what that function needs rather than pulling in
+/// a general-purpose color crate, the same reasoning that kept [`Attr`] a hand-rolled bitfield.
+mod color_math {
+    pub type Rgb = (u8, u8, u8);
+
+    fn to_unit(c: u8) -> f32 {
+        c as f32 / 255.0
+    }
+
+    fn from_unit(c: f32) -> u8 {
+        (c.clamp(0.0, 1.0) * 255.0).round() as u8
+    }
+
+    pub fn rgb_to_hsl(rgb: Rgb) -> (f32, f32, f32) {
+        let (r, g, b) = (to_unit(rgb.0), to_unit(rgb.1), to_unit(rgb.2));
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let delta = max - min;
+        if delta.abs() < f32::EPSILON {
+            return (0.0, 0.0, l);
+        }
+        let s = if l > 0.5 { delta / (2.0 - max - min) } else { delta / (max + min) };
+        let h = if max == r {
+            ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / delta + 2.0
+        } else {
+            (r - g) / delta + 4.0
+        };
+        (h * 60.0, s, l)
+    }
+
+    pub fn hsl_to_rgb(h: f32, s: f32, l: f32) -> Rgb {
+        if s.abs() < f32::EPSILON {
+            let v = from_unit(l);
+            return (v, v, v);
+        }
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let h_prime = h.rem_euclid(360.0) / 60.0;
+        let x = c * (1.0 - (h_prime % 2.0 - 1.0).abs());
+        let (r1, g1, b1) = match h_prime as i32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = l - c / 2.0;
+        (from_unit(r1 + m), from_unit(g1 + m), from_unit(b1 + m))
+    }
+
+    /// Linear interpolation between `a` and `b` per sRGB channel, `t` in `[0, 1]`.
+    pub fn mix(a: Rgb, b: Rgb, t: f32) -> Rgb {
+        let lerp = |x: u8, y: u8| from_unit(to_unit(x) + (to_unit(y) - to_unit(x)) * t);
+        (lerp(a.0, b.0), lerp(a.1, b.1), lerp(a.2, b.2))
+    }
+
+    /// Shift `rgb`'s HSL lightness by `delta` (positive lightens, negative darkens), clamped to
+    /// `[0, 1]`.
+    pub fn shift_lightness(rgb: Rgb, delta: f32) -> Rgb {
+        let (h, s, l) = rgb_to_hsl(rgb);
+        hsl_to_rgb(h, s, (l + delta).clamp(0.0, 1.0))
+    }
+
+    /// Rotate `rgb`'s hue to `degrees`, keeping its saturation and lightness.
+    pub fn with_hue(rgb: Rgb, degrees: f32) -> Rgb {
+        let (_, s, l) = rgb_to_hsl(rgb);
+        hsl_to_rgb(degrees, s, l)
+    }
+
+    fn linearize(c: u8) -> f64 {
+        let c = to_unit(c) as f64;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    }
+
+    /// WCAG relative luminance: `0.2126 R + 0.7152 G + 0.0722 B` on linearized channels.
+    pub fn relative_luminance(rgb: Rgb) -> f64 {
+        0.2126 * linearize(rgb.0) + 0.7152 * linearize(rgb.1) + 0.0722 * linearize(rgb.2)
+    }
+
+    /// WCAG contrast ratio between two colors: `(L1 + 0.05) / (L2 + 0.05)` with `L1` the lighter
+    /// of the two relative luminances.
+    pub fn contrast_ratio(a: Rgb, b: Rgb) -> f64 {
+        let (la, lb) = (relative_luminance(a), relative_luminance(b));
+        let (l1, l2) = if la >= lb { (la, lb) } else { (lb, la) };
+        (l1 + 0.05) / (l2 + 0.05)
+    }
+
+    /// Push `fg`'s lightness away from `bg`'s until [`contrast_ratio`] reaches `min_ratio`,
+    /// bounded at 20 steps of 5% lightness each so a pathological input can't loop forever.
+    pub fn ensure_contrast(fg: Rgb, bg: Rgb, min_ratio: f64) -> Rgb {
+        let (_, _, bg_l) = rgb_to_hsl(bg);
+        let (_, _, fg_l) = rgb_to_hsl(fg);
+        let direction: f32 = if fg_l >= bg_l { 1.0 } else { -1.0 };
+
+        let mut candidate = fg;
+        for _ in 0..20 {
+            if contrast_ratio(candidate, bg) >= min_ratio {
+                break;
+            }
+            candidate = shift_lightness(candidate, direction * 0.05);
+        }
+        candidate
+    }
+}
+
 impl Theme {
     /// Gruvbox Dark theme (default)
     pub fn gruvbox_dark() -> Self {
@@ -124,12 +440,12 @@ impl Theme {
             border_focused: ColorSpec::Rgb(254, 128, 25), // #fe8019 (bright orange)
             title: ColorSpec::Rgb(142, 192, 124),        // #8ec07c (aqua)
             status_bar: ColorSpec::Rgb(60, 56, 54),      // #3c3836
-            status_bar_mode: ColorSpec::Rgb(251, 241, 199), // #fbf1c7 (fg0)
+            status_bar_mode: ThemeAttribute { fg: ColorSpec::Rgb(251, 241, 199), bg: None, attrs: Attr::BOLD }, // #fbf1c7 (fg0)
             
             // Text styles
             text_normal: ColorSpec::Rgb(235, 219, 178),  // #ebdbb2
             text_dim: ColorSpec::Rgb(146, 131, 116),     // #928374
-            text_bold: ColorSpec::Rgb(251, 241, 199),    // #fbf1c7
+            text_bold: ThemeAttribute { fg: ColorSpec::Rgb(251, 241, 199), bg: None, attrs: Attr::BOLD },    // #fbf1c7
             text_highlight: ColorSpec::Rgb(250, 189, 47), // #fabd2f (yellow)
             
             // Interactive elements
@@ -149,8 +465,11 @@ impl Theme {
             email_from: ColorSpec::Rgb(142, 192, 124),   // #8ec07c (aqua)
             email_subject: ColorSpec::Rgb(235, 219, 178), // #ebdbb2
             email_date: ColorSpec::Rgb(146, 131, 116),   // #928374
-            email_unread: ColorSpec::Rgb(251, 241, 199), // #fbf1c7 (bold fg)
-            
+            email_unread: ThemeAttribute { fg: ColorSpec::Rgb(251, 241, 199), bg: None, attrs: Attr::BOLD }, // #fbf1c7 (bold fg)
+            email_row_even: ColorSpec::Rgb(40, 40, 40),  // #282828 (background)
+            email_row_odd: ColorSpec::Rgb(60, 56, 54),   // #3c3836 (status_bar)
+            email_attachment_flag: ColorSpec::Rgb(250, 189, 47), // #fabd2f (yellow)
+
             // Compose view
             compose_field_label: ColorSpec::Rgb(142, 192, 124), // #8ec07c (aqua)
             compose_field_value: ColorSpec::Rgb(235, 219, 178), // #ebdbb2
@@ -158,7 +477,7 @@ impl Theme {
             
             // Markdown preview
             markdown_heading: ColorSpec::Rgb(250, 189, 47),     // #fabd2f (yellow)
-            markdown_emphasis: ColorSpec::Rgb(254, 128, 25),    // #fe8019 (orange)
+            markdown_emphasis: ThemeAttribute { fg: ColorSpec::Rgb(254, 128, 25), bg: None, attrs: Attr::ITALIC },    // #fe8019 (orange)
             markdown_link: ColorSpec::Rgb(131, 165, 152),       // #83a598 (blue)
             markdown_code: ColorSpec::Rgb(184, 187, 38),        // #b8bb26 (green)
         }
@@ -178,12 +497,12 @@ impl Theme {
             border_focused: ColorSpec::Rgb(255, 121, 198), // #ff79c6 (pink)
             title: ColorSpec::Rgb(139, 233, 253),        // #8be9fd (cyan)
             status_bar: ColorSpec::Rgb(68, 71, 90),      // #44475a (selection)
-            status_bar_mode: ColorSpec::Rgb(248, 248, 242), // #f8f8f2
+            status_bar_mode: ThemeAttribute { fg: ColorSpec::Rgb(248, 248, 242), bg: None, attrs: Attr::BOLD }, // #f8f8f2
             
             // Text styles
             text_normal: ColorSpec::Rgb(248, 248, 242),  // #f8f8f2
             text_dim: ColorSpec::Rgb(98, 114, 164),      // #6272a4
-            text_bold: ColorSpec::Rgb(255, 255, 255),    // white
+            text_bold: ThemeAttribute { fg: ColorSpec::Rgb(255, 255, 255), bg: None, attrs: Attr::BOLD },    // white
             text_highlight: ColorSpec::Rgb(241, 250, 140), // #f1fa8c (yellow)
             
             // Interactive elements
@@ -203,8 +522,11 @@ impl Theme {
             email_from: ColorSpec::Rgb(139, 233, 253),   // #8be9fd (cyan)
             email_subject: ColorSpec::Rgb(248, 248, 242), // #f8f8f2
             email_date: ColorSpec::Rgb(98, 114, 164),    // #6272a4
-            email_unread: ColorSpec::Rgb(255, 121, 198), // #ff79c6 (pink)
-            
+            email_unread: ThemeAttribute { fg: ColorSpec::Rgb(255, 121, 198), bg: None, attrs: Attr::BOLD }, // #ff79c6 (pink)
+            email_row_even: ColorSpec::Rgb(40, 42, 54),  // #282a36 (background)
+            email_row_odd: ColorSpec::Rgb(68, 71, 90),   // #44475a (status_bar)
+            email_attachment_flag: ColorSpec::Rgb(241, 250, 140), // #f1fa8c (yellow)
+
             // Compose view
             compose_field_label: ColorSpec::Rgb(139, 233, 253), // #8be9fd (cyan)
             compose_field_value: ColorSpec::Rgb(248, 248, 242), // #f8f8f2
@@ -212,7 +534,7 @@ impl Theme {
             
             // Markdown preview
             markdown_heading: ColorSpec::Rgb(255, 121, 198),    // #ff79c6 (pink)
-            markdown_emphasis: ColorSpec::Rgb(189, 147, 249),   // #bd93f9 (purple)
+            markdown_emphasis: ThemeAttribute { fg: ColorSpec::Rgb(189, 147, 249), bg: None, attrs: Attr::ITALIC },   // #bd93f9 (purple)
             markdown_link: ColorSpec::Rgb(139, 233, 253),       // #8be9fd (cyan)
             markdown_code: ColorSpec::Rgb(80, 250, 123),        // #50fa7b (green)
         }
@@ -232,12 +554,12 @@ impl Theme {
             border_focused: ColorSpec::Rgb(136, 192, 208), // #88c0d0 (frost 1)
             title: ColorSpec::Rgb(143, 188, 187),        // #8fbcbb (frost 0)
             status_bar: ColorSpec::Rgb(59, 66, 82),      // #3b4252
-            status_bar_mode: ColorSpec::Rgb(236, 239, 244), // #eceff4
+            status_bar_mode: ThemeAttribute { fg: ColorSpec::Rgb(236, 239, 244), bg: None, attrs: Attr::BOLD }, // #eceff4
             
             // Text styles
             text_normal: ColorSpec::Rgb(236, 239, 244),  // #eceff4
             text_dim: ColorSpec::Rgb(76, 86, 106),       // #4c566a
-            text_bold: ColorSpec::Rgb(236, 239, 244),    // #eceff4
+            text_bold: ThemeAttribute { fg: ColorSpec::Rgb(236, 239, 244), bg: None, attrs: Attr::BOLD },    // #eceff4
             text_highlight: ColorSpec::Rgb(235, 203, 139), // #ebcb8b (aurora yellow)
             
             // Interactive elements
@@ -257,8 +579,11 @@ impl Theme {
             email_from: ColorSpec::Rgb(143, 188, 187),   // #8fbcbb (frost 0)
             email_subject: ColorSpec::Rgb(236, 239, 244), // #eceff4
             email_date: ColorSpec::Rgb(76, 86, 106),     // #4c566a
-            email_unread: ColorSpec::Rgb(229, 233, 240), // #e5e9f0 (snow 1)
-            
+            email_unread: ThemeAttribute { fg: ColorSpec::Rgb(229, 233, 240), bg: None, attrs: Attr::BOLD }, // #e5e9f0 (snow 1)
+            email_row_even: ColorSpec::Rgb(46, 52, 64),  // #2e3440 (background)
+            email_row_odd: ColorSpec::Rgb(59, 66, 82),   // #3b4252 (status_bar)
+            email_attachment_flag: ColorSpec::Rgb(235, 203, 139), // #ebcb8b (aurora yellow)
+
             // Compose view
             compose_field_label: ColorSpec::Rgb(143, 188, 187), // #8fbcbb (frost 0)
             compose_field_value: ColorSpec::Rgb(236, 239, 244), // #eceff4
@@ -266,7 +591,7 @@ impl Theme {
             
             // Markdown preview
             markdown_heading: ColorSpec::Rgb(136, 192, 208),    // #88c0d0 (frost 1)
-            markdown_emphasis: ColorSpec::Rgb(180, 142, 173),   // #b48ead (aurora purple)
+            markdown_emphasis: ThemeAttribute { fg: ColorSpec::Rgb(180, 142, 173), bg: None, attrs: Attr::ITALIC },   // #b48ead (aurora purple)
             markdown_link: ColorSpec::Rgb(94, 129, 172),        // #5e81ac (frost 3)
             markdown_code: ColorSpec::Rgb(163, 190, 140),       // #a3be8c (aurora green)
         }
@@ -286,12 +611,12 @@ impl Theme {
             border_focused: ColorSpec::Rgb(38, 139, 210), // #268bd2 (blue)
             title: ColorSpec::Rgb(42, 161, 152),         // #2aa198 (cyan)
             status_bar: ColorSpec::Rgb(7, 54, 66),       // #073642
-            status_bar_mode: ColorSpec::Rgb(238, 232, 213), // #eee8d5
+            status_bar_mode: ThemeAttribute { fg: ColorSpec::Rgb(238, 232, 213), bg: None, attrs: Attr::BOLD }, // #eee8d5
             
             // Text styles
             text_normal: ColorSpec::Rgb(131, 148, 150),  // #839496
             text_dim: ColorSpec::Rgb(88, 110, 117),      // #586e75
-            text_bold: ColorSpec::Rgb(238, 232, 213),    // #eee8d5
+            text_bold: ThemeAttribute { fg: ColorSpec::Rgb(238, 232, 213), bg: None, attrs: Attr::BOLD },    // #eee8d5
             text_highlight: ColorSpec::Rgb(181, 137, 0), // #b58900 (yellow)
             
             // Interactive elements
@@ -311,8 +636,11 @@ impl Theme {
             email_from: ColorSpec::Rgb(42, 161, 152),    // #2aa198 (cyan)
             email_subject: ColorSpec::Rgb(131, 148, 150), // #839496
             email_date: ColorSpec::Rgb(88, 110, 117),    // #586e75
-            email_unread: ColorSpec::Rgb(238, 232, 213), // #eee8d5
-            
+            email_unread: ThemeAttribute { fg: ColorSpec::Rgb(238, 232, 213), bg: None, attrs: Attr::BOLD }, // #eee8d5
+            email_row_even: ColorSpec::Rgb(0, 43, 54),   // #002b36 (background)
+            email_row_odd: ColorSpec::Rgb(7, 54, 66),    // #073642 (status_bar)
+            email_attachment_flag: ColorSpec::Rgb(203, 75, 22), // #cb4b16 (orange)
+
             // Compose view
             compose_field_label: ColorSpec::Rgb(42, 161, 152), // #2aa198 (cyan)
             compose_field_value: ColorSpec::Rgb(131, 148, 150), // #839496
@@ -320,12 +648,72 @@ impl Theme {
             
             // Markdown preview
             markdown_heading: ColorSpec::Rgb(203, 75, 22),      // #cb4b16 (orange)
-            markdown_emphasis: ColorSpec::Rgb(211, 54, 130),    // #d33682 (magenta)
+            markdown_emphasis: ThemeAttribute { fg: ColorSpec::Rgb(211, 54, 130), bg: None, attrs: Attr::ITALIC },    // #d33682 (magenta)
             markdown_link: ColorSpec::Rgb(38, 139, 210),        // #268bd2 (blue)
             markdown_code: ColorSpec::Rgb(133, 153, 0),         // #859900 (green)
         }
     }
-    
+
+    /// Solarized Light theme: [`Self::solarized_dark`]'s light companion (see [`ThemeVariant`]).
+    /// Solarized's base tones swap symmetrically between the two - `base03`/`base02`/`base01`/
+    /// `base0` in dark mode become `base3`/`base2`/`base1`/`base00` here - while every accent
+    /// color (yellow/orange/red/magenta/violet/blue/cyan/green) stays identical.
+    pub fn solarized_light() -> Self {
+        Self {
+            name: "Solarized Light".to_string(),
+
+            // Base colors
+            background: ColorSpec::Rgb(253, 246, 227),   // #fdf6e3 (base3)
+            foreground: ColorSpec::Rgb(101, 123, 131),   // #657b83 (base00)
+
+            // UI Elements
+            border: ColorSpec::Rgb(147, 161, 161),       // #93a1a1 (base1)
+            border_focused: ColorSpec::Rgb(38, 139, 210), // #268bd2 (blue)
+            title: ColorSpec::Rgb(42, 161, 152),         // #2aa198 (cyan)
+            status_bar: ColorSpec::Rgb(238, 232, 213),   // #eee8d5 (base2)
+            status_bar_mode: ThemeAttribute { fg: ColorSpec::Rgb(0, 43, 54), bg: None, attrs: Attr::BOLD }, // #002b36 (base03)
+
+            // Text styles
+            text_normal: ColorSpec::Rgb(101, 123, 131),  // #657b83 (base00)
+            text_dim: ColorSpec::Rgb(147, 161, 161),     // #93a1a1 (base1)
+            text_bold: ThemeAttribute { fg: ColorSpec::Rgb(0, 43, 54), bg: None, attrs: Attr::BOLD },    // #002b36 (base03)
+            text_highlight: ColorSpec::Rgb(181, 137, 0), // #b58900 (yellow)
+
+            // Interactive elements
+            cursor: ColorSpec::Rgb(238, 232, 213),       // #eee8d5 (base2)
+            selection: ColorSpec::Rgb(38, 139, 210),     // #268bd2 (blue)
+            visual_selection: ColorSpec::Rgb(147, 161, 161), // #93a1a1 (base1)
+            active_field: ColorSpec::Rgb(42, 161, 152),  // #2aa198 (cyan)
+            insert_mode: ColorSpec::Rgb(181, 137, 0),    // #b58900 (yellow)
+
+            // Status indicators
+            success: ColorSpec::Rgb(133, 153, 0),        // #859900 (green)
+            warning: ColorSpec::Rgb(203, 75, 22),        // #cb4b16 (orange)
+            error: ColorSpec::Rgb(220, 50, 47),          // #dc322f (red)
+            info: ColorSpec::Rgb(108, 113, 196),         // #6c71c4 (violet)
+
+            // Email list
+            email_from: ColorSpec::Rgb(42, 161, 152),    // #2aa198 (cyan)
+            email_subject: ColorSpec::Rgb(101, 123, 131), // #657b83 (base00)
+            email_date: ColorSpec::Rgb(147, 161, 161),   // #93a1a1 (base1)
+            email_unread: ThemeAttribute { fg: ColorSpec::Rgb(0, 43, 54), bg: None, attrs: Attr::BOLD }, // #002b36 (base03)
+            email_row_even: ColorSpec::Rgb(253, 246, 227), // #fdf6e3 (background)
+            email_row_odd: ColorSpec::Rgb(238, 232, 213),  // #eee8d5 (status_bar)
+            email_attachment_flag: ColorSpec::Rgb(203, 75, 22), // #cb4b16 (orange)
+
+            // Compose view
+            compose_field_label: ColorSpec::Rgb(42, 161, 152), // #2aa198 (cyan)
+            compose_field_value: ColorSpec::Rgb(101, 123, 131), // #657b83 (base00)
+            compose_field_empty: ColorSpec::Rgb(147, 161, 161),  // #93a1a1 (base1)
+
+            // Markdown preview
+            markdown_heading: ColorSpec::Rgb(203, 75, 22),      // #cb4b16 (orange)
+            markdown_emphasis: ThemeAttribute { fg: ColorSpec::Rgb(211, 54, 130), bg: None, attrs: Attr::ITALIC },    // #d33682 (magenta)
+            markdown_link: ColorSpec::Rgb(38, 139, 210),        // #268bd2 (blue)
+            markdown_code: ColorSpec::Rgb(133, 153, 0),         // #859900 (green)
+        }
+    }
+
     /// Tokyo Night theme
     pub fn tokyo_night() -> Self {
         Self {
@@ -340,12 +728,12 @@ impl Theme {
             border_focused: ColorSpec::Rgb(125, 207, 255), // #7dcfff (cyan)
             title: ColorSpec::Rgb(125, 207, 255),        // #7dcfff (cyan)
             status_bar: ColorSpec::Rgb(36, 40, 59),      // #24283b
-            status_bar_mode: ColorSpec::Rgb(192, 202, 245), // #c0caf5
+            status_bar_mode: ThemeAttribute { fg: ColorSpec::Rgb(192, 202, 245), bg: None, attrs: Attr::BOLD }, // #c0caf5
             
             // Text styles
             text_normal: ColorSpec::Rgb(192, 202, 245),  // #c0caf5
             text_dim: ColorSpec::Rgb(68, 75, 106),       // #444b6a
-            text_bold: ColorSpec::Rgb(192, 202, 245),    // #c0caf5
+            text_bold: ThemeAttribute { fg: ColorSpec::Rgb(192, 202, 245), bg: None, attrs: Attr::BOLD },    // #c0caf5
             text_highlight: ColorSpec::Rgb(224, 175, 104), // #e0af68 (yellow)
             
             // Interactive elements
@@ -365,8 +753,11 @@ impl Theme {
             email_from: ColorSpec::Rgb(125, 207, 255),   // #7dcfff (cyan)
             email_subject: ColorSpec::Rgb(192, 202, 245), // #c0caf5
             email_date: ColorSpec::Rgb(68, 75, 106),     // #444b6a
-            email_unread: ColorSpec::Rgb(192, 202, 245), // #c0caf5
-            
+            email_unread: ThemeAttribute { fg: ColorSpec::Rgb(192, 202, 245), bg: None, attrs: Attr::BOLD }, // #c0caf5
+            email_row_even: ColorSpec::Rgb(26, 27, 38),  // #1a1b26 (background)
+            email_row_odd: ColorSpec::Rgb(36, 40, 59),   // #24283b (status_bar)
+            email_attachment_flag: ColorSpec::Rgb(224, 175, 104), // #e0af68 (yellow)
+
             // Compose view
             compose_field_label: ColorSpec::Rgb(125, 207, 255), // #7dcfff (cyan)
             compose_field_value: ColorSpec::Rgb(192, 202, 245), // #c0caf5
@@ -374,33 +765,603 @@ impl Theme {
             
             // Markdown preview
             markdown_heading: ColorSpec::Rgb(187, 154, 247),    // #bb9af7 (purple)
-            markdown_emphasis: ColorSpec::Rgb(255, 158, 100),   // #ff9e64 (orange)
+            markdown_emphasis: ThemeAttribute { fg: ColorSpec::Rgb(255, 158, 100), bg: None, attrs: Attr::ITALIC },   // #ff9e64 (orange)
             markdown_link: ColorSpec::Rgb(125, 207, 255),       // #7dcfff (cyan)
             markdown_code: ColorSpec::Rgb(158, 206, 106),       // #9ece6a (green)
         }
     }
     
-    /// Get a theme by name
+    /// Get a theme by name: a built-in preset first, falling back to a user theme loaded from
+    /// [`Self::user_themes_dir`] whose `name` field matches (case-insensitively).
     pub fn by_name(name: &str) -> Option<Self> {
         match name.to_lowercase().as_str() {
             "gruvbox" | "gruvbox-dark" | "gruvbox_dark" => Some(Self::gruvbox_dark()),
             "dracula" => Some(Self::dracula()),
             "nord" => Some(Self::nord()),
-            "solarized" | "solarized-dark" | "solarized_dark" => Some(Self::solarized_dark()),
+            // Bare "solarized" defers to the detected terminal appearance; an explicit
+            // "-dark"/"-light" suffix always picks that variant regardless of appearance.
+            "solarized" => Some(
+                ThemeVariant::new(Self::solarized_light(), Self::solarized_dark())
+                    .resolve(Appearance::Auto)
+                    .clone(),
+            ),
+            "solarized-dark" | "solarized_dark" => Some(Self::solarized_dark()),
+            "solarized-light" | "solarized_light" => Some(Self::solarized_light()),
             "tokyo-night" | "tokyo_night" | "tokyonight" => Some(Self::tokyo_night()),
-            _ => None,
+            _ => Self::user_themes()
+                .into_iter()
+                .find(|theme| theme.name.to_lowercase() == name.to_lowercase()),
         }
     }
-    
-    /// Get all available theme names
-    pub fn available_themes() -> Vec<&'static str> {
-        vec![
+
+    /// Get all available theme names: the built-in presets followed by every user theme found in
+    /// [`Self::user_themes_dir`].
+    pub fn available_themes() -> Vec<String> {
+        let mut names: Vec<String> = [
             "gruvbox-dark",
             "dracula",
             "nord",
             "solarized-dark",
+            "solarized-light",
             "tokyo-night",
         ]
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+        names.extend(Self::user_themes().into_iter().map(|theme| theme.name));
+        names
+    }
+
+    /// `$XDG_CONFIG_HOME/tume/themes` if set, else `~/.config/tume/themes` - mirrors
+    /// `Config::candidate_config_paths`' resolution of the config file itself.
+    fn user_themes_dir() -> Option<PathBuf> {
+        if let Some(xdg_config_home) = std::env::var_os("XDG_CONFIG_HOME") {
+            let xdg_config_home = PathBuf::from(xdg_config_home);
+            if !xdg_config_home.as_os_str().is_empty() {
+                return Some(xdg_config_home.join("tume").join("themes"));
+            }
+        }
+        dirs::home_dir().map(|home| home.join(".config").join("tume").join("themes"))
+    }
+
+    /// Every theme found under [`Self::user_themes_dir`], or empty if that directory doesn't
+    /// exist or can't be determined.
+    fn user_themes() -> Vec<Theme> {
+        Self::user_themes_dir()
+            .map(|dir| Self::load_from_dir(&dir))
+            .unwrap_or_default()
+    }
+
+    /// Load every `*.toml`/`*.json` theme file in `dir`, tolerating files that omit fields: any
+    /// key a file doesn't set falls back to [`Theme::default`]'s value, so a user theme can
+    /// override just a few colors instead of respecifying the whole palette. A file that fails
+    /// to parse at all is skipped with a logged warning - the same fallback-and-warn approach
+    /// [`ColorSpec::to_color`] takes for an unrecognized color name, rather than aborting startup
+    /// over one bad theme file.
+    pub fn load_from_dir(dir: &Path) -> Vec<Theme> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+
+        let mut themes = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_json = path.extension().and_then(|e| e.to_str()) == Some("json");
+            let is_toml = path.extension().and_then(|e| e.to_str()) == Some("toml");
+            if !is_json && !is_toml {
+                continue;
+            }
+
+            let contents = match std::fs::read_to_string(&path) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    log::warn!("failed to read theme file {:?}: {}", path, e);
+                    continue;
+                }
+            };
+
+            let parsed = if is_json {
+                serde_json::from_str::<PartialTheme>(&contents).map_err(|e| e.to_string())
+            } else {
+                toml::from_str::<PartialTheme>(&contents).map_err(|e| e.to_string())
+            };
+
+            match parsed {
+                Ok(partial) => themes.push(partial.into_theme()),
+                Err(e) => log::warn!("failed to parse theme file {:?}: {}", path, e),
+            }
+        }
+        themes
+    }
+
+    /// Export this theme as a TOML file, e.g. as a starting template for a user theme placed
+    /// under [`Self::user_themes_dir`].
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let contents = toml::to_string_pretty(self).map_err(|e| e.to_string())?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        std::fs::write(path, contents).map_err(|e| e.to_string())
+    }
+
+    /// Look up a theme field's raw (possibly still-linked) color by the same name a `{ link =
+    /// "..." }` in a theme file would reference - i.e. its field name in [`Theme`]/[`PartialTheme`].
+    /// A [`ThemeAttribute`] field resolves to its foreground, since that's what a plain color link
+    /// conceptually points at.
+    fn field(&self, name: &str) -> Option<ColorSpec> {
+        Some(match name {
+            "background" => self.background.clone(),
+            "foreground" => self.foreground.clone(),
+            "border" => self.border.clone(),
+            "border_focused" => self.border_focused.clone(),
+            "title" => self.title.clone(),
+            "status_bar" => self.status_bar.clone(),
+            "status_bar_mode" => self.status_bar_mode.fg.clone(),
+            "text_normal" => self.text_normal.clone(),
+            "text_dim" => self.text_dim.clone(),
+            "text_bold" => self.text_bold.fg.clone(),
+            "text_highlight" => self.text_highlight.clone(),
+            "cursor" => self.cursor.clone(),
+            "selection" => self.selection.clone(),
+            "visual_selection" => self.visual_selection.clone(),
+            "active_field" => self.active_field.clone(),
+            "insert_mode" => self.insert_mode.clone(),
+            "success" => self.success.clone(),
+            "warning" => self.warning.clone(),
+            "error" => self.error.clone(),
+            "info" => self.info.clone(),
+            "email_from" => self.email_from.clone(),
+            "email_subject" => self.email_subject.clone(),
+            "email_date" => self.email_date.clone(),
+            "email_unread" => self.email_unread.fg.clone(),
+            "email_row_even" => self.email_row_even.clone(),
+            "email_row_odd" => self.email_row_odd.clone(),
+            "email_attachment_flag" => self.email_attachment_flag.clone(),
+            "compose_field_label" => self.compose_field_label.clone(),
+            "compose_field_value" => self.compose_field_value.clone(),
+            "compose_field_empty" => self.compose_field_empty.clone(),
+            "markdown_heading" => self.markdown_heading.clone(),
+            "markdown_emphasis" => self.markdown_emphasis.fg.clone(),
+            "markdown_link" => self.markdown_link.clone(),
+            "markdown_code" => self.markdown_code.clone(),
+            _ => return None,
+        })
+    }
+
+    /// Upper bound on how many hops [`Self::resolve_link`] follows before assuming a cycle -
+    /// generous for any theme file's actual link depth, but still finite.
+    const MAX_LINK_HOPS: usize = 32;
+
+    /// Follow a `{ link = "..." }` chain starting at field `start` to its terminal, non-`Link`
+    /// color. Falls back to `ColorSpec::Named("reset")` - the same [`Color::Reset`] fallback
+    /// [`ColorSpec::to_color`] uses for an unrecognized color name - and logs a warning, for a
+    /// cycle (tracked with a visited-name set) or a link naming a field that doesn't exist,
+    /// rather than failing theme resolution outright over one bad link.
+    fn resolve_link(&self, start: &str) -> ColorSpec {
+        let mut visited = std::collections::HashSet::new();
+        let mut current = start.to_string();
+
+        for _ in 0..Self::MAX_LINK_HOPS {
+            if !visited.insert(current.clone()) {
+                log::warn!("theme color link cycle detected at '{}', defaulting to Reset", current);
+                return ColorSpec::Named("reset".to_string());
+            }
+            match self.field(&current) {
+                Some(ColorSpec::Link { link }) => current = link,
+                Some(resolved) => return resolved,
+                None => {
+                    log::warn!(
+                        "theme color link '{}' references unknown field '{}', defaulting to Reset",
+                        start, current
+                    );
+                    return ColorSpec::Named("reset".to_string());
+                }
+            }
+        }
+
+        log::warn!(
+            "theme color link '{}' did not resolve within {} hops, defaulting to Reset",
+            start,
+            Self::MAX_LINK_HOPS
+        );
+        ColorSpec::Named("reset".to_string())
+    }
+
+    /// Resolve every `ColorSpec::Link` in this theme - wherever it appears, including inside a
+    /// [`ThemeAttribute`]'s `fg`/`bg` - to its terminal concrete color, so a user can write
+    /// `active_field = { link = "title" }` and have it stay in sync with `title` as that field
+    /// changes. Run once after load (see [`PartialTheme::into_theme`]); the rest of the app never
+    /// has to know links exist.
+    pub fn resolve(&self) -> Theme {
+        let resolve_spec = |spec: &ColorSpec| match spec {
+            ColorSpec::Link { link } => self.resolve_link(link),
+            other => other.clone(),
+        };
+        let resolve_attr = |attr: &ThemeAttribute| ThemeAttribute {
+            fg: resolve_spec(&attr.fg),
+            bg: attr.bg.as_ref().map(|bg| resolve_spec(bg)),
+            attrs: attr.attrs,
+        };
+
+        Theme {
+            name: self.name.clone(),
+            background: resolve_spec(&self.background),
+            foreground: resolve_spec(&self.foreground),
+            border: resolve_spec(&self.border),
+            border_focused: resolve_spec(&self.border_focused),
+            title: resolve_spec(&self.title),
+            status_bar: resolve_spec(&self.status_bar),
+            status_bar_mode: resolve_attr(&self.status_bar_mode),
+            text_normal: resolve_spec(&self.text_normal),
+            text_dim: resolve_spec(&self.text_dim),
+            text_bold: resolve_attr(&self.text_bold),
+            text_highlight: resolve_spec(&self.text_highlight),
+            cursor: resolve_spec(&self.cursor),
+            selection: resolve_spec(&self.selection),
+            visual_selection: resolve_spec(&self.visual_selection),
+            active_field: resolve_spec(&self.active_field),
+            insert_mode: resolve_spec(&self.insert_mode),
+            success: resolve_spec(&self.success),
+            warning: resolve_spec(&self.warning),
+            error: resolve_spec(&self.error),
+            info: resolve_spec(&self.info),
+            email_from: resolve_spec(&self.email_from),
+            email_subject: resolve_spec(&self.email_subject),
+            email_date: resolve_spec(&self.email_date),
+            email_unread: resolve_attr(&self.email_unread),
+            email_row_even: resolve_spec(&self.email_row_even),
+            email_row_odd: resolve_spec(&self.email_row_odd),
+            email_attachment_flag: resolve_spec(&self.email_attachment_flag),
+            compose_field_label: resolve_spec(&self.compose_field_label),
+            compose_field_value: resolve_spec(&self.compose_field_value),
+            compose_field_empty: resolve_spec(&self.compose_field_empty),
+            markdown_heading: resolve_spec(&self.markdown_heading),
+            markdown_emphasis: resolve_attr(&self.markdown_emphasis),
+            markdown_link: resolve_spec(&self.markdown_link),
+            markdown_code: resolve_spec(&self.markdown_code),
+        }
+    }
+
+    /// Build a [`Theme`] from a [base16](https://github.com/chriskempson/base16) palette: sixteen
+    /// colors `base00`-`base0F`, `base00` the darkest background through `base07` the lightest
+    /// foreground, `base08`-`base0F` the accent reds/oranges/yellows/greens/cyans/blues/purples/
+    /// browns. Maps them onto tume's fields using base16's conventional UI assignment; any field
+    /// not covered by that assignment keeps [`Theme::default`]'s value (its bold/italic `attrs`
+    /// included), so a base16 scheme only ever changes colors, not text styling.
+    pub fn from_base16(name: &str, palette: [(u8, u8, u8); 16]) -> Theme {
+        let rgb = |slot: usize| {
+            let (r, g, b) = palette[slot];
+            ColorSpec::Rgb(r, g, b)
+        };
+
+        let mut theme = Theme::default();
+        theme.name = name.to_string();
+        theme.background = rgb(0x0);
+        theme.foreground = rgb(0x5);
+        theme.border = rgb(0x3);
+        theme.border_focused = rgb(0xd);
+        theme.title = rgb(0xd);
+        theme.active_field = rgb(0xd);
+        theme.error = rgb(0x8);
+        theme.warning = rgb(0xa);
+        theme.success = rgb(0xb);
+        theme.info = rgb(0xc);
+        theme.markdown_heading = rgb(0xa);
+        theme.markdown_emphasis.fg = rgb(0x9);
+        theme.markdown_link = rgb(0xd);
+        theme.markdown_code = rgb(0xb);
+        theme.selection = rgb(0x2);
+        theme.text_dim = rgb(0x3);
+        theme.text_highlight = rgb(0xa);
+        theme.resolve()
+    }
+
+    /// Parse a standard base16 `scheme.yaml` - a `scheme: "Name"` line plus sixteen `baseXX:
+    /// "rrggbb"` entries - into a [`Theme`] via [`Self::from_base16`]. Hand-parses just this
+    /// file's `key: value`-per-line shape rather than pulling in a full YAML parser for sixteen
+    /// fixed hex fields, the same reasoning that kept [`Attr`] a hand-rolled bitfield instead of a
+    /// `bitflags` dependency.
+    pub fn from_base16_yaml(contents: &str) -> Result<Theme, String> {
+        let mut name = None;
+        let mut palette: [Option<(u8, u8, u8)>; 16] = [None; 16];
+
+        for line in contents.lines() {
+            let Some((key, value)) = line.trim().split_once(':') else {
+                continue;
+            };
+            let key = key.trim();
+            let value = value.trim().trim_matches('"').trim_matches('\'');
+
+            if key == "scheme" {
+                name = Some(value.to_string());
+                continue;
+            }
+
+            let Some(slot_hex) = key.strip_prefix("base") else {
+                continue;
+            };
+            let slot = usize::from_str_radix(slot_hex, 16)
+                .map_err(|_| format!("invalid base16 slot key {:?}", key))?;
+            if slot >= 16 {
+                return Err(format!("invalid base16 slot key {:?}", key));
+            }
+
+            let hex = value.strip_prefix('#').unwrap_or(value);
+            match ColorSpec::parse(&format!("#{hex}"))? {
+                ColorSpec::Rgb(r, g, b) => palette[slot] = Some((r, g, b)),
+                other => unreachable!("ColorSpec::parse(\"#...\") always returns Rgb, got {:?}", other),
+            }
+        }
+
+        let mut full_palette = [(0u8, 0u8, 0u8); 16];
+        for (slot, color) in palette.into_iter().enumerate() {
+            full_palette[slot] =
+                color.ok_or_else(|| format!("scheme.yaml is missing base{:02x}", slot))?;
+        }
+
+        Ok(Self::from_base16(&name.unwrap_or_else(|| "base16".to_string()), full_palette))
+    }
+
+    /// Synthesize a full [`Theme`] from just `background`, `foreground`, and an `accent`, via the
+    /// HSL lightness/saturation math in [`color_math`]: mixing toward `background`/white, small
+    /// lightness shifts for UI chrome, and hue rotation to canonical angles (error/warning/
+    /// success/info) while keeping the accent's saturation and lightness. `foreground` is pushed
+    /// away from `background` first (see [`color_math::ensure_contrast`]) so it clears the WCAG
+    /// 4.5:1 AA contrast ratio before anything else derives from it.
+    pub fn generate(
+        name: &str,
+        background: (u8, u8, u8),
+        foreground: (u8, u8, u8),
+        accent: (u8, u8, u8),
+    ) -> Theme {
+        use color_math::{ensure_contrast, mix, shift_lightness, with_hue};
+
+        let foreground = ensure_contrast(foreground, background, 4.5);
+        let rgb = |c: (u8, u8, u8)| ColorSpec::Rgb(c.0, c.1, c.2);
+
+        let text_dim = mix(foreground, background, 0.5);
+        let text_bold_fg = mix(foreground, (255, 255, 255), 0.3);
+        let border = shift_lightness(background, 0.15);
+        let chrome_bg = shift_lightness(background, 0.10);
+        let selection = shift_lightness(accent, -0.10);
+        let error = with_hue(accent, 0.0);
+        let warning = with_hue(accent, 45.0);
+        let success = with_hue(accent, 120.0);
+        let info = with_hue(accent, 210.0);
+
+        Theme {
+            name: name.to_string(),
+            background: rgb(background),
+            foreground: rgb(foreground),
+            border: rgb(border),
+            border_focused: rgb(accent),
+            title: rgb(accent),
+            status_bar: rgb(chrome_bg),
+            status_bar_mode: ThemeAttribute { fg: rgb(accent), bg: None, attrs: Attr::BOLD },
+            text_normal: rgb(foreground),
+            text_dim: rgb(text_dim),
+            text_bold: ThemeAttribute { fg: rgb(text_bold_fg), bg: None, attrs: Attr::BOLD },
+            text_highlight: rgb(warning),
+            cursor: rgb(chrome_bg),
+            selection: rgb(selection),
+            visual_selection: rgb(chrome_bg),
+            active_field: rgb(accent),
+            insert_mode: rgb(success),
+            success: rgb(success),
+            warning: rgb(warning),
+            error: rgb(error),
+            info: rgb(info),
+            email_from: rgb(foreground),
+            email_subject: rgb(text_bold_fg),
+            email_date: rgb(text_dim),
+            email_unread: ThemeAttribute { fg: rgb(text_bold_fg), bg: None, attrs: Attr::BOLD },
+            email_row_even: rgb(background),
+            email_row_odd: rgb(shift_lightness(background, 0.04)),
+            email_attachment_flag: rgb(warning),
+            compose_field_label: rgb(accent),
+            compose_field_value: rgb(foreground),
+            compose_field_empty: rgb(text_dim),
+            markdown_heading: rgb(accent),
+            markdown_emphasis: ThemeAttribute { fg: rgb(warning), bg: None, attrs: Attr::ITALIC },
+            markdown_link: rgb(accent),
+            markdown_code: rgb(success),
+        }
+        .resolve()
+    }
+}
+
+/// Which of a theme's light/dark variants to use. `Auto` defers to [`detect_appearance`]'s OSC 11
+/// terminal background query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Appearance {
+    Light,
+    Dark,
+    Auto,
+}
+
+/// A theme's light and dark companions (e.g. [`Theme::solarized_light`] paired with
+/// [`Theme::solarized_dark`]), so a single preset name can resolve to whichever one matches the
+/// user's [`Appearance`].
+#[derive(Debug, Clone)]
+pub struct ThemeVariant {
+    pub light: Theme,
+    pub dark: Theme,
+}
+
+impl ThemeVariant {
+    pub fn new(light: Theme, dark: Theme) -> Self {
+        ThemeVariant { light, dark }
+    }
+
+    /// Resolve to whichever of `light`/`dark` matches `appearance`, querying the terminal via
+    /// [`detect_appearance`] for [`Appearance::Auto`].
+    pub fn resolve(&self, appearance: Appearance) -> &Theme {
+        let appearance = match appearance {
+            Appearance::Auto => detect_appearance(),
+            explicit => explicit,
+        };
+        match appearance {
+            Appearance::Light => &self.light,
+            _ => &self.dark,
+        }
+    }
+}
+
+/// How long [`detect_appearance`] waits for an OSC 11 reply before giving up.
+const OSC11_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// Detect whether the terminal is running on a light or dark background by sending an OSC 11
+/// query (`\x1b]11;?\x07`) and parsing the `rgb:RRRR/GGGG/BBBB` reply, classifying it by WCAG
+/// relative luminance (see [`color_math::relative_luminance`]): `> 0.5` is [`Appearance::Light`],
+/// otherwise [`Appearance::Dark`]. Falls back to `Dark` if the terminal doesn't answer within
+/// [`OSC11_TIMEOUT`] or the reply doesn't parse - most terminals that don't support OSC 11 simply
+/// stay silent, rather than erroring. Assumes raw mode is already enabled (as `main` does for the
+/// whole app lifetime), since the reply arrives as raw bytes on stdin rather than a crossterm key
+/// event.
+pub fn detect_appearance() -> Appearance {
+    match query_osc11_background() {
+        Some(rgb) if color_math::relative_luminance(rgb) > 0.5 => Appearance::Light,
+        _ => Appearance::Dark,
+    }
+}
+
+fn query_osc11_background() -> Option<color_math::Rgb> {
+    use std::io::Write;
+
+    std::io::stdout().write_all(b"\x1b]11;?\x07").ok()?;
+    std::io::stdout().flush().ok()?;
+
+    // The reply is read on its own thread so a terminal that never answers can't block the
+    // caller past OSC11_TIMEOUT; a thread left blocked on that read is harmless since the process
+    // is terminal-bound for its whole lifetime anyway.
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        use std::io::Read;
+        let mut stdin = std::io::stdin();
+        let mut reply = Vec::new();
+        let mut byte = [0u8; 1];
+        while reply.len() < 64 {
+            match stdin.read(&mut byte) {
+                Ok(1) => {
+                    reply.push(byte[0]);
+                    if byte[0] == 0x07 || reply.ends_with(b"\x1b\\") {
+                        break;
+                    }
+                }
+                _ => break,
+            }
+        }
+        let _ = tx.send(reply);
+    });
+
+    let reply = rx.recv_timeout(OSC11_TIMEOUT).ok()?;
+    parse_osc11_reply(&reply)
+}
+
+/// Parse an OSC 11 reply body `rgb:RRRR/GGGG/BBBB` (4 hex digits per channel, X11 `rgb:` syntax)
+/// down to 8-bit RGB by keeping each channel's high byte.
+fn parse_osc11_reply(reply: &[u8]) -> Option<color_math::Rgb> {
+    let text = std::str::from_utf8(reply).ok()?;
+    let body = &text[text.find("rgb:")? + 4..];
+    let body = body.trim_end_matches(['\x07', '\x1b', '\\']);
+
+    let parse_channel = |s: &str| -> Option<u8> {
+        let value = u32::from_str_radix(s, 16).ok()?;
+        let bits = s.len() * 4;
+        Some((value >> (bits.saturating_sub(8))) as u8)
+    };
+
+    let mut channels = body.split('/');
+    let r = parse_channel(channels.next()?)?;
+    let g = parse_channel(channels.next()?)?;
+    let b = parse_channel(channels.next()?)?;
+    Some((r, g, b))
+}
+
+/// Mirror of [`Theme`] with every field optional, used by [`Theme::load_from_dir`] so a theme
+/// file can set just the colors it wants to override. Any field left unset is filled in from
+/// [`Theme::default`] by [`Self::into_theme`].
+#[derive(Debug, Default, Deserialize)]
+struct PartialTheme {
+    name: Option<String>,
+    background: Option<ColorSpec>,
+    foreground: Option<ColorSpec>,
+    border: Option<ColorSpec>,
+    border_focused: Option<ColorSpec>,
+    title: Option<ColorSpec>,
+    status_bar: Option<ColorSpec>,
+    status_bar_mode: Option<ThemeAttribute>,
+    text_normal: Option<ColorSpec>,
+    text_dim: Option<ColorSpec>,
+    text_bold: Option<ThemeAttribute>,
+    text_highlight: Option<ColorSpec>,
+    cursor: Option<ColorSpec>,
+    selection: Option<ColorSpec>,
+    visual_selection: Option<ColorSpec>,
+    active_field: Option<ColorSpec>,
+    insert_mode: Option<ColorSpec>,
+    success: Option<ColorSpec>,
+    warning: Option<ColorSpec>,
+    error: Option<ColorSpec>,
+    info: Option<ColorSpec>,
+    email_from: Option<ColorSpec>,
+    email_subject: Option<ColorSpec>,
+    email_date: Option<ColorSpec>,
+    email_unread: Option<ThemeAttribute>,
+    email_row_even: Option<ColorSpec>,
+    email_row_odd: Option<ColorSpec>,
+    email_attachment_flag: Option<ColorSpec>,
+    compose_field_label: Option<ColorSpec>,
+    compose_field_value: Option<ColorSpec>,
+    compose_field_empty: Option<ColorSpec>,
+    markdown_heading: Option<ColorSpec>,
+    markdown_emphasis: Option<ThemeAttribute>,
+    markdown_link: Option<ColorSpec>,
+    markdown_code: Option<ColorSpec>,
+}
+
+impl PartialTheme {
+    fn into_theme(self) -> Theme {
+        let default = Theme::default();
+        Theme {
+            name: self.name.unwrap_or(default.name),
+            background: self.background.unwrap_or(default.background),
+            foreground: self.foreground.unwrap_or(default.foreground),
+            border: self.border.unwrap_or(default.border),
+            border_focused: self.border_focused.unwrap_or(default.border_focused),
+            title: self.title.unwrap_or(default.title),
+            status_bar: self.status_bar.unwrap_or(default.status_bar),
+            status_bar_mode: self.status_bar_mode.unwrap_or(default.status_bar_mode),
+            text_normal: self.text_normal.unwrap_or(default.text_normal),
+            text_dim: self.text_dim.unwrap_or(default.text_dim),
+            text_bold: self.text_bold.unwrap_or(default.text_bold),
+            text_highlight: self.text_highlight.unwrap_or(default.text_highlight),
+            cursor: self.cursor.unwrap_or(default.cursor),
+            selection: self.selection.unwrap_or(default.selection),
+            visual_selection: self.visual_selection.unwrap_or(default.visual_selection),
+            active_field: self.active_field.unwrap_or(default.active_field),
+            insert_mode: self.insert_mode.unwrap_or(default.insert_mode),
+            success: self.success.unwrap_or(default.success),
+            warning: self.warning.unwrap_or(default.warning),
+            error: self.error.unwrap_or(default.error),
+            info: self.info.unwrap_or(default.info),
+            email_from: self.email_from.unwrap_or(default.email_from),
+            email_subject: self.email_subject.unwrap_or(default.email_subject),
+            email_date: self.email_date.unwrap_or(default.email_date),
+            email_unread: self.email_unread.unwrap_or(default.email_unread),
+            email_row_even: self.email_row_even.unwrap_or(default.email_row_even),
+            email_row_odd: self.email_row_odd.unwrap_or(default.email_row_odd),
+            email_attachment_flag: self.email_attachment_flag.unwrap_or(default.email_attachment_flag),
+            compose_field_label: self.compose_field_label.unwrap_or(default.compose_field_label),
+            compose_field_value: self.compose_field_value.unwrap_or(default.compose_field_value),
+            compose_field_empty: self.compose_field_empty.unwrap_or(default.compose_field_empty),
+            markdown_heading: self.markdown_heading.unwrap_or(default.markdown_heading),
+            markdown_emphasis: self.markdown_emphasis.unwrap_or(default.markdown_emphasis),
+            markdown_link: self.markdown_link.unwrap_or(default.markdown_link),
+            markdown_code: self.markdown_code.unwrap_or(default.markdown_code),
+        }
+        .resolve()
     }
 }
 
@@ -429,7 +1390,238 @@ mod tests {
         let indexed = ColorSpec::Indexed(42);
         assert_eq!(indexed.to_color(), Color::Indexed(42));
     }
+
+    #[test]
+    fn test_color_spec_parse_accepts_named_hex_and_indexed() {
+        assert_eq!(ColorSpec::parse("red").unwrap(), ColorSpec::Named("red".to_string()));
+        assert_eq!(ColorSpec::parse("#ff8000").unwrap(), ColorSpec::Rgb(255, 128, 0));
+        assert_eq!(ColorSpec::parse("200").unwrap(), ColorSpec::Indexed(200));
+    }
+
+    #[test]
+    fn test_color_spec_parse_rejects_unknown_names_and_malformed_hex() {
+        assert!(ColorSpec::parse("not-a-color").is_err());
+        assert!(ColorSpec::parse("#zzzzzz").is_err());
+        assert!(ColorSpec::parse("#fff").is_err());
+    }
     
+    #[test]
+    fn test_theme_attribute_deserializes_fg_only_table_as_no_attrs() {
+        let attr: ThemeAttribute = toml::from_str("fg = \"red\"").unwrap();
+        assert_eq!(attr, ThemeAttribute { fg: ColorSpec::Named("red".to_string()), bg: None, attrs: Attr::NONE });
+    }
+
+    #[test]
+    fn test_theme_attribute_deserializes_full_table_with_attrs() {
+        let attr: ThemeAttribute =
+            toml::from_str("fg = \"red\"\nbg = \"black\"\nattrs = [\"bold\", \"italic\"]").unwrap();
+        assert_eq!(attr.fg, ColorSpec::Named("red".to_string()));
+        assert_eq!(attr.bg, Some(ColorSpec::Named("black".to_string())));
+        assert!(attr.attrs.contains(Attr::BOLD));
+        assert!(attr.attrs.contains(Attr::ITALIC));
+        assert!(!attr.attrs.contains(Attr::UNDERLINE));
+    }
+
+    #[test]
+    fn test_theme_attribute_to_style_folds_attrs_into_modifier() {
+        let attr = ThemeAttribute { fg: ColorSpec::Rgb(1, 2, 3), bg: Some(ColorSpec::Rgb(4, 5, 6)), attrs: Attr::BOLD | Attr::ITALIC };
+        let style = attr.to_style();
+        assert_eq!(style.fg, Some(Color::Rgb(1, 2, 3)));
+        assert_eq!(style.bg, Some(Color::Rgb(4, 5, 6)));
+        assert!(style.add_modifier.contains(Modifier::BOLD));
+        assert!(style.add_modifier.contains(Modifier::ITALIC));
+        assert!(!style.add_modifier.contains(Modifier::UNDERLINED));
+    }
+
+    #[test]
+    fn test_resolve_follows_link_to_terminal_color() {
+        let mut theme = Theme::default();
+        theme.title = ColorSpec::Rgb(10, 20, 30);
+        theme.active_field = ColorSpec::Link { link: "title".to_string() };
+
+        let resolved = theme.resolve();
+        assert_eq!(resolved.active_field, ColorSpec::Rgb(10, 20, 30));
+    }
+
+    #[test]
+    fn test_resolve_follows_chain_through_multiple_links() {
+        let mut theme = Theme::default();
+        theme.title = ColorSpec::Rgb(1, 2, 3);
+        theme.border = ColorSpec::Link { link: "title".to_string() };
+        theme.active_field = ColorSpec::Link { link: "border".to_string() };
+
+        let resolved = theme.resolve();
+        assert_eq!(resolved.active_field, ColorSpec::Rgb(1, 2, 3));
+    }
+
+    #[test]
+    fn test_resolve_cycle_falls_back_to_reset() {
+        let mut theme = Theme::default();
+        theme.title = ColorSpec::Link { link: "border".to_string() };
+        theme.border = ColorSpec::Link { link: "title".to_string() };
+
+        let resolved = theme.resolve();
+        assert_eq!(resolved.title, ColorSpec::Named("reset".to_string()));
+        assert_eq!(resolved.border, ColorSpec::Named("reset".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_unknown_key_falls_back_to_reset() {
+        let mut theme = Theme::default();
+        theme.title = ColorSpec::Link { link: "not_a_real_field".to_string() };
+
+        let resolved = theme.resolve();
+        assert_eq!(resolved.title, ColorSpec::Named("reset".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_follows_link_inside_theme_attribute() {
+        let mut theme = Theme::default();
+        theme.title = ColorSpec::Rgb(9, 9, 9);
+        theme.text_bold = ThemeAttribute {
+            fg: ColorSpec::Link { link: "title".to_string() },
+            bg: Some(ColorSpec::Link { link: "title".to_string() }),
+            attrs: Attr::BOLD,
+        };
+
+        let resolved = theme.resolve();
+        assert_eq!(resolved.text_bold.fg, ColorSpec::Rgb(9, 9, 9));
+        assert_eq!(resolved.text_bold.bg, Some(ColorSpec::Rgb(9, 9, 9)));
+        assert!(resolved.text_bold.attrs.contains(Attr::BOLD));
+    }
+
+    #[test]
+    fn test_from_base16_maps_conventional_slots() {
+        let mut palette = [(0u8, 0u8, 0u8); 16];
+        for (i, slot) in palette.iter_mut().enumerate() {
+            *slot = (i as u8, i as u8, i as u8);
+        }
+
+        let theme = Theme::from_base16("my-scheme", palette);
+        assert_eq!(theme.name, "my-scheme");
+        assert_eq!(theme.background, ColorSpec::Rgb(0, 0, 0));
+        assert_eq!(theme.foreground, ColorSpec::Rgb(5, 5, 5));
+        assert_eq!(theme.border, ColorSpec::Rgb(3, 3, 3));
+        assert_eq!(theme.border_focused, ColorSpec::Rgb(13, 13, 13));
+        assert_eq!(theme.title, ColorSpec::Rgb(13, 13, 13));
+        assert_eq!(theme.active_field, ColorSpec::Rgb(13, 13, 13));
+        assert_eq!(theme.error, ColorSpec::Rgb(8, 8, 8));
+        assert_eq!(theme.warning, ColorSpec::Rgb(10, 10, 10));
+        assert_eq!(theme.success, ColorSpec::Rgb(11, 11, 11));
+        assert_eq!(theme.info, ColorSpec::Rgb(12, 12, 12));
+        assert_eq!(theme.markdown_heading, ColorSpec::Rgb(10, 10, 10));
+        assert_eq!(theme.markdown_emphasis.fg, ColorSpec::Rgb(9, 9, 9));
+        assert_eq!(theme.markdown_link, ColorSpec::Rgb(13, 13, 13));
+        assert_eq!(theme.markdown_code, ColorSpec::Rgb(11, 11, 11));
+        assert_eq!(theme.selection, ColorSpec::Rgb(2, 2, 2));
+        assert_eq!(theme.text_dim, ColorSpec::Rgb(3, 3, 3));
+        assert_eq!(theme.text_highlight, ColorSpec::Rgb(10, 10, 10));
+        // Fields base16 doesn't cover keep Theme::default()'s attrs (e.g. bold stays bold).
+        assert!(theme.text_bold.attrs.contains(Attr::BOLD));
+    }
+
+    #[test]
+    fn test_from_base16_yaml_parses_scheme_file() {
+        let yaml = r#"
+scheme: "Example Scheme"
+author: "Someone"
+base00: "181818"
+base01: "282828"
+base02: "383838"
+base03: "585858"
+base04: "b8b8b8"
+base05: "d8d8d8"
+base06: "e8e8e8"
+base07: "f8f8f8"
+base08: "ab4642"
+base09: "dc9656"
+base0A: "f7ca88"
+base0B: "a1b56c"
+base0C: "86c1b9"
+base0D: "7cafc2"
+base0E: "ba8baf"
+base0F: "a16946"
+"#;
+        let theme = Theme::from_base16_yaml(yaml).unwrap();
+        assert_eq!(theme.name, "Example Scheme");
+        assert_eq!(theme.background, ColorSpec::Rgb(0x18, 0x18, 0x18));
+        assert_eq!(theme.foreground, ColorSpec::Rgb(0xd8, 0xd8, 0xd8));
+        assert_eq!(theme.error, ColorSpec::Rgb(0xab, 0x46, 0x42));
+        assert_eq!(theme.title, ColorSpec::Rgb(0x7c, 0xaf, 0xc2));
+    }
+
+    #[test]
+    fn test_from_base16_yaml_rejects_missing_slots() {
+        let err = Theme::from_base16_yaml("scheme: \"Incomplete\"\nbase00: \"181818\"\n").unwrap_err();
+        assert!(err.contains("base01"));
+    }
+
+    #[test]
+    fn test_generate_assigns_name_and_base_colors() {
+        let theme = Theme::generate("Generated", (20, 20, 20), (230, 230, 230), (80, 140, 220));
+        assert_eq!(theme.name, "Generated");
+        assert_eq!(theme.background, ColorSpec::Rgb(20, 20, 20));
+        assert_eq!(theme.border_focused, ColorSpec::Rgb(80, 140, 220));
+        assert_eq!(theme.title, theme.border_focused);
+        assert_eq!(theme.active_field, theme.border_focused);
+    }
+
+    #[test]
+    fn test_generate_derives_status_colors_from_rotated_accent_hue() {
+        let theme = Theme::generate("Generated", (20, 20, 20), (230, 230, 230), (80, 140, 220));
+        let ColorSpec::Rgb(r, g, b) = theme.error else { panic!("expected Rgb") };
+        let (h, _, _) = color_math::rgb_to_hsl((r, g, b));
+        assert!(h < 5.0 || h > 355.0, "expected error hue near 0 degrees, got {h}");
+    }
+
+    #[test]
+    fn test_generate_pushes_low_contrast_foreground_to_meet_wcag_aa() {
+        // Foreground barely distinguishable from background before enforcement.
+        let theme = Theme::generate("Low Contrast", (20, 20, 20), (30, 30, 30), (80, 140, 220));
+        let ColorSpec::Rgb(bg_r, bg_g, bg_b) = theme.background else { panic!("expected Rgb") };
+        let ColorSpec::Rgb(fg_r, fg_g, fg_b) = theme.foreground else { panic!("expected Rgb") };
+        let ratio = color_math::contrast_ratio((fg_r, fg_g, fg_b), (bg_r, bg_g, bg_b));
+        assert!(ratio >= 4.5, "expected contrast ratio >= 4.5, got {ratio}");
+    }
+
+    #[test]
+    fn test_color_math_mix_interpolates_linearly_in_srgb() {
+        assert_eq!(color_math::mix((0, 0, 0), (200, 200, 200), 0.5), (100, 100, 100));
+    }
+
+    #[test]
+    fn test_color_math_shift_lightness_lightens_and_darkens() {
+        let lighter = color_math::shift_lightness((50, 50, 50), 0.2);
+        let darker = color_math::shift_lightness((50, 50, 50), -0.2);
+        assert!(lighter.0 > 50);
+        assert!(darker.0 < 50);
+    }
+
+    #[test]
+    fn test_theme_variant_resolve_picks_explicit_appearance() {
+        let variant = ThemeVariant::new(Theme::solarized_light(), Theme::solarized_dark());
+        assert_eq!(variant.resolve(Appearance::Light).name, "Solarized Light");
+        assert_eq!(variant.resolve(Appearance::Dark).name, "Solarized Dark");
+    }
+
+    #[test]
+    fn test_by_name_solarized_dark_and_light_pick_exact_variant() {
+        assert_eq!(Theme::by_name("solarized-dark").unwrap().name, "Solarized Dark");
+        assert_eq!(Theme::by_name("solarized-light").unwrap().name, "Solarized Light");
+    }
+
+    #[test]
+    fn test_parse_osc11_reply_reads_x11_rgb_syntax() {
+        let reply = b"\x1b]11;rgb:2323/2323/2323\x07";
+        let (r, g, b) = parse_osc11_reply(reply).unwrap();
+        assert_eq!((r, g, b), (0x23, 0x23, 0x23));
+    }
+
+    #[test]
+    fn test_parse_osc11_reply_rejects_malformed_input() {
+        assert!(parse_osc11_reply(b"not an osc11 reply").is_none());
+    }
+
     #[test]
     fn test_default_theme_is_gruvbox() {
         let default = Theme::default();
@@ -439,9 +1631,53 @@ mod tests {
     #[test]
     fn test_available_themes() {
         let themes = Theme::available_themes();
-        assert!(themes.contains(&"gruvbox-dark"));
-        assert!(themes.contains(&"dracula"));
-        assert!(themes.contains(&"nord"));
+        assert!(themes.iter().any(|t| t == "gruvbox-dark"));
+        assert!(themes.iter().any(|t| t == "dracula"));
+        assert!(themes.iter().any(|t| t == "nord"));
         assert!(themes.len() >= 5);
     }
+
+    #[test]
+    fn test_load_from_dir_applies_defaults_for_missing_fields() {
+        let dir = std::env::temp_dir().join(format!("tume-test-themes-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("partial.toml"),
+            "name = \"Partial\"\nmarkdown_link = \"red\"\n",
+        )
+        .unwrap();
+
+        let themes = Theme::load_from_dir(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(themes.len(), 1);
+        assert_eq!(themes[0].name, "Partial");
+        assert_eq!(themes[0].markdown_link, ColorSpec::Named("red".to_string()));
+        assert_eq!(themes[0].background, Theme::default().background);
+    }
+
+    #[test]
+    fn test_load_from_dir_skips_unparseable_files_and_missing_dirs() {
+        let dir = std::env::temp_dir().join(format!("tume-test-themes-missing-{}", std::process::id()));
+        assert!(Theme::load_from_dir(&dir).is_empty());
+
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("broken.toml"), "not valid toml {{{").unwrap();
+        assert!(Theme::load_from_dir(&dir).is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_save_round_trips_through_load_from_dir() {
+        let dir = std::env::temp_dir().join(format!("tume-test-themes-save-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("gruvbox-export.toml");
+
+        Theme::gruvbox_dark().save(&path).unwrap();
+        let themes = Theme::load_from_dir(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(themes.len(), 1);
+        assert_eq!(themes[0].name, "Gruvbox Dark");
+    }
 }
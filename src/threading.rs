@@ -0,0 +1,227 @@
+/// JWZ conversation threading.
+///
+/// Implements Jamie Zawinski's message-threading algorithm
+/// (<https://www.jwz.org/doc/threading.html>): messages are grouped into containers keyed by
+/// `message_id`, linked parent->child by walking each message's `References` header (falling
+/// back to `In-Reply-To`). An empty root container (referenced but never actually seen) with a
+/// single child is skipped so that child becomes the root; other empty containers stay in the
+/// tree. Every message that ends up in the same tree is assigned the same `thread_id`, derived
+/// from the tree's root container.
+use std::collections::{HashMap, HashSet};
+
+/// The subset of a stored email needed to place it in a thread; `db::DbEmail` maps into this.
+#[derive(Debug, Clone)]
+pub struct ThreadableMessage {
+    pub id: i64,
+    pub message_id: Option<String>,
+    pub in_reply_to: Option<String>,
+    pub references: Option<String>,
+}
+
+/// A node in the threading graph, keyed by `message_id`. `message` is `None` for containers
+/// that only exist because some other message referenced their id but they were never
+/// themselves seen (e.g. the original of a reply chain we don't have).
+struct Container {
+    message: Option<i64>,
+    parent: Option<String>,
+    children: Vec<String>,
+}
+
+impl Container {
+    fn new() -> Self {
+        Self { message: None, parent: None, children: Vec::new() }
+    }
+}
+
+/// The message-ids a message references, oldest first: the `References` header split on
+/// whitespace, or a single-element list from `In-Reply-To` when `References` is absent.
+fn parse_references(msg: &ThreadableMessage) -> Vec<String> {
+    let refs = msg.references.as_deref().unwrap_or("").trim();
+    if !refs.is_empty() {
+        return refs.split_whitespace().map(|s| s.to_string()).collect();
+    }
+
+    match msg.in_reply_to.as_deref().map(str::trim) {
+        Some(irt) if !irt.is_empty() => vec![irt.to_string()],
+        _ => Vec::new(),
+    }
+}
+
+/// Whether linking `child_id` under `parent_id` would close a cycle, i.e. `child_id` is
+/// already an ancestor of `parent_id`.
+fn creates_loop(containers: &HashMap<String, Container>, parent_id: &str, child_id: &str) -> bool {
+    let mut seen = HashSet::new();
+    let mut current = Some(parent_id.to_string());
+    while let Some(id) = current {
+        if id == child_id {
+            return true;
+        }
+        if !seen.insert(id.clone()) {
+            return true; // already-cyclic graph; refuse rather than loop forever
+        }
+        current = containers.get(&id).and_then(|c| c.parent.clone());
+    }
+    false
+}
+
+/// Link `child_id` as a child of `parent_id`, unless that would introduce a loop or the child
+/// is already parented (JWZ keeps the first link found rather than reparenting).
+fn link(containers: &mut HashMap<String, Container>, parent_id: &str, child_id: &str) {
+    if parent_id == child_id || creates_loop(containers, parent_id, child_id) {
+        return;
+    }
+    if containers.get(child_id).and_then(|c| c.parent.as_deref()).is_some() {
+        return;
+    }
+
+    containers.entry(parent_id.to_string()).or_insert_with(Container::new);
+    let parent = containers.get_mut(parent_id).unwrap();
+    if !parent.children.iter().any(|c| c == child_id) {
+        parent.children.push(child_id.to_string());
+    }
+    containers.get_mut(child_id).unwrap().parent = Some(parent_id.to_string());
+}
+
+fn build_containers(messages: &[ThreadableMessage]) -> HashMap<String, Container> {
+    let mut containers: HashMap<String, Container> = HashMap::new();
+
+    for msg in messages {
+        let msg_id = match msg.message_id.as_deref().map(str::trim) {
+            Some(id) if !id.is_empty() => id.to_string(),
+            _ => continue, // nothing to key a container on
+        };
+
+        containers.entry(msg_id.clone()).or_insert_with(Container::new).message = Some(msg.id);
+
+        let references = parse_references(msg);
+        for pair in references.windows(2) {
+            containers.entry(pair[0].clone()).or_insert_with(Container::new);
+            containers.entry(pair[1].clone()).or_insert_with(Container::new);
+            link(&mut containers, &pair[0], &pair[1]);
+        }
+        if let Some(last) = references.last() {
+            containers.entry(last.clone()).or_insert_with(Container::new);
+            link(&mut containers, last, &msg_id);
+        }
+    }
+
+    containers
+}
+
+/// Walk up from an empty root container while it has exactly one child, promoting that child
+/// to root in its place (an empty container with multiple children stays, as the synthetic
+/// root of the whole tree).
+fn effective_root(containers: &HashMap<String, Container>, id: &str) -> String {
+    let mut current = id.to_string();
+    loop {
+        match containers.get(&current) {
+            Some(c) if c.message.is_none() && c.children.len() == 1 => {
+                current = c.children[0].clone();
+            }
+            _ => return current,
+        }
+    }
+}
+
+fn assign_subtree(containers: &HashMap<String, Container>, id: &str, thread_id: &str, out: &mut HashMap<i64, String>) {
+    let Some(container) = containers.get(id) else { return };
+    if let Some(msg_id) = container.message {
+        out.insert(msg_id, thread_id.to_string());
+    }
+    for child in &container.children {
+        assign_subtree(containers, child, thread_id, out);
+    }
+}
+
+/// Thread `messages` and return the `thread_id` each message (by its database row id) should
+/// be assigned. Messages without a `message_id` can't be threaded and are omitted.
+pub fn compute_threads(messages: &[ThreadableMessage]) -> HashMap<i64, String> {
+    let containers = build_containers(messages);
+
+    let roots: Vec<String> = containers
+        .iter()
+        .filter(|(_, c)| c.parent.is_none())
+        .map(|(id, _)| id.clone())
+        .collect();
+
+    let mut assignment = HashMap::new();
+    for root in roots {
+        let thread_id = effective_root(&containers, &root);
+        assign_subtree(&containers, &thread_id, &thread_id.clone(), &mut assignment);
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(id: i64, message_id: &str, in_reply_to: Option<&str>, references: Option<&str>) -> ThreadableMessage {
+        ThreadableMessage {
+            id,
+            message_id: Some(message_id.to_string()),
+            in_reply_to: in_reply_to.map(|s| s.to_string()),
+            references: references.map(|s| s.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_unrelated_messages_get_distinct_threads() {
+        let messages = vec![msg(1, "<a@x>", None, None), msg(2, "<b@x>", None, None)];
+        let threads = compute_threads(&messages);
+        assert_ne!(threads[&1], threads[&2]);
+    }
+
+    #[test]
+    fn test_reply_chain_shares_thread_via_in_reply_to() {
+        let messages = vec![
+            msg(1, "<a@x>", None, None),
+            msg(2, "<b@x>", Some("<a@x>"), None),
+            msg(3, "<c@x>", Some("<b@x>"), None),
+        ];
+        let threads = compute_threads(&messages);
+        assert_eq!(threads[&1], threads[&2]);
+        assert_eq!(threads[&2], threads[&3]);
+    }
+
+    #[test]
+    fn test_references_header_links_whole_chain() {
+        let messages = vec![
+            msg(1, "<a@x>", None, None),
+            msg(2, "<b@x>", None, None),
+            msg(3, "<c@x>", None, Some("<a@x> <b@x>")),
+        ];
+        let threads = compute_threads(&messages);
+        assert_eq!(threads[&1], threads[&2]);
+        assert_eq!(threads[&2], threads[&3]);
+    }
+
+    #[test]
+    fn test_missing_ancestor_creates_placeholder_root() {
+        // "<a@x>" is referenced but never itself ingested; its two replies should still thread
+        // together under a synthetic root.
+        let messages = vec![
+            msg(2, "<b@x>", Some("<a@x>"), None),
+            msg(3, "<c@x>", Some("<a@x>"), None),
+        ];
+        let threads = compute_threads(&messages);
+        assert_eq!(threads[&2], threads[&3]);
+    }
+
+    #[test]
+    fn test_message_without_message_id_is_not_threaded() {
+        let messages = vec![ThreadableMessage { id: 1, message_id: None, in_reply_to: None, references: None }];
+        let threads = compute_threads(&messages);
+        assert!(threads.is_empty());
+    }
+
+    #[test]
+    fn test_cyclic_references_do_not_infinite_loop() {
+        let messages = vec![
+            msg(1, "<a@x>", Some("<b@x>"), None),
+            msg(2, "<b@x>", Some("<a@x>"), None),
+        ];
+        let threads = compute_threads(&messages);
+        assert_eq!(threads.len(), 2);
+    }
+}
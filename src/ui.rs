@@ -3,13 +3,13 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
 };
 use tui_markdown::from_str;
 
-use crate::app::{App, ComposeField, ComposeMode, View, CredentialField, CredentialsMode};
+use crate::app::{App, ComposeField, ComposeMode, ComposeState, ListingStyle, View, CredentialField, CredentialsMode};
 use crate::credentials::StorageBackend;
-use crate::theme::Theme;
+use crate::theme::{Theme, ThemeAttribute};
 
 // Layout constants
 const MIN_WIDTH_FOR_VERTICAL_SPLIT: u16 = 120;
@@ -41,8 +41,14 @@ fn convert_color(core_color: ratatui_core::style::Color) -> Color {
 }
 
 // Helper function to build email metadata display
-fn build_email_metadata<'a>(from: &'a str, subject: &'a str, date: &'a str) -> Vec<Line<'a>> {
-    vec![
+fn build_email_metadata<'a>(
+    from: &'a str,
+    subject: &'a str,
+    date: &'a str,
+    pgp_status: Option<&'a str>,
+    list_archive_url: Option<&'a str>,
+) -> Vec<Line<'a>> {
+    let mut lines = vec![
         Line::from(vec![
             Span::styled("From: ", Style::default().add_modifier(Modifier::BOLD)),
             Span::raw(from),
@@ -55,7 +61,20 @@ fn build_email_metadata<'a>(from: &'a str, subject: &'a str, date: &'a str) -> V
             Span::styled("Date: ", Style::default().add_modifier(Modifier::BOLD)),
             Span::raw(date),
         ]),
-    ]
+    ];
+    if let Some(status) = pgp_status {
+        lines.push(Line::from(vec![
+            Span::styled("PGP: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(status),
+        ]));
+    }
+    if let Some(url) = list_archive_url {
+        lines.push(Line::from(vec![
+            Span::styled("List-Archive: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(url),
+        ]));
+    }
+    lines
 }
 
 pub fn draw(f: &mut Frame, app: &App) {
@@ -77,6 +96,18 @@ pub fn draw(f: &mut Frame, app: &App) {
         View::CredentialsSetup => render_credentials_setup(f, chunks[1], app),
         View::CredentialsUnlock => render_credentials_unlock(f, chunks[1], app),
         View::CredentialsManagement => render_credentials_management(f, chunks[1], app),
+        View::CommandLine => {
+            // The command line is an overlay over whichever view it was opened from
+            match app.command_line_state.as_ref().map(|s| s.return_view) {
+                Some(View::EmailDetail) => render_email_detail(f, chunks[1], app),
+                _ => render_inbox(f, chunks[1], app),
+            }
+        }
+        View::Contacts => render_contacts(f, chunks[1], app),
+        View::NotificationHistory => render_notification_history(f, chunks[1], app),
+        View::FolderList => render_folder_list(f, chunks[1], app),
+        View::ThreadList => render_thread_list(f, chunks[1], app),
+        View::AccountStatus => render_account_status(f, chunks[1], app),
     }
 
     render_footer(f, chunks[2], app);
@@ -144,39 +175,50 @@ fn render_inbox(f: &mut Frame, area: Rect, app: &App) {
 
 fn render_inbox_list(f: &mut Frame, area: Rect, app: &App) {
     let theme = &app.theme;
-    
+    let compact = app.listing_style == ListingStyle::Compact;
+
     let items: Vec<ListItem> = app
         .emails
         .iter()
         .enumerate()
         .map(|(i, email)| {
-            // Determine style based on visual selection and cursor position
+            // Base row style: alternating background so a long list is easier to scan, plus
+            // bold + `email_unread` for an unseen message (dimmer normal text otherwise).
+            let row_bg = if i % 2 == 0 { theme.email_row_even.to_color() } else { theme.email_row_odd.to_color() };
+            let base_style = if email.is_unseen() {
+                styled_with(Style::default().bg(row_bg), &theme.email_unread)
+            } else {
+                Style::default().bg(row_bg).fg(theme.text_normal.to_color())
+            };
+
+            // Selection/cursor styles override the background and foreground, but still compose
+            // on top of `base_style` (e.g. an unseen row under the cursor stays bold) rather than
+            // replacing it wholesale.
             let style = if i == app.selected_index && app.is_email_selected(i) {
                 // Cursor position within visual selection - use a distinct color
-                Style::default()
-                    .bg(theme.cursor.to_color())
-                    .fg(theme.text_bold.to_color())
-                    .add_modifier(Modifier::BOLD)
+                styled_with(base_style.bg(theme.cursor.to_color()), &theme.text_bold)
             } else if app.is_email_selected(i) {
                 // In visual mode and selected (but not cursor)
-                Style::default()
+                base_style
                     .bg(theme.visual_selection.to_color())
                     .fg(theme.text_normal.to_color())
                     .add_modifier(Modifier::BOLD)
             } else if i == app.selected_index {
                 // Cursor position (not selected in visual mode)
-                Style::default()
-                    .bg(theme.selection.to_color())
-                    .fg(theme.text_bold.to_color())
-                    .add_modifier(Modifier::BOLD)
+                styled_with(base_style.bg(theme.selection.to_color()), &theme.text_bold)
             } else {
-                Style::default().fg(theme.text_normal.to_color())
+                base_style
             };
 
-            // Calculate column widths for proper alignment
-            // From: 30 chars, Subject: remaining space - 20 for date, Date: 20 chars
-            let from_width = 30;
-            let date_width = 20;
+            // One-char flags ahead of the list marker: `*` for unseen, `@` for attachments.
+            let unseen_flag = if email.is_unseen() { "*" } else { " " };
+            let attachment_flag = !email.attachments.is_empty();
+
+            // Calculate column widths for proper alignment. Compact mode drops the date column
+            // and narrows the from column, so more rows' worth of subjects fit on screen.
+            let from_width = if compact { 18 } else { 30 };
+            let date_width = if compact { 0 } else { 20 };
+            let list_marker = if email.is_list_mail() { "L " } else { "  " };
 
             // Helper function to safely truncate strings at character boundaries
             let truncate_str = |s: &str, max_len: usize| -> String {
@@ -198,17 +240,10 @@ fn render_inbox_list(f: &mut Frame, area: Rect, app: &App) {
                 format!("{:<width$}", &email.from, width = from_width)
             };
 
-            // Truncate date field if too long
-            let date_display = if email.date.len() > date_width {
-                truncate_str(&email.date, date_width)
-            } else {
-                format!("{:<width$}", &email.date, width = date_width)
-            };
-
             // Calculate subject width (remaining space)
             let available_width = area.width.saturating_sub(4) as usize; // subtract borders
             let subject_width = available_width
-                .saturating_sub(from_width + date_width + 4) // subtract column separators
+                .saturating_sub(from_width + date_width + 8) // subtract column separators + flags + list marker
                 .max(10); // ensure minimum readable width
 
             let subject_display = if email.subject.len() > subject_width {
@@ -217,10 +252,33 @@ fn render_inbox_list(f: &mut Frame, area: Rect, app: &App) {
                 format!("{:<width$}", &email.subject, width = subject_width)
             };
 
-            let content = Line::from(format!(
-                "{}  {}  {}",
-                from_display, subject_display, date_display
-            ));
+            let rest = if compact {
+                format!(" {}  {}{}", from_display, list_marker, subject_display)
+            } else {
+                // Truncate date field if too long
+                let date_display = if email.date.len() > date_width {
+                    truncate_str(&email.date, date_width)
+                } else {
+                    format!("{:<width$}", &email.date, width = date_width)
+                };
+                format!(
+                    " {}  {}{}  {}",
+                    from_display, list_marker, subject_display, date_display
+                )
+            };
+
+            let content = Line::from(vec![
+                Span::styled(unseen_flag, style),
+                Span::styled(
+                    if attachment_flag { "@" } else { " " },
+                    if attachment_flag {
+                        style.fg(theme.email_attachment_flag.to_color())
+                    } else {
+                        style
+                    },
+                ),
+                Span::styled(rest, style),
+            ]);
 
             ListItem::new(content).style(style)
         })
@@ -228,6 +286,8 @@ fn render_inbox_list(f: &mut Frame, area: Rect, app: &App) {
 
     let title = if app.visual_mode {
         format!("Inbox - VISUAL LINE ({} selected)", app.visual_selections.len())
+    } else if compact {
+        "Inbox - Compact (Shift-L to cycle listing style)".to_string()
     } else {
         "Inbox (j/k to navigate, Enter to read, V for visual mode, q to quit)".to_string()
     };
@@ -252,7 +312,7 @@ fn render_inbox_preview(f: &mut Frame, area: Rect, app: &App) {
             .split(area);
 
         // Email metadata
-        let metadata = build_email_metadata(&email.from, &email.subject, &email.date);
+        let metadata = build_email_metadata(&email.from, &email.subject, &email.date, None, None);
 
         let metadata_widget =
             Paragraph::new(metadata).block(
@@ -262,8 +322,14 @@ fn render_inbox_preview(f: &mut Frame, area: Rect, app: &App) {
                     .title("Preview"));
         f.render_widget(metadata_widget, chunks[0]);
 
-        // Email body
-        let body = Paragraph::new(email.body.as_str())
+        // Email body: styled HTML rendering when the message carried a `text/html` alternative
+        // (same conversion `render_email_detail` uses), plain text otherwise. There's no room
+        // for a raw/rendered toggle in this compact pane, so it always renders.
+        let body_text = match &email.body_html {
+            Some(html) => Text::from(html_to_styled_lines(html, theme)),
+            None => Text::from(email.body.as_str()),
+        };
+        let body = Paragraph::new(body_text)
             .block(Block::default()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(theme.border.to_color()))
@@ -281,17 +347,226 @@ fn render_inbox_preview(f: &mut Frame, area: Rect, app: &App) {
     }
 }
 
+/// Converts an HTML email body into styled `Line`s for `render_email_detail`'s rendered HTML
+/// view: headings bold, links underlined and footnoted with their URL, list items bulleted, and
+/// block quotes indented and colored. A handful of tag names handled by hand, not a real HTML
+/// parser - inbound mail HTML is rarely more than headings/paragraphs/links/lists/quotes, and
+/// this parallels how [`crate::mime::html_to_text`] only lightly strips tags rather than fully
+/// parsing them. Reuses the compose Markdown preview's colors (`Theme::markdown_*`) and
+/// `Span`-rebuilding approach rather than inventing a parallel palette.
+fn html_to_styled_lines(html: &str, theme: &Theme) -> Vec<Line<'static>> {
+    let heading_color = theme.markdown_heading.to_color();
+    let link_color = theme.markdown_link.to_color();
+    let quote_color = theme.text_dim.to_color();
+
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut text_run = String::new();
+
+    let mut heading_depth = 0usize;
+    let mut bold_depth = 0usize;
+    let mut italic_depth = 0usize;
+    let mut quote_depth = 0usize;
+    let mut list_depth = 0usize;
+    let mut link_href: Option<String> = None;
+
+    macro_rules! flush_text {
+        () => {
+            if !text_run.is_empty() {
+                let decoded = crate::mime::decode_html_entities(&text_run);
+                text_run.clear();
+                if !decoded.is_empty() {
+                    let mut style = Style::default();
+                    if heading_depth > 0 {
+                        style = style.fg(heading_color).add_modifier(Modifier::BOLD);
+                    }
+                    if link_href.is_some() {
+                        style = style.fg(link_color).add_modifier(Modifier::UNDERLINED);
+                    }
+                    if quote_depth > 0 {
+                        style = style.fg(quote_color);
+                    }
+                    if bold_depth > 0 {
+                        style = style.add_modifier(Modifier::BOLD);
+                    }
+                    if italic_depth > 0 {
+                        style = style.add_modifier(Modifier::ITALIC);
+                    }
+                    spans.push(Span::styled(decoded, style));
+                }
+            }
+        };
+    }
+    macro_rules! newline {
+        () => {
+            flush_text!();
+            if !spans.is_empty() {
+                lines.push(Line::from(std::mem::take(&mut spans)));
+            }
+        };
+    }
+
+    let mut rest = html;
+    while let Some(lt) = rest.find('<') {
+        text_run.push_str(&rest[..lt]);
+        rest = &rest[lt + 1..];
+        let Some(gt) = rest.find('>') else {
+            // Unterminated tag - treat the rest as plain text rather than drop it.
+            text_run.push('<');
+            text_run.push_str(rest);
+            rest = "";
+            break;
+        };
+        let tag = rest[..gt].trim();
+        rest = &rest[gt + 1..];
+
+        let closing = tag.starts_with('/');
+        let body = tag.trim_start_matches('/').trim_end_matches('/').trim();
+        let name_end = body.find(|c: char| c.is_whitespace()).unwrap_or(body.len());
+        let name = body[..name_end].to_ascii_lowercase();
+
+        match name.as_str() {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                newline!();
+                heading_depth = if closing { heading_depth.saturating_sub(1) } else { heading_depth + 1 };
+            }
+            "b" | "strong" => {
+                flush_text!();
+                bold_depth = if closing { bold_depth.saturating_sub(1) } else { bold_depth + 1 };
+            }
+            "i" | "em" => {
+                flush_text!();
+                italic_depth = if closing { italic_depth.saturating_sub(1) } else { italic_depth + 1 };
+            }
+            "a" if closing => {
+                flush_text!();
+                if let Some(href) = link_href.take() {
+                    spans.push(Span::styled(format!(" [{href}]"), Style::default().fg(link_color)));
+                }
+            }
+            "a" => {
+                flush_text!();
+                link_href = extract_href_attr(body);
+            }
+            "blockquote" => {
+                newline!();
+                if closing {
+                    quote_depth = quote_depth.saturating_sub(1);
+                } else {
+                    quote_depth += 1;
+                    text_run.push_str(&"  ".repeat(quote_depth));
+                }
+            }
+            "ul" | "ol" => {
+                newline!();
+                list_depth = if closing { list_depth.saturating_sub(1) } else { list_depth + 1 };
+            }
+            "li" if !closing => {
+                newline!();
+                text_run.push_str(&"  ".repeat(list_depth.saturating_sub(1).min(1)));
+                text_run.push_str("\u{2022} ");
+            }
+            "p" | "div" | "br" | "tr" => { newline!(); }
+            _ => {}
+        }
+    }
+    text_run.push_str(rest);
+    newline!();
+
+    lines
+}
+
+/// Pulls the `href="..."` (or `href='...'`/unquoted) attribute value out of an opening `<a ...>`
+/// tag's body (everything between `<` and `>`, tag name included).
+fn extract_href_attr(tag_body: &str) -> Option<String> {
+    let lower = tag_body.to_ascii_lowercase();
+    let after_href = &tag_body[lower.find("href")? + "href".len()..];
+    let after_eq = after_href.trim_start().strip_prefix('=')?.trim_start();
+    match after_eq.chars().next()? {
+        quote @ ('"' | '\'') => {
+            let rest = &after_eq[1..];
+            let end = rest.find(quote)?;
+            Some(rest[..end].to_string())
+        }
+        _ => {
+            let end = after_eq.find(|c: char| c.is_whitespace()).unwrap_or(after_eq.len());
+            Some(after_eq[..end].to_string())
+        }
+    }
+}
+
+/// Layers a [`ThemeAttribute`]'s foreground, background, and attribute modifiers onto `style`
+/// without disturbing whatever else `style` already carries (e.g. a selection/row background set
+/// before the themed foreground is applied on top).
+fn styled_with(style: Style, attr: &ThemeAttribute) -> Style {
+    let mut style = style.fg(attr.fg.to_color()).add_modifier(attr.attrs.to_modifier());
+    if let Some(bg) = &attr.bg {
+        style = style.bg(bg.to_color());
+    }
+    style
+}
+
+/// Appends a numbered link index below `lines`, styled like the footnoted URLs in
+/// [`html_to_styled_lines`], plus a follow-link-mode prompt showing the digits typed so far.
+/// Shared by the compose Markdown preview and the message detail view, the two places
+/// `crate::links::find_links` numbers links for the `o`/`follow_link` binding.
+fn append_link_index(
+    mut lines: Vec<Line<'static>>,
+    links: &[crate::links::DetectedLink],
+    theme: &Theme,
+    follow_mode: bool,
+    digits: &str,
+) -> Vec<Line<'static>> {
+    if links.is_empty() {
+        return lines;
+    }
+    let link_color = theme.markdown_link.to_color();
+    lines.push(Line::from(""));
+    if follow_mode {
+        lines.push(Line::styled(
+            format!("Follow link: type a number, Enter to open ({digits})"),
+            styled_with(Style::default(), &theme.text_bold),
+        ));
+    }
+    for link in links {
+        let target = match &link.target {
+            crate::links::LinkTarget::Url(u) => u.clone(),
+            crate::links::LinkTarget::Email(e) => format!("mailto:{e}"),
+        };
+        lines.push(Line::styled(
+            format!("[{}] {}", link.index, target),
+            Style::default().fg(link_color).add_modifier(Modifier::UNDERLINED),
+        ));
+    }
+    lines
+}
+
 fn render_email_detail(f: &mut Frame, area: Rect, app: &App) {
     let theme = &app.theme;
     
     if let Some(email) = app.get_selected_email() {
+        let has_attachments = !email.attachments.is_empty();
+        let list_archive_url = email.list_archive_url();
+        let metadata_height = 5
+            + email.pgp_status.is_some() as u16
+            + list_archive_url.is_some() as u16;
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Length(5), Constraint::Min(0)])
+            .constraints([
+                Constraint::Length(metadata_height),
+                Constraint::Min(0),
+                Constraint::Length(if has_attachments { email.attachments.len() as u16 + 2 } else { 0 }),
+            ])
             .split(area);
 
         // Email metadata
-        let metadata = build_email_metadata(&email.from, &email.subject, &email.date);
+        let metadata = build_email_metadata(
+            &email.from,
+            &email.subject,
+            &email.date,
+            email.pgp_status.as_deref(),
+            list_archive_url.as_deref(),
+        );
 
         let metadata_widget = Paragraph::new(metadata).block(
             Block::default()
@@ -301,19 +576,138 @@ fn render_email_detail(f: &mut Frame, area: Rect, app: &App) {
         );
         f.render_widget(metadata_widget, chunks[0]);
 
-        // Email body
-        let body = Paragraph::new(email.body.as_str())
+        // Email body: text/plain preferentially, toggled to a styled rendering of the
+        // `text/html` alternative via `t` (see `App::toggle_html_view`), with `p` further
+        // toggling that HTML view between the styled rendering and the raw source (see
+        // `App::toggle_html_source`) - the same raw/rendered split `show_preview` gives the
+        // compose body's Markdown.
+        let (mut body_lines, title): (Vec<Line<'static>>, &str) = if app.show_html_view {
+            match &email.body_html {
+                Some(html) if app.show_html_source => (
+                    Text::from(html.as_str()).lines,
+                    "Message - HTML source (p: rendered, t to toggle, h to go back)",
+                ),
+                Some(html) => (
+                    html_to_styled_lines(html, theme),
+                    "Message - HTML view (p: raw source, t to toggle, h to go back, o: Follow link)",
+                ),
+                None => (Text::from(email.body.as_str()).lines, "Message (h to go back)"),
+            }
+        } else {
+            (
+                Text::from(email.body.as_str()).lines,
+                if email.body_html.is_some() {
+                    "Message (t: HTML view, h to go back, o: Follow link)"
+                } else {
+                    "Message (h to go back, o: Follow link)"
+                },
+            )
+        };
+
+        if let Some(source) = app.detail_link_source() {
+            let links = crate::links::find_links(&source);
+            body_lines = append_link_index(
+                body_lines,
+                &links,
+                theme,
+                app.detail_link_follow_mode,
+                &app.detail_link_follow_digits,
+            );
+        }
+        let body_text = Text::from(body_lines);
+
+        let body = Paragraph::new(body_text)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(theme.border.to_color()))
-                    .title("Message (h to go back)"),
+                    .title(title),
             )
             .wrap(Wrap { trim: false });
         f.render_widget(body, chunks[1]);
+
+        if has_attachments {
+            let lines: Vec<String> = email
+                .attachments
+                .iter()
+                .map(|a| format!("{} ({}, {} bytes)", a.filename, a.content_type, a.size))
+                .collect();
+            let attachments_widget = Paragraph::new(lines.join("\n")).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.border.to_color()))
+                    .title("Attachments (s to save first)"),
+            );
+            f.render_widget(attachments_widget, chunks[2]);
+        }
     }
 }
 
+/// Per-account dashboard opened with `s` from the inbox: message counts for the currently
+/// loaded folder, the active credentials storage backend, the current theme, and the background
+/// watcher's last contact with the server - an at-a-glance complement to the account name
+/// `render_header` already shows.
+fn render_account_status(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border.to_color()))
+        .title("Account Status (Esc/q to return)");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let account = app
+        .current_account_id
+        .and_then(|id| app.accounts.iter().find(|a| a.id == id));
+
+    let total = app.emails.len();
+    let unseen = app.emails.iter().filter(|e| e.is_unseen()).count();
+    let archived = app.emails.iter().filter(|e| e.is_archived()).count();
+
+    let backend = app.credentials_manager.as_ref().map(|m| m.backend());
+    let last_sync = app.last_sync_at.lock().unwrap().map(format_notification_timestamp);
+    let connection = if app.mail_watch_stop.is_some() {
+        "Connected (watching for new mail)"
+    } else {
+        "Not connected"
+    };
+
+    let label_style = styled_with(Style::default(), &theme.text_bold);
+    let value_style = Style::default().fg(theme.text_normal.to_color());
+    let line = |label: &'static str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{label}: "), label_style),
+            Span::styled(value, value_style),
+        ])
+    };
+
+    let lines = vec![
+        line(
+            "Account",
+            account
+                .map(|a| format!("{} <{}>", a.name, a.email))
+                .unwrap_or_else(|| "No account configured".to_string()),
+        ),
+        Line::from(""),
+        line("Total (current folder)", total.to_string()),
+        line("Unseen", unseen.to_string()),
+        line("Archived", archived.to_string()),
+        Line::from(""),
+        line(
+            "Storage backend",
+            backend.map(|b| b.as_str().to_string()).unwrap_or_else(|| "Unknown".to_string()),
+        ),
+        line("Theme", theme.name.clone()),
+        Line::from(""),
+        line("Last sync", last_sync.unwrap_or_else(|| "Never this session".to_string())),
+        line("Connection", connection.to_string()),
+    ];
+
+    let para = Paragraph::new(lines).wrap(Wrap { trim: false });
+    f.render_widget(para, inner);
+}
+
 fn render_footer(f: &mut Frame, area: Rect, app: &App) {
     let theme = &app.theme;
     
@@ -322,19 +716,23 @@ fn render_footer(f: &mut Frame, area: Rect, app: &App) {
             if app.visual_mode {
                 "j/k: Extend selection | d: Delete selected | a: Archive selected | Esc: Exit visual mode"
             } else {
-                "j/k: Navigate | Enter/l: Read | V: Visual mode | p: Preview | s: Sync | d: Delete | a: Archive | c: Compose | m: Creds | q: Quit"
+                "j/k: Navigate | Enter/l: Read | V: Visual mode | p: Preview | s: Status | d: Delete | a: Archive | c: Compose | b: Contacts | n: History | g: Folders | t: Threads | L: Listing style | m: Creds | q: Quit"
             }
         }
         View::EmailDetail => {
-            "h/Esc: Back | d: Delete | a: Archive | r: Reply | f: Forward | q: Quit"
+            "h/Esc: Back | d: Delete | a: Archive | r: Reply | f: Forward | t: Toggle HTML | p: Raw/rendered HTML | s: Save attachment | o: Follow link | q: Quit"
         }
         View::Compose => {
             if let Some(ref compose) = app.compose_state {
-                match compose.mode {
-                    ComposeMode::Normal => {
-                        "i: Insert | j/k: Navigate | d: Clear | p: Preview | w: Save draft | Esc/q: Exit"
+                if compose.attachment_prompt.is_some() {
+                    "Type a file path | Enter: Add | Esc: Cancel"
+                } else {
+                    match compose.mode {
+                        ComposeMode::Normal => {
+                            "i: Insert | j/k: Navigate | d: Clear/remove | a: Add attachment | J/K: Select attachment | p: Preview | w: Save draft | e: Editor | s: Sign | E: Encrypt | o: Follow link | b: Contacts | Enter: Send | Esc/q: Exit"
+                        }
+                        ComposeMode::Insert => "Esc: Normal mode | Type to edit field | Tab: Complete contact (recipients)",
                     }
-                    ComposeMode::Insert => "Esc: Normal mode | Type to edit field",
                 }
             } else {
                 ""
@@ -342,12 +740,20 @@ fn render_footer(f: &mut Frame, area: Rect, app: &App) {
         }
         View::CredentialsSetup => {
             if let Some(setup) = &app.credentials_setup_state {
-                if setup.provider_selection_mode {
-                    "j/k: Navigate | Enter/l: Select provider | Esc/q: Cancel"
+                if setup.backend_selection_mode {
+                    "j/k: Navigate | Enter/l: Select backend | Esc/q: Cancel"
+                } else if setup.provider_selection_mode {
+                    "j/k: Navigate | Enter/l: Select provider | b: Change storage backend | Esc/q: Cancel"
                 } else {
                     match setup.mode {
+                        crate::app::CredentialsMode::Normal if setup.uses_oauth2() && setup.supports_device_code() => {
+                            "i: Insert | j/k: Navigate fields | h: Back to providers | P: Toggle passwords | o: Authorize | D: Device code | O: Toggle custom OAuth2 | Enter: Save | Esc/q: Cancel"
+                        }
+                        crate::app::CredentialsMode::Normal if setup.uses_oauth2() => {
+                            "i: Insert | j/k: Navigate fields | h: Back to providers | P: Toggle passwords | o: Authorize | O: Toggle custom OAuth2 | Enter: Save | Esc/q: Cancel"
+                        }
                         crate::app::CredentialsMode::Normal => {
-                            "i: Insert | j/k: Navigate fields | h: Back to providers | P: Toggle passwords | Enter: Save | Esc/q: Cancel"
+                            "i: Insert | j/k: Navigate fields | h: Back to providers | P: Toggle passwords | t: Test connection | O: Toggle custom OAuth2 | Enter: Save | Esc/q: Cancel"
                         }
                         crate::app::CredentialsMode::Insert => {
                             "Esc: Normal mode | Type to edit field | Left/Right: Move cursor"
@@ -355,7 +761,7 @@ fn render_footer(f: &mut Frame, area: Rect, app: &App) {
                     }
                 }
             } else {
-                "i: Insert | j/k: Navigate fields | P: Toggle passwords | Enter: Save | Esc: Cancel"
+                "i: Insert | j/k: Navigate fields | P: Toggle passwords | t: Test connection | Enter: Save | Esc: Cancel"
             }
         }
         View::CredentialsUnlock => {
@@ -364,16 +770,45 @@ fn render_footer(f: &mut Frame, area: Rect, app: &App) {
         View::CredentialsManagement => {
             "r: Reset credentials | Esc: Back to inbox"
         }
+        View::CommandLine => {
+            "Enter: Run command | Up/Down: History | Esc: Cancel"
+        }
+        View::Contacts => {
+            if app.contacts_state.as_ref().map(|s| s.add_state.is_some()).unwrap_or(false) {
+                "Enter: Save contact | Esc: Cancel"
+            } else {
+                "j/k: Navigate | Enter: Insert into compose | a: Add | d: Delete | Esc/q: Back"
+            }
+        }
+        View::NotificationHistory => {
+            "j/k: Navigate | Esc/q: Back"
+        }
+        View::FolderList => {
+            "j/k: Navigate | Enter: Switch folder | Esc/q: Back"
+        }
+        View::ThreadList => {
+            if app.thread_list_state.as_ref().and_then(|s| s.expanded_message_index).is_some() {
+                "j/k: Navigate | Enter: Read | Esc/h: Collapse"
+            } else {
+                "j/k: Navigate | Enter/l: Expand | Esc/h/q: Back"
+            }
+        }
+        View::AccountStatus => "Esc/q: Back",
     };
 
     // Build status bar with mode indicator and current theme
     let mode_text = match app.current_view {
         View::InboxList if app.visual_mode => " VISUAL LINE ",
+        View::CommandLine => " COMMAND ",
         View::Compose => {
             if let Some(ref compose) = app.compose_state {
-                match compose.mode {
-                    ComposeMode::Normal => " NORMAL ",
-                    ComposeMode::Insert => " INSERT ",
+                if compose.attachment_prompt.is_some() {
+                    " ATTACH "
+                } else {
+                    match compose.mode {
+                        ComposeMode::Normal => " NORMAL ",
+                        ComposeMode::Insert => " INSERT ",
+                    }
                 }
             } else {
                 ""
@@ -389,6 +824,13 @@ fn render_footer(f: &mut Frame, area: Rect, app: &App) {
                 ""
             }
         }
+        View::Contacts => {
+            if app.contacts_state.as_ref().map(|s| s.add_state.is_some()).unwrap_or(false) {
+                " INSERT "
+            } else {
+                " NORMAL "
+            }
+        }
         _ => "",
     };
     
@@ -402,7 +844,47 @@ fn render_footer(f: &mut Frame, area: Rect, app: &App) {
     // Theme name in footer
     let theme_indicator = format!(" {} ", app.theme.name);
 
-    let text = if let Some(ref msg) = app.status_message {
+    // PGP sign/encrypt indicator, shown while composing
+    let compose_flags_text = match (app.current_view, &app.compose_state) {
+        (View::Compose, Some(compose)) => {
+            let mut flags = String::new();
+            if compose.sign {
+                flags.push_str("[signed]");
+            }
+            if compose.encrypt {
+                flags.push_str("[encrypted]");
+            }
+            if compose.body.contains("<#part") {
+                flags.push_str("[mml]");
+            }
+            if !compose.attachments.is_empty() {
+                flags.push_str(&format!("[{} attachment{}]", compose.attachments.len(), if compose.attachments.len() == 1 { "" } else { "s" }));
+            }
+            flags
+        }
+        _ => String::new(),
+    };
+
+    let text = if let Some(ref state) = app.command_line_state {
+        let prompt = format!(":{}", state.buffer);
+        let message_line = if let Some(ref err) = state.error_message {
+            Line::from(Span::styled(err.as_str(), Style::default().fg(theme.warning.to_color())))
+        } else {
+            Line::from(Span::styled(help_text, Style::default().fg(theme.text_dim.to_color())))
+        };
+        vec![
+            Line::from(vec![
+                Span::styled(mode_text,
+                    Style::default()
+                        .bg(theme.status_bar_mode.to_color())
+                        .fg(theme.status_bar.to_color())
+                        .add_modifier(Modifier::BOLD)),
+                Span::raw(" "),
+                Span::styled(prompt, Style::default().fg(theme.text_normal.to_color())),
+            ]),
+            message_line,
+        ]
+    } else if let Some(ref msg) = app.status_message {
         vec![
             Line::from(vec![
                 Span::styled(mode_text, 
@@ -413,6 +895,7 @@ fn render_footer(f: &mut Frame, area: Rect, app: &App) {
                 Span::raw(" "),
                 Span::styled(msg, Style::default().fg(theme.warning.to_color())),
                 Span::raw("  "),
+                Span::styled(compose_flags_text.clone(), Style::default().fg(theme.text_bold.to_color())),
                 Span::styled(email_count, Style::default().fg(theme.text_dim.to_color())),
                 Span::styled(theme_indicator, Style::default().fg(theme.text_dim.to_color())),
             ]),
@@ -421,12 +904,13 @@ fn render_footer(f: &mut Frame, area: Rect, app: &App) {
     } else {
         vec![
             Line::from(vec![
-                Span::styled(mode_text, 
+                Span::styled(mode_text,
                     Style::default()
                         .bg(theme.status_bar_mode.to_color())
                         .fg(theme.status_bar.to_color())  // Use status_bar color for better contrast
                         .add_modifier(Modifier::BOLD)),
                 Span::raw(" "),
+                Span::styled(compose_flags_text, Style::default().fg(theme.text_bold.to_color())),
                 Span::styled(email_count, Style::default().fg(theme.text_dim.to_color())),
                 Span::styled(theme_indicator, Style::default().fg(theme.text_dim.to_color())),
             ]),
@@ -453,6 +937,9 @@ fn render_compose(f: &mut Frame, area: Rect, app: &App) {
                 Constraint::Length(3),
                 Constraint::Length(3),
                 Constraint::Min(0),
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(3),
             ])
             .split(area);
 
@@ -512,6 +999,14 @@ fn render_compose(f: &mut Frame, area: Rect, app: &App) {
             f.set_cursor_position((cursor_x.min(chunks[0].right().saturating_sub(2)), cursor_y));
         }
 
+        // Contact completion popup, floating just below the Recipients field
+        if compose.current_field == ComposeField::Recipients
+            && compose.mode == ComposeMode::Insert
+            && !compose.completion_candidates.is_empty()
+        {
+            render_completion_popup(f, chunks[0], compose, theme);
+        }
+
         // Subject field
         let subject_style = if compose.current_field == ComposeField::Subject {
             if compose.mode == ComposeMode::Insert {
@@ -571,7 +1066,7 @@ fn render_compose(f: &mut Frame, area: Rect, app: &App) {
             if compose.current_field == ComposeField::Body && compose.mode == ComposeMode::Insert {
                 "Body [INSERT] - Preview"
             } else if compose.current_field == ComposeField::Body {
-                "Body [NORMAL] - Preview"
+                "Body [NORMAL] - Preview (e: Editor, o: Follow link)"
             } else {
                 "Body - Preview"
             }
@@ -579,7 +1074,7 @@ fn render_compose(f: &mut Frame, area: Rect, app: &App) {
             if compose.current_field == ComposeField::Body && compose.mode == ComposeMode::Insert {
                 "Body [INSERT]"
             } else if compose.current_field == ComposeField::Body {
-                "Body [NORMAL]"
+                "Body [NORMAL] (e: Editor)"
             } else {
                 "Body"
             }
@@ -628,6 +1123,14 @@ fn render_compose(f: &mut Frame, area: Rect, app: &App) {
                 }
                 lines.push(Line::from(spans));
             }
+            let links = crate::links::find_links(&compose.body);
+            let lines = append_link_index(
+                lines,
+                &links,
+                theme,
+                compose.link_follow_mode,
+                &compose.link_follow_digits,
+            );
             let markdown_text = Text::from(lines);
 
             let body_widget = Paragraph::new(markdown_text)
@@ -690,6 +1193,391 @@ fn render_compose(f: &mut Frame, area: Rect, app: &App) {
                 ));
             }
         }
+
+        render_compose_pgp_row(f, chunks[3], compose, theme, ComposeField::Sign, "Sign", sign_row_text(compose));
+        render_compose_pgp_row(f, chunks[4], compose, theme, ComposeField::Encrypt, "Encrypt", encrypt_row_text(compose));
+
+        render_compose_attachments(f, chunks[5], compose, theme);
+    }
+}
+
+/// Last (sixth) block of `render_compose`, below the `Sign`/`Encrypt` rows: the attached files, each with its size and a
+/// `gpg::guess_content_type_for_display`-inferred MIME type, or the path-entry prompt opened by
+/// `a` in place of the list while it's active.
+fn render_compose_attachments(f: &mut Frame, area: Rect, compose: &ComposeState, theme: &Theme) {
+    let focused = compose.current_field == ComposeField::Attachments;
+    let border_style = Style::default().fg(if focused {
+        theme.border_focused.to_color()
+    } else {
+        theme.border.to_color()
+    });
+
+    if let Some(ref prompt) = compose.attachment_prompt {
+        let widget = Paragraph::new(Line::from(prompt.buffer.as_str())).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(border_style)
+                .title("Attach file: path (Enter to add, Esc to cancel)"),
+        );
+        f.render_widget(widget, area);
+
+        let cursor_x = area.x + 1 + prompt.cursor_position as u16;
+        let cursor_y = area.y + 1;
+        f.set_cursor_position((cursor_x.min(area.right().saturating_sub(2)), cursor_y));
+        return;
+    }
+
+    let title = if focused {
+        "Attachments [NORMAL] (a: add, d: remove)"
+    } else {
+        "Attachments"
+    };
+
+    if compose.attachments.is_empty() {
+        let empty = Paragraph::new(Span::styled("<empty>", Style::default().fg(theme.compose_field_empty.to_color())))
+            .block(Block::default().borders(Borders::ALL).border_style(border_style).title(title));
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let line = Line::from(
+        compose
+            .attachments
+            .iter()
+            .enumerate()
+            .map(|(i, path)| {
+                let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| path.display().to_string());
+                let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+                let content_type = crate::gpg::guess_content_type_for_display(path);
+                let label = format!(" {} ({} bytes, {}) ", name, size, content_type);
+                let style = if focused && i == compose.attachment_selected {
+                    styled_with(Style::default().bg(theme.selection.to_color()), &theme.text_bold)
+                } else {
+                    Style::default().fg(theme.compose_field_value.to_color())
+                };
+                Span::styled(label, style)
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    let widget = Paragraph::new(line).block(Block::default().borders(Borders::ALL).border_style(border_style).title(title));
+    f.render_widget(widget, area);
+}
+
+/// One of the two PGP status rows drawn between the body and the attachment list - `Sign` or
+/// `Encrypt` - with the same focused/unfocused border treatment `render_compose`'s text fields
+/// use. Toggling happens via the `s`/`Shift-E` shortcuts (see `App::compose_toggle_sign`/
+/// `compose_toggle_encrypt`), which work regardless of which field has focus; giving these rows
+/// their own `ComposeField` variant just makes them visible and navigable to, like meli's
+/// `Sign`/`Encrypt` cursor states.
+fn render_compose_pgp_row(
+    f: &mut Frame,
+    area: Rect,
+    compose: &ComposeState,
+    theme: &Theme,
+    field: ComposeField,
+    label: &str,
+    value: String,
+) {
+    let focused = compose.current_field == field;
+    let label_style = if focused {
+        Style::default().fg(theme.active_field.to_color()).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.compose_field_label.to_color())
+    };
+
+    let widget = Paragraph::new(Line::from(vec![
+        Span::styled(format!("{label}: "), label_style),
+        Span::styled(value, Style::default().fg(theme.compose_field_value.to_color())),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(if focused { theme.border_focused.to_color() } else { theme.border.to_color() }))
+            .title(if focused { format!("{label} [NORMAL]") } else { label.to_string() }),
+    );
+    f.render_widget(widget, area);
+}
+
+/// Content for the `Sign` row: just the toggle state, no recipient lookup needed.
+fn sign_row_text(compose: &ComposeState) -> String {
+    if compose.sign { "on".to_string() } else { "off".to_string() }
+}
+
+/// Content for the `Encrypt` row: the toggle state plus, when it's on, the resolved recipients -
+/// or a `"no key found for <addr>"` warning for the first recipient `gpg` doesn't have a public
+/// key for, the same spot `Sign`/`Encrypt`'s state is otherwise shown.
+fn encrypt_row_text(compose: &ComposeState) -> String {
+    if !compose.encrypt {
+        return "off".to_string();
+    }
+
+    let recipients = crate::gpg::recipients_from_field(&compose.recipients);
+    if recipients.is_empty() {
+        return "on (no recipients set)".to_string();
+    }
+
+    match recipients.iter().find(|addr| !crate::gpg::has_public_key(addr)) {
+        Some(addr) => format!("to {} - no key found for {addr}", recipients.join(", ")),
+        None => format!("to {}", recipients.join(", ")),
+    }
+}
+
+/// Floating suggestion list shown under the recipients field while contact completions are
+/// available; Tab cycles through `compose.completion_candidates` (see `App::compose_cycle_completion`).
+fn render_completion_popup(f: &mut Frame, field_area: Rect, compose: &ComposeState, theme: &Theme) {
+    let height = (compose.completion_candidates.len() as u16 + 2).min(7);
+    let popup_area = Rect {
+        x: field_area.x,
+        y: field_area.y + field_area.height,
+        width: field_area.width,
+        height,
+    };
+
+    let items: Vec<ListItem> = compose
+        .completion_candidates
+        .iter()
+        .enumerate()
+        .map(|(i, contact)| {
+            let label = match &contact.display_name {
+                Some(name) => format!("{} <{}>", name, contact.address),
+                None => contact.address.clone(),
+            };
+            let style = if i == compose.completion_index {
+                styled_with(Style::default().bg(theme.selection.to_color()), &theme.text_bold)
+            } else {
+                Style::default().fg(theme.text_normal.to_color())
+            };
+            ListItem::new(Line::from(label)).style(style)
+        })
+        .collect();
+
+    let popup = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_focused.to_color()))
+            .title("Contacts (Tab to cycle)"),
+    );
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(popup, popup_area);
+}
+
+fn render_contacts(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+
+    let Some(ref state) = app.contacts_state else {
+        return;
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border.to_color()))
+        .title("Contacts (j/k navigate, Enter insert, a add, d delete)");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    if let Some(ref add_state) = state.add_state {
+        let input_area = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(inner);
+
+        let input_widget = Paragraph::new(Line::from(add_state.buffer.as_str())).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border_focused.to_color()))
+                .title("New contact: Name <email> (Enter to save, Esc to cancel)"),
+        );
+        f.render_widget(input_widget, input_area[0]);
+
+        let cursor_x = input_area[0].x + 1 + add_state.cursor_position as u16;
+        let cursor_y = input_area[0].y + 1;
+        f.set_cursor_position((cursor_x.min(input_area[0].right().saturating_sub(2)), cursor_y));
+        return;
+    }
+
+    let items: Vec<ListItem> = state
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let label = match entry.display_name() {
+                Some(name) => format!("{} <{}>", name, entry.email()),
+                None => entry.email().to_string(),
+            };
+            let label = if entry.is_editable() {
+                label
+            } else {
+                format!("{} [vcf]", label)
+            };
+
+            let style = if i == state.selected_index {
+                styled_with(Style::default().bg(theme.selection.to_color()), &theme.text_bold)
+            } else {
+                Style::default().fg(theme.text_normal.to_color())
+            };
+            ListItem::new(Line::from(label)).style(style)
+        })
+        .collect();
+
+    let list = List::new(items);
+    f.render_widget(list, inner);
+}
+
+/// Format a Unix-seconds timestamp as a UTC `HH:MM:SS` time-of-day, since there's no date/time
+/// formatting crate in this project to lean on.
+fn format_notification_timestamp(timestamp: u64) -> String {
+    let secs_of_day = timestamp % 86_400;
+    format!("{:02}:{:02}:{:02}", secs_of_day / 3600, (secs_of_day % 3600) / 60, secs_of_day % 60)
+}
+
+fn render_notification_history(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border.to_color()))
+        .title("Notification History (j/k navigate, Esc to return)");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    if app.notification_history.is_empty() {
+        let empty = Paragraph::new("No notifications yet").style(Style::default().fg(theme.text_normal.to_color()));
+        f.render_widget(empty, inner);
+        return;
+    }
+
+    let selected_index = app.notification_history_state.as_ref().map(|s| s.selected_index).unwrap_or(0);
+    let items: Vec<ListItem> = app
+        .notification_history
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let label = format!("[{}] {}", format_notification_timestamp(entry.timestamp), entry.message);
+            let style = if i == selected_index {
+                styled_with(Style::default().bg(theme.selection.to_color()), &theme.text_bold)
+            } else {
+                Style::default().fg(theme.text_normal.to_color())
+            };
+            ListItem::new(Line::from(label)).style(style)
+        })
+        .collect();
+
+    let list = List::new(items);
+    f.render_widget(list, inner);
+}
+
+fn render_folder_list(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border.to_color()))
+        .title("Folders (j/k navigate, Enter to switch, Esc to return)");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    if app.folders.is_empty() {
+        let empty = Paragraph::new("No folders cached yet - syncing from the server...")
+            .style(Style::default().fg(theme.text_normal.to_color()));
+        f.render_widget(empty, inner);
+        return;
+    }
+
+    let selected_index = app.folder_list_state.as_ref().map(|s| s.selected_index).unwrap_or(0);
+    let items: Vec<ListItem> = app
+        .folders
+        .iter()
+        .enumerate()
+        .map(|(i, folder)| {
+            let label = match &folder.special_use {
+                Some(special_use) => format!("{} ({})", folder.name, special_use.trim_start_matches('\\')),
+                None => folder.name.clone(),
+            };
+            let style = if i == selected_index {
+                styled_with(Style::default().bg(theme.selection.to_color()), &theme.text_bold)
+            } else {
+                Style::default().fg(theme.text_normal.to_color())
+            };
+            ListItem::new(Line::from(label)).style(style)
+        })
+        .collect();
+
+    let list = List::new(items);
+    f.render_widget(list, inner);
+}
+
+fn render_thread_list(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let Some(state) = app.thread_list_state.as_ref() else { return };
+
+    match state.expanded_message_index {
+        Some(selected) => {
+            let Some(group) = app.thread_groups.get(state.selected_index) else { return };
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border.to_color()))
+                .title("Conversation (j/k navigate, Enter to read, Esc to collapse)");
+            let inner = block.inner(area);
+            f.render_widget(block, area);
+
+            let items: Vec<ListItem> = group
+                .email_indices
+                .iter()
+                .enumerate()
+                .filter_map(|(i, &email_index)| {
+                    let email = app.emails.get(email_index)?;
+                    let style = if i == selected {
+                        styled_with(Style::default().bg(theme.selection.to_color()), &theme.text_bold)
+                    } else {
+                        Style::default().fg(theme.text_normal.to_color())
+                    };
+                    let label = format!("{}  {}  {}", email.date, email.from, email.subject);
+                    Some(ListItem::new(Line::from(label)).style(style))
+                })
+                .collect();
+
+            f.render_widget(List::new(items), inner);
+        }
+        None => {
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border.to_color()))
+                .title("Threads (j/k navigate, Enter to expand, Esc to return)");
+            let inner = block.inner(area);
+            f.render_widget(block, area);
+
+            if app.thread_groups.is_empty() {
+                let empty = Paragraph::new("No conversations yet")
+                    .style(Style::default().fg(theme.text_normal.to_color()));
+                f.render_widget(empty, inner);
+                return;
+            }
+
+            let items: Vec<ListItem> = app
+                .thread_groups
+                .iter()
+                .enumerate()
+                .filter_map(|(i, group)| {
+                    let latest_index = *group.email_indices.last()?;
+                    let latest = app.emails.get(latest_index)?;
+                    let style = if i == state.selected_index {
+                        styled_with(Style::default().bg(theme.selection.to_color()), &theme.text_bold)
+                    } else {
+                        Style::default().fg(theme.text_normal.to_color())
+                    };
+                    let label = if group.email_indices.len() > 1 {
+                        format!("({}) {}  {}", group.email_indices.len(), latest.from, latest.subject)
+                    } else {
+                        format!("{}  {}", latest.from, latest.subject)
+                    };
+                    Some(ListItem::new(Line::from(label)).style(style))
+                })
+                .collect();
+
+            f.render_widget(List::new(items), inner);
+        }
     }
 }
 
@@ -699,14 +1587,112 @@ fn render_credentials_setup(f: &mut Frame, area: Rect, app: &App) {
         None => return,
     };
 
-    // Check if we're in provider selection mode
-    if setup.provider_selection_mode {
+    if setup.backend_selection_mode {
+        render_backend_selection(f, area, app);
+    } else if setup.provider_selection_mode {
         render_provider_selection(f, area, app);
+    } else if setup.is_local_backend() {
+        render_local_backend_fields(f, area, app);
     } else {
         render_credentials_fields(f, area, app);
     }
 }
 
+fn render_backend_selection(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let setup = match &app.credentials_setup_state {
+        Some(s) => s,
+        None => return,
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border.to_color()))
+        .title(" Choose a Mail Backend ")
+        .style(Style::default().fg(theme.title.to_color()));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(6)])
+        .split(inner);
+
+    let instructions = Paragraph::new(vec![Line::from(Span::styled(
+        "Where does this account's mail live?",
+        Style::default().add_modifier(Modifier::BOLD).fg(theme.text_highlight.to_color()),
+    ))])
+    .wrap(Wrap { trim: false });
+    f.render_widget(instructions, chunks[0]);
+
+    let kinds = crate::app::CredentialsSetupState::backend_kinds();
+    let items: Vec<ListItem> = kinds
+        .iter()
+        .enumerate()
+        .map(|(i, kind)| {
+            let style = if i == setup.backend_list_index {
+                Style::default().fg(theme.active_field.to_color()).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text_normal.to_color())
+            };
+            let marker = if i == setup.backend_list_index { "▸ " } else { "  " };
+            let content = vec![
+                Line::from(vec![Span::styled(marker, style), Span::styled(kind.label(), style)]),
+                Line::from(vec![
+                    Span::raw("    "),
+                    Span::styled(kind.description(), Style::default().fg(theme.text_dim.to_color())),
+                ]),
+            ];
+            ListItem::new(content)
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border.to_color()))
+            .title("Backends"),
+    );
+    f.render_widget(list, chunks[1]);
+}
+
+fn render_local_backend_fields(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let setup = match &app.credentials_setup_state {
+        Some(s) => s,
+        None => return,
+    };
+
+    let mode_str = match setup.mode {
+        crate::app::CredentialsMode::Normal => "NORMAL",
+        crate::app::CredentialsMode::Insert => "INSERT",
+    };
+    let title = format!(" {} Setup [{}] ", setup.backend.label(), mode_str);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border.to_color()))
+        .title(title);
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(6)])
+        .split(inner);
+
+    let instructions = Paragraph::new(vec![Line::from(setup.backend.description())]).wrap(Wrap { trim: false });
+    f.render_widget(instructions, chunks[0]);
+
+    let field_lines = vec![
+        build_field_line("Account name/email:", &setup.imap_username, setup.current_field == CredentialField::ImapUsername, setup.mode),
+        build_field_line("Local directory:", &setup.backend_path, setup.current_field == CredentialField::BackendPath, setup.mode),
+    ];
+    let fields_para = Paragraph::new(field_lines).wrap(Wrap { trim: false });
+    f.render_widget(fields_para, chunks[1]);
+}
+
 fn render_provider_selection(f: &mut Frame, area: Rect, app: &App) {
     let theme = &app.theme;
     let setup = match &app.credentials_setup_state {
@@ -746,7 +1732,7 @@ fn render_provider_selection(f: &mut Frame, area: Rect, app: &App) {
             Style::default().add_modifier(Modifier::BOLD).fg(theme.text_highlight.to_color()),
         )),
         Line::from(""),
-        Line::from(format!("Credentials will be stored using: {}", backend.as_str())),
+        Line::from(format!("Credentials will be stored using: {} (press 'b' to change)", backend.as_str())),
     ];
     let instructions_para = Paragraph::new(instructions).wrap(Wrap { trim: false });
     f.render_widget(instructions_para, chunks[0]);
@@ -774,11 +1760,11 @@ fn render_provider_selection(f: &mut Frame, area: Rect, app: &App) {
             let content = vec![
                 Line::from(vec![
                     Span::styled(marker, style),
-                    Span::styled(provider.name, style),
+                    Span::styled(provider.name.as_str(), style),
                 ]),
                 Line::from(vec![
                     Span::raw("    "),
-                    Span::styled(provider.description, Style::default().fg(theme.text_dim.to_color())),
+                    Span::styled(provider.description.as_str(), Style::default().fg(theme.text_dim.to_color())),
                 ]),
             ];
 
@@ -811,7 +1797,7 @@ fn render_credentials_fields(f: &mut Frame, area: Rect, app: &App) {
         .as_ref()
         .and_then(|id| crate::providers::EmailProvider::by_id(id))
         .map(|p| p.name)
-        .unwrap_or("Custom");
+        .unwrap_or_else(|| "Custom".to_string());
 
     // Title with mode indicator
     let mode_str = match setup.mode {
@@ -863,20 +1849,60 @@ fn render_credentials_fields(f: &mut Frame, area: Rect, app: &App) {
     field_lines.push(build_field_line("IMAP Server:", &setup.imap_server, setup.current_field == CredentialField::ImapServer, setup.mode));
     field_lines.push(build_field_line("IMAP Port:", &setup.imap_port, setup.current_field == CredentialField::ImapPort, setup.mode));
     field_lines.push(build_field_line("IMAP Username:", &setup.imap_username, setup.current_field == CredentialField::ImapUsername, setup.mode));
-    field_lines.push(build_field_line("IMAP Password:", 
-        if setup.show_passwords { &setup.imap_password } else { &imap_pwd_masked },
-        setup.current_field == CredentialField::ImapPassword, setup.mode));
+    if setup.uses_oauth2() {
+        field_lines.push(Line::from(Span::styled(
+            "  (password not used - authorize with 'o' below)",
+            Style::default().fg(Color::DarkGray),
+        )));
+    } else {
+        field_lines.push(build_field_line("IMAP Password:",
+            if setup.show_passwords { &setup.imap_password } else { &imap_pwd_masked },
+            setup.current_field == CredentialField::ImapPassword, setup.mode));
+    }
     field_lines.push(Line::from(""));
-    
+
     // SMTP fields
     field_lines.push(Line::from(Span::styled("SMTP Configuration", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))));
     field_lines.push(build_field_line("SMTP Server:", &setup.smtp_server, setup.current_field == CredentialField::SmtpServer, setup.mode));
     field_lines.push(build_field_line("SMTP Port:", &setup.smtp_port, setup.current_field == CredentialField::SmtpPort, setup.mode));
     field_lines.push(build_field_line("SMTP Username:", &setup.smtp_username, setup.current_field == CredentialField::SmtpUsername, setup.mode));
-    field_lines.push(build_field_line("SMTP Password:", 
-        if setup.show_passwords { &setup.smtp_password } else { &smtp_pwd_masked },
-        setup.current_field == CredentialField::SmtpPassword, setup.mode));
-    
+    if setup.uses_oauth2() {
+        field_lines.push(Line::from(Span::styled(
+            "  (password not used - authorize with 'o' below)",
+            Style::default().fg(Color::DarkGray),
+        )));
+    } else {
+        field_lines.push(build_field_line("SMTP Password:",
+            if setup.show_passwords { &setup.smtp_password } else { &smtp_pwd_masked },
+            setup.current_field == CredentialField::SmtpPassword, setup.mode));
+    }
+
+    if setup.custom_oauth2 {
+        field_lines.push(Line::from(""));
+        field_lines.push(Line::from(Span::styled("Custom OAuth2 Provider", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))));
+        field_lines.push(build_field_line("Client ID:", &setup.oauth_client_id, setup.current_field == CredentialField::OAuthClientId, setup.mode));
+        field_lines.push(build_field_line("Auth URL:", &setup.oauth_auth_url, setup.current_field == CredentialField::OAuthAuthUrl, setup.mode));
+        field_lines.push(build_field_line("Token URL:", &setup.oauth_token_url, setup.current_field == CredentialField::OAuthTokenUrl, setup.mode));
+        field_lines.push(build_field_line("Scopes (space-separated):", &setup.oauth_scopes, setup.current_field == CredentialField::OAuthScopes, setup.mode));
+    }
+
+    if setup.uses_oauth2() {
+        field_lines.push(Line::from(""));
+        field_lines.push(Line::from(Span::styled("OAuth2 Authorization", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))));
+        let status = setup.oauth_status.as_deref().unwrap_or("Not authorized yet - press 'o' to open the provider's sign-in page");
+        let status_style = match &setup.oauth_token {
+            Some(_) => Style::default().fg(Color::Green),
+            None => Style::default().fg(Color::Gray),
+        };
+        field_lines.push(Line::from(Span::styled(format!("  {}", status), status_style)));
+        if let Some(device) = &setup.device_authorization {
+            field_lines.push(Line::from(Span::styled(
+                format!("  Code: {}  ->  {}", device.user_code, device.verification_uri),
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            )));
+        }
+    }
+
     // Master password fields (only for encrypted file backend)
     if backend == StorageBackend::EncryptedFile {
         field_lines.push(Line::from(""));
@@ -894,13 +1920,26 @@ fn render_credentials_fields(f: &mut Frame, area: Rect, app: &App) {
 
     // Render mode-specific tips
     let backend_info = if setup.mode == crate::app::CredentialsMode::Normal {
-        vec![
+        let mut tips = vec![
             Line::from(""),
             Line::from(Span::styled("Tip:", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))),
             Line::from("  Press 'i' to enter Insert mode to edit fields"),
             Line::from("  Press 'P' to toggle password visibility"),
-            Line::from("  Press 'h' on first field to go back to provider selection"),
-        ]
+        ];
+        if setup.uses_oauth2() {
+            tips.push(Line::from("  Press 'o' to authorize with this provider"));
+            if setup.supports_device_code() {
+                tips.push(Line::from("  Press 'D' to authorize via device code instead (no local browser needed)"));
+            }
+        } else {
+            tips.push(Line::from("  Press 't' to test the connection before saving"));
+        }
+        if setup.can_toggle_custom_oauth2() {
+            let label = if setup.custom_oauth2 { "disable" } else { "enable" };
+            tips.push(Line::from(format!("  Press 'O' to {} manual OAuth2 (client ID/auth URL/token URL/scopes)", label)));
+        }
+        tips.push(Line::from("  Press 'h' on first field to go back to provider selection"));
+        tips
     } else {
         vec![
             Line::from(""),
@@ -0,0 +1,165 @@
+//! Minimal vCard (`.vcf`) reader for the read-only side of the Contacts view. Only pulls out the
+//! `FN` (full name) and `EMAIL` properties from each `VCARD` block - enough to populate an
+//! address book, without trying to be a general-purpose vCard library.
+
+use std::path::Path;
+
+/// One contact read from a `.vcf` file
+#[derive(Debug, Clone, PartialEq)]
+pub struct VcardContact {
+    pub name: Option<String>,
+    pub email: String,
+}
+
+/// Load every `.vcf` file directly inside `folder` and return the contacts found in them.
+/// Missing or unreadable files/folders are skipped rather than erroring, since a read-only
+/// import folder is optional and shouldn't block the Contacts view from opening.
+pub fn load_vcf_folder(folder: &Path) -> Vec<VcardContact> {
+    let Ok(entries) = std::fs::read_dir(folder) else {
+        return Vec::new();
+    };
+
+    let mut contacts = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("vcf") {
+            continue;
+        }
+        if let Ok(contents) = std::fs::read_to_string(&path) {
+            contacts.extend(parse_vcf(&contents));
+        }
+    }
+    contacts
+}
+
+/// Parse the contents of a single `.vcf` file, which may contain multiple `VCARD` blocks
+fn parse_vcf(contents: &str) -> Vec<VcardContact> {
+    let mut contacts = Vec::new();
+    let mut name: Option<String> = None;
+    let mut emails: Vec<String> = Vec::new();
+    let mut in_card = false;
+
+    for line in unfold_lines(contents) {
+        let line = line.trim();
+        if line.eq_ignore_ascii_case("BEGIN:VCARD") {
+            in_card = true;
+            name = None;
+            emails.clear();
+            continue;
+        }
+        if line.eq_ignore_ascii_case("END:VCARD") {
+            if in_card {
+                for email in emails.drain(..) {
+                    contacts.push(VcardContact {
+                        name: name.clone(),
+                        email,
+                    });
+                }
+            }
+            in_card = false;
+            continue;
+        }
+        if !in_card {
+            continue;
+        }
+
+        let Some((property, value)) = line.split_once(':') else {
+            continue;
+        };
+        // Strip `;TYPE=...`-style parameters off the property name (e.g. `EMAIL;TYPE=work`)
+        let property = property.split(';').next().unwrap_or(property);
+
+        match property.to_uppercase().as_str() {
+            "FN" => name = Some(value.trim().to_string()),
+            "EMAIL" => {
+                let value = value.trim();
+                if !value.is_empty() {
+                    emails.push(value.to_lowercase());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    contacts
+}
+
+/// Filter `contacts` down to those whose name or email contains `term` (case-insensitive). Used
+/// to feed vCard-imported contacts into the same recipient autocomplete as the database-backed
+/// address book - see `App::compose_update_completions`.
+pub fn search<'a>(contacts: &'a [VcardContact], term: &str) -> Vec<&'a VcardContact> {
+    if term.is_empty() {
+        return Vec::new();
+    }
+    let needle = term.to_lowercase();
+    contacts
+        .iter()
+        .filter(|c| {
+            c.email.to_lowercase().contains(&needle)
+                || c.name.as_deref().map(|n| n.to_lowercase().contains(&needle)).unwrap_or(false)
+        })
+        .collect()
+}
+
+/// Un-fold vCard's RFC 6350 line-continuation convention: a line beginning with a space or tab
+/// is a continuation of the previous line.
+fn unfold_lines(contents: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw_line in contents.lines() {
+        if (raw_line.starts_with(' ') || raw_line.starts_with('\t')) && !lines.is_empty() {
+            let last = lines.last_mut().unwrap();
+            last.push_str(raw_line.trim_start());
+        } else {
+            lines.push(raw_line.to_string());
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_vcard() {
+        let vcf = "BEGIN:VCARD\nVERSION:3.0\nFN:Ada Lovelace\nEMAIL:ada@example.com\nEND:VCARD\n";
+        let contacts = parse_vcf(vcf);
+        assert_eq!(contacts.len(), 1);
+        assert_eq!(contacts[0].name.as_deref(), Some("Ada Lovelace"));
+        assert_eq!(contacts[0].email, "ada@example.com");
+    }
+
+    #[test]
+    fn test_parse_multiple_emails_per_card() {
+        let vcf = "BEGIN:VCARD\nFN:Grace Hopper\nEMAIL;TYPE=work:grace@navy.mil\nEMAIL;TYPE=home:grace@home.example\nEND:VCARD\n";
+        let contacts = parse_vcf(vcf);
+        assert_eq!(contacts.len(), 2);
+        assert!(contacts.iter().all(|c| c.name.as_deref() == Some("Grace Hopper")));
+    }
+
+    #[test]
+    fn test_parse_multiple_cards_in_one_file() {
+        let vcf = "BEGIN:VCARD\nFN:A\nEMAIL:a@example.com\nEND:VCARD\nBEGIN:VCARD\nFN:B\nEMAIL:b@example.com\nEND:VCARD\n";
+        let contacts = parse_vcf(vcf);
+        assert_eq!(contacts.len(), 2);
+        assert_eq!(contacts[1].email, "b@example.com");
+    }
+
+    #[test]
+    fn test_ignores_card_with_no_email() {
+        let vcf = "BEGIN:VCARD\nFN:No Email\nEND:VCARD\n";
+        assert!(parse_vcf(vcf).is_empty());
+    }
+
+    #[test]
+    fn test_search_matches_name_or_email_case_insensitively() {
+        let contacts = vec![
+            VcardContact { name: Some("Ada Lovelace".to_string()), email: "ada@example.com".to_string() },
+            VcardContact { name: Some("Grace Hopper".to_string()), email: "grace@navy.mil".to_string() },
+        ];
+        assert_eq!(search(&contacts, "ADA").len(), 1);
+        assert_eq!(search(&contacts, "navy").len(), 1);
+        assert!(search(&contacts, "nobody").is_empty());
+        assert!(search(&contacts, "").is_empty());
+    }
+}
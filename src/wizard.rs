@@ -0,0 +1,330 @@
+//! First-run interactive account setup, invoked from `main` before the TUI starts when
+//! [`crate::config::Config`] has no accounts configured yet. Modeled on Himalaya's
+//! `account/wizard.rs`/`backend/wizard.rs`: a select prompt for the receiving backend, a second
+//! select for the (optional) sending backend, a confirmation before anything is written, and
+//! finally the populated [`Account`] saved through `Config::set_account` + `Config::save`.
+//!
+//! Falls back to leaving the skeleton file [`crate::config::Config::load_from`] already wrote
+//! in place when stdin isn't a TTY - there's no one to prompt, and a skeleton the user can
+//! hand-edit is better than a wizard that reads EOF and writes garbage.
+
+use crate::app::CredentialsSetupState;
+use crate::config::{
+    Account, AccountBackend, Config, FolderAliases, FolderSyncFilter, SecretRef, SendBackend, SendEncryption,
+};
+use anyhow::{Context, Result};
+use std::io::{self, BufRead, IsTerminal, Write};
+
+/// Where wizard prompts read input from and write output to - a trait so tests can drive the
+/// wizard against an in-memory transcript instead of real stdin/stdout.
+pub trait Prompt {
+    fn input(&mut self, label: &str) -> Result<String>;
+    fn select(&mut self, label: &str, options: &[String]) -> Result<usize>;
+    fn confirm(&mut self, label: &str) -> Result<bool>;
+}
+
+/// Reads lines from `R` and writes prompts to `W` - the real stdin/stdout path.
+pub struct TerminalPrompt<R, W> {
+    reader: R,
+    writer: W,
+}
+
+impl<R: BufRead, W: Write> TerminalPrompt<R, W> {
+    pub fn new(reader: R, writer: W) -> Self {
+        Self { reader, writer }
+    }
+
+    fn read_line(&mut self) -> Result<String> {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).context("Failed to read from stdin")?;
+        Ok(line.trim().to_string())
+    }
+}
+
+impl<R: BufRead, W: Write> Prompt for TerminalPrompt<R, W> {
+    fn input(&mut self, label: &str) -> Result<String> {
+        write!(self.writer, "{}: ", label)?;
+        self.writer.flush()?;
+        self.read_line()
+    }
+
+    fn select(&mut self, label: &str, options: &[String]) -> Result<usize> {
+        writeln!(self.writer, "{}", label)?;
+        for (i, option) in options.iter().enumerate() {
+            writeln!(self.writer, "  {}) {}", i + 1, option)?;
+        }
+        loop {
+            write!(self.writer, "> ")?;
+            self.writer.flush()?;
+            let line = self.read_line()?;
+            if let Ok(choice) = line.parse::<usize>() {
+                if choice >= 1 && choice <= options.len() {
+                    return Ok(choice - 1);
+                }
+            }
+            writeln!(self.writer, "Please enter a number between 1 and {}", options.len())?;
+        }
+    }
+
+    fn confirm(&mut self, label: &str) -> Result<bool> {
+        write!(self.writer, "{} [y/N]: ", label)?;
+        self.writer.flush()?;
+        let line = self.read_line()?;
+        Ok(matches!(line.to_lowercase().as_str(), "y" | "yes"))
+    }
+}
+
+/// Entry point for first-run setup: if stdin is a TTY, walk the user through [`run_wizard`] and
+/// save the result via `Config::set_account` + `Config::save`; otherwise a no-op, leaving the
+/// skeleton file on disk untouched. Returns whether an account was added.
+pub fn run_first_run_setup(config: &mut Config) -> Result<bool> {
+    if !io::stdin().is_terminal() {
+        return Ok(false);
+    }
+
+    let stdin = io::stdin();
+    let mut prompt = TerminalPrompt::new(stdin.lock(), io::stdout());
+    match run_wizard(&mut prompt, config)? {
+        Some((key, account)) => {
+            config.set_account(key, account);
+            config.save()?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Walk `prompt` through account key/name/email, a receiving-backend select, its
+/// backend-specific fields, an optional sending-backend select, and a final confirmation.
+/// Returns `None` if the user declines to confirm. `existing` is consulted to reject an account
+/// key that's already taken, and the new account is made the default only when `existing` has
+/// no accounts yet.
+pub fn run_wizard(prompt: &mut dyn Prompt, existing: &Config) -> Result<Option<(String, Account)>> {
+    writeln!(io::stdout(), "No accounts configured yet - let's set one up.").ok();
+
+    let key = loop {
+        let key = prompt.input("Account key (e.g. \"work\", \"personal\")")?;
+        if key.is_empty() {
+            continue;
+        }
+        if existing.accounts.contains_key(&key) {
+            writeln!(io::stdout(), "An account named {:?} already exists.", key).ok();
+            continue;
+        }
+        break key;
+    };
+
+    let name = prompt.input("Display name")?;
+    let email = prompt.input("Email address")?;
+
+    let backend_kinds = CredentialsSetupState::backend_kinds();
+    let backend_labels: Vec<String> = backend_kinds
+        .iter()
+        .map(|kind| format!("{} - {}", kind.label(), kind.description()))
+        .collect();
+    let backend_choice = prompt.select("Receiving backend", &backend_labels)?;
+    let backend = match &backend_kinds[backend_choice] {
+        AccountBackend::Maildir { .. } => {
+            let path = prompt.input("Maildir root path")?;
+            AccountBackend::Maildir { path: path.into() }
+        }
+        #[cfg(feature = "notmuch")]
+        AccountBackend::Notmuch { .. } => {
+            let path = prompt.input("Notmuch database path")?;
+            AccountBackend::Notmuch { database_path: path.into() }
+        }
+        AccountBackend::Imap => AccountBackend::Imap,
+    };
+
+    let send_backend = prompt_send_backend(prompt)?;
+
+    let account = Account {
+        name,
+        email,
+        provider: "custom".to_string(),
+        default: existing.accounts.is_empty(),
+        color: None,
+        display_order: None,
+        folder_sync: FolderSyncFilter::All,
+        folder_aliases: FolderAliases::default(),
+        backend,
+        send_backend,
+        settings: crate::config::Settings::default(),
+    };
+
+    writeln!(
+        io::stdout(),
+        "About to save account {:?} ({}) <{}>",
+        key, account.name, account.email
+    )
+    .ok();
+    if prompt.confirm("Save this account?")? {
+        Ok(Some((key, account)))
+    } else {
+        Ok(None)
+    }
+}
+
+/// The second select of the wizard: "same as receiving backend" (the common case, `None`), or a
+/// distinct SMTP/Sendmail path (see [`SendBackend`]) that [`crate::email_sync::send_transport_for_account`]
+/// actually sends through instead of the receiving credentials - the only way a local-only
+/// `Maildir`/`Notmuch` account (picked in the prior select) can send mail at all.
+fn prompt_send_backend(prompt: &mut dyn Prompt) -> Result<Option<SendBackend>> {
+    let options = [
+        "Same as receiving backend (default)".to_string(),
+        "SMTP".to_string(),
+        "Sendmail".to_string(),
+    ];
+    match prompt.select("Sending backend", &options)? {
+        1 => {
+            let host = prompt.input("SMTP host")?;
+            let port: u16 = prompt.input("SMTP port")?.parse().unwrap_or(587);
+            let login = prompt.input("SMTP login")?;
+            let encryption_options = ["ssl_tls (default)".to_string(), "start_tls".to_string(), "none".to_string()];
+            let encryption = match prompt.select("SMTP encryption", &encryption_options)? {
+                1 => SendEncryption::StartTls,
+                2 => SendEncryption::None,
+                _ => SendEncryption::SslTls,
+            };
+            let secret = prompt_smtp_secret(prompt, &login)?;
+            Ok(Some(SendBackend::Smtp { host, port, login, encryption, secret }))
+        }
+        2 => {
+            let command = prompt.input("Sendmail command (e.g. \"/usr/sbin/sendmail -t\")")?;
+            Ok(Some(SendBackend::Sendmail { command }))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Where the SMTP password for `login` comes from - a select over the [`SecretRef`] variants,
+/// in the order we'd like people to actually pick them: a keyring lookup first, then a command,
+/// with inline storage last since it's the one we'd rather nobody choose.
+fn prompt_smtp_secret(prompt: &mut dyn Prompt, login: &str) -> Result<SecretRef> {
+    let options = [
+        "System keyring (recommended)".to_string(),
+        "Shell command (e.g. \"pass show mail/work\")".to_string(),
+        "Type the password now (stored in plain text in config.toml)".to_string(),
+    ];
+    match prompt.select("Where should the SMTP password come from?", &options)? {
+        0 => Ok(SecretRef::Keyring { provider_id: "smtp".to_string(), username: login.to_string() }),
+        1 => Ok(SecretRef::Command { command: prompt.input("Secret command")? }),
+        _ => Ok(SecretRef::Inline { value: prompt.input("SMTP password")? }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Drives the wizard from a scripted list of answers instead of real stdin, for tests.
+    struct ScriptedPrompt {
+        answers: std::collections::VecDeque<String>,
+    }
+
+    impl ScriptedPrompt {
+        fn new(answers: &[&str]) -> Self {
+            Self { answers: answers.iter().map(|s| s.to_string()).collect() }
+        }
+
+        fn next(&mut self) -> String {
+            self.answers.pop_front().expect("wizard asked more questions than scripted")
+        }
+    }
+
+    impl Prompt for ScriptedPrompt {
+        fn input(&mut self, _label: &str) -> Result<String> {
+            Ok(self.next())
+        }
+
+        fn select(&mut self, _label: &str, _options: &[String]) -> Result<usize> {
+            Ok(self.next().parse()?)
+        }
+
+        fn confirm(&mut self, _label: &str) -> Result<bool> {
+            Ok(matches!(self.next().as_str(), "y" | "yes"))
+        }
+    }
+
+    #[test]
+    fn test_wizard_builds_imap_account_with_no_distinct_send_backend() {
+        let mut prompt = ScriptedPrompt::new(&[
+            "work", "Work Email", "me@work.com",
+            "0", // backend select: Imap
+            "0", // send backend select: same as receiving
+            "y", // confirm
+        ]);
+
+        let (key, account) = run_wizard(&mut prompt, &Config::default()).unwrap().unwrap();
+        assert_eq!(key, "work");
+        assert_eq!(account.email, "me@work.com");
+        assert_eq!(account.backend, AccountBackend::Imap);
+        assert_eq!(account.send_backend, None);
+        assert!(account.default);
+    }
+
+    #[test]
+    fn test_wizard_builds_maildir_account_with_smtp_send_backend() {
+        let mut prompt = ScriptedPrompt::new(&[
+            "side", "Side Project", "side@project.io",
+            "1", "/home/me/Maildir/side", // backend select: Maildir, then path
+            "1", "smtp.project.io", "587", "side@project.io", "1", // send backend: Smtp, start_tls
+            "1", "pass show smtp/side", // secret source: shell command
+            "y",
+        ]);
+
+        let (key, account) = run_wizard(&mut prompt, &Config::default()).unwrap().unwrap();
+        assert_eq!(key, "side");
+        assert_eq!(account.backend, AccountBackend::Maildir { path: "/home/me/Maildir/side".into() });
+        assert_eq!(
+            account.send_backend,
+            Some(SendBackend::Smtp {
+                host: "smtp.project.io".to_string(),
+                port: 587,
+                login: "side@project.io".to_string(),
+                encryption: SendEncryption::StartTls,
+                secret: SecretRef::Command { command: "pass show smtp/side".to_string() },
+            })
+        );
+    }
+
+    #[test]
+    fn test_wizard_declining_confirmation_returns_none() {
+        let mut prompt = ScriptedPrompt::new(&[
+            "work", "Work Email", "me@work.com",
+            "0", "0", "n",
+        ]);
+
+        assert!(run_wizard(&mut prompt, &Config::default()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_wizard_rejects_duplicate_account_key() {
+        let mut existing = Config::default();
+        existing.set_account(
+            "work".to_string(),
+            Account {
+                name: "Existing".to_string(),
+                email: "existing@work.com".to_string(),
+                provider: "imap".to_string(),
+                default: true,
+                color: None,
+                display_order: None,
+                folder_sync: FolderSyncFilter::All,
+                folder_aliases: FolderAliases::default(),
+                backend: AccountBackend::Imap,
+                send_backend: None,
+                settings: crate::config::Settings::default(),
+            },
+        );
+
+        let mut prompt = ScriptedPrompt::new(&[
+            "work", "personal", // first key rejected, second accepted
+            "Personal", "me@personal.com",
+            "0", "0", "y",
+        ]);
+
+        let (key, _account) = run_wizard(&mut prompt, &existing).unwrap().unwrap();
+        assert_eq!(key, "personal");
+    }
+}